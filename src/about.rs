@@ -0,0 +1,63 @@
+use crate::app_dirs::AppDirs;
+use std::path::{Path, PathBuf};
+
+/// About 面板展示的信息（只读）
+#[derive(Debug, Clone)]
+pub struct AboutInfo {
+    pub version: &'static str,
+    pub build: String,
+    pub cache_dir: PathBuf,
+    pub data_dir: PathBuf,
+    pub reader_cache_dir: PathBuf,
+    pub cache_entries: usize,
+    pub cache_bytes: u64,
+    pub settings_path: PathBuf,
+}
+
+impl AboutInfo {
+    pub fn collect(dirs: &AppDirs) -> Self {
+        let reader_cache_dir = dirs.reader_cache();
+        let (cache_entries, cache_bytes) =
+            crate::reader::cache_usage(&reader_cache_dir).unwrap_or((0, 0));
+
+        Self {
+            version: env!("CARGO_PKG_VERSION"),
+            build: format!(
+                "{} · {}-{}",
+                if cfg!(debug_assertions) {
+                    "debug"
+                } else {
+                    "release"
+                },
+                std::env::consts::OS,
+                std::env::consts::ARCH
+            ),
+            cache_dir: dirs.cache.clone(),
+            data_dir: dirs.data.clone(),
+            reader_cache_dir,
+            cache_entries,
+            cache_bytes,
+            settings_path: crate::settings::settings_path(&dirs.data),
+        }
+    }
+
+    /// (标签, 值) 列表，按显示顺序
+    pub fn rows(&self) -> Vec<(&'static str, String)> {
+        let path = |p: &Path| p.display().to_string();
+        vec![
+            ("Version", self.version.to_string()),
+            ("Build", self.build.clone()),
+            ("Cache directory", path(&self.cache_dir)),
+            (
+                "Reader cache",
+                format!(
+                    "{} entries, {}",
+                    self.cache_entries,
+                    crate::diagnostics::format_bytes(self.cache_bytes)
+                ),
+            ),
+            ("Data directory", path(&self.data_dir)),
+            ("Settings file", path(&self.settings_path)),
+        ]
+    }
+}
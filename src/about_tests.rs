@@ -0,0 +1,25 @@
+use crate::about::AboutInfo;
+use crate::app_dirs::AppDirs;
+use std::path::PathBuf;
+
+#[test]
+fn about_cache_paths_follow_app_dirs() {
+    let root = std::env::temp_dir().join(format!("oneapp-about-{}", std::process::id()));
+    let dirs = AppDirs::in_dir(&root);
+
+    let info = AboutInfo::collect(&dirs);
+
+    assert_eq!(info.cache_dir, dirs.cache);
+    assert_eq!(info.reader_cache_dir, dirs.cache.join("reader"));
+    // 设置属于用户数据，不在可能被清空的缓存目录里
+    assert_eq!(info.settings_path, dirs.data.join("settings.json"));
+    assert_eq!((info.cache_entries, info.cache_bytes), (0, 0));
+    assert_eq!(info.version, env!("CARGO_PKG_VERSION"));
+
+    let rows = info.rows();
+    let cache_row = rows
+        .iter()
+        .find(|(label, _)| *label == "Cache directory")
+        .map(|(_, value)| PathBuf::from(value));
+    assert_eq!(cache_row, Some(dirs.cache));
+}
@@ -1,67 +1,319 @@
-use crate::models::{Comment, RawComment, Story};
-use futures::{future::join_all, AsyncReadExt as _};
+use crate::models::{
+    Comment, CommentLimits, NewsChannel, PollOption, RawComment, Story, StorySummary,
+};
+use crate::retry::{self, FetchError, RetryPolicy};
+use crate::source::NewsSource;
+use async_lock::Semaphore;
+use futures::future::{join_all, LocalBoxFuture};
+use futures::stream::{self, StreamExt};
+use futures::FutureExt as _;
 use gpui::http_client::{AsyncBody, HttpClient};
-use std::collections::HashMap;
+use gpui::BackgroundExecutor;
+use serde::Deserialize;
+use std::collections::{HashMap, HashSet};
 use std::sync::Arc;
+use std::time::Duration;
 
 const BASE_URL: &str = "https://hacker-news.firebaseio.com/v0";
-const MAX_COMMENT_DEPTH: usize = 3;
-const MAX_COMMENTS_PER_LEVEL: usize = 10;
+/// Algolia 的 item 接口一次返回整棵评论树
+const ALGOLIA_ITEMS_URL: &str = "https://hn.algolia.com/api/v1/items";
+/// Algolia 的全文搜索接口
+const ALGOLIA_SEARCH_URL: &str = "https://hn.algolia.com/api/v1/search";
+/// 普通响应的大小上限（Algolia 的大讨论树可能有几 MB）
+const MAX_JSON_BYTES: usize = 16 * 1024 * 1024;
+/// 列表里单个 story 的上限，异常大的条目直接跳过
+const MAX_SUMMARY_BYTES: usize = 256 * 1024;
+/// 默认同时进行的请求数；评论树逐条请求时不会一下打开几十个连接
+const DEFAULT_MAX_CONCURRENT_REQUESTS: usize = 8;
+/// 网络错误、超时、限流和 5xx 最多试 4 次，第一次重试前等 250ms
+const API_RETRY: RetryPolicy = RetryPolicy {
+    attempts: 4,
+    base_delay: Duration::from_millis(250),
+};
+
+/// 各个来源共用的请求方式：限制同时进行的请求数，超时和暂时性错误退避重试。
+/// 克隆出来的 fetcher 共用同一组许可
+#[derive(Clone)]
+pub struct HttpFetcher {
+    client: Arc<dyn HttpClient>,
+    /// 用来计时请求超时；没有时（测试里）不限时
+    executor: Option<BackgroundExecutor>,
+    max_concurrent_requests: usize,
+    /// 所有请求共用的许可，克隆出来的客户端也共用同一个上限
+    request_permits: Arc<Semaphore>,
+}
 
 #[derive(Clone)]
 pub struct HackerNewsClient {
-    client: Arc<dyn HttpClient>,
+    fetcher: HttpFetcher,
+    comment_limits: CommentLimits,
+    use_algolia: bool,
 }
 
-impl HackerNewsClient {
+/// Algolia `items/<id>` 返回的节点，`children` 为嵌套的回复
+#[derive(Debug, Clone, Deserialize)]
+pub struct AlgoliaItem {
+    pub id: i64,
+    pub author: Option<String>,
+    pub text: Option<String>,
+    pub created_at_i: Option<i64>,
+    pub parent_id: Option<i64>,
+    #[serde(default)]
+    pub children: Vec<AlgoliaItem>,
+}
+
+/// Algolia `search` 的响应，只取需要的字段
+#[derive(Debug, Clone, Deserialize)]
+pub struct AlgoliaSearchResponse {
+    #[serde(default)]
+    pub hits: Vec<AlgoliaHit>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct AlgoliaHit {
+    #[serde(rename = "objectID")]
+    pub object_id: String,
+    pub title: Option<String>,
+    pub url: Option<String>,
+    pub points: Option<i32>,
+    pub author: Option<String>,
+    pub created_at_i: Option<i64>,
+    pub num_comments: Option<i32>,
+    pub story_text: Option<String>,
+}
+
+impl HttpFetcher {
     pub fn new(client: Arc<dyn HttpClient>) -> Self {
-        Self { client }
+        Self {
+            client,
+            executor: None,
+            max_concurrent_requests: DEFAULT_MAX_CONCURRENT_REQUESTS,
+            request_permits: Arc::new(Semaphore::new(DEFAULT_MAX_CONCURRENT_REQUESTS)),
+        }
+    }
+
+    #[must_use]
+    pub fn with_executor(mut self, executor: BackgroundExecutor) -> Self {
+        self.executor = Some(executor);
+        self
+    }
+
+    /// 同时进行的请求上限，至少为 1
+    #[must_use]
+    pub fn with_max_concurrent_requests(mut self, limit: usize) -> Self {
+        let limit = limit.max(1);
+        self.max_concurrent_requests = limit;
+        self.request_permits = Arc::new(Semaphore::new(limit));
+        self
+    }
+
+    pub fn max_concurrent_requests(&self) -> usize {
+        self.max_concurrent_requests
     }
 
-    async fn get_json<T>(&self, url: &str) -> Result<T, String>
+    pub async fn get_json<T>(&self, url: &str) -> Result<T, String>
     where
         T: serde::de::DeserializeOwned + Send + 'static,
     {
-        let response = self
-            .client
-            .get(url, AsyncBody::empty(), true)
+        self.get_json_limited(url, MAX_JSON_BYTES).await
+    }
+
+    pub async fn get_json_limited<T>(&self, url: &str, limit: usize) -> Result<T, String>
+    where
+        T: serde::de::DeserializeOwned + Send + 'static,
+    {
+        let bytes = self.get_bytes_limited(url, limit).await?;
+        serde_json::from_slice(&bytes).map_err(|e| e.to_string())
+    }
+
+    /// 读取响应，暂时性的错误按 `API_RETRY` 退避重试
+    pub async fn get_bytes_limited(&self, url: &str, limit: usize) -> Result<Vec<u8>, String> {
+        retry::with_retries(
+            API_RETRY,
+            || self.fetch_bytes(url, limit),
+            |delay| {
+                // 没有 executor（测试里）时立即重试
+                let timer = self.executor.as_ref().map(|executor| executor.timer(delay));
+                async move {
+                    if let Some(timer) = timer {
+                        timer.await;
+                    }
+                }
+            },
+        )
+        .await
+    }
+
+    /// 发一次请求并读完响应；超时算作可以重试的错误
+    async fn fetch_bytes(&self, url: &str, limit: usize) -> Result<Vec<u8>, FetchError> {
+        // 等到有空闲的许可才发请求，响应读完才归还，退避等待期间不占用
+        let _permit = self.request_permits.acquire().await;
+        let fetch = async {
+            let response = self
+                .client
+                .get(url, AsyncBody::empty(), true)
+                .await
+                .map_err(|e| FetchError::transient(e.to_string()))?;
+
+            let status = response.status();
+            if !status.is_success() {
+                return Err(FetchError::from_status(status, url));
+            }
+
+            let mut body = response.into_body();
+            crate::reader::read_to_end_limited(&mut body, limit)
+                .await
+                .map_err(FetchError::from)
+        };
+        let Some(executor) = &self.executor else {
+            return fetch.await;
+        };
+        let timeout = crate::reader::fetch_timeout();
+        crate::reader::with_timeout(fetch.map(Ok), timeout, executor.timer(timeout))
             .await
-            .map_err(|e| e.to_string())?;
+            .unwrap_or_else(|error| Err(FetchError::transient(error)))
+    }
+}
 
-        if !response.status().is_success() {
-            return Err(format!("HTTP {} for {}", response.status(), url));
+impl HackerNewsClient {
+    pub fn new(client: Arc<dyn HttpClient>) -> Self {
+        Self::from_fetcher(HttpFetcher::new(client))
+    }
+
+    /// 和其他来源共用同一个 fetcher，同时进行的请求数一起计算
+    pub fn from_fetcher(fetcher: HttpFetcher) -> Self {
+        Self {
+            fetcher,
+            comment_limits: NewsChannel::HackerNews.comment_limits(),
+            use_algolia: true,
         }
+    }
 
-        let mut body = response.into_body();
-        let mut bytes = Vec::new();
-        body.read_to_end(&mut bytes)
-            .await
-            .map_err(|e| e.to_string())?;
+    #[must_use]
+    pub fn with_algolia(mut self, use_algolia: bool) -> Self {
+        self.use_algolia = use_algolia;
+        self
+    }
 
-        serde_json::from_slice(&bytes).map_err(|e| e.to_string())
+    /// 同时进行的请求上限，至少为 1
+    #[must_use]
+    pub fn with_max_concurrent_requests(mut self, limit: usize) -> Self {
+        self.fetcher = self.fetcher.with_max_concurrent_requests(limit);
+        self
+    }
+
+    #[must_use]
+    pub fn with_comment_limits(mut self, comment_limits: CommentLimits) -> Self {
+        self.comment_limits = comment_limits;
+        self
     }
 
     async fn fetch_item<T>(&self, id: i64) -> Option<T>
     where
         T: serde::de::DeserializeOwned + Send + 'static,
     {
-        let url = format!("{}/item/{}.json", BASE_URL, id);
-        self.get_json(&url).await.ok()
+        self.fetcher.get_json(&item_api_url(id)).await.ok()
+    }
+
+    /// 调试用：不经过模型类型，原样返回 item 的 JSON
+    pub async fn fetch_item_raw(&self, id: i64) -> Option<serde_json::Value> {
+        self.fetch_item(id).await
     }
 
-    pub async fn fetch_top_stories(&self, limit: usize) -> Result<Vec<Story>, String> {
-        let url = format!("{}/topstories.json", BASE_URL);
-        let ids: Vec<i64> = self.get_json(&url).await?;
+    /// 列表只取摘要，`kids` 和正文在选中时由 `fetch_story` 获取
+    pub async fn fetch_top_stories(&self, limit: usize) -> Result<Vec<StorySummary>, String> {
+        self.fetch_stories(NewsChannel::HackerNews, limit).await
+    }
 
+    /// 频道榜单的前 `limit` 条摘要
+    pub async fn fetch_stories(
+        &self,
+        channel: NewsChannel,
+        limit: usize,
+    ) -> Result<Vec<StorySummary>, String> {
+        let ids = self.fetch_story_ids(channel).await?;
         let ids: Vec<i64> = ids.into_iter().take(limit).collect();
+        Ok(self.fetch_summaries(channel, &ids).await)
+    }
 
-        // 并发获取所有 stories
-        let futures: Vec<_> = ids.iter().map(|&id| self.fetch_item::<Story>(id)).collect();
+    /// 频道榜单上全部 story 的 id，按排名顺序
+    pub async fn fetch_story_ids(&self, channel: NewsChannel) -> Result<Vec<i64>, String> {
+        let url = format!("{}/{}", BASE_URL, channel.endpoint());
+        self.fetcher.get_json(&url).await
+    }
+
+    /// 获取一页摘要，同时最多 `max_concurrent_requests` 个请求，失败的条目跳过
+    pub async fn fetch_summaries(&self, channel: NewsChannel, ids: &[i64]) -> Vec<StorySummary> {
+        let results: Vec<_> = stream::iter(ids.iter().map(|&id| async move {
+            self.fetcher
+                .get_json_limited::<StorySummary>(&item_api_url(id), MAX_SUMMARY_BYTES)
+                .await
+                .ok()
+        }))
+        .buffered(self.fetcher.max_concurrent_requests())
+        .collect()
+        .await;
+
+        let mut stories: Vec<StorySummary> = results.into_iter().flatten().collect();
+        if channel.sorts_by_score() {
+            stories.sort_by(|a, b| b.score.cmp(&a.score));
+        }
+        stories
+    }
+
+    /// 用 Algolia 全文搜索 story，按相关度返回前 `limit` 条
+    pub async fn search_stories(&self, query: &str, limit: usize) -> Result<Vec<Story>, String> {
+        let url = url::Url::parse_with_params(
+            ALGOLIA_SEARCH_URL,
+            &[
+                ("query", query),
+                ("tags", "story"),
+                ("hitsPerPage", &limit.to_string()),
+            ],
+        )
+        .map_err(|e| e.to_string())?;
+        let response: AlgoliaSearchResponse = self.fetcher.get_json(url.as_str()).await?;
+        Ok(stories_from_algolia_hits(response.hits))
+    }
+
+    /// 选中时获取完整的 story（含 `kids` 和正文）
+    pub async fn fetch_story(&self, id: i64) -> Result<Story, String> {
+        self.fetcher.get_json(&item_api_url(id)).await
+    }
+
+    /// 悬停预览用：story 的前 `count` 条顶级评论，已删除的跳过
+    pub async fn fetch_comment_preview(
+        &self,
+        story_id: i64,
+        count: usize,
+    ) -> Result<Vec<Comment>, String> {
+        let story = self.fetch_story(story_id).await?;
+        let kids = story.kids.unwrap_or_default();
+        let futures: Vec<_> = kids
+            .iter()
+            .take(count)
+            .map(|&id| self.fetch_item::<RawComment>(id))
+            .collect();
         let results = join_all(futures).await;
 
-        let mut stories: Vec<Story> = results.into_iter().flatten().collect();
-        stories.sort_by(|a, b| b.score.cmp(&a.score));
-        Ok(stories)
+        Ok(results
+            .into_iter()
+            .flatten()
+            .filter(|raw| raw.by.is_some() && raw.text.is_some())
+            .map(Comment::from)
+            .collect())
+    }
+
+    /// 投票帖的选项，按 `parts` 的顺序；取不到的选项跳过，不是投票帖时为空
+    pub async fn fetch_poll_options(&self, story: &Story) -> Vec<PollOption> {
+        let Some(parts) = story.parts.as_ref().filter(|_| story.is_poll()) else {
+            return Vec::new();
+        };
+        let futures: Vec<_> = parts
+            .iter()
+            .map(|&id| self.fetch_item::<PollOption>(id))
+            .collect();
+        join_all(futures).await.into_iter().flatten().collect()
     }
 
     pub async fn fetch_comments(&self, story: &Story) -> Result<Vec<Comment>, String> {
@@ -70,8 +322,16 @@ impl HackerNewsClient {
             None => return Ok(Vec::new()),
         };
 
-        // 限制顶级评论数量
-        let kids: Vec<i64> = kids.into_iter().take(MAX_COMMENTS_PER_LEVEL).collect();
+        // 优先一次请求拿整棵树，失败时退回逐条请求
+        if self.use_algolia {
+            let url = format!("{}/{}", ALGOLIA_ITEMS_URL, story.id);
+            if let Ok(item) = self.fetcher.get_json::<AlgoliaItem>(&url).await {
+                return Ok(comments_from_algolia(&item, &kids, self.comment_limits));
+            }
+        }
+
+        // 限制顶级评论数量，其余的由 `fetch_more_comments` 按需加载
+        let kids = self.initial_root_ids(&kids);
 
         // 递归获取评论
         let comments = self.fetch_comments_recursive(&kids, 0).await;
@@ -81,15 +341,61 @@ impl HackerNewsClient {
         Ok(sorted)
     }
 
+    /// 打开 story 时 `fetch_comments` 会加载的顶级评论
+    pub fn initial_root_ids(&self, kids: &[i64]) -> Vec<i64> {
+        next_root_batch(kids, &HashSet::new(), self.comment_limits.max_per_level)
+    }
+
+    /// 还没加载的下一批顶级评论，数量与首次加载相同
+    pub fn next_root_ids(&self, kids: &[i64], loaded: &HashSet<i64>) -> Vec<i64> {
+        next_root_batch(kids, loaded, self.comment_limits.max_per_level)
+    }
+
+    /// 获取一批顶级评论及其回复（同样受层级限制），按深度优先排好，可以直接接在已有评论后面
+    pub async fn fetch_more_comments(&self, root_ids: &[i64]) -> Vec<Comment> {
+        let comments = self.fetch_comments_recursive(root_ids, 0).await;
+        self.sort_comments_tree(&comments, root_ids)
+    }
+
+    /// 获取评论的全部直接回复，以及它们下面按同样上限展开的回复。
+    /// `existing_depth` 是父评论的层级，返回的评论深度接在它后面，按深度优先排好
+    pub async fn fetch_replies(
+        &self,
+        parent_id: i64,
+        existing_depth: usize,
+    ) -> Result<Vec<Comment>, String> {
+        let parent: RawComment = self
+            .fetch_item(parent_id)
+            .await
+            .ok_or_else(|| format!("Failed to load comment {}", parent_id))?;
+        let kids = parent.kids.unwrap_or_default();
+
+        // 用户点了“加载更多”，直接回复不受每层数量上限，分批请求
+        let mut comments = Vec::new();
+        for batch in kids.chunks(self.comment_limits.max_per_level.max(1)) {
+            comments.extend(self.fetch_comments_recursive(batch, 0).await);
+        }
+
+        let mut replies = self.sort_comments_tree(&comments, &kids);
+        for reply in &mut replies {
+            reply.depth += existing_depth + 1;
+        }
+        Ok(replies)
+    }
+
     async fn fetch_comments_recursive(&self, ids: &[i64], depth: usize) -> Vec<Comment> {
-        if depth > MAX_COMMENT_DEPTH || ids.is_empty() {
+        if depth > self.comment_limits.max_depth || ids.is_empty() {
             return Vec::new();
         }
 
         // 限制每层评论数量
-        let ids: Vec<i64> = ids.iter().take(MAX_COMMENTS_PER_LEVEL).copied().collect();
+        let ids: Vec<i64> = ids
+            .iter()
+            .take(self.comment_limits.max_per_level)
+            .copied()
+            .collect();
 
-        // 并发获取当前层的所有评论
+        // 并发获取当前层的所有评论，实际同时发出的请求数由 `request_permits` 限制，结果保持原顺序
         let futures: Vec<_> = ids
             .iter()
             .map(|&id| self.fetch_item::<RawComment>(id))
@@ -100,7 +406,9 @@ impl HackerNewsClient {
         let mut all_kid_ids: Vec<Vec<i64>> = Vec::new();
 
         for raw in results.into_iter().flatten() {
-            if raw.by.is_some() {
+            // 作者已删除但仍有回复的评论保留为 [deleted] 占位，避免整棵子树丢失
+            let has_kids = raw.kids.as_ref().is_some_and(|k| !k.is_empty());
+            if raw.by.is_some() || has_kids {
                 let kids = raw.kids.clone();
                 let reply_count = kids.as_ref().map_or(0, |k| k.len());
                 let comment = Comment::from(raw).with_depth(depth);
@@ -175,3 +483,166 @@ impl HackerNewsClient {
         }
     }
 }
+
+// 直接转给上面的同名方法，行为和直接调用完全一致
+impl NewsSource for HackerNewsClient {
+    fn fetch_story_ids(
+        &self,
+        channel: NewsChannel,
+    ) -> LocalBoxFuture<'_, Result<Vec<i64>, String>> {
+        HackerNewsClient::fetch_story_ids(self, channel).boxed_local()
+    }
+
+    fn fetch_summaries<'a>(
+        &'a self,
+        channel: NewsChannel,
+        ids: &'a [i64],
+    ) -> LocalBoxFuture<'a, Vec<StorySummary>> {
+        HackerNewsClient::fetch_summaries(self, channel, ids).boxed_local()
+    }
+
+    fn search_stories<'a>(
+        &'a self,
+        query: &'a str,
+        limit: usize,
+    ) -> LocalBoxFuture<'a, Result<Vec<Story>, String>> {
+        HackerNewsClient::search_stories(self, query, limit).boxed_local()
+    }
+
+    fn fetch_story(&self, id: i64) -> LocalBoxFuture<'_, Result<Story, String>> {
+        HackerNewsClient::fetch_story(self, id).boxed_local()
+    }
+
+    fn fetch_comment_preview(
+        &self,
+        story_id: i64,
+        count: usize,
+    ) -> LocalBoxFuture<'_, Result<Vec<Comment>, String>> {
+        HackerNewsClient::fetch_comment_preview(self, story_id, count).boxed_local()
+    }
+
+    fn fetch_poll_options<'a>(&'a self, story: &'a Story) -> LocalBoxFuture<'a, Vec<PollOption>> {
+        HackerNewsClient::fetch_poll_options(self, story).boxed_local()
+    }
+
+    fn fetch_comments<'a>(
+        &'a self,
+        story: &'a Story,
+    ) -> LocalBoxFuture<'a, Result<Vec<Comment>, String>> {
+        HackerNewsClient::fetch_comments(self, story).boxed_local()
+    }
+
+    fn initial_root_ids(&self, kids: &[i64]) -> Vec<i64> {
+        HackerNewsClient::initial_root_ids(self, kids)
+    }
+
+    fn next_root_ids(&self, kids: &[i64], loaded: &HashSet<i64>) -> Vec<i64> {
+        HackerNewsClient::next_root_ids(self, kids, loaded)
+    }
+
+    fn fetch_more_comments<'a>(&'a self, root_ids: &'a [i64]) -> LocalBoxFuture<'a, Vec<Comment>> {
+        HackerNewsClient::fetch_more_comments(self, root_ids).boxed_local()
+    }
+
+    fn fetch_replies(
+        &self,
+        parent_id: i64,
+        existing_depth: usize,
+    ) -> LocalBoxFuture<'_, Result<Vec<Comment>, String>> {
+        HackerNewsClient::fetch_replies(self, parent_id, existing_depth).boxed_local()
+    }
+
+    fn fetch_item_raw(&self, id: i64) -> LocalBoxFuture<'_, Option<serde_json::Value>> {
+        HackerNewsClient::fetch_item_raw(self, id).boxed_local()
+    }
+}
+
+/// 按 story `kids` 的顺序跳过已加载的顶级评论，取接下来的 `limit` 条
+pub fn next_root_batch(kids: &[i64], loaded: &HashSet<i64>, limit: usize) -> Vec<i64> {
+    kids.iter()
+        .filter(|id| !loaded.contains(id))
+        .take(limit)
+        .copied()
+        .collect()
+}
+
+/// item 在 Firebase API 上的地址
+pub fn item_api_url(id: i64) -> String {
+    format!("{}/item/{}.json", BASE_URL, id)
+}
+
+/// 搜索结果转成 `Story`；没有数字 id 或标题的条目跳过，`kids` 在选中时再获取
+pub fn stories_from_algolia_hits(hits: Vec<AlgoliaHit>) -> Vec<Story> {
+    hits.into_iter()
+        .filter_map(|hit| {
+            Some(Story {
+                id: hit.object_id.parse().ok()?,
+                title: hit.title.filter(|t| !t.is_empty())?,
+                url: hit.url.filter(|u| !u.is_empty()),
+                score: hit.points.unwrap_or_default(),
+                by: hit.author.unwrap_or_default(),
+                time: hit.created_at_i.unwrap_or_default(),
+                descendants: hit.num_comments,
+                kids: None,
+                text: hit.story_text.filter(|t| !t.is_empty()),
+                parts: None,
+                story_type: "story".to_string(),
+            })
+        })
+        .collect()
+}
+
+/// 把 Algolia 的评论树展开成与逐条请求相同的深度优先列表。
+/// 顶级评论按 story 的 `kids` 排序（HN 的排名顺序），嵌套回复保持 Algolia 的顺序。
+pub fn comments_from_algolia(
+    story: &AlgoliaItem,
+    ranked_kids: &[i64],
+    limits: CommentLimits,
+) -> Vec<Comment> {
+    let mut top_level: Vec<&AlgoliaItem> = story.children.iter().collect();
+    top_level.sort_by_key(|item| {
+        ranked_kids
+            .iter()
+            .position(|&id| id == item.id)
+            .unwrap_or(usize::MAX)
+    });
+
+    let mut result = Vec::new();
+    collect_algolia_comments(&top_level, story.id, 0, limits, &mut result);
+    result
+}
+
+fn collect_algolia_comments(
+    items: &[&AlgoliaItem],
+    parent: i64,
+    depth: usize,
+    limits: CommentLimits,
+    result: &mut Vec<Comment>,
+) {
+    if depth > limits.max_depth {
+        return;
+    }
+
+    for item in items.iter().take(limits.max_per_level) {
+        // 与逐条请求一致：已删除且没有回复的评论直接丢弃
+        if item.author.is_none() && item.children.is_empty() {
+            continue;
+        }
+
+        let kids: Vec<i64> = item.children.iter().map(|c| c.id).collect();
+        result.push(Comment {
+            id: item.id,
+            by: item.author.clone(),
+            text: item.text.clone(),
+            time: item.created_at_i.unwrap_or_default(),
+            reply_count: kids.len(),
+            kids: (!kids.is_empty()).then_some(kids),
+            parent: item.parent_id.unwrap_or(parent),
+            depth,
+            avatar_url: None,
+        });
+
+        let children: Vec<&AlgoliaItem> = item.children.iter().collect();
+        collect_algolia_comments(&children, item.id, depth + 1, limits, result);
+    }
+}
@@ -0,0 +1,551 @@
+use crate::api::{self, AlgoliaItem, HackerNewsClient};
+use crate::models::{CommentLimits, NewsChannel, Story};
+use crate::settings::Settings;
+use crate::source::NewsSource;
+use crate::test_support::fake_http;
+use gpui::http_client::{AsyncBody, FakeHttpClient, HttpClient, Response};
+use std::collections::HashSet;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::task::Poll;
+
+/// 按请求路径返回固定 JSON 的假客户端，未知路径返回 404
+fn fake_client(routes: &[(&str, &str)]) -> HackerNewsClient {
+    HackerNewsClient::new(fake_http(routes))
+}
+
+fn story_with_kids(kids: Vec<i64>) -> Story {
+    Story {
+        id: 100,
+        title: "Story".to_string(),
+        url: None,
+        score: 1,
+        by: "op".to_string(),
+        time: 1_700_000_000,
+        descendants: Some(kids.len() as i32),
+        kids: Some(kids),
+        text: None,
+        parts: None,
+        story_type: "story".to_string(),
+    }
+}
+
+#[test]
+fn deleted_parent_keeps_live_replies() {
+    let client = fake_client(&[
+        (
+            "/v0/item/1.json",
+            r#"{"id":1,"deleted":true,"time":1700000000,"parent":100,"kids":[2],"type":"comment"}"#,
+        ),
+        (
+            "/v0/item/2.json",
+            r#"{"id":2,"by":"bob","text":"still here","time":1700000100,"parent":1,"type":"comment"}"#,
+        ),
+        (
+            "/v0/item/3.json",
+            r#"{"id":3,"deleted":true,"time":1700000200,"parent":100,"type":"comment"}"#,
+        ),
+    ]);
+
+    let comments =
+        futures::executor::block_on(client.fetch_comments(&story_with_kids(vec![1, 3]))).unwrap();
+
+    let summary = comments
+        .iter()
+        .map(|c| (c.id, c.depth, c.author().to_string()))
+        .collect::<Vec<_>>();
+    assert_eq!(
+        summary,
+        vec![(1, 0, "[deleted]".to_string()), (2, 1, "bob".to_string())]
+    );
+    assert_eq!(comments[0].reply_count, 1);
+    assert_eq!(comments[0].clean_text(), "[deleted]");
+}
+
+/// 每条评论都有一个子回复，形成一条很深的链
+fn comment_chain(len: i64) -> Vec<(String, String)> {
+    (1..=len)
+        .map(|id| {
+            let kids = if id < len {
+                format!(r#","kids":[{}]"#, id + 1)
+            } else {
+                String::new()
+            };
+            (
+                format!("/v0/item/{id}.json"),
+                format!(
+                    r#"{{"id":{id},"by":"user{id}","text":"reply","time":1700000000,"parent":{}{kids},"type":"comment"}}"#,
+                    id - 1
+                ),
+            )
+        })
+        .collect()
+}
+
+#[test]
+fn source_comment_limits_apply_without_user_override() {
+    let routes = comment_chain(8);
+    let routes = routes
+        .iter()
+        .map(|(path, body)| (path.as_str(), body.as_str()))
+        .collect::<Vec<_>>();
+    let story = story_with_kids(vec![1]);
+
+    let defaults = NewsChannel::HackerNews.comment_limits();
+    let limits = Settings::default().comment_limits(defaults);
+    assert_eq!(limits, defaults);
+
+    let client = fake_client(&routes).with_comment_limits(limits);
+    let comments = futures::executor::block_on(client.fetch_comments(&story)).unwrap();
+    let max_depth = comments.iter().map(|c| c.depth).max();
+    assert_eq!(comments.len(), defaults.max_depth + 1);
+    assert_eq!(max_depth, Some(defaults.max_depth));
+
+    let settings = Settings {
+        comment_max_depth: Some(1),
+        ..Settings::default()
+    };
+    let client = fake_client(&routes).with_comment_limits(settings.comment_limits(defaults));
+    let comments = futures::executor::block_on(client.fetch_comments(&story)).unwrap();
+    assert_eq!(comments.len(), 2);
+}
+
+#[test]
+fn loading_more_roots_appends_the_next_batch_in_order() {
+    let item = |id: i64, parent: i64, kids: &str| {
+        format!(
+            r#"{{"id":{id},"by":"user{id}","text":"hi","time":1700000000,"parent":{parent}{kids},"type":"comment"}}"#
+        )
+    };
+    let routes = [
+        ("/v0/item/1.json".to_string(), item(1, 100, "")),
+        (
+            "/v0/item/2.json".to_string(),
+            item(2, 100, r#","kids":[21]"#),
+        ),
+        ("/v0/item/21.json".to_string(), item(21, 2, "")),
+        (
+            "/v0/item/3.json".to_string(),
+            item(3, 100, r#","kids":[31]"#),
+        ),
+        ("/v0/item/31.json".to_string(), item(31, 3, "")),
+        ("/v0/item/4.json".to_string(), item(4, 100, "")),
+    ];
+    let routes = routes
+        .iter()
+        .map(|(path, body)| (path.as_str(), body.as_str()))
+        .collect::<Vec<_>>();
+    let limits = CommentLimits {
+        max_depth: 4,
+        max_per_level: 2,
+    };
+    let client = fake_client(&routes).with_comment_limits(limits);
+    let kids = vec![1, 2, 3, 4];
+    let story = story_with_kids(kids.clone());
+
+    let mut comments = futures::executor::block_on(client.fetch_comments(&story)).unwrap();
+    let mut loaded: HashSet<i64> = client.initial_root_ids(&kids).into_iter().collect();
+    assert_eq!(
+        comments.iter().map(|c| c.id).collect::<Vec<_>>(),
+        vec![1, 2, 21]
+    );
+
+    let next = client.next_root_ids(&kids, &loaded);
+    assert_eq!(next, vec![3, 4]);
+    comments.extend(futures::executor::block_on(
+        client.fetch_more_comments(&next),
+    ));
+    loaded.extend(next);
+
+    let summary = comments.iter().map(|c| (c.id, c.depth)).collect::<Vec<_>>();
+    assert_eq!(
+        summary,
+        vec![(1, 0), (2, 0), (21, 1), (3, 0), (31, 1), (4, 0)]
+    );
+    assert!(client.next_root_ids(&kids, &loaded).is_empty());
+}
+
+#[test]
+fn fetch_replies_returns_every_direct_reply_below_the_parent_depth() {
+    let client = fake_client(&[
+        (
+            "/v0/item/5.json",
+            r#"{"id":5,"by":"alice","text":"deep","time":1700000000,"parent":4,"kids":[6,7],"type":"comment"}"#,
+        ),
+        (
+            "/v0/item/6.json",
+            r#"{"id":6,"by":"bob","text":"reply","time":1700000100,"parent":5,"kids":[8],"type":"comment"}"#,
+        ),
+        (
+            "/v0/item/7.json",
+            r#"{"id":7,"by":"carol","text":"reply","time":1700000200,"parent":5,"type":"comment"}"#,
+        ),
+        (
+            "/v0/item/8.json",
+            r#"{"id":8,"by":"dave","text":"nested","time":1700000300,"parent":6,"type":"comment"}"#,
+        ),
+    ])
+    .with_comment_limits(CommentLimits {
+        max_depth: 3,
+        max_per_level: 1,
+    });
+
+    let replies = futures::executor::block_on(client.fetch_replies(5, 3)).unwrap();
+    let summary = replies
+        .iter()
+        .map(|c| (c.id, c.depth, c.parent))
+        .collect::<Vec<_>>();
+    assert_eq!(summary, vec![(6, 4, 5), (8, 5, 6), (7, 4, 5)]);
+}
+
+const ALGOLIA_TREE: &str = r#"{
+    "id": 100, "author": "op", "created_at_i": 1700000000, "parent_id": null,
+    "children": [
+        {"id": 2, "author": "carol", "text": "second ranked", "created_at_i": 1700000200, "parent_id": 100, "children": []},
+        {"id": 1, "author": "alice", "text": "first ranked", "created_at_i": 1700000100, "parent_id": 100, "children": [
+            {"id": 11, "author": "bob", "text": "reply", "created_at_i": 1700000300, "parent_id": 1, "children": [
+                {"id": 111, "author": "dave", "text": "nested", "created_at_i": 1700000400, "parent_id": 11, "children": []}
+            ]},
+            {"id": 12, "author": null, "text": null, "created_at_i": 1700000500, "parent_id": 1, "children": []}
+        ]}
+    ]
+}"#;
+
+#[test]
+fn algolia_tree_maps_to_depth_first_comments_in_ranked_order() {
+    let item: AlgoliaItem = serde_json::from_str(ALGOLIA_TREE).unwrap();
+    let comments =
+        api::comments_from_algolia(&item, &[1, 2], NewsChannel::HackerNews.comment_limits());
+
+    let summary = comments
+        .iter()
+        .map(|c| (c.id, c.depth, c.parent))
+        .collect::<Vec<_>>();
+    assert_eq!(
+        summary,
+        vec![(1, 0, 100), (11, 1, 1), (111, 2, 11), (2, 0, 100)]
+    );
+    assert_eq!(comments[0].kids, Some(vec![11, 12]));
+    assert_eq!(comments[0].reply_count, 2);
+    assert_eq!(comments[2].author(), "dave");
+    assert_eq!(comments[2].time, 1_700_000_400);
+}
+
+#[test]
+fn fetch_comments_uses_algolia_tree_and_falls_back_to_items() {
+    let story = story_with_kids(vec![1, 2]);
+
+    let client = fake_client(&[("/api/v1/items/100", ALGOLIA_TREE)]);
+    let comments = futures::executor::block_on(client.fetch_comments(&story)).unwrap();
+    assert_eq!(comments.len(), 4);
+
+    // Algolia 不可用时逐条请求 Firebase
+    let client = fake_client(&[(
+        "/v0/item/1.json",
+        r#"{"id":1,"by":"alice","text":"hi","time":1700000000,"parent":100,"type":"comment"}"#,
+    )]);
+    let comments = futures::executor::block_on(client.fetch_comments(&story)).unwrap();
+    assert_eq!(comments.iter().map(|c| c.id).collect::<Vec<_>>(), vec![1]);
+}
+
+#[test]
+fn list_loads_summaries_and_selection_fetches_the_full_story() {
+    let client = fake_client(&[
+        ("/v0/topstories.json", "[1, 2]"),
+        (
+            "/v0/item/1.json",
+            r#"{"id":1,"title":"Ask HN: Anything?","score":5,"by":"op","time":1700000000,"descendants":2,"kids":[10,11],"text":"Long body","type":"story"}"#,
+        ),
+        (
+            "/v0/item/2.json",
+            r#"{"id":2,"title":"A link","url":"https://example.com","score":9,"by":"op","time":1700000000,"descendants":0,"type":"story"}"#,
+        ),
+    ]);
+
+    let summaries = futures::executor::block_on(client.fetch_top_stories(30)).unwrap();
+    let summary = summaries
+        .iter()
+        .map(|s| (s.id, s.has_text, s.comment_count()))
+        .collect::<Vec<_>>();
+    assert_eq!(summary, vec![(2, false, 0), (1, true, 2)]);
+
+    let story = futures::executor::block_on(client.fetch_story(1)).unwrap();
+    assert_eq!(story.kids, Some(vec![10, 11]));
+    assert_eq!(story.text.as_deref(), Some("Long body"));
+    assert_eq!(summaries[1].to_story().kids, None);
+}
+
+#[test]
+fn opening_a_story_refreshes_its_listed_score() {
+    let client = fake_client(&[
+        ("/v0/topstories.json", "[1]"),
+        (
+            "/v0/item/1.json",
+            r#"{"id":1,"title":"Show HN: A thing","score":5,"by":"op","time":1700000000,"descendants":1,"kids":[10],"type":"story"}"#,
+        ),
+    ]);
+    let mut stories = futures::executor::block_on(client.fetch_top_stories(30)).unwrap();
+
+    // 列表缓存之后分数和评论数又涨了
+    let client = fake_client(&[(
+        "/v0/item/1.json",
+        r#"{"id":1,"title":"Show HN: A thing","score":42,"by":"op","time":1700000000,"descendants":7,"kids":[10,11],"type":"story"}"#,
+    )]);
+    let story = futures::executor::block_on(client.fetch_story(1)).unwrap();
+
+    assert!(stories[0].refresh_from(&story));
+    assert_eq!((stories[0].score, stories[0].comment_count()), (42, 7));
+    assert!(!stories[0].refresh_from(&story));
+}
+
+#[test]
+fn fetch_item_raw_returns_the_json_unchanged() {
+    let client = fake_client(&[(
+        "/v0/item/7.json",
+        r#"{"id":7,"by":"alice","text":"hi","time":1700000000,"parent":1,"type":"comment","dead":true}"#,
+    )]);
+
+    let value = futures::executor::block_on(client.fetch_item_raw(7)).unwrap();
+    assert_eq!(value["id"], 7);
+    assert_eq!(value["dead"], true);
+    assert_eq!(futures::executor::block_on(client.fetch_item_raw(8)), None);
+}
+
+#[test]
+fn channels_load_their_own_feed() {
+    let client = fake_client(&[
+        ("/v0/newstories.json", "[2, 1]"),
+        ("/v0/askstories.json", "[1]"),
+        (
+            "/v0/item/1.json",
+            r#"{"id":1,"title":"Ask HN: Anything?","score":50,"by":"op","time":1700000000,"text":"Body","type":"story"}"#,
+        ),
+        (
+            "/v0/item/2.json",
+            r#"{"id":2,"title":"Just posted","score":1,"by":"op","time":1700000600,"type":"story"}"#,
+        ),
+    ]);
+
+    // New 保持榜单的时间顺序，不按分数重排
+    let new = futures::executor::block_on(client.fetch_stories(NewsChannel::New, 30)).unwrap();
+    assert_eq!(new.iter().map(|s| s.id).collect::<Vec<_>>(), vec![2, 1]);
+
+    let ask = futures::executor::block_on(client.fetch_stories(NewsChannel::Ask, 30)).unwrap();
+    assert_eq!(ask.iter().map(|s| s.id).collect::<Vec<_>>(), vec![1]);
+
+    // 没有对应榜单时报错，而不是退回首页
+    assert!(futures::executor::block_on(client.fetch_stories(NewsChannel::Jobs, 30)).is_err());
+    for channel in NewsChannel::all() {
+        assert_eq!(NewsChannel::from_key(channel.key()), Some(*channel));
+    }
+}
+
+#[test]
+fn search_maps_algolia_hits_to_stories() {
+    let client = fake_client(&[(
+        "/api/v1/search",
+        r#"{"hits": [
+            {"objectID": "42", "title": "Rust in production", "url": "https://example.com/rust", "points": 310, "author": "alice", "created_at_i": 1700000000, "num_comments": 120},
+            {"objectID": "43", "title": "Ask HN: Learning Rust?", "url": null, "points": 12, "author": "bob", "created_at_i": 1700000500, "num_comments": 4, "story_text": "Where to start?"},
+            {"objectID": "not-a-number", "title": "Broken", "points": 1}
+        ]}"#,
+    )]);
+
+    let stories = futures::executor::block_on(client.search_stories("rust", 30)).unwrap();
+    let summary = stories
+        .iter()
+        .map(|s| (s.id, s.score, s.by.as_str(), s.descendants))
+        .collect::<Vec<_>>();
+    assert_eq!(
+        summary,
+        vec![(42, 310, "alice", Some(120)), (43, 12, "bob", Some(4))]
+    );
+    assert_eq!(stories[0].url.as_deref(), Some("https://example.com/rust"));
+    assert_eq!(stories[1].text.as_deref(), Some("Where to start?"));
+    assert_eq!(stories[1].kids, None);
+}
+
+/// 让出一次执行权，让同时发出的其他请求也开始
+async fn yield_once() {
+    let mut yielded = false;
+    futures::future::poll_fn(|cx| {
+        if yielded {
+            return Poll::Ready(());
+        }
+        yielded = true;
+        cx.waker().wake_by_ref();
+        Poll::Pending
+    })
+    .await
+}
+
+#[test]
+fn comment_requests_stay_within_the_concurrency_limit() {
+    let in_flight = Arc::new(AtomicUsize::new(0));
+    let peak = Arc::new(AtomicUsize::new(0));
+    let http: Arc<dyn HttpClient> = FakeHttpClient::create({
+        let in_flight = in_flight.clone();
+        let peak = peak.clone();
+        move |req| {
+            let in_flight = in_flight.clone();
+            let peak = peak.clone();
+            async move {
+                let now = in_flight.fetch_add(1, Ordering::SeqCst) + 1;
+                peak.fetch_max(now, Ordering::SeqCst);
+                yield_once().await;
+                in_flight.fetch_sub(1, Ordering::SeqCst);
+
+                let id = req
+                    .uri()
+                    .path()
+                    .trim_start_matches("/v0/item/")
+                    .trim_end_matches(".json")
+                    .to_string();
+                let body = format!(
+                    r#"{{"id":{id},"by":"u{id}","text":"hi","time":1700000000,"parent":100,"type":"comment"}}"#
+                );
+                Ok(Response::builder()
+                    .status(200)
+                    .body(AsyncBody::from(body))
+                    .unwrap())
+            }
+        }
+    });
+    let client = HackerNewsClient::new(http)
+        .with_algolia(false)
+        .with_max_concurrent_requests(3);
+
+    let kids = (1..=10).collect::<Vec<i64>>();
+    let comments =
+        futures::executor::block_on(client.fetch_comments(&story_with_kids(kids.clone()))).unwrap();
+
+    // 顺序和 `kids` 一致，但同时最多 3 个请求
+    let ids = comments.iter().map(|c| c.id).collect::<Vec<_>>();
+    assert_eq!(ids, kids);
+    assert_eq!(peak.load(Ordering::SeqCst), 3);
+}
+
+/// 前 `failures` 次返回 `status`，之后返回一个 story；同时记录请求次数
+fn flaky_client(status: u16, failures: usize) -> (HackerNewsClient, Arc<AtomicUsize>) {
+    let requests = Arc::new(AtomicUsize::new(0));
+    let http: Arc<dyn HttpClient> = FakeHttpClient::create({
+        let requests = requests.clone();
+        move |_req| {
+            let attempt = requests.fetch_add(1, Ordering::SeqCst);
+            async move {
+                let response = if attempt < failures {
+                    Response::builder().status(status).body(AsyncBody::empty())
+                } else {
+                    Response::builder().status(200).body(AsyncBody::from(
+                        r#"{"id":7,"title":"Back","score":1,"by":"op","time":1700000000,"type":"story"}"#
+                            .to_string(),
+                    ))
+                };
+                Ok(response.unwrap())
+            }
+        }
+    });
+    (HackerNewsClient::new(http), requests)
+}
+
+#[test]
+fn server_errors_are_retried_but_missing_items_are_not() {
+    let (client, requests) = flaky_client(503, 2);
+    let story = futures::executor::block_on(client.fetch_story(7)).unwrap();
+    assert_eq!(story.title, "Back");
+    assert_eq!(requests.load(Ordering::SeqCst), 3);
+
+    // 第一次加三次重试之后放弃
+    let (client, requests) = flaky_client(500, usize::MAX);
+    let error = futures::executor::block_on(client.fetch_story(7)).unwrap_err();
+    assert!(error.starts_with("HTTP 500"), "{error}");
+    assert_eq!(requests.load(Ordering::SeqCst), 4);
+
+    // 限流和请求超时也是暂时的
+    for status in [408, 429] {
+        let (client, requests) = flaky_client(status, 1);
+        assert!(futures::executor::block_on(client.fetch_story(7)).is_ok());
+        assert_eq!(requests.load(Ordering::SeqCst), 2);
+    }
+
+    let (client, requests) = flaky_client(404, 1);
+    assert!(futures::executor::block_on(client.fetch_story(7)).is_err());
+    assert_eq!(requests.load(Ordering::SeqCst), 1);
+}
+
+#[test]
+fn poll_options_load_in_order_and_skip_missing_parts() {
+    let client = fake_client(&[
+        (
+            "/v0/item/50.json",
+            r#"{"id":50,"title":"Poll: Tabs or spaces?","score":40,"by":"op","time":1700000000,"descendants":0,"parts":[51,52,53],"type":"poll"}"#,
+        ),
+        (
+            "/v0/item/51.json",
+            r#"{"id":51,"text":"Tabs","score":12,"by":"op","time":1700000000,"poll":50,"type":"pollopt"}"#,
+        ),
+        (
+            "/v0/item/53.json",
+            r#"{"id":53,"text":"Spaces &amp; more","score":30,"by":"op","time":1700000000,"poll":50,"type":"pollopt"}"#,
+        ),
+    ]);
+
+    let story = futures::executor::block_on(client.fetch_story(50)).unwrap();
+    assert!(story.is_poll());
+    assert_eq!(story.parts, Some(vec![51, 52, 53]));
+
+    let options = futures::executor::block_on(client.fetch_poll_options(&story));
+    let options = options
+        .iter()
+        .map(|option| (option.id, option.label(), option.score))
+        .collect::<Vec<_>>();
+    assert_eq!(
+        options,
+        vec![
+            (51, "Tabs".to_string(), 12),
+            (53, "Spaces & more".to_string(), 30)
+        ]
+    );
+
+    // 普通 story 即使带了 parts 也不请求
+    let story = Story {
+        parts: Some(vec![51]),
+        ..story_with_kids(Vec::new())
+    };
+    assert!(futures::executor::block_on(client.fetch_poll_options(&story)).is_empty());
+}
+
+#[test]
+fn news_source_trait_object_matches_the_client() {
+    let client = fake_client(&[
+        ("/v0/topstories.json", "[2, 1]"),
+        (
+            "/v0/item/1.json",
+            r#"{"id":1,"title":"Higher","score":50,"by":"op","time":1700000000,"kids":[3],"type":"story"}"#,
+        ),
+        (
+            "/v0/item/2.json",
+            r#"{"id":2,"title":"Lower","score":5,"by":"op","time":1700000600,"type":"story"}"#,
+        ),
+        (
+            "/v0/item/3.json",
+            r#"{"id":3,"by":"alice","text":"First","time":1700000100,"parent":1,"type":"comment"}"#,
+        ),
+    ])
+    .with_algolia(false);
+    let source: Arc<dyn NewsSource> = Arc::new(client.clone());
+
+    let ids = futures::executor::block_on(source.fetch_story_ids(NewsChannel::HackerNews)).unwrap();
+    assert_eq!(ids, vec![2, 1]);
+    assert_eq!(
+        futures::executor::block_on(source.fetch_summaries(NewsChannel::HackerNews, &ids)),
+        futures::executor::block_on(client.fetch_summaries(NewsChannel::HackerNews, &ids))
+    );
+
+    let story = futures::executor::block_on(source.fetch_story(1)).unwrap();
+    assert_eq!(
+        futures::executor::block_on(source.fetch_comments(&story)).unwrap(),
+        futures::executor::block_on(client.fetch_comments(&story)).unwrap()
+    );
+    assert_eq!(source.initial_root_ids(&[3]), client.initial_root_ids(&[3]));
+}
@@ -0,0 +1,110 @@
+use std::path::{Path, PathBuf};
+
+/// 应用读写文件的目录。启动时从环境变量解析一次，之后作为参数传给需要读写文件的地方；
+/// 测试用 `in_dir` 指向临时目录，不会碰到真实目录，也不用改进程的环境变量
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AppDirs {
+    /// 阅读缓存和导出文件
+    pub cache: PathBuf,
+    /// 用户设置、书签和阅读记录。缓存目录可能被系统或清理工具清空，这些不能放在那里
+    pub data: PathBuf,
+}
+
+impl AppDirs {
+    pub fn from_env() -> Self {
+        Self {
+            cache: cache_dir_from_env(),
+            data: data_dir_from_env(),
+        }
+    }
+
+    /// 全部放在 `root` 下面
+    pub fn in_dir(root: &Path) -> Self {
+        Self {
+            cache: root.join("cache"),
+            data: root.join("data"),
+        }
+    }
+
+    /// 早期版本把这些文件放在缓存目录。数据目录里还没有同名文件时把旧文件搬过来，
+    /// 搬不动时保留原样，下次启动再试
+    pub fn migrate_from_cache(&self, file_names: &[&str]) {
+        for name in file_names {
+            let from = self.cache.join(name);
+            let to = self.data.join(name);
+            if to.exists() || !from.is_file() {
+                continue;
+            }
+            if std::fs::create_dir_all(&self.data).is_err() {
+                return;
+            }
+            // 两个目录可能不在同一个文件系统上，rename 失败时改为复制
+            if std::fs::rename(&from, &to).is_err() {
+                let _ = std::fs::copy(&from, &to);
+            }
+        }
+    }
+
+    /// 按 URL 缓存的文章
+    pub fn reader_cache(&self) -> PathBuf {
+        self.cache.join("reader")
+    }
+
+    /// 导出的快照和主题
+    pub fn exports(&self) -> PathBuf {
+        self.cache.join("exports")
+    }
+}
+
+fn cache_dir_from_env() -> PathBuf {
+    if let Some(dir) = std::env::var_os("ONEAPP_CACHE_DIR") {
+        return PathBuf::from(dir);
+    }
+
+    if let Some(dir) = std::env::var_os("XDG_CACHE_HOME") {
+        return PathBuf::from(dir).join("oneapp");
+    }
+
+    #[cfg(target_os = "macos")]
+    {
+        if let Some(home) = std::env::var_os("HOME") {
+            return PathBuf::from(home).join("Library/Caches/OneApp");
+        }
+    }
+
+    if let Some(home) = std::env::var_os("HOME").or_else(|| std::env::var_os("USERPROFILE")) {
+        return PathBuf::from(home).join(".cache/oneapp");
+    }
+
+    std::env::temp_dir().join("oneapp-cache")
+}
+
+fn data_dir_from_env() -> PathBuf {
+    if let Some(dir) = std::env::var_os("ONEAPP_DATA_DIR") {
+        return PathBuf::from(dir);
+    }
+
+    if let Some(dir) = std::env::var_os("XDG_CONFIG_HOME") {
+        return PathBuf::from(dir).join("oneapp");
+    }
+
+    #[cfg(target_os = "macos")]
+    {
+        if let Some(home) = std::env::var_os("HOME") {
+            return PathBuf::from(home).join("Library/Application Support/OneApp");
+        }
+    }
+
+    #[cfg(target_os = "windows")]
+    {
+        if let Some(dir) = std::env::var_os("APPDATA") {
+            return PathBuf::from(dir).join("OneApp");
+        }
+    }
+
+    if let Some(home) = std::env::var_os("HOME").or_else(|| std::env::var_os("USERPROFILE")) {
+        return PathBuf::from(home).join(".config/oneapp");
+    }
+
+    std::env::temp_dir().join("oneapp-data")
+}
@@ -0,0 +1,29 @@
+use crate::app_dirs::AppDirs;
+
+#[test]
+fn legacy_files_move_from_cache_to_data_dir() {
+    let root = std::env::temp_dir().join(format!("oneapp-dirs-{}", std::process::id()));
+    let _ = std::fs::remove_dir_all(&root);
+    let dirs = AppDirs::in_dir(&root);
+    std::fs::create_dir_all(&dirs.cache).unwrap();
+    std::fs::write(dirs.cache.join("settings.json"), "{\"old\":true}").unwrap();
+    std::fs::write(dirs.cache.join("bookmarks.json"), "old").unwrap();
+    // 数据目录里已经有的文件不会被旧文件覆盖
+    std::fs::create_dir_all(&dirs.data).unwrap();
+    std::fs::write(dirs.data.join("bookmarks.json"), "new").unwrap();
+
+    dirs.migrate_from_cache(&["settings.json", "bookmarks.json", "missing.json"]);
+
+    assert_eq!(
+        std::fs::read_to_string(dirs.data.join("settings.json")).unwrap(),
+        "{\"old\":true}"
+    );
+    assert!(!dirs.cache.join("settings.json").exists());
+    assert_eq!(
+        std::fs::read_to_string(dirs.data.join("bookmarks.json")).unwrap(),
+        "new"
+    );
+    assert!(!dirs.data.join("missing.json").exists());
+
+    let _ = std::fs::remove_dir_all(&root);
+}
@@ -0,0 +1,479 @@
+use crate::app_dirs::AppDirs;
+use crate::comment_search_tests::comment;
+use crate::history::SeenComments;
+use crate::models::{NewsChannel, ReaderSource, StorySort};
+use crate::reader::{ReaderLinkTarget, ReaderLoadState};
+use crate::reader_view::{ReaderTheme, ReaderWidth};
+use crate::settings::Settings;
+use crate::theme::Theme;
+use crate::{AppState, SIDEBAR_WIDTH, TITLEBAR_HEIGHT, TRAFFIC_LIGHTS_RIGHT};
+use gpui::TestAppContext;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+/// 每个测试一个临时目录，不读写真实的设置和本地记录
+fn test_dirs() -> AppDirs {
+    static NEXT: AtomicUsize = AtomicUsize::new(0);
+    let id = NEXT.fetch_add(1, Ordering::Relaxed);
+    AppDirs::in_dir(&std::env::temp_dir().join(format!("oneapp-app-{}-{id}", std::process::id())))
+}
+
+#[gpui::test]
+fn opening_reader_moves_focus_and_closing_restores_it(cx: &mut TestAppContext) {
+    let (view, cx) = cx.add_window_view(|cx| AppState::new(Settings::default(), test_dirs(), cx));
+
+    view.update(cx, |state, cx| {
+        state.open_reader("https://example.com/article".to_string(), None, cx);
+        assert!(state.reader_focus_handle.is_focused(cx));
+
+        state.close_reader(cx);
+        assert!(state.story_list_focus_handle.is_focused(cx));
+    });
+}
+
+#[gpui::test]
+fn reader_tabs_keep_their_own_article_and_scroll(cx: &mut TestAppContext) {
+    let (view, cx) = cx.add_window_view(|cx| AppState::new(Settings::default(), test_dirs(), cx));
+
+    view.update(cx, |state, cx| {
+        state.open_reader(
+            "https://example.com/a".to_string(),
+            Some("A".to_string()),
+            cx,
+        );
+        state
+            .reader_scroll_handle
+            .set_offset(gpui::point(gpui::px(0.), gpui::px(-300.)));
+        state.open_reader(
+            "https://example.com/b".to_string(),
+            Some("B".to_string()),
+            cx,
+        );
+
+        let urls = state
+            .reader_tabs
+            .tabs()
+            .iter()
+            .map(|tab| tab.url.as_str())
+            .collect::<Vec<_>>();
+        assert_eq!(urls, vec!["https://example.com/a", "https://example.com/b"]);
+        assert_eq!(state.reader().unwrap().url, "https://example.com/b");
+        assert_eq!(state.reader_scroll_handle.offset().y, gpui::px(0.));
+
+        // 重新打开 A 回到它自己的标签和滚动位置
+        state.open_reader("https://example.com/a".to_string(), None, cx);
+        assert_eq!(state.reader_tabs.tabs().len(), 2);
+        assert_eq!(state.reader().unwrap().title(), "A");
+        assert_eq!(state.reader_scroll_handle.offset().y, gpui::px(-300.));
+
+        state.close_reader_tab(0, cx);
+        assert_eq!(state.reader().unwrap().url, "https://example.com/b");
+
+        for i in 0..crate::reader::MAX_READER_TABS + 2 {
+            state.open_reader(format!("https://example.com/{i}"), None, cx);
+        }
+        assert_eq!(
+            state.reader_tabs.tabs().len(),
+            crate::reader::MAX_READER_TABS
+        );
+    });
+}
+
+#[gpui::test]
+fn article_links_replace_or_open_a_tab_per_setting(cx: &mut TestAppContext) {
+    let settings = Settings {
+        reader_link_target: ReaderLinkTarget::Replace,
+        ..Settings::default()
+    };
+    let (view, cx) = cx.add_window_view(|cx| AppState::new(settings, test_dirs(), cx));
+
+    view.update(cx, |state, cx| {
+        state.open_reader(
+            "https://example.com/a".to_string(),
+            Some("A".to_string()),
+            cx,
+        );
+        state
+            .reader_scroll_handle
+            .set_offset(gpui::point(gpui::px(0.), gpui::px(-200.)));
+
+        // 默认在当前标签替换，← Back 回到原文和原来的位置
+        state.open_article_link("https://example.com/linked", cx);
+        assert_eq!(state.reader_tabs.tabs().len(), 1);
+        assert_eq!(state.reader().unwrap().url, "https://example.com/linked");
+        assert!(state.reader().unwrap().can_go_back());
+
+        state.reader_back(cx);
+        assert_eq!(state.reader().unwrap().url, "https://example.com/a");
+        assert!(!state.reader().unwrap().can_go_back());
+        assert_eq!(state.reader_scroll_handle.offset().y, gpui::px(-200.));
+
+        // 新标签模式：原文的标签保持不变
+        state.settings.reader_link_target = ReaderLinkTarget::NewTab;
+        state.open_article_link("https://example.com/linked", cx);
+        let urls = state
+            .reader_tabs
+            .tabs()
+            .iter()
+            .map(|tab| tab.url.as_str())
+            .collect::<Vec<_>>();
+        assert_eq!(
+            urls,
+            vec!["https://example.com/a", "https://example.com/linked"]
+        );
+        assert!(!state.reader().unwrap().can_go_back());
+
+        // 没有后退页面时 ← Back 关闭阅读器
+        state.reader_back(cx);
+        assert!(state.reader().is_none());
+        assert_eq!(state.reader_tabs.tabs().len(), 2);
+    });
+}
+
+fn link_story(id: i64) -> crate::models::Story {
+    crate::models::Story {
+        id,
+        title: format!("Story {id}"),
+        url: Some(format!("https://example.com/{id}")),
+        score: 1,
+        by: "op".to_string(),
+        time: 1_700_000_000,
+        descendants: Some(1),
+        kids: Some(vec![id * 10]),
+        text: None,
+        parts: None,
+        story_type: "story".to_string(),
+    }
+}
+
+#[gpui::test]
+fn split_view_selection_opens_article_and_comments(cx: &mut TestAppContext) {
+    let settings = Settings {
+        split_view_on_select: true,
+        ..Settings::default()
+    };
+    let (view, cx) = cx.add_window_view(|cx| AppState::new(settings, test_dirs(), cx));
+
+    view.update(cx, |state, cx| {
+        state.stories = vec![(&link_story(1)).into()];
+        state.select_story(1, cx);
+
+        assert_eq!(state.selected_story_id, Some(1));
+        assert!(state.is_loading_comments);
+        let reader = state.reader().expect("reader pane should open");
+        assert_eq!(reader.url, "https://example.com/1");
+    });
+}
+
+#[gpui::test]
+fn channel_shortcut_index_selects_matching_channel(cx: &mut TestAppContext) {
+    let (view, cx) = cx.add_window_view(|cx| AppState::new(Settings::default(), test_dirs(), cx));
+
+    view.update(cx, |state, cx| {
+        let last = NewsChannel::all().len() - 1;
+        state.select_channel_at(last, cx);
+        assert_eq!(state.selected_channel, NewsChannel::all()[last]);
+
+        // 超出范围的快捷键不切换频道
+        let before = state.selected_channel;
+        state.select_channel_at(NewsChannel::all().len() + 3, cx);
+        assert_eq!(state.selected_channel, before);
+
+        state.select_channel_at(0, cx);
+        assert_eq!(state.selected_channel, NewsChannel::all()[0]);
+    });
+}
+
+#[gpui::test]
+fn story_with_link_and_text_opens_configured_reader_source(cx: &mut TestAppContext) {
+    let story = crate::models::Story {
+        text: Some("Looking for feedback<p>Second paragraph with <i>details</i>.".to_string()),
+        ..link_story(7)
+    };
+
+    let settings = Settings {
+        reader_default_source: ReaderSource::SelfText,
+        ..Settings::default()
+    };
+    let (view, cx) = cx.add_window_view(|cx| AppState::new(settings, test_dirs(), cx));
+    view.update(cx, |state, cx| {
+        state.read_story(&story, cx);
+
+        let reader = state.reader().expect("reader should open");
+        assert_eq!(reader.url, story.item_url());
+        let ReaderLoadState::Ready(article) = &reader.state else {
+            panic!("self-text should render without a fetch");
+        };
+        assert_eq!(article.blocks.len(), 2);
+    });
+
+    let (view, cx) = cx.add_window_view(|cx| AppState::new(Settings::default(), test_dirs(), cx));
+    view.update(cx, |state, cx| {
+        state.read_story(&story, cx);
+        let reader = state.reader().expect("reader should open");
+        assert_eq!(reader.url, "https://example.com/7");
+    });
+}
+
+#[gpui::test]
+fn seen_comments_render_muted_and_new_ones_stay_primary(cx: &mut TestAppContext) {
+    let settings = Settings {
+        dim_seen_comments: true,
+        ..Settings::default()
+    };
+    let (view, cx) = cx.add_window_view(|cx| AppState::new(settings, test_dirs(), cx));
+
+    view.update(cx, |state, cx| {
+        state.stories = vec![(&link_story(1)).into()];
+        state.seen_comments = SeenComments::default();
+        state.seen_comments.record(1, [10], 1_700_000_000);
+        state.select_story(1, cx);
+
+        assert_eq!(state.comment_text_color(10), state.theme.text_muted);
+        assert_eq!(state.comment_text_color(11), state.theme.text_primary);
+
+        state.settings.dim_seen_comments = false;
+        assert_eq!(state.comment_text_color(10), state.theme.text_primary);
+    });
+}
+
+#[gpui::test]
+fn compact_header_shares_the_traffic_light_row(cx: &mut TestAppContext) {
+    let (view, cx) = cx.add_window_view(|cx| AppState::new(Settings::default(), test_dirs(), cx));
+
+    view.update(cx, |state, _cx| {
+        let spacious = state.story_list_header_height();
+        assert_eq!(state.story_list_title_inset(), 0.);
+
+        state.settings.compact_header = true;
+        assert_eq!(state.story_list_header_height(), TITLEBAR_HEIGHT);
+        assert!(state.story_list_header_height() < spacious);
+        // 标题从 traffic lights 右侧开始，按钮区域不被遮挡
+        assert!(SIDEBAR_WIDTH + state.story_list_title_inset() >= TRAFFIC_LIGHTS_RIGHT);
+    });
+}
+
+#[gpui::test]
+fn comment_search_expands_collapsed_branch_and_escape_restores(cx: &mut TestAppContext) {
+    let (view, cx) = cx.add_window_view(|cx| AppState::new(Settings::default(), test_dirs(), cx));
+
+    view.update(cx, |state, cx| {
+        state.selected_story_id = Some(100);
+        state.comments = vec![
+            comment(1, 100, 0, "top level"),
+            comment(2, 1, 1, "buried answer"),
+            comment(3, 100, 0, "unrelated"),
+        ];
+        state.collapsed_comments.insert(1);
+        assert!(state.visible_comments().iter().all(|c| c.id != 2));
+
+        state.start_comment_search(cx);
+        state.set_comment_search_query("answer".to_string(), cx);
+        assert!(!state.is_collapsed(1));
+        assert!(state.visible_comments().iter().any(|c| c.id == 2));
+
+        // 查找词不再命中时恢复原来的折叠
+        state.set_comment_search_query("nothing here".to_string(), cx);
+        assert!(state.is_collapsed(1));
+
+        state.set_comment_search_query("answer".to_string(), cx);
+        state.clear_comment_search(cx);
+        assert!(state.comment_search.is_none());
+        assert!(state.is_collapsed(1));
+    });
+}
+
+#[gpui::test]
+fn sidebar_toggle_swaps_the_palette(cx: &mut TestAppContext) {
+    let (view, cx) = cx.add_window_view(|cx| AppState::new(Settings::default(), test_dirs(), cx));
+
+    view.update(cx, |state, cx| {
+        let light = crate::theme::Theme::light().bg_primary;
+        let dark = crate::theme::Theme::dark().bg_primary;
+        state.set_dark_theme(false, cx);
+
+        state.toggle_theme(cx);
+        assert!(state.is_dark);
+        assert_eq!(state.theme.bg_primary, dark);
+
+        state.toggle_theme(cx);
+        assert!(!state.is_dark);
+        assert_eq!(state.theme.bg_primary, light);
+    });
+}
+
+#[gpui::test]
+fn system_appearance_is_followed_until_the_user_toggles(cx: &mut TestAppContext) {
+    let (view, cx) = cx.add_window_view(|cx| AppState::new(Settings::default(), test_dirs(), cx));
+
+    view.update(cx, |state, cx| {
+        state.on_system_appearance_changed(true, cx);
+        assert!(state.is_dark);
+        state.on_system_appearance_changed(false, cx);
+        assert!(!state.is_dark);
+
+        // 手动切换之后系统外观的变化不再覆盖用户的选择
+        state.toggle_theme(cx);
+        assert!(state.is_dark);
+        state.on_system_appearance_changed(false, cx);
+        assert!(state.is_dark);
+    });
+}
+
+#[gpui::test]
+fn system_appearance_is_ignored_when_disabled(cx: &mut TestAppContext) {
+    let settings = Settings {
+        follow_system_appearance: false,
+        ..Settings::default()
+    };
+    let (view, cx) = cx.add_window_view(|cx| AppState::new(settings, test_dirs(), cx));
+    view.update(cx, |state, cx| {
+        state.on_system_appearance_changed(true, cx);
+        assert!(!state.is_dark);
+    });
+}
+
+#[gpui::test]
+fn rank_jump_follows_the_displayed_order(cx: &mut TestAppContext) {
+    let (view, cx) = cx.add_window_view(|cx| AppState::new(Settings::default(), test_dirs(), cx));
+
+    view.update(cx, |state, cx| {
+        state.stories = (1..=3)
+            .map(|id| {
+                let mut story = link_story(id);
+                story.descendants = Some(id as i32 * 10);
+                (&story).into()
+            })
+            .collect();
+        state
+            .settings
+            .set_story_sort(NewsChannel::HackerNews, StorySort::Comments);
+
+        // 按评论数排序后第 1 名是评论最多的 3
+        state.jump_to_rank(1, cx);
+        assert_eq!(state.selected_story_id, Some(3));
+        state.jump_to_rank(3, cx);
+        assert_eq!(state.selected_story_id, Some(1));
+
+        // 越界时保持当前选中
+        state.jump_to_rank(4, cx);
+        state.jump_to_rank(0, cx);
+        assert_eq!(state.selected_story_id, Some(1));
+        assert_eq!(state.toast.as_deref(), Some("No story #0 (list has 3)"));
+    });
+}
+
+#[gpui::test]
+fn reader_quick_bar_adjusts_font_scale_and_layout(cx: &mut TestAppContext) {
+    let (view, cx) = cx.add_window_view(|cx| AppState::new(Settings::default(), test_dirs(), cx));
+
+    view.update(cx, |state, cx| {
+        state.reader_quick_bar_open = true;
+        state.step_reader_font_scale(true, cx);
+        state.step_reader_font_scale(true, cx);
+        assert!((state.settings.reader_font_scale - 1.2).abs() < 1e-4);
+        assert_eq!(
+            state.reader_layout().font_scale,
+            state.settings.reader_font_scale
+        );
+
+        state.step_reader_font_scale(false, cx);
+        assert!((state.reader_layout().font_scale - 1.1).abs() < 1e-4);
+
+        state.set_reader_width(ReaderWidth::Wide, cx);
+        state.toggle_reader_images(cx);
+        assert_eq!(state.settings.reader_width, ReaderWidth::Wide);
+        assert!(state.reader_images(cx).text_only);
+
+        // Sepia 只换文章的配色，界面主题不变
+        state.set_reader_theme(ReaderTheme::Sepia, cx);
+        assert_eq!(
+            *state.settings.reader_theme.resolve(&state.theme),
+            Theme::sepia()
+        );
+        assert_ne!(state.theme, Theme::sepia());
+    });
+}
+
+#[gpui::test]
+fn collapse_all_keeps_only_top_level_comments(cx: &mut TestAppContext) {
+    let (view, cx) = cx.add_window_view(|cx| AppState::new(Settings::default(), test_dirs(), cx));
+
+    view.update(cx, |state, cx| {
+        state.selected_story_id = Some(100);
+        state.comments = vec![
+            crate::models::Comment {
+                kids: Some(vec![2]),
+                ..comment(1, 100, 0, "top level")
+            },
+            crate::models::Comment {
+                kids: Some(vec![3]),
+                ..comment(2, 1, 1, "reply")
+            },
+            comment(3, 2, 2, "nested reply"),
+            comment(4, 100, 0, "no replies"),
+        ];
+
+        state.collapse_all_comments(cx);
+        let visible: Vec<i64> = state.visible_comments().iter().map(|c| c.id).collect();
+        assert_eq!(visible, vec![1, 4]);
+        assert!(!state.is_collapsed(4));
+
+        state.expand_all_comments(cx);
+        assert_eq!(state.visible_comments().len(), 4);
+
+        // 从嵌套回复折叠整个评论串
+        state.collapse_thread(3, cx);
+        assert!(state.is_collapsed(1));
+        assert!(!state.is_collapsed(2));
+    });
+}
+
+#[gpui::test]
+fn large_threads_only_lay_out_comments_near_the_viewport(cx: &mut TestAppContext) {
+    let (view, cx) = cx.add_window_view(|cx| AppState::new(Settings::default(), test_dirs(), cx));
+
+    view.update(cx, |state, _cx| {
+        state.selected_story_id = Some(1);
+        state.selected_story = Some(link_story(1));
+        state.comments = (0..500)
+            .map(|id| comment(id + 10, 1, 0, "a comment"))
+            .collect();
+    });
+    cx.draw(
+        gpui::point(gpui::px(0.), gpui::px(0.)),
+        gpui::size(gpui::px(1200.), gpui::px(800.)),
+        |_| view.clone(),
+    );
+
+    view.update(cx, |state, _cx| {
+        assert_eq!(state.comment_rows.indices.len(), 500);
+        // 第 0 项是标题，评论从第 1 项开始
+        let laid_out = (1..=500)
+            .filter(|&ix| state.comment_list.bounds_for_item(ix).is_some())
+            .count();
+        assert!(laid_out > 0);
+        assert!(laid_out < 100, "{laid_out} of 500 comments were laid out");
+    });
+}
+
+#[gpui::test]
+fn story_list_renders_only_rows_in_the_viewport(cx: &mut TestAppContext) {
+    let (view, cx) = cx.add_window_view(|cx| AppState::new(Settings::default(), test_dirs(), cx));
+
+    view.update(cx, |state, _cx| {
+        state.is_loading = false;
+        state.stories = (1..=300).map(|id| (&link_story(id)).into()).collect();
+    });
+    cx.draw(
+        gpui::point(gpui::px(0.), gpui::px(0.)),
+        gpui::size(gpui::px(1200.), gpui::px(800.)),
+        |_| view.clone(),
+    );
+
+    view.update(cx, |state, _cx| {
+        // 每行一样高，最后一行可见的位置由行高直接算出
+        assert!(state.story_row_height().is_some());
+        let last_row = state.last_visible_story_row().unwrap();
+        assert!(last_row > 0 && last_row < 50, "last visible row {last_row}");
+    });
+}
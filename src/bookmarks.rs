@@ -0,0 +1,113 @@
+use crate::models::{NewsChannel, Story, StorySummary};
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+
+pub(crate) const BOOKMARKS_FILE: &str = "bookmarks.json";
+
+/// 收藏的 story，保存完整记录：离开榜单以后也能从收藏列表打开
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+pub struct Bookmarks {
+    /// 按收藏顺序，最新的在最后
+    entries: Vec<Bookmark>,
+    /// 列表显示用的摘要，最新收藏的在前
+    #[serde(skip)]
+    summaries: Vec<StorySummary>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct Bookmark {
+    pub story: Story,
+    /// 收藏时间（unix 秒）
+    pub saved_at: i64,
+    /// 收藏时所在的频道（`NewsChannel::key`），打开时从同一个来源加载。
+    /// 早期的收藏没有这一项，那时只有 HN
+    #[serde(default)]
+    pub channel: String,
+}
+
+impl Bookmarks {
+    pub fn load(dir: &Path) -> Self {
+        let mut bookmarks: Self = std::fs::read(bookmarks_path(dir))
+            .ok()
+            .and_then(|bytes| serde_json::from_slice(&bytes).ok())
+            .unwrap_or_default();
+        bookmarks.rebuild_summaries();
+        bookmarks
+    }
+
+    pub fn save(&self, dir: &Path) -> Result<(), String> {
+        let path = bookmarks_path(dir);
+        let json = serde_json::to_vec_pretty(self).map_err(|e| e.to_string())?;
+        crate::settings::write_atomic(&path, &json)
+    }
+
+    pub fn contains(&self, story_id: i64) -> bool {
+        self.entries.iter().any(|entry| entry.story.id == story_id)
+    }
+
+    /// 收藏来自哪个频道，没有收藏时为 `None`
+    pub fn channel(&self, story_id: i64) -> Option<NewsChannel> {
+        self.entries
+            .iter()
+            .find(|entry| entry.story.id == story_id)
+            .map(|entry| NewsChannel::from_key(&entry.channel).unwrap_or(NewsChannel::HackerNews))
+    }
+
+    /// 保存的完整记录
+    pub fn get(&self, story_id: i64) -> Option<&Story> {
+        self.entries
+            .iter()
+            .find(|entry| entry.story.id == story_id)
+            .map(|entry| &entry.story)
+    }
+
+    pub fn summaries(&self) -> &[StorySummary] {
+        &self.summaries
+    }
+
+    /// 收藏或取消收藏，返回之后是否处于收藏状态
+    pub fn toggle(&mut self, story: Story, channel: NewsChannel, now: i64) -> bool {
+        let saved = match self.entries.iter().position(|e| e.story.id == story.id) {
+            Some(index) => {
+                self.entries.remove(index);
+                false
+            }
+            None => {
+                self.entries.push(Bookmark {
+                    story,
+                    saved_at: now,
+                    channel: channel.key().to_string(),
+                });
+                true
+            }
+        };
+        self.rebuild_summaries();
+        saved
+    }
+
+    /// 打开收藏时用刚获取的 story 更新保存的记录，返回是否有变化
+    pub fn refresh(&mut self, story: &Story) -> bool {
+        let Some(entry) = self.entries.iter_mut().find(|e| e.story.id == story.id) else {
+            return false;
+        };
+        if entry.story == *story {
+            return false;
+        }
+        entry.story = story.clone();
+        self.rebuild_summaries();
+        true
+    }
+
+    fn rebuild_summaries(&mut self) {
+        self.summaries = self
+            .entries
+            .iter()
+            .rev()
+            .map(|entry| StorySummary::from(&entry.story))
+            .collect();
+    }
+}
+
+fn bookmarks_path(dir: &Path) -> PathBuf {
+    dir.join(BOOKMARKS_FILE)
+}
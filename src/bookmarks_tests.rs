@@ -0,0 +1,68 @@
+use crate::bookmarks::Bookmarks;
+use crate::models::{NewsChannel, Story};
+
+fn story(id: i64, score: i32) -> Story {
+    Story {
+        id,
+        title: format!("Story {id}"),
+        url: Some(format!("https://example.com/{id}")),
+        score,
+        by: "op".to_string(),
+        time: 1_700_000_000,
+        descendants: Some(3),
+        kids: Some(vec![id * 10]),
+        text: None,
+        parts: None,
+        story_type: "story".to_string(),
+    }
+}
+
+#[test]
+fn bookmarks_list_most_recent_first_and_toggle_off() {
+    let mut bookmarks = Bookmarks::default();
+    assert!(bookmarks.toggle(story(1, 10), NewsChannel::HackerNews, 100));
+    assert!(bookmarks.toggle(story(2, 20), NewsChannel::HackerNews, 200));
+
+    let ids = bookmarks
+        .summaries()
+        .iter()
+        .map(|s| s.id)
+        .collect::<Vec<_>>();
+    assert_eq!(ids, vec![2, 1]);
+    assert_eq!(
+        bookmarks.get(1).and_then(|s| s.kids.clone()),
+        Some(vec![10])
+    );
+
+    assert!(!bookmarks.toggle(story(1, 10), NewsChannel::HackerNews, 300));
+    assert!(!bookmarks.contains(1));
+    assert_eq!(bookmarks.summaries().len(), 1);
+}
+
+#[test]
+fn opening_a_bookmark_refreshes_the_saved_record() {
+    let mut bookmarks = Bookmarks::default();
+    bookmarks.toggle(story(1, 10), NewsChannel::HackerNews, 100);
+
+    assert!(!bookmarks.refresh(&story(1, 10)));
+    assert!(bookmarks.refresh(&story(1, 42)));
+    assert_eq!(bookmarks.summaries()[0].score, 42);
+    // 没收藏的 story 不会被加进来
+    assert!(!bookmarks.refresh(&story(2, 5)));
+    assert!(!bookmarks.contains(2));
+}
+
+#[test]
+fn bookmarks_remember_their_channel() {
+    let mut bookmarks = Bookmarks::default();
+    bookmarks.toggle(story(-7, 10), NewsChannel::Lobsters, 100);
+    assert_eq!(bookmarks.channel(-7), Some(NewsChannel::Lobsters));
+    assert_eq!(bookmarks.channel(8), None);
+
+    // 早期的收藏没有记录频道，按 HN 打开
+    let legacy: Bookmarks = serde_json::from_str(
+        r#"{"entries":[{"story":{"id":1,"title":"Old","score":1,"by":"op","time":1,"type":"story"},"saved_at":100}]}"#,
+    )
+    .unwrap();
+    assert_eq!(legacy.channel(1), Some(NewsChannel::HackerNews));
+}
@@ -0,0 +1,105 @@
+use crate::models::Comment;
+use std::collections::HashSet;
+use std::ops::Range;
+
+/// 评论区内查找的状态
+#[derive(Debug, Clone, Default)]
+pub struct CommentSearch {
+    pub query: String,
+    /// 命中的评论 id，按评论顺序
+    pub matches: Vec<i64>,
+    pub current: usize,
+    /// 开始查找前的折叠状态，结束查找时恢复
+    pub saved_collapsed: HashSet<i64>,
+}
+
+impl CommentSearch {
+    pub fn new(collapsed: &HashSet<i64>) -> Self {
+        Self {
+            saved_collapsed: collapsed.clone(),
+            ..Self::default()
+        }
+    }
+
+    pub fn current_match(&self) -> Option<i64> {
+        self.matches.get(self.current).copied()
+    }
+
+    pub fn cycle(&mut self, forward: bool) {
+        let len = self.matches.len();
+        if len == 0 {
+            return;
+        }
+        self.current = if forward {
+            (self.current + 1) % len
+        } else {
+            (self.current + len - 1) % len
+        };
+    }
+
+    /// 状态栏上显示的 "2/5"
+    pub fn status(&self) -> String {
+        if self.matches.is_empty() {
+            if self.query.is_empty() {
+                String::new()
+            } else {
+                "No matches".to_string()
+            }
+        } else {
+            format!("{}/{}", self.current + 1, self.matches.len())
+        }
+    }
+}
+
+/// 文本中所有命中位置（ASCII 不区分大小写），返回字节范围
+pub fn match_ranges(text: &str, query: &str) -> Vec<Range<usize>> {
+    let needle = query.as_bytes();
+    if needle.is_empty() {
+        return Vec::new();
+    }
+
+    let haystack = text.as_bytes();
+    let mut ranges = Vec::new();
+    let mut start = 0;
+    while start + needle.len() <= haystack.len() {
+        if haystack[start..start + needle.len()].eq_ignore_ascii_case(needle) {
+            ranges.push(start..start + needle.len());
+            start += needle.len();
+        } else {
+            start += 1;
+        }
+    }
+    ranges
+}
+
+/// 正文包含查找词的评论 id
+pub fn find_matches(comments: &[Comment], query: &str) -> Vec<i64> {
+    let query = query.trim();
+    if query.is_empty() {
+        return Vec::new();
+    }
+    comments
+        .iter()
+        .filter(|comment| !match_ranges(&comment.clean_text(), query).is_empty())
+        .map(|comment| comment.id)
+        .collect()
+}
+
+/// 要让命中的评论可见需要展开的评论：它的所有祖先。命中的评论本身保持原来的折叠状态，
+/// 它的回复不会因为查找被整个展开
+///
+/// `comments` 是按深度优先排好的列表（和界面显示顺序一致）
+pub fn ids_to_expand(comments: &[Comment], matches: &[i64]) -> HashSet<i64> {
+    let matches = matches.iter().copied().collect::<HashSet<_>>();
+    let mut expand = HashSet::new();
+    let mut ancestors: Vec<i64> = Vec::new();
+
+    for comment in comments {
+        ancestors.truncate(comment.depth);
+        if matches.contains(&comment.id) {
+            expand.extend(ancestors.iter().copied());
+        }
+        ancestors.push(comment.id);
+    }
+    expand
+}
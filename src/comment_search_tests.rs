@@ -0,0 +1,58 @@
+use crate::comment_search::{self, CommentSearch};
+use crate::models::Comment;
+
+pub(crate) fn comment(id: i64, parent: i64, depth: usize, text: &str) -> Comment {
+    Comment {
+        id,
+        by: Some(format!("user{id}")),
+        text: Some(text.to_string()),
+        time: 1_700_000_000,
+        kids: None,
+        parent,
+        depth,
+        reply_count: 0,
+        avatar_url: None,
+    }
+}
+
+#[test]
+fn match_ranges_ignore_ascii_case() {
+    assert_eq!(
+        comment_search::match_ranges("Rust and rust, RUST!", "rust"),
+        vec![0..4, 9..13, 15..19]
+    );
+    assert_eq!(
+        comment_search::match_ranges("naïve café", "café"),
+        vec![7..12]
+    );
+    assert!(comment_search::match_ranges("anything", "").is_empty());
+}
+
+#[test]
+fn matches_expand_their_ancestors() {
+    let comments = vec![
+        comment(1, 100, 0, "top level"),
+        comment(2, 1, 1, "a reply"),
+        comment(3, 2, 2, "the needle is here"),
+        comment(4, 100, 0, "another thread"),
+        comment(5, 4, 1, "nothing"),
+    ];
+
+    let matches = comment_search::find_matches(&comments, "NEEDLE");
+    assert_eq!(matches, vec![3]);
+
+    let mut expand = comment_search::ids_to_expand(&comments, &matches)
+        .into_iter()
+        .collect::<Vec<_>>();
+    expand.sort_unstable();
+    assert_eq!(expand, vec![1, 2]);
+
+    let mut search = CommentSearch {
+        matches: vec![3, 5],
+        ..CommentSearch::default()
+    };
+    search.cycle(false);
+    assert_eq!(search.current_match(), Some(5));
+    search.cycle(true);
+    assert_eq!(search.status(), "1/2");
+}
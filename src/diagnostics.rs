@@ -0,0 +1,130 @@
+use futures::AsyncReadExt as _;
+use gpui::http_client::{AsyncBody, HttpClient};
+use std::path::Path;
+use std::sync::Arc;
+
+pub const HN_TOPSTORIES_URL: &str = "https://hacker-news.firebaseio.com/v0/topstories.json";
+pub const READER_PROBE_URL: &str = "https://example.com/";
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum CheckStatus {
+    Pending,
+    Pass(String),
+    Fail(String),
+}
+
+#[derive(Debug, Clone)]
+pub struct DiagnosticCheck {
+    pub name: &'static str,
+    pub status: CheckStatus,
+}
+
+#[derive(Debug, Clone)]
+pub struct DiagnosticsReport {
+    pub checks: Vec<DiagnosticCheck>,
+}
+
+impl DiagnosticsReport {
+    pub const HN_API: usize = 0;
+    pub const READER_FETCH: usize = 1;
+    pub const CACHE_WRITABLE: usize = 2;
+    pub const CACHE_USAGE: usize = 3;
+
+    pub fn pending() -> Self {
+        let names = [
+            "Hacker News API",
+            "Reader fetch",
+            "Cache directory writable",
+            "Reader disk cache",
+        ];
+        Self {
+            checks: names
+                .into_iter()
+                .map(|name| DiagnosticCheck {
+                    name,
+                    status: CheckStatus::Pending,
+                })
+                .collect(),
+        }
+    }
+
+    pub fn set(&mut self, index: usize, status: CheckStatus) {
+        if let Some(check) = self.checks.get_mut(index) {
+            check.status = status;
+        }
+    }
+}
+
+/// 拉取 `topstories.json` 并确认返回的是非空 id 列表
+pub async fn check_hn_api(http: Arc<dyn HttpClient>, url: &str) -> CheckStatus {
+    let bytes = match fetch_bytes(http, url).await {
+        Ok(bytes) => bytes,
+        Err(e) => return CheckStatus::Fail(e),
+    };
+    match serde_json::from_slice::<Vec<i64>>(&bytes) {
+        Ok(ids) if !ids.is_empty() => CheckStatus::Pass(format!("{} story ids", ids.len())),
+        Ok(_) => CheckStatus::Fail("Empty story list".to_string()),
+        Err(e) => CheckStatus::Fail(format!("Unexpected response: {e}")),
+    }
+}
+
+pub async fn check_reader_fetch(http: Arc<dyn HttpClient>, url: &str) -> CheckStatus {
+    match fetch_bytes(http, url).await {
+        Ok(bytes) => CheckStatus::Pass(format!("{} bytes from {}", bytes.len(), url)),
+        Err(e) => CheckStatus::Fail(e),
+    }
+}
+
+async fn fetch_bytes(http: Arc<dyn HttpClient>, url: &str) -> Result<Vec<u8>, String> {
+    let response = http
+        .get(url, AsyncBody::empty(), true)
+        .await
+        .map_err(|e| e.to_string())?;
+    if !response.status().is_success() {
+        return Err(format!("HTTP {} for {}", response.status(), url));
+    }
+
+    let mut bytes = Vec::new();
+    response
+        .into_body()
+        .read_to_end(&mut bytes)
+        .await
+        .map_err(|e| e.to_string())?;
+    Ok(bytes)
+}
+
+/// 写入并删除一个临时文件，确认目录可写
+pub fn check_cache_writable(dir: &Path) -> CheckStatus {
+    let probe = dir.join(".oneapp-diagnostics");
+    let result = std::fs::create_dir_all(dir)
+        .and_then(|_| std::fs::write(&probe, b"ok"))
+        .and_then(|_| std::fs::remove_file(&probe));
+    match result {
+        Ok(()) => CheckStatus::Pass(dir.display().to_string()),
+        Err(e) => CheckStatus::Fail(format!("{}: {e}", dir.display())),
+    }
+}
+
+/// 统计 `reader/` 下缓存文件的数量和大小
+pub fn check_cache_usage(dir: &Path) -> CheckStatus {
+    match crate::reader::cache_usage(&dir.join("reader")) {
+        Ok((0, _)) => CheckStatus::Pass("Empty".to_string()),
+        Ok((count, bytes)) => {
+            CheckStatus::Pass(format!("{count} entries, {}", format_bytes(bytes)))
+        }
+        Err(e) => CheckStatus::Fail(e.to_string()),
+    }
+}
+
+pub fn format_bytes(bytes: u64) -> String {
+    const KB: f64 = 1024.0;
+    const MB: f64 = KB * 1024.0;
+    let b = bytes as f64;
+    if b >= MB {
+        format!("{:.1} MB", b / MB)
+    } else if b >= KB {
+        format!("{:.1} KB", b / KB)
+    } else {
+        format!("{bytes} B")
+    }
+}
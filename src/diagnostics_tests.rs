@@ -0,0 +1,77 @@
+use crate::diagnostics::{self, CheckStatus};
+use gpui::http_client::{AsyncBody, FakeHttpClient, HttpClient, Response};
+use std::sync::Arc;
+
+fn client_returning(status: u16, body: &'static str) -> Arc<dyn HttpClient> {
+    FakeHttpClient::create(move |_req| async move {
+        Ok(Response::builder()
+            .status(status)
+            .body(AsyncBody::from(body.to_string()))
+            .unwrap())
+    })
+}
+
+fn temp_dir(name: &str) -> std::path::PathBuf {
+    let dir = std::env::temp_dir().join(format!("oneapp-{name}-{}", std::process::id()));
+    let _ = std::fs::remove_dir_all(&dir);
+    dir
+}
+
+#[test]
+fn hn_api_check_passes_on_id_list_and_fails_otherwise() {
+    let ok = futures::executor::block_on(diagnostics::check_hn_api(
+        client_returning(200, "[1,2,3]"),
+        diagnostics::HN_TOPSTORIES_URL,
+    ));
+    assert_eq!(ok, CheckStatus::Pass("3 story ids".to_string()));
+
+    let server_error = futures::executor::block_on(diagnostics::check_hn_api(
+        client_returning(503, ""),
+        diagnostics::HN_TOPSTORIES_URL,
+    ));
+    assert!(matches!(server_error, CheckStatus::Fail(msg) if msg.contains("503")));
+
+    let garbage = futures::executor::block_on(diagnostics::check_hn_api(
+        client_returning(200, "<html>"),
+        diagnostics::HN_TOPSTORIES_URL,
+    ));
+    assert!(matches!(garbage, CheckStatus::Fail(_)));
+}
+
+#[test]
+fn reader_fetch_check_reports_body_size() {
+    let status = futures::executor::block_on(diagnostics::check_reader_fetch(
+        client_returning(200, "hello"),
+        "https://example.com/",
+    ));
+    assert_eq!(
+        status,
+        CheckStatus::Pass("5 bytes from https://example.com/".to_string())
+    );
+}
+
+#[test]
+fn cache_checks_write_probe_and_count_entries() {
+    let dir = temp_dir("diagnostics");
+
+    assert!(matches!(
+        diagnostics::check_cache_writable(&dir),
+        CheckStatus::Pass(_)
+    ));
+    assert!(!dir.join(".oneapp-diagnostics").exists());
+    assert_eq!(
+        diagnostics::check_cache_usage(&dir),
+        CheckStatus::Pass("Empty".to_string())
+    );
+
+    std::fs::create_dir_all(dir.join("reader")).unwrap();
+    std::fs::write(dir.join("reader/a.json"), vec![b'x'; 2048]).unwrap();
+    std::fs::write(dir.join("reader/b.json"), b"{}").unwrap();
+    std::fs::write(dir.join("reader/ignored.tmp"), b"x").unwrap();
+    assert_eq!(
+        diagnostics::check_cache_usage(&dir),
+        CheckStatus::Pass("2 entries, 2.0 KB".to_string())
+    );
+
+    let _ = std::fs::remove_dir_all(&dir);
+}
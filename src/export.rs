@@ -0,0 +1,72 @@
+use crate::models::StorySummary;
+use serde::Serialize;
+use std::path::{Path, PathBuf};
+
+/// 导出的 feed 快照
+#[derive(Debug, Serialize)]
+pub struct FeedSnapshot<'a> {
+    pub exported_at: i64,
+    pub channel: &'a str,
+    pub stories: &'a [StorySummary],
+}
+
+pub fn snapshot_json(channel: &str, stories: &[StorySummary]) -> Result<String, String> {
+    let snapshot = FeedSnapshot {
+        exported_at: chrono::Utc::now().timestamp(),
+        channel,
+        stories,
+    };
+    serde_json::to_string_pretty(&snapshot).map_err(|e| e.to_string())
+}
+
+/// 生成 OPML 文档，`feeds` 为 (标题, feed URL)
+pub fn opml_document(title: &str, feeds: &[(String, String)]) -> String {
+    let mut out = String::new();
+    out.push_str("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+    out.push_str("<opml version=\"2.0\">\n");
+    out.push_str("  <head>\n");
+    out.push_str(&format!(
+        "    <title>{}</title>\n",
+        html_escape::encode_text(title)
+    ));
+    out.push_str("  </head>\n");
+    out.push_str("  <body>\n");
+    for (feed_title, url) in feeds {
+        out.push_str(&format!(
+            "    <outline type=\"rss\" text=\"{0}\" title=\"{0}\" xmlUrl=\"{1}\"/>\n",
+            html_escape::encode_double_quoted_attribute(feed_title),
+            html_escape::encode_double_quoted_attribute(url),
+        ));
+    }
+    out.push_str("  </body>\n");
+    out.push_str("</opml>\n");
+    out
+}
+
+/// 写入 `dir`（`AppDirs::exports`），返回文件路径
+pub fn write_export(dir: &Path, file_name: &str, contents: &str) -> Result<PathBuf, String> {
+    std::fs::create_dir_all(dir).map_err(|e| e.to_string())?;
+
+    let path = dir.join(file_name);
+    std::fs::write(&path, contents).map_err(|e| e.to_string())?;
+    Ok(path)
+}
+
+pub fn export_file_name(channel: &str, extension: &str) -> String {
+    let slug = channel
+        .chars()
+        .map(|ch| {
+            if ch.is_ascii_alphanumeric() {
+                ch.to_ascii_lowercase()
+            } else {
+                '-'
+            }
+        })
+        .collect::<String>();
+    format!(
+        "oneapp-{}-{}.{}",
+        slug.trim_matches('-'),
+        chrono::Utc::now().format("%Y%m%d-%H%M%S"),
+        extension
+    )
+}
@@ -0,0 +1,37 @@
+use crate::{export, models::StorySummary, test_support::story_summary};
+
+fn story(id: i64, title: &str) -> StorySummary {
+    StorySummary {
+        title: title.to_string(),
+        ..story_summary(id)
+    }
+}
+
+#[test]
+fn snapshot_json_contains_channel_timestamp_and_stories() {
+    let stories = vec![story(1, "First"), story(2, "Second")];
+    let json = export::snapshot_json("Hacker News", &stories).unwrap();
+    let value: serde_json::Value = serde_json::from_str(&json).unwrap();
+
+    assert_eq!(value["channel"], "Hacker News");
+    assert!(value["exported_at"].as_i64().is_some_and(|t| t > 0));
+    assert_eq!(value["stories"].as_array().map(Vec::len), Some(2));
+    assert_eq!(value["stories"][0]["title"], "First");
+    assert_eq!(value["stories"][1]["type"], "story");
+}
+
+#[test]
+fn opml_document_lists_feeds_with_escaped_attributes() {
+    let feeds = vec![(
+        "Tom & Jerry's \"Blog\"".to_string(),
+        "https://example.com/feed.xml?a=1&b=2".to_string(),
+    )];
+    let opml = export::opml_document("OneApp feeds", &feeds);
+
+    assert!(opml.starts_with("<?xml version=\"1.0\" encoding=\"UTF-8\"?>"));
+    assert!(opml.contains("<opml version=\"2.0\">"));
+    assert!(opml.contains("<title>OneApp feeds</title>"));
+    assert!(opml.contains("xmlUrl=\"https://example.com/feed.xml?a=1&amp;b=2\""));
+    assert!(opml.contains("text=\"Tom &amp; Jerry's &quot;Blog&quot;\""));
+    assert!(opml.trim_end().ends_with("</opml>"));
+}
@@ -0,0 +1,346 @@
+use crate::api::HttpFetcher;
+use crate::models::{Comment, NewsChannel, PollOption, Story, StorySummary};
+use crate::source::NewsSource;
+use futures::future::{self, LocalBoxFuture};
+use futures::stream::{self, StreamExt};
+use futures::FutureExt as _;
+use std::collections::{HashMap, HashSet};
+use std::sync::{Arc, Mutex};
+
+/// 订阅源条目的 id 都不大于它的相反数，和 Lobsters 的负数 id 分开
+const FEED_ID_BASE: i64 = 1 << 60;
+/// 单个订阅源的大小上限，全文输出的源也很少超过这个大小
+pub const MAX_FEED_BYTES: usize = 8 * 1024 * 1024;
+
+/// 用户添加的 RSS/Atom 订阅地址。设置里保存一份，`FeedSource` 拿着同一个列表，
+/// 添加或删除之后下次刷新 Feeds 频道就会生效
+#[derive(Debug, Clone, Default)]
+pub struct FeedList {
+    urls: Arc<Mutex<Vec<String>>>,
+    /// 加载过的源的标题（订阅地址 -> 标题），只在内存里
+    titles: Arc<Mutex<HashMap<String, String>>>,
+}
+
+/// 解析出来的一个订阅源
+#[derive(Debug, Clone)]
+pub struct ParsedFeed {
+    /// 源的标题，没有时用网站的域名
+    pub title: String,
+    pub stories: Vec<Story>,
+}
+
+impl FeedList {
+    pub fn new(urls: Vec<String>) -> Self {
+        Self {
+            urls: Arc::new(Mutex::new(urls)),
+            titles: Arc::default(),
+        }
+    }
+
+    pub fn urls(&self) -> Vec<String> {
+        self.urls.lock().unwrap_or_else(|e| e.into_inner()).clone()
+    }
+
+    pub fn set(&self, urls: Vec<String>) {
+        *self.urls.lock().unwrap_or_else(|e| e.into_inner()) = urls;
+    }
+
+    /// 源的标题；还没加载过时用订阅地址的域名
+    pub fn title(&self, url: &str) -> String {
+        let loaded = self
+            .titles
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .get(url)
+            .cloned();
+        loaded
+            .filter(|title| !title.is_empty())
+            .or_else(|| {
+                url::Url::parse(url)
+                    .ok()
+                    .and_then(|parsed| crate::reader::host_without_www(&parsed))
+            })
+            .unwrap_or_else(|| url.to_string())
+    }
+
+    fn set_title(&self, url: &str, title: &str) {
+        self.titles
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .insert(url.to_string(), title.to_string());
+    }
+}
+
+/// Feeds 频道的数据来源：请求全部订阅源，合成一个按时间倒序的列表。
+/// 条目只存在于源里，没有评论，打开时从加载过的条目里取
+#[derive(Clone)]
+pub struct FeedSource {
+    fetcher: HttpFetcher,
+    /// 订阅的地址，调用方改动列表后下次加载生效
+    feeds: FeedList,
+    /// 加载过的条目，按 id 取完整内容
+    items: Arc<Mutex<HashMap<i64, Story>>>,
+}
+
+impl FeedSource {
+    pub fn new(fetcher: HttpFetcher, feeds: FeedList) -> Self {
+        Self {
+            fetcher,
+            feeds,
+            items: Arc::default(),
+        }
+    }
+
+    /// 请求全部订阅源，合在一起按时间倒序；只有全部失败时才报错
+    pub async fn fetch_items(&self) -> Result<Vec<i64>, String> {
+        let urls = self.feeds.urls();
+        let results: Vec<Result<Vec<Story>, String>> =
+            stream::iter(urls.iter().map(|url| async move {
+                let bytes = self.fetcher.get_bytes_limited(url, MAX_FEED_BYTES).await?;
+                let feed = parse_feed(&bytes, url).map_err(|e| format!("{url}: {e}"))?;
+                self.feeds.set_title(url, &feed.title);
+                Ok(feed.stories)
+            }))
+            .buffered(self.fetcher.max_concurrent_requests())
+            .collect()
+            .await;
+
+        let mut first_error = None;
+        let mut stories = Vec::new();
+        for result in results {
+            match result {
+                Ok(items) => stories.extend(items),
+                Err(error) => {
+                    first_error.get_or_insert(error);
+                }
+            }
+        }
+        if stories.is_empty() {
+            if let Some(error) = first_error {
+                return Err(error);
+            }
+        }
+        stories.sort_by(|a, b| b.time.cmp(&a.time));
+
+        // 源里重复的条目（guid 相同）只保留最新的一条
+        let mut items = self.items.lock().unwrap_or_else(|e| e.into_inner());
+        let mut seen = HashSet::new();
+        let mut ids = Vec::new();
+        for story in stories {
+            if seen.insert(story.id) {
+                ids.push(story.id);
+                items.insert(story.id, story);
+            }
+        }
+        Ok(ids)
+    }
+
+    fn cached_item(&self, id: i64) -> Option<Story> {
+        self.items
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .get(&id)
+            .cloned()
+    }
+
+    /// 条目已经随列表加载，直接从缓存取，不请求网络
+    pub fn summaries(&self, ids: &[i64]) -> Vec<StorySummary> {
+        ids.iter()
+            .filter_map(|&id| self.cached_item(id))
+            .map(|story| StorySummary::from(&story))
+            .collect()
+    }
+
+    pub fn story(&self, id: i64) -> Result<Story, String> {
+        self.cached_item(id)
+            .ok_or_else(|| "This item is no longer in its feed".to_string())
+    }
+
+    /// 订阅源没有搜索服务，在加载过的条目里按标题匹配（不区分大小写），最新的在前
+    pub fn search(&self, query: &str, limit: usize) -> Vec<Story> {
+        let query = query.to_lowercase();
+        let items = self.items.lock().unwrap_or_else(|e| e.into_inner());
+        let mut matches: Vec<Story> = items
+            .values()
+            .filter(|story| story.title.to_lowercase().contains(&query))
+            .cloned()
+            .collect();
+        matches.sort_by(|a, b| b.time.cmp(&a.time));
+        matches.truncate(limit);
+        matches
+    }
+}
+
+impl NewsSource for FeedSource {
+    // 只有 Feeds 一个频道
+    fn fetch_story_ids(
+        &self,
+        _channel: NewsChannel,
+    ) -> LocalBoxFuture<'_, Result<Vec<i64>, String>> {
+        self.fetch_items().boxed_local()
+    }
+
+    // 合并后的列表已经按时间排好，不按分数重排
+    fn fetch_summaries<'a>(
+        &'a self,
+        _channel: NewsChannel,
+        ids: &'a [i64],
+    ) -> LocalBoxFuture<'a, Vec<StorySummary>> {
+        future::ready(self.summaries(ids)).boxed_local()
+    }
+
+    fn search_stories<'a>(
+        &'a self,
+        query: &'a str,
+        limit: usize,
+    ) -> LocalBoxFuture<'a, Result<Vec<Story>, String>> {
+        future::ready(Ok(self.search(query, limit))).boxed_local()
+    }
+
+    fn fetch_story(&self, id: i64) -> LocalBoxFuture<'_, Result<Story, String>> {
+        future::ready(self.story(id)).boxed_local()
+    }
+
+    // 订阅源的条目没有评论和投票，下面都是空的
+    fn fetch_comment_preview(
+        &self,
+        _story_id: i64,
+        _count: usize,
+    ) -> LocalBoxFuture<'_, Result<Vec<Comment>, String>> {
+        future::ready(Ok(Vec::new())).boxed_local()
+    }
+
+    fn fetch_poll_options<'a>(&'a self, _story: &'a Story) -> LocalBoxFuture<'a, Vec<PollOption>> {
+        future::ready(Vec::new()).boxed_local()
+    }
+
+    fn fetch_comments<'a>(
+        &'a self,
+        _story: &'a Story,
+    ) -> LocalBoxFuture<'a, Result<Vec<Comment>, String>> {
+        future::ready(Ok(Vec::new())).boxed_local()
+    }
+
+    fn initial_root_ids(&self, _kids: &[i64]) -> Vec<i64> {
+        Vec::new()
+    }
+
+    fn next_root_ids(&self, _kids: &[i64], _loaded: &HashSet<i64>) -> Vec<i64> {
+        Vec::new()
+    }
+
+    fn fetch_more_comments<'a>(&'a self, _root_ids: &'a [i64]) -> LocalBoxFuture<'a, Vec<Comment>> {
+        future::ready(Vec::new()).boxed_local()
+    }
+
+    fn fetch_replies(
+        &self,
+        _parent_id: i64,
+        _existing_depth: usize,
+    ) -> LocalBoxFuture<'_, Result<Vec<Comment>, String>> {
+        future::ready(Ok(Vec::new())).boxed_local()
+    }
+
+    fn fetch_item_raw(&self, id: i64) -> LocalBoxFuture<'_, Option<serde_json::Value>> {
+        future::ready(
+            self.cached_item(id)
+                .and_then(|story| serde_json::to_value(story).ok()),
+        )
+        .boxed_local()
+    }
+}
+
+pub fn is_feed_id(id: i64) -> bool {
+    id <= -FEED_ID_BASE
+}
+
+/// 条目的稳定 id：订阅地址加条目的 guid（没有时 feed-rs 用链接生成）算 FNV-1a，
+/// 重启之后已读记录和书签还能对上
+pub fn item_id(feed_url: &str, entry_id: &str) -> i64 {
+    let mut hash: u64 = 0xcbf2_9ce4_8422_2325;
+    for byte in feed_url.bytes().chain([0]).chain(entry_id.bytes()) {
+        hash ^= u64::from(byte);
+        hash = hash.wrapping_mul(0x0100_0000_01b3);
+    }
+    -(FEED_ID_BASE + (hash as i64 & (FEED_ID_BASE - 1)))
+}
+
+/// 检查输入框里的订阅地址，没写协议时补上 `https://`
+pub fn normalize_feed_url(input: &str) -> Option<String> {
+    let input = input.trim();
+    if input.is_empty() {
+        return None;
+    }
+    let with_scheme = if input.contains("://") {
+        input.to_string()
+    } else {
+        format!("https://{input}")
+    };
+    let url = url::Url::parse(&with_scheme).ok()?;
+    (matches!(url.scheme(), "http" | "https") && url.host_str().is_some()).then(|| url.to_string())
+}
+
+/// 解析 RSS 2.0 / Atom，转成共用的 `Story`。
+/// `text` 是条目自带的全文（`content:encoded` 或 Atom 的 `content`），没有时用摘要，
+/// 阅读模式可以直接显示它，也可以打开链接
+pub fn parse_feed(bytes: &[u8], feed_url: &str) -> Result<ParsedFeed, String> {
+    let feed = feed_rs::parser::parse(bytes).map_err(|e| e.to_string())?;
+    let base = url::Url::parse(feed_url).ok();
+    let feed_title = feed
+        .title
+        .map(|title| title.content.trim().to_string())
+        .filter(|title| !title.is_empty())
+        .or_else(|| base.as_ref().and_then(crate::reader::host_without_www))
+        .unwrap_or_default();
+
+    let stories = feed
+        .entries
+        .into_iter()
+        .map(|entry| {
+            let link = entry
+                .links
+                .iter()
+                .find(|link| matches!(link.rel.as_deref(), None | Some("alternate")))
+                .or(entry.links.first())
+                .and_then(|link| match &base {
+                    Some(base) => base.join(link.href.trim()).ok().map(|url| url.to_string()),
+                    None => Some(link.href.trim().to_string()),
+                });
+            let title = entry
+                .title
+                .map(|title| title.content.trim().to_string())
+                .filter(|title| !title.is_empty())
+                .unwrap_or_else(|| "(untitled)".to_string());
+            let text = entry
+                .content
+                .and_then(|content| content.body)
+                .or(entry.summary.map(|summary| summary.content))
+                .filter(|text| !text.trim().is_empty());
+            Story {
+                id: item_id(feed_url, &entry.id),
+                title,
+                url: link,
+                score: 0,
+                by: entry
+                    .authors
+                    .first()
+                    .map(|author| author.name.trim().to_string())
+                    .filter(|name| !name.is_empty())
+                    .unwrap_or_else(|| feed_title.clone()),
+                time: entry
+                    .published
+                    .or(entry.updated)
+                    .map_or(0, |time| time.timestamp()),
+                descendants: None,
+                kids: None,
+                text,
+                parts: None,
+                story_type: "story".to_string(),
+            }
+        })
+        .collect();
+    Ok(ParsedFeed {
+        title: feed_title,
+        stories,
+    })
+}
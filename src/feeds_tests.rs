@@ -0,0 +1,162 @@
+use crate::api::HttpFetcher;
+use crate::feeds::{self, FeedList, FeedSource};
+use crate::lobsters;
+use crate::models::NewsChannel;
+use crate::source::NewsSource;
+use crate::test_support::fake_http;
+use std::sync::Arc;
+
+#[test]
+fn rss_items_become_stories_with_unix_times() {
+    let rss = r#"<?xml version="1.0" encoding="UTF-8"?>
+<rss version="2.0" xmlns:content="http://purl.org/rss/1.0/modules/content/">
+  <channel>
+    <title>Example Blog</title>
+    <link>https://blog.example.com/</link>
+    <item>
+      <title>Writing a parser</title>
+      <link>/posts/parser</link>
+      <guid>https://blog.example.com/posts/parser</guid>
+      <pubDate>Fri, 01 Mar 2024 18:00:00 GMT</pubDate>
+      <description>Short summary</description>
+      <content:encoded><![CDATA[<p>The whole post.</p>]]></content:encoded>
+    </item>
+    <item>
+      <title>Notes</title>
+      <link>https://blog.example.com/posts/notes</link>
+      <author>editor@example.com (Sam)</author>
+    </item>
+  </channel>
+</rss>"#;
+
+    let feed = feeds::parse_feed(rss.as_bytes(), "https://blog.example.com/feed.xml").unwrap();
+    let stories = feed.stories;
+
+    assert_eq!(feed.title, "Example Blog");
+    assert_eq!(stories.len(), 2);
+    let post = &stories[0];
+    assert_eq!(post.title, "Writing a parser");
+    assert_eq!(
+        post.url.as_deref(),
+        Some("https://blog.example.com/posts/parser")
+    );
+    assert_eq!(post.time, 1_709_316_000);
+    assert_eq!(post.by, "Example Blog");
+    // 有全文时优先用全文，不用摘要
+    assert_eq!(post.text.as_deref(), Some("<p>The whole post.</p>"));
+    assert!(feeds::is_feed_id(post.id));
+    assert!(!lobsters::is_lobsters_id(post.id));
+    assert_eq!(post.item_url(), "https://blog.example.com/posts/parser");
+
+    // 同一个源里同一个 guid 每次都得到同一个 id
+    let again = feeds::parse_feed(rss.as_bytes(), "https://blog.example.com/feed.xml").unwrap();
+    assert_eq!(again.stories[0].id, post.id);
+    assert_ne!(stories[1].id, post.id);
+}
+
+#[test]
+fn atom_entries_use_the_alternate_link_and_author() {
+    let atom = r#"<?xml version="1.0" encoding="utf-8"?>
+<feed xmlns="http://www.w3.org/2005/Atom">
+  <title>Atom Example</title>
+  <id>urn:uuid:feed</id>
+  <updated>2024-03-02T10:00:00Z</updated>
+  <entry>
+    <title>Release notes</title>
+    <id>urn:uuid:entry-1</id>
+    <link rel="self" href="https://example.org/entries/1.atom"/>
+    <link rel="alternate" href="https://example.org/releases/1"/>
+    <updated>2024-03-02T10:00:00Z</updated>
+    <author><name>Robin</name></author>
+    <summary>What changed</summary>
+  </entry>
+</feed>"#;
+
+    let stories = feeds::parse_feed(atom.as_bytes(), "https://example.org/atom.xml")
+        .unwrap()
+        .stories;
+
+    assert_eq!(stories.len(), 1);
+    assert_eq!(
+        stories[0].url.as_deref(),
+        Some("https://example.org/releases/1")
+    );
+    assert_eq!(stories[0].by, "Robin");
+    assert_eq!(stories[0].time, 1_709_373_600);
+    assert_eq!(stories[0].text.as_deref(), Some("What changed"));
+    assert!(feeds::parse_feed(b"<html>not a feed</html>", "https://example.org/").is_err());
+}
+
+#[test]
+fn feed_urls_get_a_scheme_and_must_be_http() {
+    assert_eq!(
+        feeds::normalize_feed_url("  blog.example.com/feed.xml "),
+        Some("https://blog.example.com/feed.xml".to_string())
+    );
+    assert_eq!(
+        feeds::normalize_feed_url("http://example.com/rss"),
+        Some("http://example.com/rss".to_string())
+    );
+    assert_eq!(feeds::normalize_feed_url("ftp://example.com/rss"), None);
+    assert_eq!(feeds::normalize_feed_url(""), None);
+}
+
+#[test]
+fn feed_source_merges_subscriptions_newest_first() {
+    let feed = |title: &str, date: &str| {
+        format!(
+            r#"<rss version="2.0"><channel><title>{title}</title><item><title>{title} post</title><link>https://example.com/{title}</link><pubDate>{date}</pubDate></item></channel></rss>"#
+        )
+    };
+    let older = feed("older", "Fri, 01 Mar 2024 18:00:00 GMT");
+    let newer = feed("newer", "Sat, 02 Mar 2024 18:00:00 GMT");
+    let feeds = FeedList::new(vec![
+        "https://a.example.com/older.xml".to_string(),
+        "https://b.example.com/missing.xml".to_string(),
+        "https://c.example.com/newer.xml".to_string(),
+    ]);
+    let http = fake_http(&[
+        ("/older.xml", older.as_str()),
+        ("/newer.xml", newer.as_str()),
+    ]);
+    let source: Arc<dyn NewsSource> =
+        Arc::new(FeedSource::new(HttpFetcher::new(http), feeds.clone()));
+
+    // 一个源取不到不影响其余的源
+    let ids = futures::executor::block_on(source.fetch_story_ids(NewsChannel::Feeds)).unwrap();
+    let stories = futures::executor::block_on(source.fetch_summaries(NewsChannel::Feeds, &ids));
+    assert_eq!(
+        stories.iter().map(|s| s.title.as_str()).collect::<Vec<_>>(),
+        vec!["newer post", "older post"]
+    );
+
+    // 加载过的源记下标题，没取到的用域名
+    assert_eq!(feeds.title("https://a.example.com/older.xml"), "older");
+    assert_eq!(
+        feeds.title("https://b.example.com/missing.xml"),
+        "b.example.com"
+    );
+
+    let story = futures::executor::block_on(source.fetch_story(stories[0].id)).unwrap();
+    assert_eq!(story.url.as_deref(), Some("https://example.com/newer"));
+    assert!(futures::executor::block_on(source.fetch_comments(&story))
+        .unwrap()
+        .is_empty());
+
+    // 搜索只看加载过的条目，不会请求别的服务
+    let found = futures::executor::block_on(source.search_stories("OLDER", 30)).unwrap();
+    assert_eq!(
+        found.iter().map(|s| s.title.as_str()).collect::<Vec<_>>(),
+        vec!["older post"]
+    );
+
+    // 全部失败时报错；还没有订阅时是空列表
+    feeds.set(vec!["https://b.example.com/missing.xml".to_string()]);
+    assert!(futures::executor::block_on(source.fetch_story_ids(NewsChannel::Feeds)).is_err());
+    feeds.set(Vec::new());
+    assert!(
+        futures::executor::block_on(source.fetch_story_ids(NewsChannel::Feeds))
+            .unwrap()
+            .is_empty()
+    );
+}
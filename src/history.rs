@@ -0,0 +1,190 @@
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use std::borrow::Borrow;
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::fmt::Debug;
+use std::hash::Hash;
+use std::path::{Path, PathBuf};
+
+pub(crate) const SEEN_COMMENTS_FILE: &str = "seen_comments.json";
+/// 最多记录多少个 story 的已读评论，超出时淘汰最久没访问的
+pub const MAX_SEEN_STORIES: usize = 200;
+pub(crate) const VISITED_URLS_FILE: &str = "visited_urls.json";
+pub(crate) const READ_STORIES_FILE: &str = "read_stories.json";
+/// 已读 story 最多保留条数，超出时丢弃最早标记的
+pub const MAX_READ_STORIES: usize = 5000;
+/// 访问过的链接最多保留条数，超出时丢弃最早的
+pub const MAX_VISITED_URLS: usize = 2000;
+
+/// 每个 story 已经看过的评论 id，持久化在数据目录
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+pub struct SeenComments {
+    stories: HashMap<i64, SeenStory>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+struct SeenStory {
+    visited_at: i64,
+    comment_ids: HashSet<i64>,
+}
+
+impl SeenComments {
+    pub fn load(dir: &Path) -> Self {
+        std::fs::read(seen_comments_path(dir))
+            .ok()
+            .and_then(|bytes| serde_json::from_slice(&bytes).ok())
+            .unwrap_or_default()
+    }
+
+    pub fn save(&self, dir: &Path) -> Result<(), String> {
+        let path = seen_comments_path(dir);
+        let json = serde_json::to_vec(self).map_err(|e| e.to_string())?;
+        crate::settings::write_atomic(&path, &json)
+    }
+
+    pub fn seen_for(&self, story_id: i64) -> HashSet<i64> {
+        self.stories
+            .get(&story_id)
+            .map(|story| story.comment_ids.clone())
+            .unwrap_or_default()
+    }
+
+    /// 记录本次访问看到的评论，并按访问时间淘汰旧 story
+    pub fn record(&mut self, story_id: i64, comment_ids: impl IntoIterator<Item = i64>, now: i64) {
+        let story = self.stories.entry(story_id).or_default();
+        story.visited_at = now;
+        story.comment_ids.extend(comment_ids);
+
+        while self.stories.len() > MAX_SEEN_STORIES {
+            let Some(oldest) = self
+                .stories
+                .iter()
+                .min_by_key(|(_, story)| story.visited_at)
+                .map(|(&id, _)| id)
+            else {
+                break;
+            };
+            self.stories.remove(&oldest);
+        }
+    }
+}
+
+fn seen_comments_path(dir: &Path) -> PathBuf {
+    dir.join(SEEN_COMMENTS_FILE)
+}
+
+/// `RecentSet` 里保存的一类记录：条目类型、文件名和上限
+pub trait RecentKind {
+    type Item: Clone + Eq + Hash + Debug + Serialize + DeserializeOwned;
+    const FILE: &'static str;
+    const CAPACITY: usize;
+    /// 已有的条目再次记录时移到最新（访问记录按最近访问淘汰），否则保持原位
+    const REFRESH: bool;
+}
+
+/// 有上限、按记录顺序保存的集合（最新的在后），超出上限时丢弃最早的，
+/// 持久化成数据目录里的一个 JSON 文件
+#[derive(Debug, Clone, PartialEq)]
+pub struct RecentSet<K: RecentKind> {
+    items: VecDeque<K::Item>,
+    index: HashSet<K::Item>,
+}
+
+/// 文件里的格式；早期版本的字段叫 `urls` / `ids`
+#[derive(Serialize, Deserialize)]
+struct StoredItems<T> {
+    #[serde(alias = "urls", alias = "ids")]
+    items: T,
+}
+
+impl<K: RecentKind> Default for RecentSet<K> {
+    fn default() -> Self {
+        Self {
+            items: VecDeque::new(),
+            index: HashSet::new(),
+        }
+    }
+}
+
+impl<K: RecentKind> RecentSet<K> {
+    pub fn load(dir: &Path) -> Self {
+        let items = std::fs::read(dir.join(K::FILE))
+            .ok()
+            .and_then(|bytes| serde_json::from_slice::<StoredItems<VecDeque<K::Item>>>(&bytes).ok())
+            .map(|stored| stored.items)
+            .unwrap_or_default();
+        Self {
+            index: items.iter().cloned().collect(),
+            items,
+        }
+    }
+
+    pub fn save(&self, dir: &Path) -> Result<(), String> {
+        let stored = StoredItems { items: &self.items };
+        let json = serde_json::to_vec(&stored).map_err(|e| e.to_string())?;
+        crate::settings::write_atomic(&dir.join(K::FILE), &json)
+    }
+
+    pub fn contains<Q>(&self, item: &Q) -> bool
+    where
+        K::Item: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        self.index.contains(item)
+    }
+
+    /// 记录一次，返回是否有变化
+    pub fn record(&mut self, item: K::Item) -> bool {
+        if self.index.contains(&item) {
+            if !K::REFRESH || self.items.back() == Some(&item) {
+                return false;
+            }
+            self.items.retain(|existing| *existing != item);
+        } else {
+            self.index.insert(item.clone());
+        }
+        self.items.push_back(item);
+
+        while self.items.len() > K::CAPACITY {
+            if let Some(evicted) = self.items.pop_front() {
+                self.index.remove(&evicted);
+            }
+        }
+        true
+    }
+
+    /// 全部清掉，返回是否有变化
+    pub fn clear(&mut self) -> bool {
+        if self.items.is_empty() {
+            return false;
+        }
+        self.items.clear();
+        self.index.clear();
+        true
+    }
+}
+
+/// 打开过的文章和链接
+#[derive(Debug, Clone, PartialEq)]
+pub enum VisitedUrl {}
+
+impl RecentKind for VisitedUrl {
+    type Item = String;
+    const FILE: &'static str = VISITED_URLS_FILE;
+    const CAPACITY: usize = MAX_VISITED_URLS;
+    const REFRESH: bool = true;
+}
+
+/// 已读的 story（选中过，或开启设置后滚过列表顶部）
+#[derive(Debug, Clone, PartialEq)]
+pub enum ReadStory {}
+
+impl RecentKind for ReadStory {
+    type Item = i64;
+    const FILE: &'static str = READ_STORIES_FILE;
+    const CAPACITY: usize = MAX_READ_STORIES;
+    const REFRESH: bool = false;
+}
+
+pub type VisitedUrls = RecentSet<VisitedUrl>;
+pub type ReadStories = RecentSet<ReadStory>;
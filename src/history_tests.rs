@@ -0,0 +1,90 @@
+use crate::history::{
+    ReadStories, SeenComments, VisitedUrls, MAX_SEEN_STORIES, MAX_VISITED_URLS, READ_STORIES_FILE,
+    VISITED_URLS_FILE,
+};
+
+#[test]
+fn seen_comments_accumulate_per_story() {
+    let mut seen = SeenComments::default();
+    seen.record(1, [10, 11], 100);
+    seen.record(1, [12], 200);
+    seen.record(2, [20], 150);
+
+    let mut ids = seen.seen_for(1).into_iter().collect::<Vec<_>>();
+    ids.sort_unstable();
+    assert_eq!(ids, vec![10, 11, 12]);
+    assert!(seen.seen_for(3).is_empty());
+}
+
+#[test]
+fn seen_comments_evict_least_recently_visited_story() {
+    let mut seen = SeenComments::default();
+    for story_id in 0..MAX_SEEN_STORIES as i64 {
+        seen.record(story_id, [story_id * 10], 1_000 + story_id);
+    }
+    // 重新访问最早的 story，淘汰的应该是第二早的
+    seen.record(0, [1], 5_000);
+    seen.record(9_999, [1], 6_000);
+
+    assert!(!seen.seen_for(0).is_empty());
+    assert!(seen.seen_for(1).is_empty());
+    assert!(!seen.seen_for(9_999).is_empty());
+
+    let json = serde_json::to_string(&seen).unwrap();
+    let restored: SeenComments = serde_json::from_str(&json).unwrap();
+    assert_eq!(restored, seen);
+}
+
+#[test]
+fn visited_urls_are_bounded_and_keep_recent_visits() {
+    let mut visited = VisitedUrls::default();
+    assert!(visited.record("https://example.com/0".to_string()));
+    assert!(!visited.record("https://example.com/0".to_string()));
+    for i in 1..=MAX_VISITED_URLS {
+        visited.record(format!("https://example.com/{i}"));
+    }
+
+    assert!(!visited.contains("https://example.com/0"));
+    assert!(visited.contains("https://example.com/1"));
+    assert!(visited.contains(&format!("https://example.com/{MAX_VISITED_URLS}")));
+}
+
+#[test]
+fn read_stories_can_be_cleared() {
+    let mut read = ReadStories::default();
+    assert!(read.record(1));
+    assert!(!read.record(1));
+    read.record(2);
+
+    assert!(read.clear());
+    assert!(!read.contains(&1) && !read.contains(&2));
+    assert!(!read.clear());
+    assert!(read.record(1));
+}
+
+#[test]
+fn recent_sets_round_trip_and_read_the_old_file_format() {
+    let dir = std::env::temp_dir().join(format!("oneapp-history-{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    std::fs::write(dir.join(READ_STORIES_FILE), r#"{"ids":[3,1]}"#).unwrap();
+    std::fs::write(
+        dir.join(VISITED_URLS_FILE),
+        r#"{"urls":["https://a.test"]}"#,
+    )
+    .unwrap();
+
+    let mut read = ReadStories::load(&dir);
+    assert!(read.contains(&3) && read.contains(&1));
+    // 已读标记不因重复记录而变化，访问记录会移到最新
+    assert!(!read.record(3));
+    let mut visited = VisitedUrls::load(&dir);
+    assert!(visited.record("https://b.test".to_string()));
+    assert!(visited.record("https://a.test".to_string()));
+
+    read.record(4);
+    read.save(&dir).unwrap();
+    visited.save(&dir).unwrap();
+    assert_eq!(ReadStories::load(&dir), read);
+    assert_eq!(VisitedUrls::load(&dir), visited);
+    let _ = std::fs::remove_dir_all(&dir);
+}
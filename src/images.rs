@@ -0,0 +1,245 @@
+use crate::retry::{self, FetchError, RetryPolicy};
+use gpui::http_client::{http, AsyncBody, HttpClient, HttpRequestExt, Method, RedirectPolicy};
+use gpui::{BackgroundExecutor, Image, ImageFormat};
+use serde::{Deserialize, Serialize};
+use std::cell::RefCell;
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
+use std::rc::Rc;
+use std::sync::Arc;
+use std::time::Duration;
+
+const MAX_IMAGE_BYTES: usize = 20 * 1024 * 1024;
+/// 图片下载最多试 3 次，第一次重试前等 500ms，避免对限流的服务器反复请求
+pub const IMAGE_RETRY: RetryPolicy = RetryPolicy {
+    attempts: 3,
+    base_delay: Duration::from_millis(500),
+};
+/// 一篇文章至少有这么多张图片全部加载失败，才认为图片在这个平台上整体不可用
+pub const BROKEN_IMAGES_MIN: usize = 3;
+
+/// 阅读器里图片的显示方式
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ImageMode {
+    /// 显示图片，图片整体加载不了时自动改用文字
+    #[default]
+    Images,
+    /// 只显示图片的说明文字（alt/caption），不下载图片
+    Text,
+}
+
+/// 阅读器里图片的右键操作
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ImageAction {
+    Copy,
+    Save,
+}
+
+impl ImageAction {
+    pub fn label(&self) -> &'static str {
+        match self {
+            ImageAction::Copy => "Copy Image",
+            ImageAction::Save => "Save Image…",
+        }
+    }
+}
+
+/// 下载好的图片和服务器返回的 content-type
+pub struct FetchedImage {
+    pub bytes: Vec<u8>,
+    pub content_type: Option<String>,
+}
+
+pub async fn fetch_image(
+    http_client: Arc<dyn HttpClient>,
+    url: &str,
+    executor: BackgroundExecutor,
+) -> Result<FetchedImage, String> {
+    retry::with_retries(
+        IMAGE_RETRY,
+        || fetch_image_once(http_client.clone(), url),
+        |delay| executor.timer(delay),
+    )
+    .await
+}
+
+async fn fetch_image_once(
+    http_client: Arc<dyn HttpClient>,
+    url: &str,
+) -> Result<FetchedImage, FetchError> {
+    let request = http::Request::builder()
+        .method(Method::GET)
+        .uri(url)
+        .follow_redirects(RedirectPolicy::FollowAll)
+        .header("User-Agent", "OneApp/0.1 (GPUI Reader Mode)")
+        .body(AsyncBody::empty())
+        .map_err(|e| FetchError::fatal(e.to_string()))?;
+
+    // 连接失败、超时都算暂时性的
+    let response = http_client
+        .send(request)
+        .await
+        .map_err(|e| FetchError::transient(e.to_string()))?;
+    if !response.status().is_success() {
+        return Err(FetchError::from_status(response.status(), url));
+    }
+
+    let content_type = response
+        .headers()
+        .get(http::header::CONTENT_TYPE)
+        .and_then(|v| v.to_str().ok())
+        .map(str::to_string);
+    let mut body = response.into_body();
+    let bytes = crate::reader::read_to_end_limited(&mut body, MAX_IMAGE_BYTES)
+        .await
+        .map_err(FetchError::from)?;
+    Ok(FetchedImage {
+        bytes,
+        content_type,
+    })
+}
+
+/// 转成可以直接显示的图片，格式不支持时报错
+pub fn displayable_image(image: FetchedImage, url: &str) -> Result<Arc<Image>, String> {
+    let extension = image_extension(image.content_type.as_deref(), url);
+    let format = clipboard_format(extension)
+        .ok_or_else(|| format!(".{} images aren't supported", extension))?;
+    Ok(Arc::new(Image::from_bytes(format, image.bytes)))
+}
+
+/// 手动重新加载的阅读器图片
+#[derive(Clone)]
+pub enum ImageReload {
+    Loading,
+    Loaded(Arc<Image>),
+    Failed(String),
+}
+
+/// 按 URL 记录重新加载的结果，成功后阅读器直接显示下载好的图片
+#[derive(Default)]
+pub struct ImageReloads {
+    images: HashMap<String, ImageReload>,
+}
+
+impl ImageReloads {
+    /// 开始重新加载，已经在加载或已经成功时返回 `false`
+    pub fn begin(&mut self, url: &str) -> bool {
+        if matches!(
+            self.images.get(url),
+            Some(ImageReload::Loading | ImageReload::Loaded(_))
+        ) {
+            return false;
+        }
+        self.images.insert(url.to_string(), ImageReload::Loading);
+        true
+    }
+
+    pub fn finish(&mut self, url: &str, result: Result<Arc<Image>, String>) {
+        let state = match result {
+            Ok(image) => ImageReload::Loaded(image),
+            Err(e) => ImageReload::Failed(e),
+        };
+        self.images.insert(url.to_string(), state);
+    }
+
+    pub fn get(&self, url: &str) -> Option<&ImageReload> {
+        self.images.get(url)
+    }
+}
+
+/// gpui 自己加载失败的阅读器图片。失败记录发生在绘制时，
+/// 所以用共享的内部可变性，下一帧再由视图检查
+#[derive(Clone, Default)]
+pub struct ImageFailures {
+    urls: Rc<RefCell<HashSet<String>>>,
+}
+
+impl ImageFailures {
+    pub fn record(&self, url: &str) {
+        if !self.urls.borrow().contains(url) {
+            self.urls.borrow_mut().insert(url.to_string());
+        }
+    }
+
+    /// 文章里的图片是否全部加载失败（且至少有 `BROKEN_IMAGES_MIN` 张），
+    /// 这时更可能是平台不支持解码，而不是个别图片坏了
+    pub fn all_failed<'a>(&self, urls: impl IntoIterator<Item = &'a str>) -> bool {
+        let failed = self.urls.borrow();
+        let mut total = 0;
+        for url in urls {
+            if !failed.contains(url) {
+                return false;
+            }
+            total += 1;
+        }
+        total >= BROKEN_IMAGES_MIN
+    }
+}
+
+/// 按 content-type 推断扩展名，服务器没给或不认识时看 URL 路径
+pub fn image_extension(content_type: Option<&str>, url: &str) -> &'static str {
+    let mime = content_type
+        .and_then(|value| value.split(';').next())
+        .map(|mime| mime.trim().to_ascii_lowercase());
+    let from_mime = match mime.as_deref() {
+        Some("image/png") => Some("png"),
+        Some("image/jpeg" | "image/jpg" | "image/pjpeg") => Some("jpg"),
+        Some("image/gif") => Some("gif"),
+        Some("image/webp") => Some("webp"),
+        Some("image/svg+xml") => Some("svg"),
+        Some("image/bmp") => Some("bmp"),
+        Some("image/tiff") => Some("tiff"),
+        Some("image/avif") => Some("avif"),
+        _ => None,
+    };
+
+    from_mime
+        .or_else(|| {
+            let path = url::Url::parse(url).ok()?.path().to_ascii_lowercase();
+            let (_, ext) = path.rsplit_once('.')?;
+            match ext {
+                "png" => Some("png"),
+                "jpg" | "jpeg" => Some("jpg"),
+                "gif" => Some("gif"),
+                "webp" => Some("webp"),
+                "svg" => Some("svg"),
+                "bmp" => Some("bmp"),
+                "tif" | "tiff" => Some("tiff"),
+                "avif" => Some("avif"),
+                _ => None,
+            }
+        })
+        .unwrap_or("png")
+}
+
+/// 剪贴板支持的图片格式，`None` 表示只能保存
+pub fn clipboard_format(extension: &str) -> Option<ImageFormat> {
+    match extension {
+        "png" => Some(ImageFormat::Png),
+        "jpg" => Some(ImageFormat::Jpeg),
+        "gif" => Some(ImageFormat::Gif),
+        "webp" => Some(ImageFormat::Webp),
+        "svg" => Some(ImageFormat::Svg),
+        "bmp" => Some(ImageFormat::Bmp),
+        "tiff" => Some(ImageFormat::Tiff),
+        _ => None,
+    }
+}
+
+/// 用户选的路径没有扩展名时补上推断出的扩展名
+pub fn with_image_extension(path: PathBuf, extension: &str) -> PathBuf {
+    if path.extension().is_some() {
+        path
+    } else {
+        path.with_extension(extension)
+    }
+}
+
+/// 保存对话框的默认目录：`~/Downloads`，没有时用 `fallback`
+pub fn default_save_dir(fallback: &Path) -> PathBuf {
+    std::env::var_os("HOME")
+        .map(|home| PathBuf::from(home).join("Downloads"))
+        .filter(|dir| dir.is_dir())
+        .unwrap_or_else(|| fallback.to_path_buf())
+}
@@ -0,0 +1,123 @@
+use crate::images::{self, FetchedImage, ImageFailures, ImageReload, ImageReloads};
+use crate::reader_view;
+use crate::retry::{self, FetchError};
+use gpui::ImageFormat;
+use std::cell::{Cell, RefCell};
+use std::path::PathBuf;
+use std::time::Duration;
+
+#[test]
+fn image_extension_prefers_content_type_then_url() {
+    let url = "https://example.com/media/chart.JPEG?w=800";
+
+    assert_eq!(
+        images::image_extension(Some("image/png; charset=binary"), url),
+        "png"
+    );
+    assert_eq!(images::image_extension(Some("image/svg+xml"), url), "svg");
+    // 不认识的 content-type 退回 URL 后缀
+    assert_eq!(
+        images::image_extension(Some("application/octet-stream"), url),
+        "jpg"
+    );
+    assert_eq!(
+        images::image_extension(None, "https://example.com/a.webp"),
+        "webp"
+    );
+    assert_eq!(
+        images::image_extension(None, "https://example.com/image"),
+        "png"
+    );
+
+    assert_eq!(images::clipboard_format("jpg"), Some(ImageFormat::Jpeg));
+    assert_eq!(images::clipboard_format("avif"), None);
+}
+
+#[test]
+fn chosen_save_path_gets_the_inferred_extension() {
+    assert_eq!(
+        images::with_image_extension(PathBuf::from("/tmp/diagram"), "gif"),
+        PathBuf::from("/tmp/diagram.gif")
+    );
+    // 用户自己写了扩展名时保持不变
+    assert_eq!(
+        images::with_image_extension(PathBuf::from("/tmp/diagram.png"), "gif"),
+        PathBuf::from("/tmp/diagram.png")
+    );
+}
+
+#[test]
+fn image_failing_once_is_retried_and_displayed() {
+    let url = "https://example.com/chart.png";
+    let attempts = Cell::new(0);
+    let delays = RefCell::new(Vec::new());
+
+    let fetched = futures::executor::block_on(retry::with_retries(
+        images::IMAGE_RETRY,
+        || {
+            attempts.set(attempts.get() + 1);
+            let attempt = attempts.get();
+            async move {
+                if attempt == 1 {
+                    Err(FetchError::transient("HTTP 429 Too Many Requests"))
+                } else {
+                    Ok(FetchedImage {
+                        bytes: vec![0x89, b'P', b'N', b'G'],
+                        content_type: Some("image/png".to_string()),
+                    })
+                }
+            }
+        },
+        |delay| {
+            delays.borrow_mut().push(delay);
+            async {}
+        },
+    ));
+    assert_eq!(attempts.get(), 2);
+    let delays = delays.borrow();
+    assert_eq!(delays.len(), 1);
+    let first_retry = images::IMAGE_RETRY.delay(0, 0.)..=images::IMAGE_RETRY.delay(0, 1.);
+    assert!(first_retry.contains(&delays[0]));
+    assert_eq!(*first_retry.start(), Duration::from_millis(500));
+
+    let mut reloads = ImageReloads::default();
+    assert!(reloads.begin(url));
+    assert!(!reloads.begin(url));
+    reloads.finish(
+        url,
+        fetched.and_then(|image| images::displayable_image(image, url)),
+    );
+    assert!(matches!(reloads.get(url), Some(ImageReload::Loaded(_))));
+}
+
+#[test]
+fn article_whose_images_all_fail_switches_to_text_placeholders() {
+    let failures = ImageFailures::default();
+    let urls = [
+        "https://example.com/a.png",
+        "https://example.com/b.png",
+        "https://example.com/c.png",
+    ];
+
+    // gpui 绘制失败的图片时由 fallback 记录
+    let fallback = failures.clone();
+    fallback.record(urls[0]);
+    fallback.record(urls[1]);
+    assert!(!failures.all_failed(urls));
+
+    fallback.record(urls[2]);
+    fallback.record(urls[2]);
+    assert!(failures.all_failed(urls));
+    // 图片太少时不怪平台，只是个别图片坏了
+    assert!(!failures.all_failed([urls[0]]));
+
+    assert_eq!(
+        reader_view::image_description(Some("Build graph"), Some("Figure 2: the build graph")),
+        "Image: Figure 2: the build graph"
+    );
+    assert_eq!(
+        reader_view::image_description(Some("Build graph"), None),
+        "Image: Build graph"
+    );
+    assert_eq!(reader_view::image_description(Some("  "), None), "Image");
+}
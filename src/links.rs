@@ -0,0 +1,50 @@
+/// 剪贴板等外部来源的链接分类结果
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum LinkTarget {
+    /// HN 讨论页（`news.ycombinator.com/item?id=`）
+    HackerNewsItem(i64),
+    /// 普通文章链接，用阅读模式打开
+    Article(String),
+}
+
+const HN_HOSTS: &[&str] = &["news.ycombinator.com", "ycombinator.com"];
+
+/// 只接受单个 http(s) 链接；省略协议的 `example.com/post` 按 https 处理
+#[must_use]
+pub fn classify_link(text: &str) -> Option<LinkTarget> {
+    let text = text.trim();
+    if text.is_empty() || text.contains(char::is_whitespace) {
+        return None;
+    }
+
+    let url = match url::Url::parse(text) {
+        Ok(url) => url,
+        Err(url::ParseError::RelativeUrlWithoutBase) if looks_like_host(text) => {
+            url::Url::parse(&format!("https://{text}")).ok()?
+        }
+        Err(_) => return None,
+    };
+    if !matches!(url.scheme(), "http" | "https") {
+        return None;
+    }
+    let host = url.host_str()?.trim_start_matches("www.");
+
+    if HN_HOSTS.contains(&host) && url.path() == "/item" {
+        let id = url
+            .query_pairs()
+            .find(|(key, _)| key == "id")
+            .and_then(|(_, value)| value.parse::<i64>().ok())?;
+        return Some(LinkTarget::HackerNewsItem(id));
+    }
+    Some(LinkTarget::Article(url.to_string()))
+}
+
+fn looks_like_host(text: &str) -> bool {
+    let host = text.split(['/', '?', '#']).next().unwrap_or_default();
+    host.contains('.')
+        && !host.starts_with('.')
+        && !host.ends_with('.')
+        && host
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || matches!(c, '.' | '-' | ':'))
+}
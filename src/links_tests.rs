@@ -0,0 +1,35 @@
+use crate::links::{classify_link, LinkTarget};
+
+#[test]
+fn clipboard_text_is_classified_as_item_article_or_nothing() {
+    assert_eq!(
+        classify_link("https://news.ycombinator.com/item?id=8863"),
+        Some(LinkTarget::HackerNewsItem(8863))
+    );
+    assert_eq!(
+        classify_link("  news.ycombinator.com/item?id=42&p=2\n"),
+        Some(LinkTarget::HackerNewsItem(42))
+    );
+    assert_eq!(
+        classify_link("https://example.com/posts/1?ref=hn"),
+        Some(LinkTarget::Article(
+            "https://example.com/posts/1?ref=hn".to_string()
+        ))
+    );
+    // 其他 HN 页面按普通链接处理
+    assert_eq!(
+        classify_link("https://news.ycombinator.com/newest"),
+        Some(LinkTarget::Article(
+            "https://news.ycombinator.com/newest".to_string()
+        ))
+    );
+
+    assert_eq!(classify_link(""), None);
+    assert_eq!(classify_link("just some copied words"), None);
+    assert_eq!(classify_link("hello"), None);
+    assert_eq!(classify_link("ftp://example.com/file"), None);
+    assert_eq!(
+        classify_link("https://news.ycombinator.com/item?id=abc"),
+        None
+    );
+}
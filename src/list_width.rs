@@ -0,0 +1,52 @@
+use serde::{Deserialize, Serialize};
+
+pub const STORY_LIST_DEFAULT_WIDTH: f32 = 360.0;
+pub const STORY_LIST_MIN_WIDTH: f32 = 240.0;
+/// 拖动或切换列表宽度时给详情区保留的最小宽度
+pub const STORY_LIST_MIN_DETAIL_WIDTH: f32 = 360.0;
+/// 快捷键循环切换的宽度：窄、默认、宽，之后隐藏列表
+pub const STORY_LIST_PRESET_WIDTHS: [f32; 3] =
+    [STORY_LIST_MIN_WIDTH, STORY_LIST_DEFAULT_WIDTH, 520.0];
+
+/// 双击分隔条时列表恢复到哪里
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SplitterReset {
+    /// 默认宽度
+    #[default]
+    Default,
+    /// 上次拖动后记住的宽度
+    Preferred,
+    /// 隐藏列表，只显示详情
+    Collapse,
+}
+
+/// 列表能占的最大宽度；`available` 是去掉侧边栏和分隔条后的窗口宽度
+pub fn max_width(available: f32) -> f32 {
+    (available - STORY_LIST_MIN_DETAIL_WIDTH).max(STORY_LIST_MIN_WIDTH)
+}
+
+pub fn clamp_width(width: f32, available: f32) -> f32 {
+    width.clamp(STORY_LIST_MIN_WIDTH, max_width(available))
+}
+
+/// 启动时沿用上次拖动的宽度，按当前窗口重新夹一遍（窗口可能比上次小）
+pub fn restored_width(preferred: Option<f32>, available: f32) -> f32 {
+    preferred.map_or(STORY_LIST_DEFAULT_WIDTH, |width| {
+        clamp_width(width, available)
+    })
+}
+
+/// 循环到下一档宽度，`None` 表示隐藏列表。
+///
+/// 从当前宽度往上找第一档更宽的预设（窗口太窄时几档会夹到同一宽度，直接跳过），
+/// 已经是最宽的一档就隐藏，隐藏后回到最窄的一档。
+pub fn next_width(current: Option<f32>, available: f32) -> Option<f32> {
+    let Some(current) = current else {
+        return Some(clamp_width(STORY_LIST_PRESET_WIDTHS[0], available));
+    };
+    STORY_LIST_PRESET_WIDTHS
+        .iter()
+        .map(|width| clamp_width(*width, available))
+        .find(|width| *width > current + 0.5)
+}
@@ -0,0 +1,81 @@
+use crate::list_width::{self, STORY_LIST_DEFAULT_WIDTH, STORY_LIST_MIN_WIDTH};
+
+#[test]
+fn cycling_goes_narrow_default_wide_hidden_and_back() {
+    let available = 1600.0;
+    let mut width = Some(STORY_LIST_DEFAULT_WIDTH);
+    let mut seen = Vec::new();
+    for _ in 0..4 {
+        width = list_width::next_width(width, available);
+        seen.push(width);
+    }
+
+    assert_eq!(
+        seen,
+        vec![
+            Some(520.0),
+            None,
+            Some(STORY_LIST_MIN_WIDTH),
+            Some(STORY_LIST_DEFAULT_WIDTH),
+        ]
+    );
+}
+
+#[test]
+fn cycling_stays_within_min_and_max() {
+    // 窗口只够列表加最小详情宽度：宽档夹到上限，和默认档重合的只出现一次
+    let available = 800.0;
+    let max = list_width::max_width(available);
+    assert_eq!(max, 440.0);
+
+    let mut width = Some(STORY_LIST_MIN_WIDTH);
+    let mut seen = Vec::new();
+    for _ in 0..4 {
+        width = list_width::next_width(width, available);
+        seen.push(width);
+    }
+    assert_eq!(
+        seen,
+        vec![
+            Some(STORY_LIST_DEFAULT_WIDTH),
+            Some(max),
+            None,
+            Some(STORY_LIST_MIN_WIDTH)
+        ]
+    );
+
+    // 比最小详情宽度还窄的窗口，列表仍然保持最小宽度
+    assert_eq!(list_width::clamp_width(900.0, 300.0), STORY_LIST_MIN_WIDTH);
+    assert_eq!(
+        list_width::next_width(None, 300.0),
+        Some(STORY_LIST_MIN_WIDTH)
+    );
+    assert_eq!(
+        list_width::next_width(Some(STORY_LIST_MIN_WIDTH), 300.0),
+        None
+    );
+}
+
+#[test]
+fn restored_width_is_clamped_to_the_current_window() {
+    assert_eq!(
+        list_width::restored_width(None, 800.0),
+        STORY_LIST_DEFAULT_WIDTH
+    );
+    assert_eq!(list_width::restored_width(Some(400.0), 1600.0), 400.0);
+    // 上次在大屏上拖宽了，这次窗口只剩 800：夹到给详情留出最小宽度的上限
+    assert_eq!(list_width::restored_width(Some(900.0), 800.0), 440.0);
+    assert_eq!(
+        list_width::restored_width(Some(100.0), 1600.0),
+        STORY_LIST_MIN_WIDTH
+    );
+}
+
+#[test]
+fn dragged_width_between_presets_moves_to_the_next_wider_one() {
+    assert_eq!(
+        list_width::next_width(Some(300.0), 1600.0),
+        Some(STORY_LIST_DEFAULT_WIDTH)
+    );
+    assert_eq!(list_width::next_width(Some(700.0), 1600.0), None);
+}
@@ -0,0 +1,398 @@
+use crate::api::HttpFetcher;
+use crate::models::{Comment, NewsChannel, PollOption, Story, StorySummary};
+use crate::source::NewsSource;
+use futures::future::{self, LocalBoxFuture};
+use futures::stream::{self, StreamExt};
+use futures::FutureExt as _;
+use serde::Deserialize;
+use std::collections::{HashMap, HashSet};
+use std::sync::{Arc, Mutex};
+
+pub const LOBSTERS_BASE_URL: &str = "https://lobste.rs";
+/// 首页榜单，一次返回 25 条完整的 story
+pub const HOTTEST_PATH: &str = "hottest.json";
+/// short id 只用小写字母和数字
+const SHORT_ID_RADIX: u32 = 36;
+/// story 的 short id 固定 6 位，开头的 0 转成数字后会丢，还原时补回来
+const STORY_SHORT_ID_LEN: usize = 6;
+
+/// lobste.rs 的数据来源。首页一次返回完整的 story，story 的 JSON 带着整棵评论树，
+/// 所以评论不分批加载，也没有“加载更多”
+#[derive(Clone)]
+pub struct LobstersClient {
+    fetcher: HttpFetcher,
+    /// 榜单直接带着完整的 story，取摘要时不用再逐条请求
+    summaries: Arc<Mutex<HashMap<i64, StorySummary>>>,
+}
+
+/// `hottest.json` 和 `s/<short_id>.json` 里的 story，后者额外带着整棵评论树
+#[derive(Debug, Clone, Deserialize)]
+pub struct LobstersStory {
+    pub short_id: String,
+    pub title: String,
+    #[serde(default)]
+    pub url: Option<String>,
+    #[serde(default)]
+    pub score: i32,
+    pub created_at: String,
+    #[serde(default)]
+    pub comment_count: Option<i32>,
+    /// 自述帖的正文（HTML）
+    #[serde(default)]
+    pub description: Option<String>,
+    pub submitter_user: LobstersUser,
+    #[serde(default)]
+    pub comments: Vec<LobstersComment>,
+}
+
+/// 按深度优先排好的评论，`parent_comment` 为空时是顶级评论
+#[derive(Debug, Clone, Deserialize)]
+pub struct LobstersComment {
+    pub short_id: String,
+    pub created_at: String,
+    #[serde(default)]
+    pub comment: Option<String>,
+    #[serde(default)]
+    pub is_deleted: bool,
+    #[serde(default)]
+    pub is_moderated: bool,
+    #[serde(default)]
+    pub parent_comment: Option<String>,
+    pub commenting_user: LobstersUser,
+}
+
+/// 较新的接口只给用户名，旧接口给整个用户对象
+#[derive(Debug, Clone, Deserialize)]
+#[serde(untagged)]
+pub enum LobstersUser {
+    Name(String),
+    Profile {
+        username: String,
+        #[serde(default)]
+        avatar_url: Option<String>,
+    },
+}
+
+impl LobstersUser {
+    fn username(&self) -> &str {
+        match self {
+            LobstersUser::Name(name) => name,
+            LobstersUser::Profile { username, .. } => username,
+        }
+    }
+
+    fn avatar_url(&self) -> Option<String> {
+        match self {
+            LobstersUser::Name(_) => None,
+            LobstersUser::Profile { avatar_url, .. } => avatar_url
+                .as_deref()
+                .filter(|url| !url.is_empty())
+                .map(|url| {
+                    if url.starts_with('/') {
+                        format!("{LOBSTERS_BASE_URL}{url}")
+                    } else {
+                        url.to_string()
+                    }
+                }),
+        }
+    }
+}
+
+impl LobstersClient {
+    pub fn new(fetcher: HttpFetcher) -> Self {
+        Self {
+            fetcher,
+            summaries: Arc::default(),
+        }
+    }
+
+    /// 首页：记下每条的摘要，返回按排名顺序的 id
+    pub async fn fetch_hottest(&self) -> Result<Vec<i64>, String> {
+        let hottest: Vec<LobstersStory> = self.fetcher.get_json(&hottest_url()).await?;
+        let stories: Vec<Story> = hottest.iter().filter_map(story_from_lobsters).collect();
+
+        let mut summaries = self.summaries.lock().unwrap_or_else(|e| e.into_inner());
+        for story in &stories {
+            summaries.insert(story.id, StorySummary::from(story));
+        }
+        Ok(stories.iter().map(|story| story.id).collect())
+    }
+
+    /// 榜单里记下的摘要，没有时（例如从书签打开）请求整条 story
+    async fn fetch_summary(&self, id: i64) -> Option<StorySummary> {
+        let cached = self
+            .summaries
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .get(&id)
+            .cloned();
+        match cached {
+            Some(summary) => Some(summary),
+            None => self
+                .fetch_story(id)
+                .await
+                .ok()
+                .map(|story| StorySummary::from(&story)),
+        }
+    }
+
+    /// 一页摘要，取不到的条目跳过
+    pub async fn fetch_summaries(&self, channel: NewsChannel, ids: &[i64]) -> Vec<StorySummary> {
+        let results: Vec<_> = stream::iter(ids.iter().map(|&id| self.fetch_summary(id)))
+            .buffered(self.fetcher.max_concurrent_requests())
+            .collect()
+            .await;
+
+        let mut stories: Vec<StorySummary> = results.into_iter().flatten().collect();
+        if channel.sorts_by_score() {
+            stories.sort_by(|a, b| b.score.cmp(&a.score));
+        }
+        stories
+    }
+
+    pub async fn fetch_story(&self, id: i64) -> Result<Story, String> {
+        let url = story_api_url(id).ok_or_else(|| format!("Invalid Lobsters story {}", id))?;
+        let story: LobstersStory = self.fetcher.get_json(&url).await?;
+        story_from_lobsters(&story).ok_or_else(|| format!("Invalid Lobsters story {}", id))
+    }
+
+    /// story 的 JSON 带着整棵评论树，一次请求全部拿到
+    pub async fn fetch_story_comments(&self, story_id: i64) -> Result<Vec<Comment>, String> {
+        let url = story_api_url(story_id)
+            .ok_or_else(|| format!("Invalid Lobsters story {}", story_id))?;
+        let item: LobstersStory = self.fetcher.get_json(&url).await?;
+        Ok(comments_from_lobsters(story_id, &item.comments))
+    }
+
+    /// 悬停预览用：前 `count` 条顶级评论，已删除的跳过
+    pub async fn fetch_comment_preview(
+        &self,
+        story_id: i64,
+        count: usize,
+    ) -> Result<Vec<Comment>, String> {
+        Ok(self
+            .fetch_story_comments(story_id)
+            .await?
+            .into_iter()
+            .filter(|comment| comment.depth == 0 && comment.by.is_some())
+            .take(count)
+            .collect())
+    }
+
+    /// 调试用：story 的原始 JSON；评论没有单独的接口
+    pub async fn fetch_item_raw(&self, id: i64) -> Option<serde_json::Value> {
+        self.fetcher.get_json(&story_api_url(id)?).await.ok()
+    }
+}
+
+impl NewsSource for LobstersClient {
+    // 只有首页一个榜单
+    fn fetch_story_ids(
+        &self,
+        _channel: NewsChannel,
+    ) -> LocalBoxFuture<'_, Result<Vec<i64>, String>> {
+        self.fetch_hottest().boxed_local()
+    }
+
+    fn fetch_summaries<'a>(
+        &'a self,
+        channel: NewsChannel,
+        ids: &'a [i64],
+    ) -> LocalBoxFuture<'a, Vec<StorySummary>> {
+        LobstersClient::fetch_summaries(self, channel, ids).boxed_local()
+    }
+
+    // lobste.rs 的搜索只有网页，没有 JSON 接口
+    fn search_stories<'a>(
+        &'a self,
+        _query: &'a str,
+        _limit: usize,
+    ) -> LocalBoxFuture<'a, Result<Vec<Story>, String>> {
+        future::ready(Err("Search isn't available for Lobsters".to_string())).boxed_local()
+    }
+
+    fn fetch_story(&self, id: i64) -> LocalBoxFuture<'_, Result<Story, String>> {
+        LobstersClient::fetch_story(self, id).boxed_local()
+    }
+
+    fn fetch_comment_preview(
+        &self,
+        story_id: i64,
+        count: usize,
+    ) -> LocalBoxFuture<'_, Result<Vec<Comment>, String>> {
+        LobstersClient::fetch_comment_preview(self, story_id, count).boxed_local()
+    }
+
+    // 没有投票帖
+    fn fetch_poll_options<'a>(&'a self, _story: &'a Story) -> LocalBoxFuture<'a, Vec<PollOption>> {
+        future::ready(Vec::new()).boxed_local()
+    }
+
+    fn fetch_comments<'a>(
+        &'a self,
+        story: &'a Story,
+    ) -> LocalBoxFuture<'a, Result<Vec<Comment>, String>> {
+        self.fetch_story_comments(story.id).boxed_local()
+    }
+
+    // 评论树一次加载完，下面几个都没有剩下的可取
+    fn initial_root_ids(&self, kids: &[i64]) -> Vec<i64> {
+        kids.to_vec()
+    }
+
+    fn next_root_ids(&self, _kids: &[i64], _loaded: &HashSet<i64>) -> Vec<i64> {
+        Vec::new()
+    }
+
+    fn fetch_more_comments<'a>(&'a self, _root_ids: &'a [i64]) -> LocalBoxFuture<'a, Vec<Comment>> {
+        future::ready(Vec::new()).boxed_local()
+    }
+
+    fn fetch_replies(
+        &self,
+        _parent_id: i64,
+        _existing_depth: usize,
+    ) -> LocalBoxFuture<'_, Result<Vec<Comment>, String>> {
+        future::ready(Ok(Vec::new())).boxed_local()
+    }
+
+    fn fetch_item_raw(&self, id: i64) -> LocalBoxFuture<'_, Option<serde_json::Value>> {
+        LobstersClient::fetch_item_raw(self, id).boxed_local()
+    }
+}
+
+/// Lobsters 的 short id 映射到负数 id，和 HN 的正数 id 不会冲突，
+/// 书签、已读记录等按 id 保存的状态不用区分来源
+pub fn item_id(short_id: &str) -> Option<i64> {
+    if short_id.is_empty()
+        || !short_id
+            .bytes()
+            .all(|b| b.is_ascii_lowercase() || b.is_ascii_digit())
+    {
+        return None;
+    }
+    let value = i64::from_str_radix(short_id, SHORT_ID_RADIX).ok()?;
+    (value > 0 && is_lobsters_id(-value)).then_some(-value)
+}
+
+/// `item_id` 的逆运算，HN 和订阅源的 id 返回 `None`
+pub fn short_id(id: i64) -> Option<String> {
+    if !is_lobsters_id(id) {
+        return None;
+    }
+    let mut value = id.unsigned_abs();
+    let mut digits = Vec::new();
+    while value > 0 {
+        let digit = (value % SHORT_ID_RADIX as u64) as u32;
+        digits.push(char::from_digit(digit, SHORT_ID_RADIX)?);
+        value /= SHORT_ID_RADIX as u64;
+    }
+    let short_id: String = digits.iter().rev().collect();
+    Some(format!("{short_id:0>STORY_SHORT_ID_LEN$}"))
+}
+
+/// 负数 id 里除去订阅源条目的那一段
+pub fn is_lobsters_id(id: i64) -> bool {
+    id < 0 && !crate::feeds::is_feed_id(id)
+}
+
+pub fn hottest_url() -> String {
+    format!("{LOBSTERS_BASE_URL}/{HOTTEST_PATH}")
+}
+
+/// story 的 JSON 地址，带着评论
+pub fn story_api_url(id: i64) -> Option<String> {
+    short_id(id).map(|short_id| format!("{LOBSTERS_BASE_URL}/s/{short_id}.json"))
+}
+
+/// 网站上的讨论页
+pub fn story_page_url(id: i64) -> Option<String> {
+    short_id(id).map(|short_id| format!("{LOBSTERS_BASE_URL}/s/{short_id}"))
+}
+
+fn timestamp(created_at: &str) -> i64 {
+    chrono::DateTime::parse_from_rfc3339(created_at).map_or(0, |time| time.timestamp())
+}
+
+/// 转成共用的 `Story`；`kids` 是顶级评论，只有带评论的 story JSON 才有
+pub fn story_from_lobsters(story: &LobstersStory) -> Option<Story> {
+    let kids = story
+        .comments
+        .iter()
+        .filter(|comment| comment.parent_comment.is_none())
+        .filter_map(|comment| item_id(&comment.short_id))
+        .collect::<Vec<_>>();
+    Some(Story {
+        id: item_id(&story.short_id)?,
+        title: story.title.clone(),
+        url: story.url.clone().filter(|url| !url.is_empty()),
+        score: story.score,
+        by: story.submitter_user.username().to_string(),
+        time: timestamp(&story.created_at),
+        descendants: story.comment_count,
+        kids: (!kids.is_empty()).then_some(kids),
+        text: story
+            .description
+            .clone()
+            .filter(|text| !text.trim().is_empty()),
+        parts: None,
+        story_type: "story".to_string(),
+    })
+}
+
+/// 把评论列表转成带层级的 `Comment`。Lobsters 一次返回整棵树，讨论也比 HN 小，
+/// 所以全部保留，不按 `CommentLimits` 截断，之后也不需要“加载更多”。
+/// 删除或被管理员隐藏的评论还有回复时留作 [deleted] 占位，否则直接丢弃
+pub fn comments_from_lobsters(story_id: i64, comments: &[LobstersComment]) -> Vec<Comment> {
+    let removed = |comment: &LobstersComment| comment.is_deleted || comment.is_moderated;
+
+    // 列表是深度优先的，倒着走一遍就能先看到所有回复，知道哪些评论要保留
+    let mut kept_replies: HashMap<&str, Vec<i64>> = HashMap::new();
+    let mut kept = HashSet::new();
+    for comment in comments.iter().rev() {
+        let Some(id) = item_id(&comment.short_id) else {
+            continue;
+        };
+        if removed(comment) && !kept_replies.contains_key(comment.short_id.as_str()) {
+            continue;
+        }
+        kept.insert(id);
+        if let Some(parent) = &comment.parent_comment {
+            kept_replies
+                .entry(parent.as_str())
+                .or_default()
+                .insert(0, id);
+        }
+    }
+
+    let mut depths: HashMap<&str, usize> = HashMap::new();
+    let mut result = Vec::new();
+    for comment in comments {
+        let Some(id) = item_id(&comment.short_id).filter(|id| kept.contains(id)) else {
+            continue;
+        };
+        let (parent, depth) = match comment.parent_comment.as_deref() {
+            Some(parent) => (
+                item_id(parent).unwrap_or(story_id),
+                depths.get(parent).map_or(0, |depth| depth + 1),
+            ),
+            None => (story_id, 0),
+        };
+        depths.insert(&comment.short_id, depth);
+
+        let replies = kept_replies.get(comment.short_id.as_str());
+        let is_removed = removed(comment);
+        result.push(Comment {
+            id,
+            by: (!is_removed).then(|| comment.commenting_user.username().to_string()),
+            text: comment.comment.clone().filter(|_| !is_removed),
+            time: timestamp(&comment.created_at),
+            kids: replies.cloned(),
+            parent,
+            depth,
+            reply_count: replies.map_or(0, Vec::len),
+            avatar_url: comment.commenting_user.avatar_url(),
+        });
+    }
+    result
+}
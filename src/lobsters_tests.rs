@@ -0,0 +1,131 @@
+use crate::api::HttpFetcher;
+use crate::lobsters::{self, LobstersClient, LobstersComment};
+use crate::models::NewsChannel;
+use crate::source::NewsSource;
+use crate::test_support::fake_http;
+use std::collections::HashSet;
+use std::sync::Arc;
+
+fn comment(short_id: &str, parent: Option<&str>, text: &str) -> LobstersComment {
+    serde_json::from_value(serde_json::json!({
+        "short_id": short_id,
+        "created_at": "2024-03-01T12:00:00.000-06:00",
+        "comment": text,
+        "parent_comment": parent,
+        "commenting_user": "alice",
+    }))
+    .unwrap()
+}
+
+#[test]
+fn short_ids_round_trip_through_negative_item_ids() {
+    for short_id in ["abc123", "0xz9ka", "zzzzzz"] {
+        let id = lobsters::item_id(short_id).unwrap();
+        assert!(lobsters::is_lobsters_id(id));
+        assert_eq!(lobsters::short_id(id).as_deref(), Some(short_id));
+    }
+    assert_eq!(
+        lobsters::story_api_url(lobsters::item_id("abc123").unwrap()).as_deref(),
+        Some("https://lobste.rs/s/abc123.json")
+    );
+
+    // HN 的 id 和不合法的 short id 不会被当成 Lobsters
+    assert_eq!(lobsters::short_id(8863), None);
+    assert_eq!(lobsters::item_id("ABC123"), None);
+    assert_eq!(lobsters::item_id("ab-123"), None);
+    assert_eq!(lobsters::item_id(""), None);
+}
+
+#[test]
+fn comment_tree_keeps_depth_and_drops_removed_leaves() {
+    let story_id = lobsters::item_id("story1").unwrap();
+    let mut removed = comment("gone01", Some("root01"), "");
+    removed.is_deleted = true;
+    let mut removed_parent = comment("gone02", None, "");
+    removed_parent.is_moderated = true;
+    let comments = vec![
+        comment("root01", None, "<p>First</p>"),
+        comment("reply1", Some("root01"), "<p>Reply</p>"),
+        comment("deep01", Some("reply1"), "<p>Deeper</p>"),
+        removed,
+        removed_parent,
+        comment("orphan", Some("gone02"), "<p>Still here</p>"),
+    ];
+
+    let tree = lobsters::comments_from_lobsters(story_id, &comments);
+
+    let id = |short_id| lobsters::item_id(short_id).unwrap();
+    assert_eq!(
+        tree.iter().map(|c| (c.id, c.depth)).collect::<Vec<_>>(),
+        vec![
+            (id("root01"), 0),
+            (id("reply1"), 1),
+            (id("deep01"), 2),
+            (id("gone02"), 0),
+            (id("orphan"), 1),
+        ]
+    );
+    // 被删除的叶子不算进回复数，删除但还有回复的留作占位
+    assert_eq!(tree[0].kids, Some(vec![id("reply1")]));
+    assert_eq!(tree[0].parent, story_id);
+    assert_eq!(tree[3].by, None);
+    assert_eq!(tree[3].text, None);
+    assert_eq!(tree[3].reply_count, 1);
+    assert_eq!(tree[4].by.as_deref(), Some("alice"));
+}
+
+#[test]
+fn lobsters_client_maps_hottest_and_comments_into_stories() {
+    let client = LobstersClient::new(HttpFetcher::new(fake_http(&[
+        (
+            "/hottest.json",
+            r#"[
+                {"short_id": "aaaaaa", "title": "A small language", "url": "https://example.com/lang", "score": 12, "created_at": "2024-03-01T12:00:00.000-06:00", "comment_count": 2, "description": "", "submitter_user": "alice"},
+                {"short_id": "bbbbbb", "title": "Ask: editors?", "url": "", "score": 30, "created_at": "2024-03-01T13:00:00.000-06:00", "comment_count": 0, "description": "<p>Which one?</p>", "submitter_user": {"username": "bob"}}
+            ]"#,
+        ),
+        (
+            "/s/aaaaaa.json",
+            r#"{"short_id": "aaaaaa", "title": "A small language", "url": "https://example.com/lang", "score": 12, "created_at": "2024-03-01T12:00:00.000-06:00", "comment_count": 2, "submitter_user": "alice",
+                "comments": [
+                    {"short_id": "cccccc", "created_at": "2024-03-01T12:30:00.000-06:00", "comment": "<p>Neat</p>", "parent_comment": null, "commenting_user": {"username": "carol", "avatar_url": "/avatars/carol-100.png"}},
+                    {"short_id": "dddddd", "created_at": "2024-03-01T12:45:00.000-06:00", "comment": "<p>Agreed</p>", "parent_comment": "cccccc", "commenting_user": "dave"}
+                ]}"#,
+        ),
+    ])));
+    let source: Arc<dyn NewsSource> = Arc::new(client);
+
+    // 和 HN 首页一样按分数排序
+    let ids = futures::executor::block_on(source.fetch_story_ids(NewsChannel::Lobsters)).unwrap();
+    let stories = futures::executor::block_on(source.fetch_summaries(NewsChannel::Lobsters, &ids));
+    assert_eq!(
+        stories.iter().map(|s| s.title.as_str()).collect::<Vec<_>>(),
+        vec!["Ask: editors?", "A small language"]
+    );
+    assert_eq!(stories[0].by, "bob");
+    assert_eq!(stories[0].url, None);
+    assert!(stories[0].has_text);
+    assert_eq!(stories[1].descendants, Some(2));
+    assert_eq!(stories[1].time, 1_709_316_000);
+
+    let story = futures::executor::block_on(source.fetch_story(stories[1].id)).unwrap();
+    assert_eq!(story.item_url(), "https://lobste.rs/s/aaaaaa");
+    let comments = futures::executor::block_on(source.fetch_comments(&story)).unwrap();
+    assert_eq!(
+        comments
+            .iter()
+            .map(|c| (c.by.as_deref(), c.depth))
+            .collect::<Vec<_>>(),
+        vec![(Some("carol"), 0), (Some("dave"), 1)]
+    );
+    assert_eq!(story.kids, Some(vec![comments[0].id]));
+    assert_eq!(
+        comments[0].avatar_url.as_deref(),
+        Some("https://lobste.rs/avatars/carol-100.png")
+    );
+    // 整棵树一次加载完，不会再去请求更多评论
+    let kids = story.kids.clone().unwrap_or_default();
+    assert_eq!(source.initial_root_ids(&kids), kids);
+    let loaded = kids.iter().copied().collect::<HashSet<_>>();
+    assert!(source.next_root_ids(&kids, &loaded).is_empty());
+}
@@ -1,95 +1,775 @@
+mod about;
 mod api;
+mod app_dirs;
+mod bookmarks;
+mod comment_search;
+mod diagnostics;
+mod export;
+mod feeds;
+mod history;
+mod images;
+mod links;
+mod list_width;
+mod lobsters;
 mod models;
+mod motion;
+mod paging;
+mod previews;
 mod reader;
 mod reader_view;
+mod retry;
+mod send_to;
+mod settings;
+mod skeleton;
+mod snooze;
+mod source;
+mod stats;
+mod story_search;
+mod text_input;
 mod theme;
+mod tls;
+mod unfurls;
 
+#[cfg(test)]
+mod about_tests;
+#[cfg(test)]
+mod api_tests;
+#[cfg(test)]
+mod app_dirs_tests;
+#[cfg(test)]
+mod app_tests;
+#[cfg(test)]
+mod bookmarks_tests;
+#[cfg(test)]
+mod comment_search_tests;
+#[cfg(test)]
+mod diagnostics_tests;
+#[cfg(test)]
+mod export_tests;
+#[cfg(test)]
+mod feeds_tests;
+#[cfg(test)]
+mod history_tests;
+#[cfg(test)]
+mod images_tests;
+#[cfg(test)]
+mod links_tests;
+#[cfg(test)]
+mod list_width_tests;
+#[cfg(test)]
+mod lobsters_tests;
+#[cfg(test)]
+mod models_tests;
+#[cfg(test)]
+mod motion_tests;
+#[cfg(test)]
+mod paging_tests;
+#[cfg(test)]
+mod previews_tests;
+#[cfg(test)]
+mod reader_tests;
+#[cfg(test)]
+mod reader_view_tests;
+#[cfg(test)]
+mod retry_tests;
 #[cfg(test)]
 mod scroll_tests;
+#[cfg(test)]
+mod send_to_tests;
+#[cfg(test)]
+mod settings_tests;
+#[cfg(test)]
+mod skeleton_tests;
+#[cfg(test)]
+mod snooze_tests;
+#[cfg(test)]
+mod stats_tests;
+#[cfg(test)]
+mod story_search_tests;
+#[cfg(test)]
+mod test_support;
+#[cfg(test)]
+mod text_input_tests;
+#[cfg(test)]
+mod theme_tests;
+#[cfg(test)]
+mod tls_tests;
+#[cfg(test)]
+mod unfurls_tests;
 
-use api::HackerNewsClient;
+use api::{HackerNewsClient, HttpFetcher};
+use app_dirs::AppDirs;
+use bookmarks::Bookmarks;
+use comment_search::CommentSearch;
+use feeds::{FeedList, FeedSource};
 use gpui::http_client::HttpClient;
 use gpui::prelude::*;
 use gpui::{
-    div, hsla, point, px, rems, size, AnyElement, App, AppContext, AsyncWindowContext, Bounds,
-    Div, ElementId, FocusHandle, FontWeight, Hsla, IntoElement, MouseButton, MouseDownEvent,
-    MouseMoveEvent, MouseUpEvent, Render, Stateful, TitlebarOptions,
-    ViewContext, WeakView, WindowBounds, WindowOptions, ScrollHandle,
+    actions, deferred, div, hsla, img, impl_actions, list, point, pulsating_between, px, relative,
+    rems, size, uniform_list, Animation, AnimationExt, AnyElement, App, AppContext,
+    AsyncWindowContext, Bounds, ClipboardItem, Div, ElementId, FocusHandle, FontWeight,
+    HighlightStyle, Hsla, Image, IntoElement, KeyBinding, KeyDownEvent, ListAlignment, ListState,
+    MouseButton, MouseDownEvent, MouseMoveEvent, MouseUpEvent, ObjectFit, PathPromptOptions,
+    Pixels, Point, Render, ScrollHandle, ScrollWheelEvent, StyledText, TextStyle, TitlebarOptions,
+    UniformListScrollHandle, ViewContext, WeakView, WindowContext, WindowOptions,
 };
-use models::{Comment, NewsChannel, Story};
-use reader::{ReaderLoadState, ReaderSession};
-use reqwest_client::ReqwestClient;
+use history::{ReadStories, SeenComments, VisitedUrls};
+use images::{ImageAction, ImageFailures, ImageMode, ImageReloads};
+use links::LinkTarget;
+use list_width::{SplitterReset, STORY_LIST_DEFAULT_WIDTH};
+use lobsters::LobstersClient;
+use models::{
+    Comment, CommentAvatar, CommentSort, NewsChannel, PollOption, ReaderSource, Story, StoryAction,
+    StorySummary,
+};
+use motion::Motion;
+use paging::StoryPager;
+use previews::{CommentPreview, CommentPreviews};
+use reader::{LoadedPage, ReaderLinkTarget, ReaderLoadState, ReaderSession, ReaderTabs};
+use reader_view::{ReaderFont, ReaderTheme, ReaderWidth};
+use serde::Deserialize;
+use settings::Settings;
+use snooze::{SnoozeDuration, SnoozedStories};
+use source::NewsSource;
+use stats::ReadingStats;
 use std::collections::{HashMap, HashSet, VecDeque};
+use std::rc::Rc;
 use std::sync::Arc;
+use std::time::{Duration, Instant};
+use story_search::StorySearch;
 use theme::Theme;
+use unfurls::Unfurls;
 
 /// macOS traffic light 按钮区域的高度
 const TITLEBAR_HEIGHT: f32 = 38.0;
+/// traffic lights 右边缘到窗口左边的距离（含 12px 的起始偏移）
+const TRAFFIC_LIGHTS_RIGHT: f32 = 72.0;
+/// 默认布局下列表标题行的高度
+const STORY_LIST_TITLE_HEIGHT: f32 = 52.0;
 const SIDEBAR_WIDTH: f32 = 56.0;
-const STORY_LIST_DEFAULT_WIDTH: f32 = 360.0;
-const STORY_LIST_MIN_WIDTH: f32 = 240.0;
-const STORY_LIST_MIN_DETAIL_WIDTH: f32 = 360.0;
 const SPLITTER_WIDTH: f32 = 8.0;
 const READER_CACHE_MAX_ENTRIES: usize = 32;
+const TOAST_DURATION: Duration = Duration::from_secs(3);
+/// 窗口位置和大小停止变化这么久之后才保存
+const WINDOW_BOUNDS_SAVE_DELAY: Duration = Duration::from_millis(500);
+/// 刷新相对时间、检查定时深色主题的周期
+const CLOCK_TICK: Duration = Duration::from_secs(60);
+/// 加载骨架屏一次呼吸的时长
+const SKELETON_PULSE: Duration = Duration::from_millis(1200);
+/// 阅读器标题栏收起/展开、分隔条复位时的淡入
+const STATE_TRANSITION: Duration = Duration::from_millis(150);
+/// 跳到查找命中的评论时，和评论区顶部保留的距离
+const COMMENT_MATCH_SCROLL_MARGIN: f32 = 16.0;
+/// 从目录或小地图跳到标题时，标题上方保留的距离
+const READER_HEADING_SCROLL_MARGIN: f32 = 16.0;
+/// 在列表里输入排名跳转时最多接受几位数字
+const MAX_RANK_JUMP_DIGITS: usize = 3;
+
+/// 按侧边栏的顺序跳到第 N 个频道（从 0 开始）
+#[derive(Clone, Debug, PartialEq, Deserialize)]
+struct SelectChannel(usize);
+
+impl_actions!(oneapp, [SelectChannel]);
+actions!(
+    oneapp,
+    [
+        NextChannel,
+        TogglePinnedChannel,
+        MoveChannelUp,
+        MoveChannelDown,
+        FindInComments,
+        ToggleThreadCollapse,
+        OpenFromClipboard,
+        ToggleReaderMinimap,
+        ToggleCleanComments,
+        ShowRawJson,
+        ExportTheme,
+        ImportTheme,
+        ExportFeedsOpml,
+        CycleStoryListWidth,
+        MarkAllStoriesRead,
+        ClearReadStories,
+        ClearReaderCache,
+        RefreshStories,
+        ToggleReaderScrollDebug
+    ]
+);
+
+/// 覆盖在主界面之上的面板
+enum Overlay {
+    Diagnostics(diagnostics::DiagnosticsReport),
+    About(about::AboutInfo),
+    Snoozed,
+    Stats,
+    /// 调试用：HN API 返回的原始 JSON，`json` 为 None 时还在加载
+    RawJson {
+        item_id: i64,
+        json: Option<Result<String, String>>,
+    },
+}
+
+/// 评论列表当前的行，每次渲染前和 `AppState::comment_list` 同步。
+/// 列表第 0 项是评论区上方的内容，最后一项是“加载更多”，中间每项一条可见评论
+#[derive(Default)]
+struct CommentRows {
+    /// story、是否分栏、是否整洁布局；任何一个变化都整个重建列表
+    key: Option<(i64, bool, bool)>,
+    /// 可见评论在 `AppState::comments` 里的下标
+    indices: Vec<usize>,
+    ids: Vec<i64>,
+    /// 开启显示总回复数时每条评论的后代数
+    totals: Option<HashMap<i64, usize>>,
+    missing: HashMap<i64, usize>,
+}
 
 // Application State
 struct AppState {
     theme: Theme,
-    stories: Vec<Story>,
+    is_dark: bool,
+    /// 导入的自定义主题，设置后代替内置的浅色/深色主题
+    custom_theme: Option<Theme>,
+    /// 上一次检查时定时深色主题是否生效，只在边界处切换主题
+    quiet_hours_active: bool,
+    /// 系统当前是否为深色外观
+    system_dark: bool,
+    /// 用户手动切换过主题，系统外观变化时不再覆盖
+    theme_overridden: bool,
+    stories: Vec<StorySummary>,
     selected_story_id: Option<i64>,
+    /// 每次选中 story 加一，异步结果只写回发起它的那次选择
+    selection_generation: usize,
+    /// 鼠标所在的列表行；停留超过 `PREVIEW_HOVER_DELAY` 后才显示评论预览
+    hovered_story: Option<i64>,
+    previewed_story: Option<i64>,
+    comment_previews: CommentPreviews,
+    /// 评论里链接的标题预览，按链接缓存
+    unfurls: Unfurls,
+    /// 阅读器里手动重新加载的图片（gpui 自己加载失败后不会再试）
+    image_reloads: ImageReloads,
+    /// gpui 加载失败的阅读器图片，绘制时记录
+    image_failures: ImageFailures,
+    /// 本次运行中图片整体加载不了（可能是平台不支持解码），阅读器改为显示说明文字
+    images_unavailable: bool,
+    /// 选中的完整 story（含 `kids` 和正文），列表里只有摘要
+    selected_story: Option<Story>,
     comments: Vec<Comment>,
+    /// 已经请求过的顶级评论，其余的点“加载更多”时再分批获取
+    loaded_comment_roots: HashSet<i64>,
+    is_loading_more_comments: bool,
+    /// 已经补充获取过回复的评论，不再重复请求
+    expanded_replies: HashSet<i64>,
+    loading_replies: HashSet<i64>,
     collapsed_comments: HashSet<i64>,
+    /// 顶级评论串的排列方式，切换 story 时保留
+    comment_sort: CommentSort,
+    /// 鼠标所在的评论，用于快捷键折叠它所在的评论串
+    hovered_comment: Option<i64>,
+    comment_search: Option<CommentSearch>,
+    comment_search_focus_handle: FocusHandle,
+    /// 列表顶部的全文搜索，输入非空时替代频道列表
+    story_search: StorySearch,
+    story_search_focus_handle: FocusHandle,
+    /// Feeds 频道的订阅列表，和客户端共用
+    feeds: FeedList,
+    /// Feeds 频道顶部“添加订阅”输入框的内容
+    feed_input: String,
+    feed_input_focus_handle: FocusHandle,
+    /// 列表获得焦点时输入的排名数字，回车后跳到对应的 story
+    rank_jump: Option<String>,
+    /// 详情页或分栏评论列的虚拟列表，只渲染视口附近的评论
+    comment_list: ListState,
+    comment_rows: CommentRows,
+    seen_comments: SeenComments,
+    /// 打开当前 story 之前已经看过的评论，本次新加载的评论不在其中
+    seen_before_visit: HashSet<i64>,
+    snoozed: SnoozedStories,
+    visited_urls: VisitedUrls,
+    /// 已读的 story，列表里标题变淡
+    read_stories: ReadStories,
+    bookmarks: Bookmarks,
+    /// 列表显示收藏而不是当前频道
+    showing_bookmarks: bool,
+    reading_stats: ReadingStats,
+    /// 本次运行已计入统计的文章，重新打开或切换标签时不再重复计数
+    counted_articles: HashSet<String>,
     is_loading: bool,
     is_loading_comments: bool,
+    /// 选中的投票帖的选项，None 表示还在加载（或不是投票帖）
+    poll_options: Option<Vec<PollOption>>,
     error_message: Option<String>,
     selected_channel: NewsChannel,
+    /// 切走的频道保留已加载的 stories，切回时直接显示
+    channel_stories: HashMap<NewsChannel, Vec<StorySummary>>,
+    /// 当前频道排行榜的分页状态，切换频道时和列表一起缓存
+    story_pager: StoryPager,
+    channel_pagers: HashMap<NewsChannel, StoryPager>,
+    /// 自动刷新时新出现的 story，打开之前在列表里高亮
+    new_story_ids: HashSet<i64>,
+    /// 当前频道上次从网络获取列表的时间，自动刷新按它计算间隔
+    last_story_refresh: Instant,
+    story_list_scroll_handle: UniformListScrollHandle,
     http_client: Arc<dyn HttpClient>,
-    client: Arc<HackerNewsClient>,
-    reader: Option<ReaderSession>,
+    /// 当前频道的数据来源，切换频道时换成 `source_for` 给出的来源
+    client: Arc<dyn NewsSource>,
+    hacker_news: Arc<dyn NewsSource>,
+    lobsters: Arc<dyn NewsSource>,
+    feed_source: Arc<dyn NewsSource>,
+    /// 选中的 story 来自哪个频道，评论、投票等后续请求交给同一个来源
+    story_channel: NewsChannel,
+    /// 打开的文章标签，每个标签记住自己的滚动位置
+    reader_tabs: ReaderTabs,
     reader_cache: HashMap<String, reader::ReaderArticle>,
     reader_cache_order: VecDeque<String>,
     reader_scroll_handle: ScrollHandle,
+    /// 跟踪文章各个 block 的位置，给大纲小地图用
+    reader_blocks_scroll_handle: ScrollHandle,
+    /// 整洁评论布局用的结构化正文，评论到达时解析一次
+    comment_blocks: HashMap<i64, Vec<reader::ReaderBlock>>,
+    reader_header_collapsed: bool,
+    reader_last_scroll_y: f32,
+    /// 上次绘制时的阅读进度，滚动时只在它变化后重绘进度条
+    reader_last_progress: Option<f32>,
+    /// 上次绘制时目录的当前章节和小地图视口的位置（像素），滚动时只在变化后重绘
+    reader_outline_position: (Option<usize>, i32),
+    /// 读过的文章停在哪里，按 URL 记。标签关掉之后再打开缓存里的文章也能回到原处
+    reader_scroll_positions: HashMap<String, Point<Pixels>>,
+    /// 阅读器右上角的文章目录是否展开
+    reader_toc_open: bool,
+    /// 阅读器顶栏下方的阅读设置栏是否展开
+    reader_quick_bar_open: bool,
+    /// 打开了右键菜单的文章图片（block 下标）
+    reader_image_menu: Option<usize>,
+    /// 调试用：阅读器顶栏显示滚动位置。启动时读环境变量，运行中用快捷键切换
     debug_reader_scroll: bool,
     focus_handle: FocusHandle,
+    story_list_focus_handle: FocusHandle,
+    reader_focus_handle: FocusHandle,
     story_list_width: f32,
+    /// 列表隐藏时只显示详情，宽度保留到再次显示
+    story_list_hidden: bool,
+    /// 双击分隔条复位的次数，每次复位列表重新淡入
+    story_list_resets: usize,
     is_resizing_story_list: bool,
     resize_start_x: f32,
     resize_start_width: f32,
+    toast: Option<String>,
+    toast_generation: usize,
+    /// 每次窗口位置或大小变化时加一，只有最后一次变化之后的延时任务会保存
+    window_bounds_generation: usize,
+    /// 启动时配置了自定义 CA 时显示警告横幅
+    custom_ca: Option<tls::CustomCa>,
+    /// 设置与系统偏好合并后的结果，动画都要先检查它
+    reduce_motion: bool,
+    settings: Settings,
+    /// 读写文件的目录，启动时解析一次
+    dirs: AppDirs,
+    overlay: Option<Overlay>,
 }
 
 impl AppState {
-    fn new(cx: &mut ViewContext<Self>) -> Self {
+    fn new(settings: Settings, dirs: AppDirs, cx: &mut ViewContext<Self>) -> Self {
         let focus_handle = cx.focus_handle();
         let http_client = cx.app().http_client();
         let debug_reader_scroll = std::env::var_os("ONEAPP_DEBUG_READER_SCROLL").is_some();
+        cx.observe_window_bounds(|this: &mut Self, cx: &mut ViewContext<Self>| {
+            this.remember_window_bounds(cx);
+        })
+        .detach();
+        cx.observe_window_appearance(|this: &mut Self, cx: &mut ViewContext<Self>| {
+            let dark = theme::appearance_is_dark(cx.window_appearance());
+            this.on_system_appearance_changed(dark, cx);
+        })
+        .detach();
+        let view = cx.view().downgrade();
+        let comment_list = ListState::new(0, ListAlignment::Top, px(1000.), move |ix, cx| {
+            view.upgrade()
+                .map(|view| view.update(cx, |this, cx| this.render_comment_list_item(ix, cx)))
+                .unwrap_or_else(|| div().into_any_element())
+        });
+        let feeds = FeedList::new(settings.feeds.clone());
+        // 各个来源共用同一组请求许可
+        let fetcher =
+            HttpFetcher::new(http_client.clone()).with_executor(cx.background_executor().clone());
+        let hacker_news: Arc<dyn NewsSource> = Arc::new(
+            HackerNewsClient::from_fetcher(fetcher.clone())
+                .with_comment_limits(
+                    settings.comment_limits(NewsChannel::HackerNews.comment_limits()),
+                )
+                .with_algolia(settings.batch_comment_fetch),
+        );
+        let lobsters: Arc<dyn NewsSource> = Arc::new(LobstersClient::new(fetcher.clone()));
+        let feed_source: Arc<dyn NewsSource> = Arc::new(FeedSource::new(fetcher, feeds.clone()));
+        let quiet_hours_active = settings.quiet_hours.is_active_now();
+        // 外观未知时 gpui 报告为浅色
+        let system_dark = theme::appearance_is_dark(cx.window_appearance());
+        let is_dark = quiet_hours_active || (settings.follow_system_appearance && system_dark);
+        let custom_theme = if settings.custom_theme {
+            Theme::load_custom(&dirs.data)
+        } else {
+            None
+        };
         Self {
-            theme: Theme::default(),
+            theme: custom_theme
+                .clone()
+                .unwrap_or_else(|| Theme::for_mode(is_dark)),
+            is_dark,
+            custom_theme,
+            quiet_hours_active,
+            system_dark,
+            theme_overridden: false,
             stories: Vec::new(),
             selected_story_id: None,
+            selection_generation: 0,
+            hovered_story: None,
+            previewed_story: None,
+            comment_previews: CommentPreviews::default(),
+            unfurls: Unfurls::default(),
+            image_reloads: ImageReloads::default(),
+            image_failures: ImageFailures::default(),
+            images_unavailable: false,
+            selected_story: None,
             comments: Vec::new(),
+            loaded_comment_roots: HashSet::new(),
+            is_loading_more_comments: false,
+            expanded_replies: HashSet::new(),
+            loading_replies: HashSet::new(),
             collapsed_comments: HashSet::new(),
+            comment_sort: CommentSort::default(),
+            hovered_comment: None,
+            comment_search: None,
+            comment_search_focus_handle: cx.focus_handle(),
+            story_search: StorySearch::default(),
+            story_search_focus_handle: cx.focus_handle(),
+            feeds,
+            feed_input: String::new(),
+            feed_input_focus_handle: cx.focus_handle(),
+            rank_jump: None,
+            comment_list,
+            comment_rows: CommentRows::default(),
+            seen_comments: SeenComments::load(&dirs.data),
+            seen_before_visit: HashSet::new(),
+            snoozed: SnoozedStories::load(&dirs.data, chrono::Utc::now().timestamp()),
+            visited_urls: VisitedUrls::load(&dirs.data),
+            read_stories: ReadStories::load(&dirs.data),
+            bookmarks: Bookmarks::load(&dirs.data),
+            showing_bookmarks: false,
+            reading_stats: ReadingStats::load(&dirs.data),
+            counted_articles: HashSet::new(),
             is_loading: true,
             is_loading_comments: false,
+            poll_options: None,
             error_message: None,
             selected_channel: NewsChannel::HackerNews,
-            http_client: http_client.clone(),
-            client: Arc::new(HackerNewsClient::new(http_client)),
-            reader: None,
+            channel_stories: HashMap::new(),
+            story_pager: StoryPager::default(),
+            channel_pagers: HashMap::new(),
+            new_story_ids: HashSet::new(),
+            last_story_refresh: Instant::now(),
+            story_list_scroll_handle: UniformListScrollHandle::new(),
+            http_client,
+            client: hacker_news.clone(),
+            hacker_news,
+            lobsters,
+            feed_source,
+            story_channel: NewsChannel::HackerNews,
+            reader_tabs: ReaderTabs::default(),
             reader_cache: HashMap::new(),
             reader_cache_order: VecDeque::new(),
             reader_scroll_handle: ScrollHandle::new(),
+            reader_blocks_scroll_handle: ScrollHandle::new(),
+            comment_blocks: HashMap::new(),
+            reader_header_collapsed: false,
+            reader_last_scroll_y: 0.0,
+            reader_last_progress: None,
+            reader_outline_position: (None, 0),
+            reader_scroll_positions: HashMap::new(),
+            reader_toc_open: false,
+            reader_quick_bar_open: false,
+            reader_image_menu: None,
             debug_reader_scroll,
             focus_handle,
-            story_list_width: STORY_LIST_DEFAULT_WIDTH,
+            story_list_focus_handle: cx.focus_handle(),
+            reader_focus_handle: cx.focus_handle(),
+            story_list_width: list_width::restored_width(
+                settings.story_list_preferred_width,
+                Self::available_list_width(cx),
+            ),
+            story_list_hidden: false,
+            story_list_resets: 0,
             is_resizing_story_list: false,
             resize_start_x: 0.0,
             resize_start_width: STORY_LIST_DEFAULT_WIDTH,
+            toast: None,
+            toast_generation: 0,
+            window_bounds_generation: 0,
+            custom_ca: None,
+            reduce_motion: settings.reduce_motion.unwrap_or(false),
+            settings,
+            dirs,
+            overlay: None,
+        }
+    }
+
+    fn set_dark_theme(&mut self, dark: bool, cx: &mut ViewContext<Self>) {
+        self.is_dark = dark;
+        self.theme = self
+            .custom_theme
+            .clone()
+            .unwrap_or_else(|| Theme::for_mode(dark));
+        cx.notify();
+    }
+
+    /// 侧边栏的手动切换；定时深色主题到下一个边界时才会再接管。
+    /// 使用自定义主题时先回到内置主题
+    fn toggle_theme(&mut self, cx: &mut ViewContext<Self>) {
+        self.theme_overridden = true;
+        if self.custom_theme.take().is_some() {
+            self.settings.custom_theme = false;
+            self.save_settings();
+        }
+        self.set_dark_theme(!self.is_dark, cx);
+    }
+
+    fn export_theme(&mut self, cx: &mut ViewContext<Self>) {
+        let result = self.theme.to_json().and_then(|json| {
+            export::write_export(&self.dirs.exports(), "oneapp-theme.json", &json)
+        });
+        match result {
+            Ok(path) => {
+                if let Some(dir) = path.parent() {
+                    let _ = open::that(dir);
+                }
+                self.show_toast(format!("Theme exported to {}", path.display()), cx);
+            }
+            Err(e) => self.show_toast(format!("Theme export failed: {}", e), cx),
+        }
+    }
+
+    fn import_theme(&mut self, cx: &mut ViewContext<Self>) {
+        let picked = cx.prompt_for_paths(PathPromptOptions {
+            files: true,
+            directories: false,
+            multiple: false,
+        });
+        cx.spawn(
+            |this: WeakView<Self>, mut cx: AsyncWindowContext| async move {
+                let path = match picked.await {
+                    Ok(Ok(Some(paths))) => match paths.into_iter().next() {
+                        Some(path) => Ok(path),
+                        None => return,
+                    },
+                    // 用户取消
+                    Ok(Ok(None)) | Err(_) => return,
+                    Ok(Err(e)) => Err(e.to_string()),
+                };
+                let result = path.and_then(|path| {
+                    std::fs::read_to_string(path)
+                        .map_err(|e| e.to_string())
+                        .and_then(|json| Theme::from_json(&json))
+                });
+                let _ = this.update(&mut cx, |this: &mut Self, cx: &mut ViewContext<Self>| {
+                    match result {
+                        Ok(theme) => this.apply_custom_theme(theme, cx),
+                        Err(e) => this.show_toast(format!("Invalid theme file: {}", e), cx),
+                    };
+                });
+            },
+        )
+        .detach();
+    }
+
+    /// 立即换上导入的主题，并保存下来供下次启动使用
+    fn apply_custom_theme(&mut self, theme: Theme, cx: &mut ViewContext<Self>) {
+        if let Err(e) = theme.save_custom(&self.dirs.data) {
+            self.show_toast(format!("Failed to save theme: {}", e), cx);
+        }
+        self.custom_theme = Some(theme);
+        self.settings.custom_theme = true;
+        self.save_settings();
+        self.set_dark_theme(self.is_dark, cx);
+    }
+
+    /// 没有手动切换或定时深色主题时应使用的主题
+    fn automatic_dark(&self) -> bool {
+        self.quiet_hours_active || (self.settings.follow_system_appearance && self.system_dark)
+    }
+
+    fn on_system_appearance_changed(&mut self, dark: bool, cx: &mut ViewContext<Self>) {
+        self.system_dark = dark;
+        if self.theme_overridden {
+            return;
+        }
+        let dark = self.automatic_dark();
+        if self.is_dark != dark {
+            self.set_dark_theme(dark, cx);
+        }
+    }
+
+    fn start_clock(&self, cx: &mut ViewContext<Self>) {
+        cx.spawn(
+            |this: WeakView<Self>, mut cx: AsyncWindowContext| async move {
+                loop {
+                    cx.background_executor().timer(CLOCK_TICK).await;
+                    let updated =
+                        this.update(&mut cx, |this: &mut Self, cx: &mut ViewContext<Self>| {
+                            this.on_clock_tick(cx);
+                        });
+                    if updated.is_err() {
+                        break;
+                    }
+                }
+            },
+        )
+        .detach();
+    }
+
+    fn on_clock_tick(&mut self, cx: &mut ViewContext<Self>) {
+        // 手动切换的主题保持到下一个时间边界
+        let active = self.settings.quiet_hours.is_active_now();
+        if active != self.quiet_hours_active {
+            self.quiet_hours_active = active;
+            self.theme_overridden = false;
+            let dark = self.automatic_dark();
+            if self.is_dark != dark {
+                self.set_dark_theme(dark, cx);
+            }
+        }
+        self.maybe_auto_refresh(cx);
+        // 相对时间（"5m ago"）需要定期重绘
+        cx.notify();
+    }
+
+    /// 开启自动刷新时，窗口在前台、到了间隔就在后台更新当前频道
+    fn maybe_auto_refresh(&mut self, cx: &mut ViewContext<Self>) {
+        let env = std::env::var(paging::AUTO_REFRESH_ENV).ok();
+        let Some(interval) =
+            paging::auto_refresh_interval(self.settings.auto_refresh_minutes, env.as_deref())
+        else {
+            return;
+        };
+        let busy = self.is_loading
+            || self.showing_bookmarks
+            || self.story_search.is_active()
+            || self.story_pager.is_loading();
+        if busy || !cx.is_window_active() || self.last_story_refresh.elapsed() < interval {
+            return;
+        }
+        self.auto_refresh_stories(cx);
+    }
+
+    /// 后台获取第一页并合并进列表：不显示加载状态，不动选中项，滚动位置跟着原来顶部的那一行
+    fn auto_refresh_stories(&mut self, cx: &mut ViewContext<Self>) {
+        self.last_story_refresh = Instant::now();
+        let client = self.client.clone();
+        let channel = self.selected_channel;
+
+        cx.spawn(
+            |this: WeakView<Self>, mut cx: AsyncWindowContext| async move {
+                let Ok(ids) = client.fetch_story_ids(channel).await else {
+                    return;
+                };
+                let page = ids
+                    .into_iter()
+                    .take(paging::STORY_PAGE_SIZE)
+                    .collect::<Vec<_>>();
+                let fresh = client.fetch_summaries(channel, &page).await;
+                let _ = this.update(&mut cx, |this: &mut Self, cx: &mut ViewContext<Self>| {
+                    // 期间切换了频道或者手动刷新了，这次结果不要了
+                    if this.selected_channel != channel || this.is_loading || fresh.is_empty() {
+                        return;
+                    }
+                    let anchor = this.story_list_anchor();
+                    let new_ids = paging::merge_refreshed(&mut this.stories, fresh);
+                    this.new_story_ids.extend(new_ids);
+                    if let Some(anchor) = anchor {
+                        cx.on_next_frame(move |this, cx| {
+                            this.restore_story_list_anchor(anchor, cx)
+                        });
+                    }
+                    cx.notify();
+                });
+            },
+        )
+        .detach();
+    }
+
+    /// story 列表底层的滚动状态（偏移和视口）
+    fn story_list_base_handle(&self) -> ScrollHandle {
+        self.story_list_scroll_handle.0.borrow().base_handle.clone()
+    }
+
+    /// `uniform_list` 每行一样高，列表排版过一次才知道
+    fn story_row_height(&self) -> Option<f32> {
+        let state = self.story_list_scroll_handle.0.borrow();
+        state
+            .last_item_size
+            .map(|size| size.item.height.0)
+            .filter(|height| *height > 0.)
+    }
+
+    /// 列表顶部第一行可见的 story，以及它的上边缘滚出去了多少
+    fn story_list_anchor(&self) -> Option<(i64, f32)> {
+        let scroll_top = -self.story_list_base_handle().offset().y.0;
+        if scroll_top <= 0. {
+            return None;
+        }
+        let row_height = self.story_row_height()?;
+        let stories = self.visible_stories();
+        let row = paging::rows_scrolled_past(scroll_top, &vec![row_height; stories.len()]);
+        Some((stories.get(row)?.id, scroll_top - row as f32 * row_height))
+    }
+
+    /// 列表上方插入了新行之后，把原来顶部的那一行滚回原处
+    fn restore_story_list_anchor(
+        &mut self,
+        (story_id, offset): (i64, f32),
+        cx: &mut ViewContext<Self>,
+    ) {
+        let Some(row) = self.visible_stories().iter().position(|s| s.id == story_id) else {
+            return;
+        };
+        let Some(row_height) = self.story_row_height() else {
+            return;
+        };
+        let row_top = row as f32 * row_height;
+        self.story_list_base_handle()
+            .set_offset(point(px(0.), px(-(row_top + offset))));
+        cx.notify();
+    }
+
+    fn save_settings(&self) {
+        let _ = self.settings.save(&self.dirs.data);
+    }
+
+    /// 拖动或缩放窗口时每一帧都会调用，等窗口停下来再写设置文件
+    fn remember_window_bounds(&mut self, cx: &mut ViewContext<Self>) {
+        let mode = settings::WindowMode::from_window_bounds(cx.window_bounds());
+        if self.settings.window == Some(mode) {
+            return;
         }
+        self.settings.window = Some(mode);
+        self.window_bounds_generation = self.window_bounds_generation.wrapping_add(1);
+        let generation = self.window_bounds_generation;
+
+        cx.spawn(
+            |this: WeakView<Self>, mut cx: AsyncWindowContext| async move {
+                cx.background_executor()
+                    .timer(WINDOW_BOUNDS_SAVE_DELAY)
+                    .await;
+                let _ = this.update(&mut cx, |this: &mut Self, _cx: &mut ViewContext<Self>| {
+                    if this.window_bounds_generation == generation {
+                        this.save_settings();
+                    }
+                });
+            },
+        )
+        .detach();
     }
 
     fn selected_story(&self) -> Option<&Story> {
-        self.selected_story_id
-            .and_then(|id| self.stories.iter().find(|s| s.id == id))
+        self.selected_story
+            .as_ref()
+            .filter(|story| Some(story.id) == self.selected_story_id)
     }
 
     fn cached_reader_article(&mut self, url: &str) -> Option<reader::ReaderArticle> {
@@ -123,15 +803,79 @@ impl AppState {
         cx.notify();
     }
 
+    /// 折叠所有有回复的评论，只剩顶级评论可见
+    fn collapse_all_comments(&mut self, cx: &mut ViewContext<Self>) {
+        self.collapsed_comments = self
+            .comments
+            .iter()
+            .filter(|c| c.has_replies())
+            .map(|c| c.id)
+            .collect();
+        cx.notify();
+    }
+
+    fn expand_all_comments(&mut self, cx: &mut ViewContext<Self>) {
+        self.collapsed_comments.clear();
+        cx.notify();
+    }
+
+    /// 折叠评论所在的整个顶级评论串
+    fn collapse_thread(&mut self, comment_id: i64, cx: &mut ViewContext<Self>) {
+        let root = models::thread_root(&self.comments, comment_id).unwrap_or(comment_id);
+        self.collapsed_comments.insert(root);
+        cx.notify();
+    }
+
+    /// 折叠/展开鼠标所在的顶级评论串；没有悬停目标时取评论区视口顶部的那一串
+    fn toggle_thread_under_cursor(&mut self, cx: &mut ViewContext<Self>) {
+        let target = self
+            .hovered_comment
+            .and_then(|id| models::thread_root(&self.comments, id))
+            .or_else(|| self.thread_at_viewport_top())
+            .or_else(|| self.visible_comments().first().map(|c| c.id));
+        if let Some(comment_id) = target {
+            self.toggle_collapse(comment_id, cx);
+        }
+    }
+
+    /// 只看已经排版过的行：从视口顶部那一行开始往下
+    fn thread_at_viewport_top(&self) -> Option<i64> {
+        let scroll_top = self.comment_list.logical_scroll_top();
+        let top = self.comment_list.bounds_for_item(scroll_top.item_ix)?.top()
+            + scroll_top.offset_in_item;
+        let first_row = scroll_top.item_ix.max(1);
+        let rows = self
+            .comment_rows
+            .indices
+            .iter()
+            .enumerate()
+            .skip(first_row - 1)
+            .map_while(|(row, &index)| {
+                let bounds = self.comment_list.bounds_for_item(row + 1)?;
+                Some((&self.comments[index], bounds.bottom().0))
+            })
+            .collect::<Vec<_>>();
+        let id = models::thread_root_at(&rows, top.0)?;
+        models::thread_root(&self.comments, id)
+    }
+
     fn is_collapsed(&self, comment_id: i64) -> bool {
         self.collapsed_comments.contains(&comment_id)
     }
 
     fn visible_comments(&self) -> Vec<&Comment> {
+        self.visible_comment_indices()
+            .into_iter()
+            .map(|index| &self.comments[index])
+            .collect()
+    }
+
+    /// 没被折叠掉的评论在 `comments` 里的下标
+    fn visible_comment_indices(&self) -> Vec<usize> {
         let mut visible = Vec::new();
         let mut skip_until_depth: Option<usize> = None;
 
-        for comment in &self.comments {
+        for (index, comment) in self.comments.iter().enumerate() {
             if let Some(depth) = skip_until_depth {
                 if comment.depth > depth {
                     continue;
@@ -139,7 +883,7 @@ impl AppState {
                 skip_until_depth = None;
             }
 
-            visible.push(comment);
+            visible.push(index);
 
             if self.is_collapsed(comment.id) {
                 skip_until_depth = Some(comment.depth);
@@ -149,253 +893,3937 @@ impl AppState {
         visible
     }
 
-    fn load_stories(&mut self, cx: &mut ViewContext<Self>) {
-        self.is_loading = true;
-        self.error_message = None;
-        cx.notify();
+    /// 先算出可见评论，再让虚拟列表只替换变化的那一段，
+    /// 折叠、展开和加载更多都不会丢掉其余行的高度和滚动位置
+    fn sync_comment_list(&mut self) {
+        let Some(story_id) = self.selected_story_id else {
+            return;
+        };
+        let key = (
+            story_id,
+            self.settings.split_view_on_select,
+            self.settings.clean_comment_layout,
+        );
+        let indices = if self.is_loading_comments {
+            Vec::new()
+        } else {
+            self.visible_comment_indices()
+        };
+        let ids = indices
+            .iter()
+            .map(|&index| self.comments[index].id)
+            .collect::<Vec<_>>();
 
-        let client = self.client.clone();
+        let rows = &mut self.comment_rows;
+        if rows.key != Some(key) {
+            rows.key = Some(key);
+            self.comment_list.reset(ids.len() + 2);
+        } else if let Some((range, count)) = models::rows_splice(&rows.ids, &ids) {
+            self.comment_list
+                .splice(range.start + 1..range.end + 1, count);
+        }
+        rows.totals = self
+            .settings
+            .show_total_reply_count
+            .then(|| models::descendant_counts(&self.comments));
+        rows.missing = models::missing_reply_counts(&self.comments);
+        rows.indices = indices;
+        rows.ids = ids;
+    }
 
-        cx.spawn(
-            |this: WeakView<Self>, mut cx: AsyncWindowContext| async move {
-                let result = client.fetch_top_stories(30).await;
-                let _ = this.update(&mut cx, |this: &mut Self, cx: &mut ViewContext<Self>| {
-                    match result {
-                        Ok(stories) => {
-                            this.stories = stories;
-                            this.error_message = None;
-                        }
-                        Err(e) => {
-                            this.error_message = Some(format!("Failed to load stories: {}", e));
-                        }
-                    }
-                    this.is_loading = false;
-                    cx.notify();
-                });
-            },
-        )
-        .detach();
+    fn start_comment_search(&mut self, cx: &mut ViewContext<Self>) {
+        if self.selected_story_id.is_none() {
+            return;
+        }
+        if self.comment_search.is_none() {
+            self.comment_search = Some(CommentSearch::new(&self.collapsed_comments));
+        }
+        cx.focus(&self.comment_search_focus_handle);
+        cx.notify();
     }
 
-    fn select_story(&mut self, story_id: i64, cx: &mut ViewContext<Self>) {
-        self.reader = None;
-        let story = self.stories.iter().find(|s| s.id == story_id).cloned();
+    fn set_comment_search_query(&mut self, query: String, cx: &mut ViewContext<Self>) {
+        let Some(search) = self.comment_search.as_mut() else {
+            return;
+        };
+        search.matches = comment_search::find_matches(&self.comments, &query);
+        search.query = query;
+        search.current = 0;
+        // 先回到查找前的折叠状态，再展开包含命中的分支
+        let expand = comment_search::ids_to_expand(&self.comments, &search.matches);
+        self.collapsed_comments = search
+            .saved_collapsed
+            .difference(&expand)
+            .copied()
+            .collect();
+        self.scroll_to_comment_match(cx);
+        cx.notify();
+    }
 
-        if let Some(story) = story {
-            self.selected_story_id = Some(story_id);
-            self.comments.clear();
-            self.collapsed_comments.clear();
-            self.is_loading_comments = true;
+    fn cycle_comment_match(&mut self, forward: bool, cx: &mut ViewContext<Self>) {
+        if let Some(search) = self.comment_search.as_mut() {
+            search.cycle(forward);
+            self.scroll_to_comment_match(cx);
             cx.notify();
-
-            let client = self.client.clone();
-
-            cx.spawn(
-                |this: WeakView<Self>, mut cx: AsyncWindowContext| async move {
-                    let result = client.fetch_comments(&story).await;
-                    let _ = this.update(&mut cx, |this: &mut Self, cx: &mut ViewContext<Self>| {
-                        match result {
-                            Ok(comments) => {
-                                this.comments = comments;
-                            }
-                            Err(e) => {
-                                this.error_message =
-                                    Some(format!("Failed to load comments: {}", e));
-                            }
-                        }
-                        this.is_loading_comments = false;
-                        cx.notify();
-                    });
-                },
-            )
-            .detach();
         }
     }
 
-    fn start_story_list_resize(&mut self, event: &MouseDownEvent, cx: &mut ViewContext<Self>) {
-        if event.click_count >= 2 {
-            self.story_list_width = STORY_LIST_DEFAULT_WIDTH;
-            self.is_resizing_story_list = false;
+    /// 结束查找并恢复原来的折叠状态
+    fn clear_comment_search(&mut self, cx: &mut ViewContext<Self>) {
+        if let Some(search) = self.comment_search.take() {
+            self.collapsed_comments = search.saved_collapsed;
+            cx.focus(&self.story_list_focus_handle);
             cx.notify();
-            return;
         }
-
-        self.is_resizing_story_list = true;
-        self.resize_start_x = event.position.x.0;
-        self.resize_start_width = self.story_list_width;
-        cx.notify();
     }
 
-    fn update_story_list_resize(&mut self, event: &MouseMoveEvent, cx: &mut ViewContext<Self>) {
-        if !self.is_resizing_story_list {
+    fn on_comment_search_key(&mut self, event: &KeyDownEvent, cx: &mut ViewContext<Self>) {
+        let Some(mut query) = self.comment_search.as_ref().map(|s| s.query.clone()) else {
             return;
+        };
+        let keystroke = &event.keystroke;
+        match keystroke.key.as_str() {
+            "escape" => self.clear_comment_search(cx),
+            "enter" => self.cycle_comment_match(!keystroke.modifiers.shift, cx),
+            _ if text_input::apply_keystroke(&mut query, keystroke) => {
+                self.set_comment_search_query(query, cx);
+            }
+            _ => return,
         }
+        cx.stop_propagation();
+    }
 
-        let delta = event.position.x.0 - self.resize_start_x;
-        let viewport_width = cx.window_context().viewport_size().width.0;
-        let max_by_window =
-            (viewport_width - SIDEBAR_WIDTH - SPLITTER_WIDTH - STORY_LIST_MIN_DETAIL_WIDTH)
-                .max(STORY_LIST_MIN_WIDTH);
+    /// 展开后的布局要到下一帧才画出来，之后再定位
+    fn scroll_to_comment_match(&mut self, cx: &mut ViewContext<Self>) {
+        cx.on_next_frame(|_this, cx| {
+            cx.on_next_frame(|this, cx| this.scroll_comment_match_into_view(cx));
+        });
+    }
 
-        self.story_list_width =
-            (self.resize_start_width + delta).clamp(STORY_LIST_MIN_WIDTH, max_by_window);
+    fn scroll_comment_match_into_view(&mut self, cx: &mut ViewContext<Self>) {
+        let Some(target) = self
+            .comment_search
+            .as_ref()
+            .and_then(CommentSearch::current_match)
+        else {
+            return;
+        };
+        self.sync_comment_list();
+        let Some(row) = self.comment_rows.ids.iter().position(|&id| id == target) else {
+            return;
+        };
+        self.comment_list.scroll_to_reveal_item(row + 1);
         cx.notify();
     }
 
-    fn stop_story_list_resize(&mut self, _: &MouseUpEvent, cx: &mut ViewContext<Self>) {
-        if self.is_resizing_story_list {
-            self.is_resizing_story_list = false;
-            cx.notify();
+    /// 输入停顿 `STORY_SEARCH_DEBOUNCE` 之后才请求，期间的输入只更新代号
+    fn set_story_search_query(&mut self, query: String, cx: &mut ViewContext<Self>) {
+        let generation = self.story_search.set_query(query);
+        self.story_list_base_handle()
+            .set_offset(point(px(0.), px(0.)));
+        cx.notify();
+        if !self.story_search.is_active() {
+            return;
         }
-    }
-}
 
-impl Render for AppState {
-    fn render(&mut self, cx: &mut ViewContext<Self>) -> impl IntoElement {
-        let theme = &self.theme;
+        let client = self.client.clone();
+        let query = self.story_search.query.trim().to_string();
+        cx.spawn(
+            |this: WeakView<Self>, mut cx: AsyncWindowContext| async move {
+                cx.background_executor()
+                    .timer(story_search::STORY_SEARCH_DEBOUNCE)
+                    .await;
+                let current = this
+                    .update(&mut cx, |this: &mut Self, _cx: &mut ViewContext<Self>| {
+                        this.story_search.is_current(generation)
+                    })
+                    .unwrap_or(false);
+                if !current {
+                    return;
+                }
 
-        div()
-            .size_full()
-            .flex()
+                let result = client
+                    .search_stories(&query, story_search::STORY_SEARCH_LIMIT)
+                    .await
+                    .map(|stories| stories.iter().map(StorySummary::from).collect());
+                let _ = this.update(&mut cx, |this: &mut Self, cx: &mut ViewContext<Self>| {
+                    if this.story_search.finish(generation, result) {
+                        cx.notify();
+                    }
+                });
+            },
+        )
+        .detach();
+    }
+
+    /// 保存订阅地址，正在看 Feeds 频道时立即重新加载
+    fn add_feed(&mut self, cx: &mut ViewContext<Self>) {
+        let Some(url) = feeds::normalize_feed_url(&self.feed_input) else {
+            self.show_toast("Enter a feed URL", cx);
+            return;
+        };
+        self.feed_input.clear();
+        if self.settings.feeds.contains(&url) {
+            self.show_toast("Already subscribed", cx);
+            return;
+        }
+        self.settings.feeds.push(url);
+        self.feeds_changed(cx);
+    }
+
+    fn remove_feed(&mut self, url: &str, cx: &mut ViewContext<Self>) {
+        self.settings.feeds.retain(|feed| feed != url);
+        self.feeds_changed(cx);
+    }
+
+    fn feeds_changed(&mut self, cx: &mut ViewContext<Self>) {
+        self.feeds.set(self.settings.feeds.clone());
+        self.save_settings();
+        self.channel_stories.remove(&NewsChannel::Feeds);
+        self.channel_pagers.remove(&NewsChannel::Feeds);
+        if self.selected_channel == NewsChannel::Feeds && !self.showing_bookmarks {
+            self.load_stories(cx);
+        }
+        cx.notify();
+    }
+
+    fn on_feed_input_key(&mut self, event: &KeyDownEvent, cx: &mut ViewContext<Self>) {
+        let keystroke = &event.keystroke;
+        let modifiers = &keystroke.modifiers;
+        match keystroke.key.as_str() {
+            "enter" => self.add_feed(cx),
+            "escape" => {
+                self.feed_input.clear();
+                cx.focus(&self.story_list_focus_handle);
+            }
+            // 订阅地址多半是粘贴进来的
+            "v" if modifiers.platform || modifiers.control => {
+                if let Some(text) = cx.read_from_clipboard().and_then(|item| item.text()) {
+                    self.feed_input.push_str(text.trim());
+                }
+            }
+            _ if text_input::apply_keystroke(&mut self.feed_input, keystroke) => {}
+            _ => return,
+        }
+        cx.notify();
+        cx.stop_propagation();
+    }
+
+    fn clear_story_search(&mut self, cx: &mut ViewContext<Self>) {
+        if !self.story_search.query.is_empty() {
+            self.set_story_search_query(String::new(), cx);
+        }
+    }
+
+    fn on_story_search_key(&mut self, event: &KeyDownEvent, cx: &mut ViewContext<Self>) {
+        let mut query = self.story_search.query.clone();
+        let keystroke = &event.keystroke;
+        match keystroke.key.as_str() {
+            "escape" => {
+                self.clear_story_search(cx);
+                cx.focus(&self.story_list_focus_handle);
+            }
+            _ if text_input::apply_keystroke(&mut query, keystroke) => {
+                self.set_story_search_query(query, cx);
+            }
+            _ => return,
+        }
+        cx.stop_propagation();
+    }
+
+    /// 列表有焦点时输入数字再回车，打开当前顺序下的第 n 条
+    fn on_story_list_key(&mut self, event: &KeyDownEvent, cx: &mut ViewContext<Self>) {
+        let keystroke = &event.keystroke;
+        let modifiers = &keystroke.modifiers;
+        if modifiers.control || modifiers.alt || modifiers.platform {
+            return;
+        }
+        match keystroke.key.as_str() {
+            key if key.len() == 1 && key.chars().all(|ch| ch.is_ascii_digit()) => {
+                let digits = self.rank_jump.get_or_insert_with(String::new);
+                if digits.len() < MAX_RANK_JUMP_DIGITS {
+                    digits.push_str(key);
+                }
+            }
+            "backspace" if self.rank_jump.is_some() => {
+                if let Some(digits) = self.rank_jump.as_mut() {
+                    digits.pop();
+                    if digits.is_empty() {
+                        self.rank_jump = None;
+                    }
+                }
+            }
+            "enter" if self.rank_jump.is_some() => {
+                let rank = self.rank_jump.take().and_then(|digits| digits.parse().ok());
+                if let Some(rank) = rank {
+                    self.jump_to_rank(rank, cx);
+                }
+            }
+            "escape" if self.rank_jump.is_some() => self.rank_jump = None,
+            _ => return,
+        }
+        cx.notify();
+        cx.stop_propagation();
+    }
+
+    /// 选中列表当前顺序（排序、过滤之后）里的第 `rank` 条，从 1 开始
+    fn jump_to_rank(&mut self, rank: usize, cx: &mut ViewContext<Self>) {
+        let stories = self.visible_stories();
+        let count = stories.len();
+        let Some(story_id) = rank
+            .checked_sub(1)
+            .and_then(|index| stories.get(index))
+            .map(|story| story.id)
+        else {
+            self.show_toast(format!("No story #{} (list has {})", rank, count), cx);
+            return;
+        };
+        self.story_list_scroll_handle.scroll_to_item(rank - 1);
+        self.select_story(story_id, cx);
+    }
+
+    fn load_stories(&mut self, cx: &mut ViewContext<Self>) {
+        self.is_loading = true;
+        self.error_message = None;
+        self.new_story_ids.clear();
+        self.last_story_refresh = Instant::now();
+        cx.notify();
+
+        let client = self.client.clone();
+        let channel = self.selected_channel;
+
+        cx.spawn(
+            |this: WeakView<Self>, mut cx: AsyncWindowContext| async move {
+                let result = match client.fetch_story_ids(channel).await {
+                    Ok(ids) => {
+                        let mut pager = StoryPager::new(ids);
+                        let page = pager.next_page().unwrap_or_default();
+                        let stories = client.fetch_summaries(channel, &page).await;
+                        pager.finish_page();
+                        Ok((stories, pager))
+                    }
+                    Err(e) => Err(e),
+                };
+                let _ = this.update(&mut cx, |this: &mut Self, cx: &mut ViewContext<Self>| {
+                    // 加载期间已切换频道：结果只放进缓存
+                    if this.selected_channel != channel {
+                        if let Ok((stories, pager)) = result {
+                            this.channel_stories.insert(channel, stories);
+                            this.channel_pagers.insert(channel, pager);
+                        }
+                        return;
+                    }
+                    match result {
+                        Ok((stories, pager)) => {
+                            this.stories = stories;
+                            this.story_pager = pager;
+                            this.error_message = None;
+                            // 刷新时选中的 story 保持打开（它可能只是排到了还没加载的页），
+                            // 还在新列表里就滚到它
+                            let selected_row = this.selected_story_id.and_then(|id| {
+                                this.visible_stories().iter().position(|s| s.id == id)
+                            });
+                            match selected_row {
+                                Some(row) => this.story_list_scroll_handle.scroll_to_item(row),
+                                None => this
+                                    .story_list_base_handle()
+                                    .set_offset(point(px(0.), px(0.))),
+                            }
+                            // 第一页不够填满列表时继续预取
+                            cx.on_next_frame(|this, cx| this.maybe_prefetch_stories(cx));
+                        }
+                        Err(e) => {
+                            this.error_message = Some(format!("Failed to load stories: {}", e));
+                        }
+                    }
+                    this.is_loading = false;
+                    cx.notify();
+                });
+            },
+        )
+        .detach();
+    }
+
+    /// 重新获取当前频道的列表，加载期间显示骨架屏
+    fn refresh_stories(&mut self, cx: &mut ViewContext<Self>) {
+        if self.is_loading || self.showing_bookmarks {
+            return;
+        }
+        self.load_stories(cx);
+    }
+
+    /// 列表快滚到底时预取下一页，让列表看起来没有尽头
+    fn maybe_prefetch_stories(&mut self, cx: &mut ViewContext<Self>) {
+        if self.is_loading || self.story_search.is_active() || self.showing_bookmarks {
+            return;
+        }
+        let loaded_rows = self.visible_stories().len();
+        let last_visible_row = self.last_visible_story_row().unwrap_or(0);
+        let Some(page) = self.story_pager.prefetch(
+            last_visible_row,
+            loaded_rows,
+            self.settings.story_prefetch_rows,
+        ) else {
+            return;
+        };
+        self.fetch_story_page(page, cx);
+    }
+
+    /// 列表末尾的 "Load more"：不管滚动位置，直接请求下一页
+    fn load_more_stories(&mut self, cx: &mut ViewContext<Self>) {
+        if self.is_loading {
+            return;
+        }
+        if let Some(page) = self.story_pager.next_page() {
+            self.fetch_story_page(page, cx);
+        }
+    }
+
+    /// 请求一页摘要并追加到所属频道的列表；调用前 pager 已标记为加载中
+    fn fetch_story_page(&mut self, page: Vec<i64>, cx: &mut ViewContext<Self>) {
+        cx.notify();
+
+        let client = self.client.clone();
+        let channel = self.selected_channel;
+        let epoch = self.story_pager.epoch();
+        cx.spawn(
+            |this: WeakView<Self>, mut cx: AsyncWindowContext| async move {
+                let stories = client.fetch_summaries(channel, &page).await;
+                let _ = this.update(&mut cx, |this: &mut Self, cx: &mut ViewContext<Self>| {
+                    let (list, pager) = if this.selected_channel == channel {
+                        (&mut this.stories, &mut this.story_pager)
+                    } else {
+                        match (
+                            this.channel_stories.get_mut(&channel),
+                            this.channel_pagers.get_mut(&channel),
+                        ) {
+                            (Some(list), Some(pager)) => (list, pager),
+                            _ => return,
+                        }
+                    };
+                    // 期间刷新过列表：这一页来自旧的排名
+                    if !pager.finish_page_from(epoch) {
+                        return;
+                    }
+                    // 排名在两次请求之间变化时，同一个 story 可能出现在两页里
+                    let known = list.iter().map(|s| s.id).collect::<HashSet<_>>();
+                    list.extend(stories.into_iter().filter(|s| !known.contains(&s.id)));
+                    cx.notify();
+                });
+            },
+        )
+        .detach();
+    }
+
+    /// 开启设置时，把整行滚出列表顶部的 story 标记为已读
+    fn mark_scrolled_past_read(&mut self, cx: &mut ViewContext<Self>) {
+        if !self.settings.mark_read_on_scroll_past || self.is_loading {
+            return;
+        }
+        let Some(row_height) = self.story_row_height() else {
+            return;
+        };
+        let scroll_top = -self.story_list_base_handle().offset().y.0;
+        let row_heights = vec![row_height; self.visible_stories().len()];
+        let scrolled_past = paging::rows_scrolled_past(scroll_top, &row_heights);
+
+        let ids = self
+            .visible_stories()
+            .into_iter()
+            .take(scrolled_past)
+            .map(|story| story.id)
+            .collect::<Vec<_>>();
+        let mut changed = false;
+        for id in ids {
+            changed |= self.read_stories.record(id);
+        }
+        if changed {
+            let _ = self.read_stories.save(&self.dirs.data);
+            cx.notify();
+        }
+    }
+
+    /// 把列表里当前显示的 story 都标为已读
+    fn mark_all_stories_read(&mut self, cx: &mut ViewContext<Self>) {
+        let ids = self
+            .visible_stories()
+            .into_iter()
+            .map(|story| story.id)
+            .collect::<Vec<_>>();
+        let mut marked = 0;
+        for id in ids {
+            if self.read_stories.record(id) {
+                marked += 1;
+            }
+        }
+        if marked > 0 {
+            let _ = self.read_stories.save(&self.dirs.data);
+        }
+        self.show_toast(format!("Marked {} stories as read", marked), cx);
+    }
+
+    fn clear_read_stories(&mut self, cx: &mut ViewContext<Self>) {
+        if self.read_stories.clear() {
+            let _ = self.read_stories.save(&self.dirs.data);
+        }
+        self.show_toast("Cleared read stories", cx);
+    }
+
+    fn last_visible_story_row(&self) -> Option<usize> {
+        let row_height = self.story_row_height()?;
+        let handle = self.story_list_base_handle();
+        let viewport_bottom = -handle.offset().y.0 + handle.bounds().size.height.0;
+        let rows = self.visible_stories().len();
+        let last = (viewport_bottom / row_height).ceil() as usize;
+        (rows > 0).then(|| last.clamp(1, rows) - 1)
+    }
+
+    fn select_channel(&mut self, channel: NewsChannel, cx: &mut ViewContext<Self>) {
+        // 从收藏回到当前频道时也要清掉选中的收藏
+        let leaving_bookmarks = std::mem::take(&mut self.showing_bookmarks);
+        if channel == self.selected_channel && !leaving_bookmarks {
+            return;
+        }
+
+        let previous = std::mem::replace(&mut self.selected_channel, channel);
+        self.client = self.source_for(channel);
+        self.new_story_ids.clear();
+        let stories = std::mem::take(&mut self.stories);
+        let pager = std::mem::take(&mut self.story_pager);
+        if !stories.is_empty() {
+            self.channel_stories.insert(previous, stories);
+            self.channel_pagers.insert(previous, pager);
+        }
+        self.record_seen_comments();
+        self.selected_story_id = None;
+        self.selected_story = None;
+        self.comments.clear();
+        self.collapsed_comments.clear();
+        self.hovered_comment = None;
+        self.comment_search = None;
+        self.is_loading_comments = false;
+        self.close_reader(cx);
+        self.clear_story_search(cx);
+
+        match self.channel_stories.remove(&channel) {
+            Some(stories) => {
+                self.stories = stories;
+                self.story_pager = self.channel_pagers.remove(&channel).unwrap_or_default();
+                self.is_loading = false;
+                self.error_message = None;
+                cx.notify();
+            }
+            None => self.load_stories(cx),
+        }
+    }
+
+    /// 频道对应的数据来源
+    fn source_for(&self, channel: NewsChannel) -> Arc<dyn NewsSource> {
+        match channel {
+            NewsChannel::Lobsters => self.lobsters.clone(),
+            NewsChannel::Feeds => self.feed_source.clone(),
+            _ => self.hacker_news.clone(),
+        }
+    }
+
+    /// 列表里的 story 来自哪个频道：收藏记着自己的频道，其余的属于当前频道
+    fn listed_story_channel(&self, story_id: i64) -> NewsChannel {
+        self.bookmarks
+            .channel(story_id)
+            .unwrap_or(self.selected_channel)
+    }
+
+    /// 列表切换到收藏，数据来自本地文件，不请求网络
+    fn show_bookmarks(&mut self, cx: &mut ViewContext<Self>) {
+        if self.showing_bookmarks {
+            return;
+        }
+        self.showing_bookmarks = true;
+        self.record_seen_comments();
+        self.selected_story_id = None;
+        self.selected_story = None;
+        self.comments.clear();
+        self.collapsed_comments.clear();
+        self.hovered_comment = None;
+        self.comment_search = None;
+        self.is_loading_comments = false;
+        self.close_reader(cx);
+        self.clear_story_search(cx);
+        cx.notify();
+    }
+
+    /// 收藏或取消收藏；已经打开的 story 保存完整记录，否则先存摘要，打开时再补全
+    fn toggle_bookmark(&mut self, story_id: i64, cx: &mut ViewContext<Self>) {
+        let story = self
+            .selected_story
+            .clone()
+            .filter(|story| story.id == story_id)
+            .or_else(|| self.bookmarks.get(story_id).cloned())
+            .or_else(|| {
+                self.visible_stories()
+                    .into_iter()
+                    .find(|s| s.id == story_id)
+                    .map(StorySummary::to_story)
+            });
+        let Some(story) = story else {
+            return;
+        };
+        let channel = if self.selected_story_id == Some(story_id) {
+            self.story_channel
+        } else {
+            self.selected_channel
+        };
+        self.bookmarks
+            .toggle(story, channel, chrono::Utc::now().timestamp());
+        if let Err(e) = self.bookmarks.save(&self.dirs.data) {
+            self.show_toast(format!("Failed to save bookmarks: {}", e), cx);
+        }
+        cx.notify();
+    }
+
+    fn select_channel_at(&mut self, index: usize, cx: &mut ViewContext<Self>) {
+        if let Some(&channel) = self.settings.channel_order().get(index) {
+            self.select_channel(channel, cx);
+        }
+    }
+
+    fn select_next_channel(&mut self, cx: &mut ViewContext<Self>) {
+        let channels = self.settings.channel_order();
+        let current = channels
+            .iter()
+            .position(|&c| c == self.selected_channel)
+            .unwrap_or(0);
+        self.select_channel_at((current + 1) % channels.len(), cx);
+    }
+
+    fn toggle_pinned_channel(&mut self, channel: NewsChannel, cx: &mut ViewContext<Self>) {
+        self.settings.toggle_pinned(channel);
+        self.save_settings();
+        cx.notify();
+    }
+
+    /// 在置顶区内移动当前频道
+    fn move_selected_channel(&mut self, offset: isize, cx: &mut ViewContext<Self>) {
+        if self.settings.move_pinned(self.selected_channel, offset) {
+            self.save_settings();
+            cx.notify();
+        }
+    }
+
+    fn select_story(&mut self, story_id: i64, cx: &mut ViewContext<Self>) {
+        let summary = self
+            .visible_stories()
+            .into_iter()
+            .find(|s| s.id == story_id)
+            .cloned();
+        if let Some(summary) = summary {
+            let channel = self.listed_story_channel(story_id);
+            self.show_story(summary, None, channel, cx);
+        }
+    }
+
+    /// 按 id 打开 HN item：列表里有就直接选中，否则先获取完整的 story
+    fn open_item(&mut self, item_id: i64, cx: &mut ViewContext<Self>) {
+        if self.stories.iter().any(|s| s.id == item_id) {
+            self.select_story(item_id, cx);
+            return;
+        }
+
+        let client = self.hacker_news.clone();
+        cx.spawn(
+            |this: WeakView<Self>, mut cx: AsyncWindowContext| async move {
+                let result = client.fetch_story(item_id).await;
+                let _ = this.update(&mut cx, |this: &mut Self, cx: &mut ViewContext<Self>| {
+                    match result {
+                        Ok(story) => {
+                            // 讨论页链接只会指向 HN
+                            let channel = NewsChannel::HackerNews;
+                            let summary = StorySummary::from(&story);
+                            this.show_story(summary, Some(story), channel, cx);
+                        }
+                        Err(e) => {
+                            this.show_toast(format!("Couldn't open item {}: {}", item_id, e), cx)
+                        }
+                    }
+                });
+            },
+        )
+        .detach();
+    }
+
+    /// 打开剪贴板里的链接：HN 讨论页显示为 story，其他链接用阅读模式
+    fn open_from_clipboard(&mut self, cx: &mut ViewContext<Self>) {
+        let text = cx.read_from_clipboard().and_then(|item| item.text());
+        match text.as_deref().and_then(links::classify_link) {
+            Some(LinkTarget::HackerNewsItem(item_id)) => self.open_item(item_id, cx),
+            Some(LinkTarget::Article(url)) => self.open_reader(url, None, cx),
+            None => self.show_toast("No link on the clipboard", cx),
+        }
+    }
+
+    /// 显示 story 详情；`prefetched` 是已经拿到的完整 story，没有时再去请求。
+    /// `channel` 决定向哪个来源请求 story 和评论
+    fn show_story(
+        &mut self,
+        summary: StorySummary,
+        prefetched: Option<Story>,
+        channel: NewsChannel,
+        cx: &mut ViewContext<Self>,
+    ) {
+        let story_id = summary.id;
+        if self.read_stories.record(story_id) {
+            let _ = self.read_stories.save(&self.dirs.data);
+        }
+        self.new_story_ids.remove(&story_id);
+        self.save_reader_scroll();
+        self.reader_tabs.hide();
+        self.record_seen_comments();
+        self.selected_story_id = Some(story_id);
+        self.story_channel = channel;
+        self.selection_generation = self.selection_generation.wrapping_add(1);
+        let generation = self.selection_generation;
+        // 完整的 story 到达前先用摘要（或收藏时保存的记录）显示标题等信息
+        let saved = self.bookmarks.get(story_id).cloned();
+        self.selected_story = Some(saved.clone().unwrap_or_else(|| summary.to_story()));
+        self.comments.clear();
+        self.loaded_comment_roots.clear();
+        self.is_loading_more_comments = false;
+        self.expanded_replies.clear();
+        self.loading_replies.clear();
+        self.collapsed_comments.clear();
+        self.hovered_comment = None;
+        self.comment_search = None;
+        self.seen_before_visit = self.seen_comments.seen_for(story_id);
+        self.is_loading_comments = true;
+        self.poll_options = None;
+        cx.notify();
+
+        let split_source = self
+            .settings
+            .split_view_on_select
+            .then(|| summary.reader_source(self.settings.reader_default_source))
+            .flatten();
+        let client = self.source_for(channel);
+
+        cx.spawn(
+            |this: WeakView<Self>, mut cx: AsyncWindowContext| async move {
+                let story = match prefetched {
+                    Some(story) => Ok(story),
+                    None => client.fetch_story(story_id).await,
+                };
+                let story = match (story, saved) {
+                    (Ok(story), _) => story,
+                    // 收藏的 story 在 HN 上被删除或拿不到了，用保存的记录
+                    (Err(_), Some(saved)) => saved,
+                    (Err(e), None) => {
+                        let _ =
+                            this.update(&mut cx, |this: &mut Self, cx: &mut ViewContext<Self>| {
+                                if this.is_current_selection(story_id, generation) {
+                                    this.error_message =
+                                        Some(format!("Failed to load story: {}", e));
+                                    this.is_loading_comments = false;
+                                    cx.notify();
+                                }
+                            });
+                        return;
+                    }
+                };
+                let still_selected = this
+                    .update(&mut cx, |this: &mut Self, cx: &mut ViewContext<Self>| {
+                        if !this.is_current_selection(story_id, generation) {
+                            return false;
+                        }
+                        this.selected_story = Some(story.clone());
+                        if this.settings.refresh_story_on_open {
+                            this.refresh_listed_story(&story);
+                        }
+                        if this.bookmarks.refresh(&story) {
+                            let _ = this.bookmarks.save(&this.dirs.data);
+                        }
+                        // 正文要等完整 story 到了才能打开
+                        if split_source == Some(ReaderSource::SelfText) {
+                            this.open_story_reader(&story, ReaderSource::SelfText, cx);
+                        }
+                        if story.is_poll() {
+                            this.load_poll_options(&story, generation, cx);
+                        }
+                        cx.notify();
+                        true
+                    })
+                    .unwrap_or(false);
+                if !still_selected {
+                    return;
+                }
+
+                let result = client.fetch_comments(&story).await;
+                let root_ids = client.initial_root_ids(story.kids.as_deref().unwrap_or_default());
+                let _ = this.update(&mut cx, |this: &mut Self, cx: &mut ViewContext<Self>| {
+                    if !this.is_current_selection(story_id, generation) {
+                        return;
+                    }
+                    match result {
+                        Ok(comments) => {
+                            // 一次返回整棵树的来源（Lobsters）不受首批数量限制
+                            this.loaded_comment_roots = root_ids
+                                .into_iter()
+                                .chain(comments.iter().filter(|c| c.depth == 0).map(|c| c.id))
+                                .collect();
+                            this.comments = comments;
+                            this.rebuild_comment_blocks();
+                            this.apply_comment_sort();
+                            this.load_comment_unfurls(cx);
+                            this.reading_stats.record_story(
+                                chrono::Local::now().date_naive(),
+                                this.comments.len(),
+                            );
+                            let _ = this.reading_stats.save(&this.dirs.data);
+                        }
+                        Err(e) => {
+                            this.error_message = Some(format!("Failed to load comments: {}", e));
+                        }
+                    }
+                    this.is_loading_comments = false;
+                    cx.notify();
+                });
+            },
+        )
+        .detach();
+
+        if split_source == Some(ReaderSource::Link) {
+            self.open_story_reader(&summary.to_story(), ReaderSource::Link, cx);
+        }
+    }
+
+    fn load_poll_options(&mut self, story: &Story, generation: usize, cx: &mut ViewContext<Self>) {
+        let client = self.source_for(self.story_channel);
+        let story = story.clone();
+        cx.spawn(
+            |this: WeakView<Self>, mut cx: AsyncWindowContext| async move {
+                let options = client.fetch_poll_options(&story).await;
+                let _ = this.update(&mut cx, |this: &mut Self, cx: &mut ViewContext<Self>| {
+                    if this.is_current_selection(story.id, generation) {
+                        this.poll_options = Some(options);
+                        cx.notify();
+                    }
+                });
+            },
+        )
+        .detach();
+    }
+
+    /// 还没加载的顶级评论数
+    fn remaining_root_comments(&self) -> usize {
+        self.selected_story
+            .as_ref()
+            .and_then(|story| story.kids.as_ref())
+            .map_or(0, |kids| {
+                kids.iter()
+                    .filter(|id| !self.loaded_comment_roots.contains(id))
+                    .count()
+            })
+    }
+
+    /// 获取下一批顶级评论（连同回复），接在评论列表末尾
+    fn load_more_root_comments(&mut self, cx: &mut ViewContext<Self>) {
+        if self.is_loading_comments || self.is_loading_more_comments {
+            return;
+        }
+        let Some(story_id) = self.selected_story_id else {
+            return;
+        };
+        let kids = self
+            .selected_story
+            .as_ref()
+            .and_then(|story| story.kids.clone())
+            .unwrap_or_default();
+        let client = self.source_for(self.story_channel);
+        let root_ids = client.next_root_ids(&kids, &self.loaded_comment_roots);
+        if root_ids.is_empty() {
+            return;
+        }
+        self.loaded_comment_roots.extend(root_ids.iter().copied());
+        self.is_loading_more_comments = true;
+        cx.notify();
+
+        let generation = self.selection_generation;
+        cx.spawn(
+            |this: WeakView<Self>, mut cx: AsyncWindowContext| async move {
+                let comments = client.fetch_more_comments(&root_ids).await;
+                let _ = this.update(&mut cx, |this: &mut Self, cx: &mut ViewContext<Self>| {
+                    if this.is_current_selection(story_id, generation) {
+                        this.append_comments(comments, cx);
+                    }
+                });
+            },
+        )
+        .detach();
+    }
+
+    /// 新的一批顶级评论都排在已有评论之后，直接追加仍是深度优先顺序，
+    /// 再按当前的排序方式放到各自的位置
+    fn append_comments(&mut self, comments: Vec<Comment>, cx: &mut ViewContext<Self>) {
+        self.index_comment_blocks(&comments);
+        self.comments.extend(comments);
+        self.apply_comment_sort();
+        self.is_loading_more_comments = false;
+        self.comments_added(cx);
+    }
+
+    fn apply_comment_sort(&mut self) {
+        let ranking = self
+            .selected_story
+            .as_ref()
+            .and_then(|story| story.kids.as_deref())
+            .unwrap_or_default();
+        models::sort_threads(&mut self.comments, self.comment_sort, ranking);
+        // 查找命中按评论顺序排列，跟着重排，停在原来那一条上
+        if let Some(search) = self.comment_search.as_mut() {
+            let current = search.current_match();
+            search.matches = comment_search::find_matches(&self.comments, &search.query);
+            search.current = current
+                .and_then(|id| search.matches.iter().position(|&m| m == id))
+                .unwrap_or(0);
+        }
+    }
+
+    fn set_comment_sort(&mut self, sort: CommentSort, cx: &mut ViewContext<Self>) {
+        if self.comment_sort == sort {
+            return;
+        }
+        self.comment_sort = sort;
+        self.apply_comment_sort();
+        cx.notify();
+    }
+
+    /// 补充获取评论下被层级或数量上限截掉的回复；每条评论只请求一次
+    fn load_more_replies(&mut self, comment_id: i64, cx: &mut ViewContext<Self>) {
+        let Some(story_id) = self.selected_story_id else {
+            return;
+        };
+        let Some(depth) = self
+            .comments
+            .iter()
+            .find(|c| c.id == comment_id)
+            .map(|c| c.depth)
+        else {
+            return;
+        };
+        if !self.expanded_replies.insert(comment_id) {
+            return;
+        }
+        self.loading_replies.insert(comment_id);
+        cx.notify();
+
+        let generation = self.selection_generation;
+        let client = self.source_for(self.story_channel);
+        cx.spawn(
+            |this: WeakView<Self>, mut cx: AsyncWindowContext| async move {
+                let result = client.fetch_replies(comment_id, depth).await;
+                let _ = this.update(&mut cx, |this: &mut Self, cx: &mut ViewContext<Self>| {
+                    if !this.is_current_selection(story_id, generation) {
+                        return;
+                    }
+                    this.loading_replies.remove(&comment_id);
+                    match result {
+                        Ok(replies) => {
+                            this.index_comment_blocks(&replies);
+                            models::splice_replies(&mut this.comments, comment_id, replies);
+                            this.comments_added(cx);
+                        }
+                        Err(e) => {
+                            // 允许再试一次
+                            this.expanded_replies.remove(&comment_id);
+                            this.show_toast(format!("Failed to load replies: {}", e), cx);
+                        }
+                    }
+                });
+            },
+        )
+        .detach();
+    }
+
+    /// 整洁布局下为已加载的全部评论重建块缓存，关掉时清空
+    fn rebuild_comment_blocks(&mut self) {
+        self.comment_blocks.clear();
+        let comments = std::mem::take(&mut self.comments);
+        self.index_comment_blocks(&comments);
+        self.comments = comments;
+    }
+
+    /// 整洁布局预先把新评论解析成块
+    fn index_comment_blocks(&mut self, comments: &[Comment]) {
+        if self.settings.clean_comment_layout {
+            self.comment_blocks.extend(
+                comments
+                    .iter()
+                    .map(|c| (c.id, reader_view::comment_blocks(c))),
+            );
+        }
+    }
+
+    fn comments_added(&mut self, cx: &mut ViewContext<Self>) {
+        self.load_comment_unfurls(cx);
+        cx.notify();
+    }
+
+    fn is_current_selection(&self, story_id: i64, generation: usize) -> bool {
+        self.selected_story_id == Some(story_id) && self.selection_generation == generation
+    }
+
+    /// 用刚获取的 story 更新列表（含其他频道的缓存）里的分数和评论数
+    fn refresh_listed_story(&mut self, story: &Story) {
+        let listed = self
+            .stories
+            .iter_mut()
+            .chain(self.channel_stories.values_mut().flatten())
+            .filter(|summary| summary.id == story.id);
+        for summary in listed {
+            summary.refresh_from(story);
+        }
+    }
+
+    /// 离开 story 时把加载过的评论记为看过，下次打开时它们会变暗
+    fn record_seen_comments(&mut self) {
+        let Some(story_id) = self.selected_story_id else {
+            return;
+        };
+        if !self.settings.dim_seen_comments || self.comments.is_empty() {
+            return;
+        }
+        let ids = self.comments.iter().map(|c| c.id);
+        self.seen_comments
+            .record(story_id, ids, chrono::Utc::now().timestamp());
+        let _ = self.seen_comments.save(&self.dirs.data);
+    }
+
+    fn comment_text_color(&self, comment_id: i64) -> Hsla {
+        if self.settings.dim_seen_comments && self.seen_before_visit.contains(&comment_id) {
+            self.theme.text_muted
+        } else {
+            self.theme.text_primary
+        }
+    }
+
+    fn show_toast(&mut self, message: impl Into<String>, cx: &mut ViewContext<Self>) {
+        self.toast = Some(message.into());
+        self.toast_generation = self.toast_generation.wrapping_add(1);
+        let generation = self.toast_generation;
+        cx.notify();
+
+        cx.spawn(
+            |this: WeakView<Self>, mut cx: AsyncWindowContext| async move {
+                cx.background_executor().timer(TOAST_DURATION).await;
+                let _ = this.update(&mut cx, |this: &mut Self, cx: &mut ViewContext<Self>| {
+                    if this.toast_generation == generation {
+                        this.toast = None;
+                        cx.notify();
+                    }
+                });
+            },
+        )
+        .detach();
+    }
+
+    fn export_feed_snapshot(&mut self, cx: &mut ViewContext<Self>) {
+        let channel = self.selected_channel.name();
+        let result = export::snapshot_json(channel, &self.stories).and_then(|json| {
+            let file_name = export::export_file_name(channel, "json");
+            export::write_export(&self.dirs.exports(), &file_name, &json)
+        });
+
+        match result {
+            Ok(path) => {
+                if let Some(dir) = path.parent() {
+                    let _ = open::that(dir);
+                }
+                self.show_toast(format!("Exported to {}", path.display()), cx);
+            }
+            Err(e) => self.show_toast(format!("Export failed: {}", e), cx),
+        }
+    }
+
+    /// 订阅列表导出成 OPML，可以导入别的阅读器
+    fn export_feeds_opml(&mut self, cx: &mut ViewContext<Self>) {
+        if self.settings.feeds.is_empty() {
+            self.show_toast("No feeds to export", cx);
+            return;
+        }
+        let feeds: Vec<(String, String)> = self
+            .settings
+            .feeds
+            .iter()
+            .map(|url| (self.feeds.title(url), url.clone()))
+            .collect();
+        let opml = export::opml_document("OneApp feeds", &feeds);
+        let file_name = export::export_file_name("feeds", "opml");
+
+        match export::write_export(&self.dirs.exports(), &file_name, &opml) {
+            Ok(path) => {
+                if let Some(dir) = path.parent() {
+                    let _ = open::that(dir);
+                }
+                self.show_toast(format!("Feeds exported to {}", path.display()), cx);
+            }
+            Err(e) => self.show_toast(format!("Feed export failed: {}", e), cx),
+        }
+    }
+
+    /// 列表中显示的 stories：排除仍在 snooze 中的，按当前频道的排序
+    fn visible_stories(&self) -> Vec<&StorySummary> {
+        let now = chrono::Utc::now().timestamp();
+        // 搜索结果保持 Algolia 的相关度顺序
+        if self.story_search.is_active() {
+            return self
+                .story_search
+                .results
+                .iter()
+                .flatten()
+                .filter(|story| !self.snoozed.is_snoozed(story.id, now))
+                .collect();
+        }
+        // 收藏按收藏时间，最新的在前
+        if self.showing_bookmarks {
+            return self.bookmarks.summaries().iter().collect();
+        }
+        let mut stories: Vec<&StorySummary> = self
+            .stories
+            .iter()
+            .filter(|story| !self.snoozed.is_snoozed(story.id, now))
+            .collect();
+        self.settings
+            .story_sort(self.selected_channel)
+            .sort(&mut stories);
+        stories
+    }
+
+    /// 切换当前频道的排序，并记住这个频道的选择
+    fn cycle_story_sort(&mut self, cx: &mut ViewContext<Self>) {
+        let next = self.settings.story_sort(self.selected_channel).next();
+        self.settings.set_story_sort(self.selected_channel, next);
+        self.save_settings();
+        cx.notify();
+    }
+
+    fn snooze_story(
+        &mut self,
+        story: &Story,
+        duration: SnoozeDuration,
+        cx: &mut ViewContext<Self>,
+    ) {
+        let until = duration.expires_at(&chrono::Local::now());
+        self.snoozed.snooze(story.id, story.title.clone(), until);
+        if let Err(e) = self.snoozed.save(&self.dirs.data) {
+            self.show_toast(format!("Failed to save snooze: {}", e), cx);
+        }
+
+        if self.selected_story_id == Some(story.id) {
+            self.record_seen_comments();
+            self.selected_story_id = None;
+            self.selected_story = None;
+            self.comments.clear();
+            self.close_reader(cx);
+        }
+        cx.notify();
+    }
+
+    fn unsnooze_story(&mut self, story_id: i64, cx: &mut ViewContext<Self>) {
+        if self.snoozed.unsnooze(story_id) {
+            let _ = self.snoozed.save(&self.dirs.data);
+        }
+        if self
+            .snoozed
+            .active(chrono::Utc::now().timestamp())
+            .is_empty()
+        {
+            self.overlay = None;
+        }
+        cx.notify();
+    }
+
+    fn show_snoozed(&mut self, cx: &mut ViewContext<Self>) {
+        self.overlay = Some(Overlay::Snoozed);
+        cx.notify();
+    }
+
+    fn close_overlay(&mut self, cx: &mut ViewContext<Self>) {
+        self.overlay = None;
+        cx.notify();
+    }
+
+    fn show_stats(&mut self, cx: &mut ViewContext<Self>) {
+        self.overlay = Some(Overlay::Stats);
+        cx.notify();
+    }
+
+    fn show_about(&mut self, cx: &mut ViewContext<Self>) {
+        self.overlay = Some(Overlay::About(about::AboutInfo::collect(&self.dirs)));
+        cx.notify();
+    }
+
+    /// 显示鼠标所在评论（没有时为选中的 story）的原始 JSON，只在打开调试工具时可用
+    fn show_raw_json(&mut self, cx: &mut ViewContext<Self>) {
+        if !self.settings.debug_tools {
+            return;
+        }
+        let Some(item_id) = self.hovered_comment.or(self.selected_story_id) else {
+            return;
+        };
+        self.overlay = Some(Overlay::RawJson {
+            item_id,
+            json: None,
+        });
+        cx.notify();
+
+        let client = self.source_for(self.story_channel);
+        cx.spawn(
+            |this: WeakView<Self>, mut cx: AsyncWindowContext| async move {
+                let json = match client.fetch_item_raw(item_id).await {
+                    Some(value) => serde_json::to_string_pretty(&value).map_err(|e| e.to_string()),
+                    None => Err(format!("Couldn't fetch item {}", item_id)),
+                };
+                let _ = this.update(&mut cx, |this: &mut Self, cx: &mut ViewContext<Self>| {
+                    if let Some(Overlay::RawJson {
+                        item_id: shown,
+                        json: slot,
+                    }) = this.overlay.as_mut()
+                    {
+                        if *shown == item_id {
+                            *slot = Some(json);
+                            cx.notify();
+                        }
+                    }
+                });
+            },
+        )
+        .detach();
+    }
+
+    /// 在后台删掉写坏的阅读缓存文件，删了东西时提示一下
+    /// 没有设置“减少动态效果”时跟随系统。读系统设置要启动子进程，放在后台，
+    /// 读到之前先保留动画
+    fn detect_reduce_motion(&mut self, cx: &mut ViewContext<Self>) {
+        if self.settings.reduce_motion.is_some() {
+            return;
+        }
+        let task = cx
+            .background_executor()
+            .spawn(async { motion::system_prefers_reduced_motion() });
+        cx.spawn(
+            |this: WeakView<Self>, mut cx: AsyncWindowContext| async move {
+                let system = task.await;
+                let _ = this.update(&mut cx, |this: &mut Self, cx: &mut ViewContext<Self>| {
+                    let reduce_motion =
+                        motion::resolve_reduce_motion(this.settings.reduce_motion, system);
+                    if reduce_motion != this.reduce_motion {
+                        this.reduce_motion = reduce_motion;
+                        cx.notify();
+                    }
+                });
+            },
+        )
+        .detach();
+    }
+
+    fn repair_reader_cache(&mut self, cx: &mut ViewContext<Self>) {
+        let dir = self.dirs.reader_cache();
+        let now = chrono::Utc::now().timestamp();
+        let task = cx.background_executor().spawn(async move {
+            reader::repair_disk_cache(
+                &dir,
+                now,
+                reader::CACHE_REPAIR_MAX_FILES,
+                reader::CACHE_REPAIR_BUDGET,
+            )
+        });
+        cx.spawn(
+            |this: WeakView<Self>, mut cx: AsyncWindowContext| async move {
+                let Ok(repair) = task.await else {
+                    return;
+                };
+                if repair.removed == 0 {
+                    return;
+                }
+                let _ = this.update(&mut cx, |this: &mut Self, cx: &mut ViewContext<Self>| {
+                    this.show_toast(
+                        format!("Removed {} damaged reader cache files", repair.removed),
+                        cx,
+                    );
+                });
+            },
+        )
+        .detach();
+    }
+
+    /// 删除磁盘上缓存的全部文章，连同内存里的缓存
+    fn clear_reader_cache(&mut self, cx: &mut ViewContext<Self>) {
+        self.reader_cache.clear();
+        self.reader_cache_order.clear();
+
+        let dir = self.dirs.reader_cache();
+        let task = cx.background_executor().spawn(async move {
+            let freed = reader::cache_usage(&dir).map_or(0, |(_, bytes)| bytes);
+            reader::clear_cache_files(&dir)
+                .map(|count| (count, freed))
+                .map_err(|e| e.to_string())
+        });
+        cx.spawn(
+            |this: WeakView<Self>, mut cx: AsyncWindowContext| async move {
+                let result = task.await;
+                let _ = this.update(&mut cx, |this: &mut Self, cx: &mut ViewContext<Self>| {
+                    // About 面板里的缓存用量跟着更新
+                    if let Some(Overlay::About(info)) = this.overlay.as_mut() {
+                        *info = about::AboutInfo::collect(&this.dirs);
+                    }
+                    match result {
+                        Ok((0, _)) => this.show_toast("Reader cache is already empty", cx),
+                        Ok((count, freed)) => this.show_toast(
+                            format!(
+                                "Cleared {} cached articles, freed {}",
+                                count,
+                                diagnostics::format_bytes(freed)
+                            ),
+                            cx,
+                        ),
+                        Err(e) => {
+                            this.show_toast(format!("Clearing reader cache failed: {}", e), cx)
+                        }
+                    }
+                });
+            },
+        )
+        .detach();
+    }
+
+    fn run_diagnostics(&mut self, cx: &mut ViewContext<Self>) {
+        use diagnostics::DiagnosticsReport;
+
+        let mut report = DiagnosticsReport::pending();
+        report.set(
+            DiagnosticsReport::CACHE_WRITABLE,
+            diagnostics::check_cache_writable(&self.dirs.cache),
+        );
+        report.set(
+            DiagnosticsReport::CACHE_USAGE,
+            diagnostics::check_cache_usage(&self.dirs.cache),
+        );
+        self.overlay = Some(Overlay::Diagnostics(report));
+        cx.notify();
+
+        let http_client = self.http_client.clone();
+        cx.spawn(
+            |this: WeakView<Self>, mut cx: AsyncWindowContext| async move {
+                let steps = [DiagnosticsReport::HN_API, DiagnosticsReport::READER_FETCH];
+                for step in steps {
+                    let status = if step == DiagnosticsReport::HN_API {
+                        diagnostics::check_hn_api(
+                            http_client.clone(),
+                            diagnostics::HN_TOPSTORIES_URL,
+                        )
+                        .await
+                    } else {
+                        diagnostics::check_reader_fetch(
+                            http_client.clone(),
+                            diagnostics::READER_PROBE_URL,
+                        )
+                        .await
+                    };
+                    let updated =
+                        this.update(&mut cx, |this: &mut Self, cx: &mut ViewContext<Self>| {
+                            if let Some(Overlay::Diagnostics(report)) = this.overlay.as_mut() {
+                                report.set(step, status);
+                                cx.notify();
+                            }
+                        });
+                    if updated.is_err() {
+                        break;
+                    }
+                }
+            },
+        )
+        .detach();
+    }
+
+    fn start_story_list_resize(&mut self, event: &MouseDownEvent, cx: &mut ViewContext<Self>) {
+        if event.click_count >= 2 {
+            self.is_resizing_story_list = false;
+            self.story_list_resets += 1;
+            match self.settings.story_list_reset {
+                SplitterReset::Default => self.story_list_width = STORY_LIST_DEFAULT_WIDTH,
+                SplitterReset::Preferred => {
+                    self.story_list_width = self
+                        .settings
+                        .story_list_preferred_width
+                        .unwrap_or(STORY_LIST_DEFAULT_WIDTH);
+                }
+                SplitterReset::Collapse => self.story_list_hidden = true,
+            }
+            cx.notify();
+            return;
+        }
+
+        self.is_resizing_story_list = true;
+        self.resize_start_x = event.position.x.0;
+        self.resize_start_width = self.story_list_width;
+        cx.notify();
+    }
+
+    fn update_story_list_resize(&mut self, event: &MouseMoveEvent, cx: &mut ViewContext<Self>) {
+        if !self.is_resizing_story_list {
+            return;
+        }
+
+        let delta = event.position.x.0 - self.resize_start_x;
+        self.story_list_width = list_width::clamp_width(
+            self.resize_start_width + delta,
+            Self::available_list_width(cx),
+        );
+        cx.notify();
+    }
+
+    fn stop_story_list_resize(&mut self, _: &MouseUpEvent, cx: &mut ViewContext<Self>) {
+        if self.is_resizing_story_list {
+            self.is_resizing_story_list = false;
+            // 拖动过的宽度记为偏好宽度，下次启动和双击复位时使用
+            if self.story_list_width != self.resize_start_width {
+                self.settings.story_list_preferred_width = Some(self.story_list_width);
+                self.save_settings();
+            }
+            cx.notify();
+        }
+    }
+
+    /// 窗口里去掉侧边栏和分隔条后，列表和详情共用的宽度
+    fn available_list_width(cx: &WindowContext) -> f32 {
+        cx.viewport_size().width.0 - SIDEBAR_WIDTH - SPLITTER_WIDTH
+    }
+
+    /// 快捷键：窄、默认、宽、隐藏之间循环
+    fn cycle_story_list_width(&mut self, cx: &mut ViewContext<Self>) {
+        let current = (!self.story_list_hidden).then_some(self.story_list_width);
+        match list_width::next_width(current, Self::available_list_width(cx)) {
+            Some(width) => {
+                self.story_list_width = width;
+                self.story_list_hidden = false;
+            }
+            None => self.story_list_hidden = true,
+        }
+        cx.notify();
+    }
+
+    fn show_story_list(&mut self, cx: &mut ViewContext<Self>) {
+        self.story_list_hidden = false;
+        cx.notify();
+    }
+}
+
+impl Render for AppState {
+    fn render(&mut self, cx: &mut ViewContext<Self>) -> impl IntoElement {
+        self.check_broken_images(cx);
+        self.sync_comment_list();
+        self.sync_reader_header();
+        let theme = &self.theme;
+
+        div()
+            .size_full()
+            .relative()
+            .flex()
+            .flex_row()
+            .bg(theme.bg_primary)
+            .text_color(theme.text_primary)
+            .font_family(".SystemUIFont")
+            .track_focus(&self.focus_handle)
+            .on_action(cx.listener(|this, action: &SelectChannel, cx| {
+                this.select_channel_at(action.0, cx);
+            }))
+            .on_action(cx.listener(|this, _: &NextChannel, cx| {
+                this.select_next_channel(cx);
+            }))
+            .on_action(cx.listener(|this, _: &TogglePinnedChannel, cx| {
+                this.toggle_pinned_channel(this.selected_channel, cx);
+            }))
+            .on_action(cx.listener(|this, _: &MoveChannelUp, cx| {
+                this.move_selected_channel(-1, cx);
+            }))
+            .on_action(cx.listener(|this, _: &MoveChannelDown, cx| {
+                this.move_selected_channel(1, cx);
+            }))
+            .on_action(cx.listener(|this, _: &FindInComments, cx| {
+                this.start_comment_search(cx);
+            }))
+            .on_action(cx.listener(|this, _: &ToggleThreadCollapse, cx| {
+                this.toggle_thread_under_cursor(cx);
+            }))
+            .on_action(cx.listener(|this, _: &OpenFromClipboard, cx| {
+                this.open_from_clipboard(cx);
+            }))
+            .on_action(cx.listener(|this, _: &ToggleReaderMinimap, cx| {
+                this.toggle_reader_minimap(cx);
+            }))
+            .on_action(cx.listener(|this, _: &ToggleCleanComments, cx| {
+                this.toggle_clean_comment_layout(cx);
+            }))
+            .on_action(cx.listener(|this, _: &ExportTheme, cx| {
+                this.export_theme(cx);
+            }))
+            .on_action(cx.listener(|this, _: &ImportTheme, cx| {
+                this.import_theme(cx);
+            }))
+            .on_action(cx.listener(|this, _: &ExportFeedsOpml, cx| {
+                this.export_feeds_opml(cx);
+            }))
+            .on_action(cx.listener(|this, _: &ShowRawJson, cx| {
+                this.show_raw_json(cx);
+            }))
+            .on_action(cx.listener(|this, _: &CycleStoryListWidth, cx| {
+                this.cycle_story_list_width(cx);
+            }))
+            .on_action(cx.listener(|this, _: &MarkAllStoriesRead, cx| {
+                this.mark_all_stories_read(cx);
+            }))
+            .on_action(cx.listener(|this, _: &ClearReadStories, cx| {
+                this.clear_read_stories(cx);
+            }))
+            .on_action(cx.listener(|this, _: &ClearReaderCache, cx| {
+                this.clear_reader_cache(cx);
+            }))
+            .on_action(cx.listener(|this, _: &RefreshStories, cx| {
+                this.refresh_stories(cx);
+            }))
+            .on_action(cx.listener(|this, _: &ToggleReaderScrollDebug, cx| {
+                this.debug_reader_scroll = !this.debug_reader_scroll;
+                cx.notify();
+            }))
+            .on_mouse_move(cx.listener(Self::update_story_list_resize))
+            .on_mouse_up(MouseButton::Left, cx.listener(Self::stop_story_list_resize))
+            // Sidebar
+            .child(self.render_sidebar(cx))
+            // Story List
+            .when(!self.story_list_hidden, |this| {
+                let list = self.render_story_list(cx);
+                // 复位过才淡入，启动时直接显示
+                if self.story_list_resets == 0 {
+                    this.child(list)
+                } else {
+                    let id = ElementId::NamedInteger("story-list".into(), self.story_list_resets);
+                    this.child(self.fade_in(id, list))
+                }
+            })
+            // Splitter
+            .map(|this| {
+                if self.story_list_hidden {
+                    this.child(self.render_story_list_reveal(cx))
+                } else {
+                    this.child(self.render_story_splitter(cx))
+                }
+            })
+            // Detail Panel
+            .child(self.render_detail_panel(cx))
+            // Overlay
+            .when(self.overlay.is_some(), |this| {
+                this.child(self.render_overlay(cx))
+            })
+            // Toast
+            .when_some(self.toast.clone(), |this, toast| {
+                this.child(self.render_toast(toast))
+            })
+    }
+}
+
+impl AppState {
+    /// 列表头部高度：默认在标题行上方给 traffic lights 留空，紧凑模式下共用一行
+    fn story_list_header_height(&self) -> f32 {
+        if self.settings.compact_header {
+            TITLEBAR_HEIGHT
+        } else {
+            TITLEBAR_HEIGHT + STORY_LIST_TITLE_HEIGHT
+        }
+    }
+
+    /// 紧凑模式下列表标题要让开伸进列表区域的 traffic lights
+    fn story_list_title_inset(&self) -> f32 {
+        if self.settings.compact_header {
+            (TRAFFIC_LIGHTS_RIGHT - SIDEBAR_WIDTH).max(0.)
+        } else {
+            0.
+        }
+    }
+
+    fn render_sidebar(&self, cx: &mut ViewContext<Self>) -> impl IntoElement {
+        let theme = &self.theme;
+        let hover_bg = theme.bg_hover;
+        let order = self.settings.channel_order();
+        let pinned_count = self.settings.pinned_channels().len();
+        // 置顶频道和其余频道之间的分隔线
+        let divider_at = (pinned_count > 0 && pinned_count < order.len()).then_some(pinned_count);
+
+        div()
+            .w(px(SIDEBAR_WIDTH))
+            .h_full()
+            .flex()
+            .flex_col()
+            .items_center()
+            .bg(theme.bg_secondary)
+            .border_r_1()
+            .border_color(theme.border_subtle)
+            // 顶部留空给 traffic lights
+            .child(div().h(px(TITLEBAR_HEIGHT)).w_full().flex_shrink_0())
+            // Channel icons, pinned first; right click pins or unpins
+            .children(order.into_iter().enumerate().flat_map(|(index, channel)| {
+                let selected = channel == self.selected_channel && !self.showing_bookmarks;
+                let divider = (divider_at == Some(index)).then(|| {
+                    div()
+                        .mt_2()
+                        .w(px(28.))
+                        .h(px(1.))
+                        .bg(theme.border)
+                        .into_any_element()
+                });
+                let icon = div()
+                    .id(ElementId::Name(
+                        format!("channel-{}", channel.name()).into(),
+                    ))
+                    .mt_2()
+                    .w(px(40.))
+                    .h(px(40.))
+                    .flex()
+                    .items_center()
+                    .justify_center()
+                    .rounded_lg()
+                    .text_lg()
+                    .font_weight(FontWeight::BOLD)
+                    .map(|this| {
+                        if selected {
+                            this.bg(theme.accent).text_color(hsla(0., 0., 1., 1.0))
+                        } else {
+                            this.cursor_pointer()
+                                .text_color(theme.text_muted)
+                                .hover(move |s| s.bg(hover_bg))
+                        }
+                    })
+                    .on_click(cx.listener(move |this, _event, cx| {
+                        this.select_channel(channel, cx);
+                    }))
+                    .on_mouse_down(
+                        MouseButton::Right,
+                        cx.listener(move |this, _event, cx| {
+                            this.toggle_pinned_channel(channel, cx);
+                        }),
+                    )
+                    .child(channel.icon())
+                    .into_any_element();
+                divider.into_iter().chain([icon])
+            }))
+            // Bookmarks
+            .child(
+                div()
+                    .id("sidebar-bookmarks")
+                    .mt_2()
+                    .w(px(40.))
+                    .h(px(40.))
+                    .flex()
+                    .items_center()
+                    .justify_center()
+                    .rounded_lg()
+                    .text_lg()
+                    .map(|this| {
+                        if self.showing_bookmarks {
+                            this.bg(theme.accent).text_color(hsla(0., 0., 1., 1.0))
+                        } else {
+                            this.cursor_pointer()
+                                .text_color(theme.text_muted)
+                                .hover(move |s| s.bg(hover_bg))
+                        }
+                    })
+                    .on_click(cx.listener(|this, _event, cx| {
+                        this.show_bookmarks(cx);
+                    }))
+                    .child("★"),
+            )
+            // Tools
+            .child(
+                div()
+                    .mt_auto()
+                    .mb_3()
+                    .flex()
+                    .flex_col()
+                    .items_center()
+                    .gap_1()
+                    .text_color(theme.text_muted)
+                    .child(
+                        div()
+                            .id("sidebar-theme")
+                            .w(px(36.))
+                            .h(px(32.))
+                            .flex()
+                            .items_center()
+                            .justify_center()
+                            .rounded_md()
+                            .cursor_pointer()
+                            .hover(move |s| s.bg(hover_bg))
+                            .on_click(cx.listener(|this, _event, cx| {
+                                this.toggle_theme(cx);
+                            }))
+                            .child(if self.is_dark { "☀" } else { "☾" }),
+                    )
+                    .child(
+                        div()
+                            .id("sidebar-about")
+                            .w(px(36.))
+                            .h(px(32.))
+                            .flex()
+                            .items_center()
+                            .justify_center()
+                            .rounded_md()
+                            .cursor_pointer()
+                            .hover(move |s| s.bg(hover_bg))
+                            .on_click(cx.listener(|this, _event, cx| {
+                                this.show_about(cx);
+                            }))
+                            .child("ⓘ"),
+                    )
+                    .child(
+                        div()
+                            .id("sidebar-stats")
+                            .w(px(36.))
+                            .h(px(32.))
+                            .flex()
+                            .items_center()
+                            .justify_center()
+                            .rounded_md()
+                            .cursor_pointer()
+                            .hover(move |s| s.bg(hover_bg))
+                            .on_click(cx.listener(|this, _event, cx| {
+                                this.show_stats(cx);
+                            }))
+                            .child("📊"),
+                    )
+                    .child(
+                        div()
+                            .id("sidebar-diagnostics")
+                            .w(px(36.))
+                            .h(px(32.))
+                            .flex()
+                            .items_center()
+                            .justify_center()
+                            .rounded_md()
+                            .cursor_pointer()
+                            .hover(move |s| s.bg(hover_bg))
+                            .on_click(cx.listener(|this, _event, cx| {
+                                this.run_diagnostics(cx);
+                            }))
+                            .child("🩺"),
+                    ),
+            )
+    }
+
+    fn render_story_list(&self, cx: &mut ViewContext<Self>) -> Div {
+        let theme = &self.theme;
+        let text_primary = theme.text_primary;
+        let accent_hover = theme.accent_hover;
+        let snoozed_count = self.snoozed.active(chrono::Utc::now().timestamp()).len();
+        let compact = self.settings.compact_header;
+        let title_inset = self.story_list_title_inset();
+        let list_title = if self.showing_bookmarks {
+            "Bookmarks"
+        } else {
+            self.selected_channel.name()
+        };
+        // 收藏来自本地文件，频道还在加载也不影响
+        let show_spinner =
+            self.story_search.is_searching || (self.is_loading && !self.showing_bookmarks);
+        let no_bookmarks = self.showing_bookmarks
+            && !self.story_search.is_active()
+            && self.bookmarks.summaries().is_empty();
+
+        div()
+            .track_focus(&self.story_list_focus_handle)
+            .on_key_down(cx.listener(Self::on_story_list_key))
+            .w(px(self.story_list_width))
+            .flex_shrink()
+            .h_full()
+            .flex()
+            .flex_col()
+            .bg(theme.bg_secondary)
+            // Header with titlebar spacing
+            .child(
+                div()
+                    .w_full()
+                    .h(px(self.story_list_header_height()))
+                    .flex()
+                    .flex_col()
+                    .border_b_1()
+                    .border_color(theme.border_subtle)
+                    // Titlebar spacer
+                    .when(!compact, |this| {
+                        this.child(div().h(px(TITLEBAR_HEIGHT)).w_full().flex_shrink_0())
+                    })
+                    // Title
+                    .child(
+                        div()
+                            .flex_1()
+                            .flex()
+                            .items_center()
+                            .justify_between()
+                            .px_4()
+                            .when(title_inset > 0., |this| this.pl(px(16. + title_inset)))
+                            .child(
+                                div()
+                                    .text_base()
+                                    .font_weight(FontWeight::SEMIBOLD)
+                                    .child(list_title),
+                            )
+                            .when(!self.showing_bookmarks, |this| {
+                                this.child(
+                                    div()
+                                        .flex()
+                                        .items_center()
+                                        .gap_3()
+                                        .text_xs()
+                                        .text_color(theme.text_muted)
+                                        .child(
+                                            div()
+                                                .id("story-sort")
+                                                .cursor_pointer()
+                                                .hover(move |s| s.text_color(text_primary))
+                                                .on_click(cx.listener(|this, _event, cx| {
+                                                    this.cycle_story_sort(cx);
+                                                }))
+                                                .child(format!(
+                                                    "{} ▾",
+                                                    self.settings
+                                                        .story_sort(self.selected_channel)
+                                                        .label()
+                                                )),
+                                        )
+                                        .child(
+                                            div()
+                                                .id("refresh-stories")
+                                                .when(!self.is_loading, |this| {
+                                                    this.cursor_pointer()
+                                                        .hover(move |s| s.text_color(text_primary))
+                                                })
+                                                .on_click(cx.listener(|this, _event, cx| {
+                                                    this.refresh_stories(cx);
+                                                }))
+                                                .child("↻"),
+                                        )
+                                        .when(snoozed_count > 0, |this| {
+                                            this.child(
+                                                div()
+                                                    .id("show-snoozed")
+                                                    .cursor_pointer()
+                                                    .hover(move |s| s.text_color(text_primary))
+                                                    .on_click(cx.listener(|this, _event, cx| {
+                                                        this.show_snoozed(cx);
+                                                    }))
+                                                    .child(format!("Snoozed ({})", snoozed_count)),
+                                            )
+                                        })
+                                        .when(!self.stories.is_empty(), |this| {
+                                            this.child(
+                                                div()
+                                                    .id("export-feed")
+                                                    .cursor_pointer()
+                                                    .hover(move |s| s.text_color(text_primary))
+                                                    .on_click(cx.listener(|this, _event, cx| {
+                                                        this.export_feed_snapshot(cx);
+                                                    }))
+                                                    .child("Export"),
+                                            )
+                                        }),
+                                )
+                            }),
+                    ),
+            )
+            // Error message
+            .when_some(self.error_message.clone(), |this, msg| {
+                this.child(
+                    div()
+                        .w_full()
+                        .px_4()
+                        .py_2()
+                        .bg(theme.error)
+                        .text_color(hsla(0., 0., 1., 1.0))
+                        .text_sm()
+                        .child(msg),
+                )
+            })
+            // Custom CA warning
+            .when_some(self.custom_ca.as_ref(), |this, custom_ca| {
+                this.child(
+                    div()
+                        .w_full()
+                        .px_4()
+                        .py_2()
+                        .bg(if custom_ca.is_active() {
+                            theme.warning
+                        } else {
+                            theme.error
+                        })
+                        .text_color(hsla(0., 0., 1., 1.0))
+                        .text_xs()
+                        .child(custom_ca.message()),
+                )
+            })
+            .child(self.render_story_search_box(cx))
+            .when(
+                self.selected_channel == NewsChannel::Feeds && !self.showing_bookmarks,
+                |this| this.child(self.render_feed_manager(cx)),
+            )
+            // Stories：只渲染视口里的行，状态和“加载更多”固定在列表下方
+            .child(
+                div()
+                    .flex_1()
+                    .min_h(px(0.))
+                    .flex()
+                    .flex_col()
+                    .on_scroll_wheel(cx.listener(|this, _event: &ScrollWheelEvent, cx| {
+                        this.maybe_prefetch_stories(cx);
+                        this.mark_scrolled_past_read(cx);
+                    }))
+                    .child(if show_spinner || no_bookmarks {
+                        div()
+                            .flex_1()
+                            .overflow_hidden()
+                            .child(if show_spinner {
+                                self.render_loading_indicator(cx.viewport_size().height.0)
+                                    .into_any_element()
+                            } else {
+                                div()
+                                    .w_full()
+                                    .px_4()
+                                    .py_6()
+                                    .flex()
+                                    .justify_center()
+                                    .text_sm()
+                                    .text_color(theme.text_muted)
+                                    .child("No bookmarks yet. Click ☆ on a story to save it.")
+                                    .into_any_element()
+                            })
+                            .into_any_element()
+                    } else {
+                        uniform_list(
+                            cx.view().clone(),
+                            "story-list",
+                            self.visible_stories().len(),
+                            |this, range, cx| {
+                                let stories = this.visible_stories();
+                                stories[range]
+                                    .iter()
+                                    .map(|story| {
+                                        this.render_story_row(story, cx).into_any_element()
+                                    })
+                                    .collect()
+                            },
+                        )
+                        .flex_1()
+                        .track_scroll(self.story_list_scroll_handle.clone())
+                        .into_any_element()
+                    })
+                    .when_some(
+                        self.story_search
+                            .status()
+                            .filter(|_| self.story_search.is_active()),
+                        |this, status| {
+                            this.child(
+                                div()
+                                    .w_full()
+                                    .px_4()
+                                    .py_6()
+                                    .flex()
+                                    .justify_center()
+                                    .text_sm()
+                                    .text_color(theme.text_muted)
+                                    .child(status),
+                            )
+                        },
+                    )
+                    .when(
+                        !self.is_loading
+                            && !self.story_search.is_active()
+                            && !self.showing_bookmarks
+                            && self.story_pager.is_loading(),
+                        |this| {
+                            this.child(
+                                div()
+                                    .w_full()
+                                    .py_3()
+                                    .flex()
+                                    .justify_center()
+                                    .text_xs()
+                                    .text_color(theme.text_muted)
+                                    .child("Loading more…"),
+                            )
+                        },
+                    )
+                    .when(
+                        !self.is_loading
+                            && !self.story_search.is_active()
+                            && !self.showing_bookmarks
+                            && !self.story_pager.is_loading()
+                            && self.story_pager.has_more(),
+                        |this| {
+                            this.child(
+                                div()
+                                    .id("load-more-stories")
+                                    .w_full()
+                                    .py_3()
+                                    .flex()
+                                    .justify_center()
+                                    .text_xs()
+                                    .cursor_pointer()
+                                    .text_color(theme.accent)
+                                    .hover(move |s| s.text_color(accent_hover))
+                                    .on_click(cx.listener(|this, _event, cx| {
+                                        this.load_more_stories(cx);
+                                    }))
+                                    .child(format!(
+                                        "Load more ({} left)",
+                                        self.story_pager.remaining()
+                                    )),
+                            )
+                        },
+                    ),
+            )
+            .when_some(self.rank_jump.as_ref(), |this, digits| {
+                this.child(
+                    div()
+                        .w_full()
+                        .px_4()
+                        .py_2()
+                        .border_t_1()
+                        .border_color(theme.border_subtle)
+                        .text_xs()
+                        .text_color(theme.text_secondary)
+                        .child(format!("Go to #{}  ↵", digits)),
+                )
+            })
+    }
+
+    fn render_story_splitter(&self, cx: &mut ViewContext<Self>) -> impl IntoElement {
+        let theme = &self.theme;
+        let is_resizing = self.is_resizing_story_list;
+        let divider_color = if is_resizing {
+            theme.border
+        } else {
+            theme.border_subtle
+        };
+
+        div()
+            .id("story-splitter")
+            .w(px(SPLITTER_WIDTH))
+            .h_full()
+            .flex()
+            .flex_row()
+            .cursor_col_resize()
+            .on_mouse_down(
+                MouseButton::Left,
+                cx.listener(Self::start_story_list_resize),
+            )
+            // Left half blends with story list background; right half blends with detail background.
+            .child(div().flex_1().h_full().bg(theme.bg_secondary))
+            .child(div().w(px(1.)).h_full().bg(divider_color))
+            .child(div().flex_1().h_full().bg(theme.bg_primary))
+    }
+
+    /// 列表隐藏时代替分隔条的窄条，点击后重新显示列表
+    fn render_story_list_reveal(&self, cx: &mut ViewContext<Self>) -> impl IntoElement {
+        let theme = &self.theme;
+        let hover_bg = theme.bg_hover;
+
+        div()
+            .id("story-list-reveal")
+            .w(px(SPLITTER_WIDTH))
+            .h_full()
+            .flex()
+            .items_center()
+            .justify_center()
+            .cursor_pointer()
+            .bg(theme.bg_secondary)
+            .border_r_1()
+            .border_color(theme.border_subtle)
+            .text_xs()
+            .text_color(theme.text_muted)
+            .hover(move |style| style.bg(hover_bg))
+            .on_click(cx.listener(|this, _, cx| this.show_story_list(cx)))
+            .child("›")
+    }
+
+    fn render_overlay(&self, cx: &mut ViewContext<Self>) -> impl IntoElement {
+        let theme = &self.theme;
+        let text_primary = theme.text_primary;
+
+        let (title, body) = match self.overlay.as_ref() {
+            Some(Overlay::Diagnostics(report)) => (
+                "Diagnostics",
+                self.render_diagnostics(report).into_any_element(),
+            ),
+            Some(Overlay::About(info)) => (
+                "About OneApp",
+                self.render_about(info, cx).into_any_element(),
+            ),
+            Some(Overlay::Snoozed) => (
+                "Snoozed Stories",
+                self.render_snoozed(cx).into_any_element(),
+            ),
+            Some(Overlay::Stats) => ("Reading Stats", self.render_stats().into_any_element()),
+            Some(Overlay::RawJson { item_id, json }) => (
+                "Raw JSON",
+                self.render_raw_json(*item_id, json.as_ref(), cx)
+                    .into_any_element(),
+            ),
+            None => ("", div().into_any_element()),
+        };
+
+        div()
+            .id("overlay-backdrop")
+            .absolute()
+            .top_0()
+            .left_0()
+            .size_full()
+            .flex()
+            .items_center()
+            .justify_center()
+            .bg(hsla(0., 0., 0., 0.35))
+            .on_click(cx.listener(|this, _event, cx| {
+                this.close_overlay(cx);
+            }))
+            .child(
+                div()
+                    .id("overlay-panel")
+                    .w(px(520.))
+                    .max_h(px(560.))
+                    .flex()
+                    .flex_col()
+                    .bg(theme.bg_primary)
+                    .rounded_xl()
+                    .border_1()
+                    .border_color(theme.border)
+                    .shadow_lg()
+                    .overflow_hidden()
+                    // 阻止点击面板内部时关闭
+                    .on_click(|_event, cx| cx.stop_propagation())
+                    .child(
+                        div()
+                            .w_full()
+                            .px_5()
+                            .py_3()
+                            .flex()
+                            .items_center()
+                            .justify_between()
+                            .border_b_1()
+                            .border_color(theme.border_subtle)
+                            .child(
+                                div()
+                                    .text_base()
+                                    .font_weight(FontWeight::SEMIBOLD)
+                                    .child(title),
+                            )
+                            .child(
+                                div()
+                                    .id("overlay-close")
+                                    .cursor_pointer()
+                                    .text_color(theme.text_muted)
+                                    .hover(move |s| s.text_color(text_primary))
+                                    .on_click(cx.listener(|this, _event, cx| {
+                                        this.close_overlay(cx);
+                                    }))
+                                    .child("✕"),
+                            ),
+                    )
+                    .child(
+                        div()
+                            .id("overlay-body")
+                            .flex_1()
+                            .min_h(px(0.))
+                            .overflow_y_scroll()
+                            .p_5()
+                            .child(body),
+                    ),
+            )
+    }
+
+    fn render_about(
+        &self,
+        info: &about::AboutInfo,
+        cx: &mut ViewContext<Self>,
+    ) -> impl IntoElement {
+        let theme = &self.theme;
+        let accent = theme.accent;
+        let accent_hover = theme.accent_hover;
+        let cache_dir = info.cache_dir.clone();
+
+        div()
+            .w_full()
+            .flex()
+            .flex_col()
+            .gap_3()
+            .children(info.rows().into_iter().map(|(label, value)| {
+                div()
+                    .w_full()
+                    .flex()
+                    .flex_col()
+                    .child(div().text_xs().text_color(theme.text_muted).child(label))
+                    .child(div().text_sm().whitespace_normal().child(value))
+            }))
+            .when_some(cache_dir, |this, dir| {
+                this.child(
+                    div()
+                        .id("about-reveal-cache")
+                        .mt_2()
+                        .cursor_pointer()
+                        .text_sm()
+                        .text_color(accent)
+                        .hover(move |s| s.text_color(accent_hover))
+                        .on_click(move |_event, _cx| {
+                            let _ = std::fs::create_dir_all(&dir);
+                            let _ = open::that(&dir);
+                        })
+                        .child("Reveal cache folder ↗"),
+                )
+            })
+            .when(info.cache_entries > 0, |this| {
+                this.child(
+                    div()
+                        .id("about-clear-reader-cache")
+                        .cursor_pointer()
+                        .text_sm()
+                        .text_color(accent)
+                        .hover(move |s| s.text_color(accent_hover))
+                        .on_click(cx.listener(|this, _event, cx| {
+                            this.clear_reader_cache(cx);
+                        }))
+                        .child("Clear reader cache"),
+                )
+            })
+    }
+
+    fn render_raw_json(
+        &self,
+        item_id: i64,
+        json: Option<&Result<String, String>>,
+        cx: &mut ViewContext<Self>,
+    ) -> impl IntoElement {
+        let theme = &self.theme;
+        let accent = theme.accent;
+        let accent_hover = theme.accent_hover;
+        let link = |id: &'static str, label: &'static str| {
+            div()
+                .id(id)
+                .cursor_pointer()
+                .text_sm()
+                .text_color(accent)
+                .hover(move |s| s.text_color(accent_hover))
+                .child(label)
+        };
+
+        let body = match json {
+            None => div()
+                .text_sm()
+                .text_color(theme.text_muted)
+                .child("Loading…"),
+            Some(Err(e)) => div().text_sm().text_color(theme.error).child(e.clone()),
+            Some(Ok(json)) => div()
+                .w_full()
+                .p_3()
+                .rounded_md()
+                .bg(theme.bg_secondary)
+                .font_family("Menlo")
+                .text_xs()
+                .whitespace_normal()
+                .child(json.clone()),
+        };
+        let copy = json.and_then(|json| json.as_ref().ok()).cloned();
+
+        div()
+            .w_full()
+            .flex()
+            .flex_col()
+            .gap_3()
+            .child(
+                div()
+                    .w_full()
+                    .flex()
+                    .items_center()
+                    .gap_4()
+                    .child(
+                        div()
+                            .flex_1()
+                            .text_xs()
+                            .text_color(theme.text_muted)
+                            .child(format!("item {}", item_id)),
+                    )
+                    .when_some(copy, |this, json| {
+                        this.child(link("raw-json-copy", "Copy").on_click(cx.listener(
+                            move |this, _event, cx| {
+                                cx.write_to_clipboard(ClipboardItem::new_string(json.clone()));
+                                this.show_toast("JSON copied", cx);
+                            },
+                        )))
+                    })
+                    .child(link("raw-json-open", "Open in browser ↗").on_click(
+                        move |_event, _cx| {
+                            let _ = open::that(api::item_api_url(item_id));
+                        },
+                    )),
+            )
+            .child(body)
+    }
+
+    fn render_stats(&self) -> impl IntoElement {
+        let theme = &self.theme;
+        let summary = self
+            .reading_stats
+            .summary(chrono::Local::now().date_naive());
+        let rows = [
+            ("Articles read today", summary.articles_today.to_string()),
+            (
+                "Articles read this week",
+                summary.articles_this_week.to_string(),
+            ),
+            (
+                "Total reading time",
+                format!("{} min", summary.reading_minutes),
+            ),
+            ("Stories viewed", summary.stories_viewed.to_string()),
+            ("Comments viewed", summary.comments_viewed.to_string()),
+        ];
+
+        div()
+            .w_full()
+            .flex()
+            .flex_col()
+            .gap_3()
+            .children(rows.into_iter().map(|(label, value)| {
+                div()
+                    .w_full()
+                    .flex()
+                    .justify_between()
+                    .text_sm()
+                    .child(div().text_color(theme.text_muted).child(label))
+                    .child(div().font_weight(FontWeight::SEMIBOLD).child(value))
+            }))
+            .when(!summary.top_domains.is_empty(), |this| {
+                this.child(
+                    div()
+                        .mt_2()
+                        .text_xs()
+                        .text_color(theme.text_muted)
+                        .child("Most-read sites"),
+                )
+                .children(summary.top_domains.iter().map(|(domain, count)| {
+                    div()
+                        .w_full()
+                        .flex()
+                        .justify_between()
+                        .text_sm()
+                        .child(domain.clone())
+                        .child(div().text_color(theme.text_muted).child(count.to_string()))
+                }))
+            })
+    }
+
+    fn render_snoozed(&self, cx: &mut ViewContext<Self>) -> impl IntoElement {
+        let theme = &self.theme;
+        let accent = theme.accent;
+        let accent_hover = theme.accent_hover;
+        let now = chrono::Utc::now().timestamp();
+
+        div()
+            .w_full()
+            .flex()
+            .flex_col()
+            .gap_3()
+            .children(
+                self.snoozed
+                    .active(now)
+                    .into_iter()
+                    .map(|(story_id, entry)| {
+                        let until = chrono::DateTime::from_timestamp(entry.until, 0)
+                            .map(|t| {
+                                t.with_timezone(&chrono::Local)
+                                    .format("%a %H:%M")
+                                    .to_string()
+                            })
+                            .unwrap_or_default();
+                        div()
+                            .w_full()
+                            .flex()
+                            .items_center()
+                            .gap_3()
+                            .child(
+                                div()
+                                    .flex_1()
+                                    .min_w(px(0.))
+                                    .flex()
+                                    .flex_col()
+                                    .child(
+                                        div()
+                                            .text_sm()
+                                            .whitespace_normal()
+                                            .child(entry.title.clone()),
+                                    )
+                                    .child(
+                                        div()
+                                            .text_xs()
+                                            .text_color(theme.text_muted)
+                                            .child(format!("Returns {}", until)),
+                                    ),
+                            )
+                            .child(
+                                div()
+                                    .id(ElementId::Name(format!("unsnooze-{}", story_id).into()))
+                                    .flex_shrink_0()
+                                    .cursor_pointer()
+                                    .text_sm()
+                                    .text_color(accent)
+                                    .hover(move |s| s.text_color(accent_hover))
+                                    .on_click(cx.listener(move |this, _event, cx| {
+                                        this.unsnooze_story(story_id, cx);
+                                    }))
+                                    .child("Unsnooze"),
+                            )
+                    }),
+            )
+    }
+
+    fn render_diagnostics(&self, report: &diagnostics::DiagnosticsReport) -> impl IntoElement {
+        use diagnostics::CheckStatus;
+        let theme = &self.theme;
+
+        div()
+            .w_full()
+            .flex()
+            .flex_col()
+            .gap_3()
+            .children(report.checks.iter().map(|check| {
+                let (icon, color, detail) = match &check.status {
+                    CheckStatus::Pending => ("…", theme.text_muted, "Running…".to_string()),
+                    CheckStatus::Pass(detail) => ("✓", theme.success, detail.clone()),
+                    CheckStatus::Fail(detail) => ("✗", theme.error, detail.clone()),
+                };
+                div()
+                    .w_full()
+                    .flex()
+                    .items_start()
+                    .gap_3()
+                    .child(div().w(px(16.)).text_color(color).child(icon))
+                    .child(
+                        div()
+                            .flex_1()
+                            .min_w(px(0.))
+                            .flex()
+                            .flex_col()
+                            .child(div().text_sm().child(check.name))
+                            .child(
+                                div()
+                                    .text_xs()
+                                    .text_color(theme.text_muted)
+                                    .whitespace_normal()
+                                    .child(detail),
+                            ),
+                    )
+            }))
+    }
+
+    fn render_toast(&self, message: String) -> impl IntoElement {
+        let theme = &self.theme;
+
+        div()
+            .absolute()
+            .bottom_4()
+            .right_4()
+            .max_w(px(420.))
+            .px_4()
+            .py_2()
+            .rounded_md()
+            .bg(theme.text_primary)
+            .text_color(theme.bg_primary)
+            .text_sm()
+            .shadow_md()
+            .whitespace_normal()
+            .child(message)
+    }
+
+    fn render_loading_indicator(&self, viewport_height: f32) -> impl IntoElement {
+        let theme = &self.theme;
+
+        let skeleton_bar = |max_w: f32, h: f32| {
+            div()
+                .h(px(h))
+                .w_full()
+                .max_w(px(max_w))
+                .rounded(px(3.))
+                .bg(theme.bg_tertiary)
+        };
+
+        let count = skeleton::skeleton_count(viewport_height, skeleton::STORY_ROWS);
+        let placeholders: Vec<_> = (0..count)
+            .map(|i| {
+                let title_max_w = match i % 3 {
+                    0 => 280.0,
+                    1 => 240.0,
+                    _ => 200.0,
+                };
+
+                div()
+                    .w_full()
+                    .px_4()
+                    .py_3()
+                    .border_b_1()
+                    .border_color(theme.border_subtle)
+                    .child(
+                        div()
+                            .w_full()
+                            .flex()
+                            .flex_col()
+                            .gap_2()
+                            .child(skeleton_bar(title_max_w, 14.0))
+                            .child(div().w_full().flex().gap_2().children(vec![
+                                skeleton_bar(96.0, 10.0).into_any_element(),
+                                skeleton_bar(72.0, 10.0).into_any_element(),
+                                skeleton_bar(56.0, 10.0).into_any_element(),
+                            ])),
+                    )
+                    .into_any_element()
+            })
+            .collect();
+
+        div()
+            .w_full()
+            .h_full()
+            .flex()
+            .flex_col()
+            .child(
+                div()
+                    .w_full()
+                    .px_4()
+                    .py_4()
+                    .flex()
+                    .items_center()
+                    .gap_2()
+                    .text_color(theme.text_muted)
+                    .child("⏳")
+                    .child("Loading stories…"),
+            )
+            .child(self.pulse_skeleton(
+                "story-list-skeleton",
+                div().w_full().flex().flex_col().children(placeholders),
+            ))
+    }
+
+    /// 状态切换后的淡入，`id` 变了就重新播放；减少动态效果时直接显示最终状态
+    fn fade_in<E>(&self, id: impl Into<ElementId>, element: E) -> AnyElement
+    where
+        E: IntoElement + Styled + 'static,
+    {
+        match motion::motion(self.reduce_motion, STATE_TRANSITION) {
+            Motion::Animated(duration) => element
+                .with_animation(id, Animation::new(duration), |element, delta| {
+                    element.opacity(delta)
+                })
+                .into_any_element(),
+            Motion::Instant => element.into_any_element(),
+        }
+    }
+
+    /// 骨架屏的呼吸效果；减少动态效果时保持静止
+    fn pulse_skeleton(&self, id: &'static str, skeleton: Div) -> AnyElement {
+        match motion::motion(self.reduce_motion, SKELETON_PULSE) {
+            Motion::Animated(duration) => skeleton
+                .with_animation(
+                    id,
+                    Animation::new(duration)
+                        .repeat()
+                        .with_easing(pulsating_between(0.5, 1.0)),
+                    |skeleton, delta| skeleton.opacity(delta),
+                )
+                .into_any_element(),
+            Motion::Instant => skeleton.into_any_element(),
+        }
+    }
+
+    fn render_story_row(
+        &self,
+        story: &StorySummary,
+        cx: &mut ViewContext<Self>,
+    ) -> impl IntoElement {
+        let theme = &self.theme;
+        let is_selected = self.selected_story_id == Some(story.id);
+
+        let bg_color = if is_selected {
+            theme.bg_selected
+        } else {
+            theme.bg_secondary
+        };
+
+        let story_id = story.id;
+        let title = story.title.clone();
+        let score = story.score;
+        let by = story.by.clone();
+        let domain = story.domain();
+        let time = story.time;
+        let comment_count = story.comment_count();
+        let hover_bg = theme.bg_hover;
+        let accent = theme.accent;
+        let text_muted = theme.text_muted;
+        let text_secondary = theme.text_secondary;
+        let border_subtle = theme.border_subtle;
+        let title_color = if self.read_stories.contains(&story_id) && !is_selected {
+            theme.text_muted
+        } else {
+            theme.text_primary
+        };
+        let bookmarked = self.bookmarks.contains(story_id);
+        let is_new = self.new_story_ids.contains(&story_id);
+        let group = format!("story-{}", story_id);
+
+        div()
+            .id(ElementId::Name(format!("story-{}", story_id).into()))
+            .group(group.clone())
+            .relative()
+            .w_full()
+            .px_4()
+            .py_3()
+            .cursor_pointer()
+            .bg(bg_color)
+            .hover(move |s| s.bg(hover_bg))
+            .border_b_1()
+            .border_color(border_subtle)
+            .on_click(cx.listener(move |this, _event, cx| {
+                this.select_story(story_id, cx);
+            }))
+            .on_hover(cx.listener(move |this, hovered: &bool, cx| {
+                this.on_story_hovered(story_id, *hovered, cx);
+            }))
+            // 自动刷新新出现的 story：左侧的强调色竖条
+            .when(is_new, |this| {
+                this.child(
+                    div()
+                        .absolute()
+                        .top_0()
+                        .bottom_0()
+                        .left_0()
+                        .w(px(3.))
+                        .bg(accent),
+                )
+            })
+            .child(
+                div()
+                    .w_full()
+                    .overflow_hidden()
+                    .flex()
+                    .flex_col()
+                    .gap_1()
+                    // Title：列表是 uniform_list，每行必须一样高，标题只占一行
+                    .child(
+                        div()
+                            .w_full()
+                            .text_sm()
+                            .font_weight(FontWeight::MEDIUM)
+                            .line_height(rems(1.4))
+                            .whitespace_nowrap()
+                            .overflow_hidden()
+                            .text_ellipsis()
+                            .pr_5()
+                            .text_color(title_color)
+                            .child(title),
+                    )
+                    // Meta row
+                    .child(self.render_story_meta(
+                        score,
+                        domain,
+                        &by,
+                        time,
+                        comment_count,
+                        accent,
+                        text_muted,
+                        text_secondary,
+                    )),
+            )
+            // 收藏星标：还没收藏时悬停才显示
+            .child(
+                div()
+                    .id(ElementId::Name(format!("bookmark-{}", story_id).into()))
+                    .absolute()
+                    .top_3()
+                    .right_3()
+                    .text_sm()
+                    .text_color(if bookmarked { accent } else { text_muted })
+                    .hover(move |s| s.text_color(accent))
+                    .when(!bookmarked, |this| {
+                        this.opacity(0.).group_hover(group, |s| s.opacity(1.))
+                    })
+                    .on_click(cx.listener(move |this, _event, cx| {
+                        cx.stop_propagation();
+                        this.toggle_bookmark(story_id, cx);
+                    }))
+                    .child(if bookmarked { "★" } else { "☆" }),
+            )
+            .children(self.render_comment_preview(story_id))
+    }
+
+    fn on_story_hovered(&mut self, story_id: i64, hovered: bool, cx: &mut ViewContext<Self>) {
+        if !self.settings.comment_preview_on_hover {
+            return;
+        }
+        if !hovered {
+            if self.hovered_story == Some(story_id) {
+                self.hovered_story = None;
+            }
+            if self.previewed_story == Some(story_id) {
+                self.previewed_story = None;
+                cx.notify();
+            }
+            return;
+        }
+
+        self.hovered_story = Some(story_id);
+        cx.spawn(
+            |this: WeakView<Self>, mut cx: AsyncWindowContext| async move {
+                cx.background_executor()
+                    .timer(previews::PREVIEW_HOVER_DELAY)
+                    .await;
+                let _ = this.update(&mut cx, |this: &mut Self, cx: &mut ViewContext<Self>| {
+                    // 延迟期间鼠标已经移走就不显示也不请求
+                    if this.hovered_story == Some(story_id) {
+                        this.previewed_story = Some(story_id);
+                        this.load_comment_preview(story_id, cx);
+                        cx.notify();
+                    }
+                });
+            },
+        )
+        .detach();
+    }
+
+    fn load_comment_preview(&mut self, story_id: i64, cx: &mut ViewContext<Self>) {
+        if !self.comment_previews.begin(story_id) {
+            return;
+        }
+
+        let client = self.source_for(self.listed_story_channel(story_id));
+        cx.spawn(
+            |this: WeakView<Self>, mut cx: AsyncWindowContext| async move {
+                let result = client
+                    .fetch_comment_preview(story_id, previews::PREVIEW_COMMENT_COUNT)
+                    .await;
+                let _ = this.update(&mut cx, |this: &mut Self, cx: &mut ViewContext<Self>| {
+                    this.comment_previews.finish(story_id, result);
+                    cx.notify();
+                });
+            },
+        )
+        .detach();
+    }
+
+    /// 为评论里直接贴出的链接请求标题预览，每个 story 有上限
+    fn load_comment_unfurls(&mut self, cx: &mut ViewContext<Self>) {
+        if !self.settings.comment_link_unfurls {
+            return;
+        }
+
+        let urls = self
+            .comments
+            .iter()
+            .flat_map(|comment| {
+                comment
+                    .bare_links()
+                    .into_iter()
+                    .take(unfurls::UNFURLS_PER_COMMENT)
+            })
+            .filter(|url| unfurls::should_unfurl(url))
+            .take(unfurls::UNFURLS_PER_STORY)
+            .collect::<Vec<_>>();
+        for url in urls {
+            if !self.unfurls.begin(&url) {
+                continue;
+            }
+            let http_client = self.http_client.clone();
+            cx.spawn(
+                |this: WeakView<Self>, mut cx: AsyncWindowContext| async move {
+                    let result = unfurls::fetch_unfurl(http_client, &url).await;
+                    let _ = this.update(&mut cx, |this: &mut Self, cx: &mut ViewContext<Self>| {
+                        this.unfurls.finish(&url, result);
+                        cx.notify();
+                    });
+                },
+            )
+            .detach();
+        }
+    }
+
+    /// 评论正文下方的链接预览卡片，点击用阅读模式打开
+    fn render_comment_unfurls(
+        &self,
+        comment: &Comment,
+        cx: &mut ViewContext<Self>,
+    ) -> Option<AnyElement> {
+        if !self.settings.comment_link_unfurls {
+            return None;
+        }
+        let cards = comment
+            .bare_links()
+            .iter()
+            .take(unfurls::UNFURLS_PER_COMMENT)
+            .filter_map(|url| self.unfurls.ready(url).cloned())
+            .collect::<Vec<_>>();
+        if cards.is_empty() {
+            return None;
+        }
+
+        let theme = &self.theme;
+        let hover_bg = theme.bg_hover;
+        let comment_id = comment.id;
+        Some(
+            div()
+                .flex()
+                .flex_col()
+                .gap_1()
+                .pt_1()
+                .children(cards.into_iter().enumerate().map(|(index, unfurl)| {
+                    let domain = match &unfurl.site_name {
+                        Some(site) if !site.eq_ignore_ascii_case(&unfurl.domain) => {
+                            format!("{} · {}", site, unfurl.domain)
+                        }
+                        _ => unfurl.domain.clone(),
+                    };
+                    let title = unfurl.title.clone();
+                    let url = unfurl.url.clone();
+                    div()
+                        .id(ElementId::Name(
+                            format!("comment-unfurl-{}-{}", comment_id, index).into(),
+                        ))
+                        .max_w(px(420.))
+                        .px_3()
+                        .py_2()
+                        .flex()
+                        .flex_col()
+                        .rounded_md()
+                        .border_1()
+                        .border_color(theme.border_subtle)
+                        .bg(theme.bg_secondary)
+                        .cursor_pointer()
+                        .hover(move |s| s.bg(hover_bg))
+                        .on_click(cx.listener(move |this, _event, cx| {
+                            this.open_reader(url.clone(), title.clone(), cx);
+                        }))
+                        .when_some(unfurl.title, |this, title| {
+                            this.child(
+                                div()
+                                    .text_sm()
+                                    .font_weight(FontWeight::MEDIUM)
+                                    .text_color(theme.text_primary)
+                                    .whitespace_normal()
+                                    .child(title),
+                            )
+                        })
+                        .child(div().text_xs().text_color(theme.text_muted).child(domain))
+                }))
+                .into_any_element(),
+        )
+    }
+
+    /// 悬停列表行时显示的前几条评论，浮在下一行之上
+    fn render_comment_preview(&self, story_id: i64) -> Option<AnyElement> {
+        if !self.settings.comment_preview_on_hover || self.previewed_story != Some(story_id) {
+            return None;
+        }
+        let preview = self.comment_previews.get(story_id)?;
+        let theme = &self.theme;
+
+        let status = |message: &str| {
+            div()
+                .text_xs()
+                .text_color(theme.text_muted)
+                .child(message.to_string())
+                .into_any_element()
+        };
+        let body = match preview {
+            CommentPreview::Loading => vec![status("Loading comments…")],
+            CommentPreview::Failed(_) => vec![status("Couldn't load comments")],
+            CommentPreview::Ready(comments) if comments.is_empty() => {
+                vec![status("No comments yet")]
+            }
+            CommentPreview::Ready(comments) => comments
+                .iter()
+                .map(|comment| {
+                    div()
+                        .flex()
+                        .flex_col()
+                        .gap_1()
+                        .child(
+                            div()
+                                .text_xs()
+                                .font_weight(FontWeight::SEMIBOLD)
+                                .text_color(theme.accent)
+                                .child(comment.author.clone()),
+                        )
+                        .child(
+                            div()
+                                .text_xs()
+                                .line_height(rems(1.4))
+                                .text_color(theme.text_secondary)
+                                .whitespace_normal()
+                                .child(comment.text.clone()),
+                        )
+                        .into_any_element()
+                })
+                .collect(),
+        };
+
+        Some(
+            deferred(
+                div()
+                    .absolute()
+                    .top(relative(1.))
+                    .left_4()
+                    .right_4()
+                    .mt_1()
+                    .p_3()
+                    .flex()
+                    .flex_col()
+                    .gap_3()
+                    .bg(theme.bg_primary)
+                    .border_1()
+                    .border_color(theme.border)
+                    .rounded_md()
+                    .shadow_lg()
+                    .children(body),
+            )
+            .with_priority(1)
+            .into_any_element(),
+        )
+    }
+
+    /// 相对时间，悬停时显示本地时区的完整时间；id 在各自的行或评论里唯一
+    fn render_timestamp(&self, timestamp: i64, color: Hsla) -> impl IntoElement {
+        div()
+            .id("timestamp")
+            .text_color(color)
+            .tooltip(reader_view::text_tooltip(
+                &self.theme,
+                models::format_absolute_time(timestamp),
+            ))
+            .child(models::format_relative_time(timestamp))
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    fn render_story_meta(
+        &self,
+        score: i32,
+        domain: Option<String>,
+        by: &str,
+        time: i64,
+        comment_count: i32,
+        accent: Hsla,
+        text_muted: Hsla,
+        text_secondary: Hsla,
+    ) -> impl IntoElement {
+        div()
+            .min_w(px(0.))
+            .flex()
             .flex_row()
+            .items_center()
+            .overflow_hidden()
+            .whitespace_nowrap()
+            .gap_3()
+            .text_xs()
+            .text_color(text_muted)
+            // Score
+            .child(
+                div()
+                    .flex()
+                    .items_center()
+                    .gap_1()
+                    .text_color(accent)
+                    .child("▲")
+                    .child(score.to_string()),
+            )
+            // Domain
+            .when_some(domain, |this, domain| {
+                this.child(div().text_color(text_secondary).child(domain))
+            })
+            // Author
+            .child(format!("by {}", by))
+            // Time
+            .child(self.render_timestamp(time, text_muted))
+            // Comments
+            .when(comment_count > 0, |this| {
+                this.child(
+                    div()
+                        .flex()
+                        .items_center()
+                        .gap_1()
+                        .child("💬")
+                        .child(comment_count.to_string()),
+                )
+            })
+    }
+
+    fn render_detail_panel(&self, cx: &mut ViewContext<Self>) -> impl IntoElement {
+        let theme = &self.theme;
+
+        div()
+            .flex_1()
+            .min_w(px(0.))
+            .h_full()
+            .flex()
+            .flex_col()
             .bg(theme.bg_primary)
-            .text_color(theme.text_primary)
-            .font_family(".SystemUIFont")
-            .track_focus(&self.focus_handle)
-            .on_mouse_move(cx.listener(Self::update_story_list_resize))
-            .on_mouse_up(MouseButton::Left, cx.listener(Self::stop_story_list_resize))
-            // Sidebar
-            .child(self.render_sidebar())
-            // Story List
-            .child(self.render_story_list(cx))
-            // Splitter
-            .child(self.render_story_splitter(cx))
-            // Detail Panel
-            .child(self.render_detail_panel(cx))
+            .overflow_hidden()
+            // Titlebar spacer；紧凑模式下由下面的顶栏直接占据这一行
+            .when(!self.settings.compact_header, |this| {
+                this.child(div().h(px(TITLEBAR_HEIGHT)).w_full().flex_shrink_0())
+            })
+            .child(
+                if let Some(story) = self
+                    .selected_story()
+                    .filter(|_| self.settings.split_view_on_select)
+                {
+                    self.render_split_view(story, cx).into_any_element()
+                } else if let Some(reader) = self.reader() {
+                    self.render_reader_page(reader, cx).into_any_element()
+                } else if self.selected_story().is_some() {
+                    self.render_story_detail().into_any_element()
+                } else {
+                    self.render_empty_state().into_any_element()
+                },
+            )
+    }
+
+    /// 分栏模式：左侧文章（或 HN 正文），右侧评论，两边的加载状态互不影响
+    fn render_split_view(&self, story: &Story, cx: &mut ViewContext<Self>) -> impl IntoElement {
+        let theme = &self.theme;
+
+        let article_pane = if let Some(reader) = self.reader() {
+            self.render_reader_page(reader, cx).into_any_element()
+        } else {
+            let story_text = story.text.clone();
+            let text_primary = theme.text_primary;
+            div()
+                .id("split-story-text")
+                .flex_1()
+                .min_h(px(0.))
+                .w_full()
+                .flex()
+                .flex_col()
+                .overflow_y_scroll()
+                .child(self.render_story_header(story, cx))
+                .when_some(story_text, move |this, text| {
+                    let clean_text = html_escape::decode_html_entities(&text).to_string();
+                    this.child(
+                        div()
+                            .w_full()
+                            .p_6()
+                            .text_sm()
+                            .line_height(rems(1.6))
+                            .text_color(text_primary)
+                            .whitespace_normal()
+                            .child(clean_text),
+                    )
+                })
+                .into_any_element()
+        };
+
+        div()
+            .flex_1()
+            .min_h(px(0.))
+            .w_full()
+            .min_w(px(0.))
+            .flex()
+            .flex_row()
+            .overflow_hidden()
+            .child(
+                div()
+                    .flex_1()
+                    .min_w(px(0.))
+                    .h_full()
+                    .flex()
+                    .flex_col()
+                    .overflow_hidden()
+                    .child(article_pane),
+            )
+            .child(
+                div()
+                    .id("split-comments")
+                    .w(relative(0.4))
+                    .min_w(px(280.))
+                    .h_full()
+                    .flex_shrink_0()
+                    .border_l_1()
+                    .border_color(theme.border)
+                    .flex()
+                    .flex_col()
+                    .child(list(self.comment_list.clone()).size_full()),
+            )
+    }
+
+    fn render_empty_state(&self) -> impl IntoElement {
+        let theme = &self.theme;
+
+        div()
+            .size_full()
+            .flex()
+            .items_center()
+            .justify_center()
+            .text_color(theme.text_muted)
+            .child("Select a story to read")
+    }
+
+    /// 按设置的默认来源打开阅读模式，该来源不可用时退回另一个
+    fn run_story_action(&mut self, story: &Story, action: StoryAction, cx: &mut ViewContext<Self>) {
+        match action {
+            StoryAction::Read(source) => self.open_story_reader(story, source, cx),
+            StoryAction::Discuss => self.open_inline_link(&story.item_url(), cx),
+        }
+    }
+
+    fn open_story_reader(
+        &mut self,
+        story: &Story,
+        source: ReaderSource,
+        cx: &mut ViewContext<Self>,
+    ) {
+        match source {
+            ReaderSource::Link => {
+                if let Some(url) = story.url.clone() {
+                    self.open_reader(url, Some(story.title.clone()), cx);
+                }
+            }
+            ReaderSource::SelfText => {
+                let Some(text) = story.text.as_deref() else {
+                    return;
+                };
+                let url = story.item_url();
+                if let Some(index) = self.reader_tabs.position(&url) {
+                    self.activate_reader_tab(index, cx);
+                    return;
+                }
+                let article = url::Url::parse(&url).map(|item_url| {
+                    reader::self_text_article(text, &story.title, &story.by, &item_url)
+                });
+                let state = match article {
+                    Ok(article) => {
+                        self.record_article_read(&url, &article);
+                        ReaderLoadState::Ready(article)
+                    }
+                    Err(e) => ReaderLoadState::Error(e.to_string()),
+                };
+                self.save_reader_scroll();
+                self.reset_reader_view(cx);
+                self.reader_tabs
+                    .open(ReaderSession::new(url, Some(story.title.clone()), state));
+                cx.notify();
+            }
+        }
+    }
+
+    fn reader(&self) -> Option<&ReaderSession> {
+        self.reader_tabs.active()
+    }
+
+    /// 切换标签或离开阅读器前记下当前标签的滚动位置
+    fn save_reader_scroll(&mut self) {
+        let offset = self.reader_scroll_handle.offset();
+        if let Some(tab) = self.reader_tabs.active_mut() {
+            tab.scroll_y = -offset.y.0;
+            if matches!(tab.state, ReaderLoadState::Ready(_)) {
+                self.reader_scroll_positions.insert(tab.url.clone(), offset);
+            }
+        }
+    }
+
+    fn activate_reader_tab(&mut self, index: usize, cx: &mut ViewContext<Self>) {
+        self.save_reader_scroll();
+        if self.reader_tabs.activate(index) {
+            self.restore_reader_scroll();
+        }
+        cx.notify();
+    }
+
+    fn close_reader_tab(&mut self, index: usize, cx: &mut ViewContext<Self>) {
+        let was_active = self.reader_tabs.active_index() == Some(index);
+        self.save_reader_scroll();
+        if let Some(tab) = self.reader_tabs.tabs().get(index) {
+            if matches!(tab.state, ReaderLoadState::Ready(_)) {
+                self.reader_scroll_positions
+                    .insert(tab.url.clone(), point(px(0.), px(-tab.scroll_y)));
+            }
+        }
+        self.reader_tabs.close(index);
+        if was_active {
+            if self.reader_tabs.active_index().is_some() {
+                self.restore_reader_scroll();
+            } else {
+                self.close_reader(cx);
+            }
+        }
+        cx.notify();
+    }
+
+    fn restore_reader_scroll(&mut self) {
+        let scroll_y = self.reader().map_or(0.0, |tab| tab.scroll_y);
+        self.reader_scroll_handle
+            .set_offset(point(px(0.), px(-scroll_y)));
+        self.reader_header_collapsed = false;
+        self.reader_last_scroll_y = scroll_y;
+        self.reader_image_menu = None;
+    }
+
+    fn reset_reader_view(&mut self, cx: &mut ViewContext<Self>) {
+        self.reader_scroll_handle.set_offset(point(px(0.), px(0.)));
+        self.reader_header_collapsed = false;
+        self.reader_last_scroll_y = 0.0;
+        self.reader_image_menu = None;
+        if self.settings.focus_reader_on_open {
+            cx.focus(&self.reader_focus_handle);
+        }
+    }
+
+    fn record_article_read(&mut self, url: &str, article: &reader::ReaderArticle) {
+        if !self.counted_articles.insert(url.to_string()) {
+            return;
+        }
+        let domain = url::Url::parse(url).ok().and_then(|u| {
+            u.host_str()
+                .map(|h| h.trim_start_matches("www.").to_string())
+        });
+        self.reading_stats.record_article(
+            chrono::Local::now().date_naive(),
+            domain.as_deref(),
+            article.reading_minutes().unwrap_or(0),
+        );
+        let _ = self.reading_stats.save(&self.dirs.data);
+    }
+
+    fn mark_visited(&mut self, url: &str) {
+        if self.visited_urls.record(url.to_string()) {
+            let _ = self.visited_urls.save(&self.dirs.data);
+        }
+    }
+
+    fn open_inline_link(&mut self, url: &str, cx: &mut ViewContext<Self>) {
+        let _ = open::that(models::clean_url(url));
+        self.mark_visited(url);
+        cx.notify();
+    }
+
+    /// 文章里的链接：按设置在当前标签替换（可后退）、新标签或浏览器中打开
+    fn open_article_link(&mut self, url: &str, cx: &mut ViewContext<Self>) {
+        let is_web = url::Url::parse(url).is_ok_and(|u| matches!(u.scheme(), "http" | "https"));
+        if !is_web || self.reader().is_none() {
+            return self.open_inline_link(url, cx);
+        }
+        match self.settings.reader_link_target {
+            ReaderLinkTarget::Replace => self.show_reader_page(url.to_string(), None, true, cx),
+            ReaderLinkTarget::NewTab => self.open_reader(url.to_string(), None, cx),
+            ReaderLinkTarget::Browser => self.open_inline_link(url, cx),
+        }
+    }
+
+    /// 阅读器的 ← Back：先回到这个标签跟随链接之前的页面，没有时关闭阅读器
+    fn reader_back(&mut self, cx: &mut ViewContext<Self>) {
+        if self.reader_tabs.go_back() {
+            self.restore_reader_scroll();
+            cx.notify();
+        } else {
+            self.close_reader(cx);
+        }
+    }
+
+    fn set_reader_image_menu(&mut self, index: Option<usize>, cx: &mut ViewContext<Self>) {
+        if self.reader_image_menu != index {
+            self.reader_image_menu = index;
+            cx.notify();
+        }
+    }
+
+    /// 下载文章图片后复制到剪贴板，或保存到用户选择的位置
+    fn run_image_action(&mut self, url: String, action: ImageAction, cx: &mut ViewContext<Self>) {
+        self.set_reader_image_menu(None, cx);
+        let http_client = self.http_client.clone();
+        let executor = cx.background_executor().clone();
+        let save_dir = images::default_save_dir(&self.dirs.cache);
+
+        cx.spawn(
+            |this: WeakView<Self>, mut cx: AsyncWindowContext| async move {
+                let result: Result<Option<String>, String> = async {
+                    let image = images::fetch_image(http_client, &url, executor).await?;
+                    let extension = images::image_extension(image.content_type.as_deref(), &url);
+                    match action {
+                        ImageAction::Copy => {
+                            let format = images::clipboard_format(extension).ok_or_else(|| {
+                                format!("copying .{} images isn't supported", extension)
+                            })?;
+                            let image = Image::from_bytes(format, image.bytes);
+                            let item = ClipboardItem::new_image(&image);
+                            cx.update(|cx| cx.write_to_clipboard(item))
+                                .map_err(|e| e.to_string())?;
+                            Ok(Some("Image copied".to_string()))
+                        }
+                        ImageAction::Save => {
+                            let picked = cx
+                                .update(|cx| cx.prompt_for_new_path(&save_dir))
+                                .map_err(|e| e.to_string())?;
+                            let path = match picked.await {
+                                Ok(Ok(Some(path))) => path,
+                                // 用户取消
+                                Ok(Ok(None)) | Err(_) => return Ok(None),
+                                Ok(Err(e)) => return Err(e.to_string()),
+                            };
+                            let path = images::with_image_extension(path, extension);
+                            std::fs::write(&path, &image.bytes).map_err(|e| e.to_string())?;
+                            Ok(Some(format!("Saved to {}", path.display())))
+                        }
+                    }
+                }
+                .await;
+
+                let _ =
+                    this.update(
+                        &mut cx,
+                        |this: &mut Self, cx: &mut ViewContext<Self>| match result {
+                            Ok(Some(message)) => this.show_toast(message, cx),
+                            Ok(None) => {}
+                            Err(e) => {
+                                this.show_toast(format!("{} failed: {}", action.label(), e), cx)
+                            }
+                        },
+                    );
+            },
+        )
+        .detach();
+    }
+
+    fn open_reader(&mut self, url: String, title_hint: Option<String>, cx: &mut ViewContext<Self>) {
+        self.show_reader_page(url, title_hint, false, cx);
+    }
+
+    /// `replace` 时在当前标签里打开，原来的页面进入后退栈；否则打开或切换到独立的标签
+    fn show_reader_page(
+        &mut self,
+        url: String,
+        title_hint: Option<String>,
+        replace: bool,
+        cx: &mut ViewContext<Self>,
+    ) {
+        self.mark_visited(&url);
+        // 标签和缓存都按去掉追踪参数后的地址记
+        let url = models::clean_url(&url);
+        // 已经打开过的文章切回原来的标签；加载失败的重新加载
+        if let Some(index) = self.reader_tabs.position(&url) {
+            let failed = matches!(
+                self.reader_tabs.tabs()[index].state,
+                ReaderLoadState::Error(_)
+            );
+            if !failed {
+                self.activate_reader_tab(index, cx);
+                if self.settings.focus_reader_on_open {
+                    cx.focus(&self.reader_focus_handle);
+                }
+                return;
+            }
+        }
+        self.save_reader_scroll();
+        self.reset_reader_view(cx);
+
+        let cached = self.cached_reader_article(&url);
+        if let Some(article) = &cached {
+            self.record_article_read(&url, article);
+        }
+        let loaded = cached.is_some();
+        let show_hero = self.settings.show_hero_image;
+        let state = cached.map_or(ReaderLoadState::Loading, |article| {
+            ReaderLoadState::Ready(article.for_display(show_hero))
+        });
+        let mut session = ReaderSession::new(url.clone(), title_hint.clone(), state);
+        // 缓存里的文章回到上次读到的位置，新加载的从头开始
+        if let Some(offset) = self.reader_scroll_positions.get(&url).filter(|_| loaded) {
+            session.scroll_y = -offset.y.0;
+        }
+        if replace {
+            self.reader_tabs.navigate(session);
+        } else {
+            self.reader_tabs.open(session);
+        }
+        cx.notify();
+        if loaded {
+            self.restore_reader_scroll();
+            return;
+        }
+
+        let http_client = self.http_client.clone();
+        let reader_dir = self.dirs.reader_cache();
+        let executor = cx.background_executor().clone();
+
+        cx.spawn(
+            |this: WeakView<Self>, mut cx: AsyncWindowContext| async move {
+                let result = reader::load_article(
+                    http_client,
+                    &reader_dir,
+                    &url,
+                    title_hint.as_deref(),
+                    executor,
+                )
+                .await;
+                let _ = this.update(&mut cx, |this: &mut Self, cx: &mut ViewContext<Self>| {
+                    // 标签可能已经切走或关掉，按 URL 找回它
+                    let is_active = this.reader().is_some_and(|tab| tab.url == url);
+                    let show_hero = this.settings.show_hero_image;
+                    let Some(session) = this.reader_tabs.find_mut(&url) else {
+                        return;
+                    };
+
+                    match result {
+                        Ok(LoadedPage::Pdf) => session.state = ReaderLoadState::Pdf,
+                        Ok(LoadedPage::Article(article)) => {
+                            session.state =
+                                ReaderLoadState::Ready(article.clone().for_display(show_hero));
+                            session.scroll_y = 0.0;
+                            this.record_article_read(&url, &article);
+                            this.cache_reader_article(url.clone(), article);
+                            // Reset scroll position when article finishes loading
+                            if is_active {
+                                this.reader_scroll_handle.set_offset(point(px(0.), px(0.)));
+                            }
+                        }
+                        Err(message) => session.state = ReaderLoadState::Error(message),
+                    }
+                    cx.notify();
+                });
+            },
+        )
+        .detach();
+    }
+
+    /// 文章读到的比例，按千分之一取整；文章还没排版时为 None
+    fn reader_progress(&self) -> Option<f32> {
+        let content_h = self.reader_scroll_handle.bounds_for_item(0)?.size.height;
+        let viewport_h = self.reader_scroll_handle.bounds().size.height;
+        let scroll_y = -self.reader_scroll_handle.offset().y.0;
+        let progress = reader_view::reading_progress(scroll_y, viewport_h.0, content_h.0);
+        Some((progress * 1000.).round() / 1000.)
+    }
+
+    fn on_reader_scrolled(&mut self, cx: &mut ViewContext<Self>) {
+        let progress = self.reader_progress();
+        if progress != self.reader_last_progress {
+            self.reader_last_progress = progress;
+            cx.notify();
+        }
+        // 只在状态变化时重绘，避免滚动时频繁刷新
+        if self.sync_reader_header() {
+            cx.notify();
+        }
+    }
+
+    /// 按当前滚动位置更新标题栏的收起状态，返回它是否变了。
+    /// 每次渲染都会调用，键盘、滚动条和跳转引起的滚动也会跟着收起/展开
+    fn sync_reader_header(&mut self) -> bool {
+        if !self.settings.collapse_reader_header || self.reader().is_none() {
+            return false;
+        }
+
+        let scroll_y = -self.reader_scroll_handle.offset().y.0;
+        let collapsed = reader_view::header_collapsed_after_scroll(
+            self.reader_header_collapsed,
+            self.reader_last_scroll_y,
+            scroll_y,
+        );
+        self.reader_last_scroll_y = scroll_y;
+        let changed = collapsed != self.reader_header_collapsed;
+        self.reader_header_collapsed = collapsed;
+        changed
+    }
+
+    /// 回到评论；打开的标签保留，再次打开同一篇文章时回到原来的位置
+    fn close_reader(&mut self, cx: &mut ViewContext<Self>) {
+        self.save_reader_scroll();
+        self.reader_tabs.hide();
+        if self.reader_focus_handle.contains_focused(cx) {
+            cx.focus(&self.story_list_focus_handle);
+        }
+        cx.notify();
+    }
+
+    /// 用设置里的 webhook 把文章转发到稍后读服务，结果用提示显示
+    fn send_to_read_later(&mut self, url: String, title: String, cx: &mut ViewContext<Self>) {
+        let Some(integration) = self.settings.send_to.clone() else {
+            return;
+        };
+        let http_client = self.http_client.clone();
+        cx.spawn(
+            |this: WeakView<Self>, mut cx: AsyncWindowContext| async move {
+                let result =
+                    send_to::send_article(http_client, &integration, &url, Some(&title)).await;
+                let _ = this.update(&mut cx, |this: &mut Self, cx: &mut ViewContext<Self>| {
+                    let name = integration.name.trim();
+                    match result {
+                        Ok(()) => this.show_toast(format!("Sent to {}", name), cx),
+                        Err(e) => this.show_toast(format!("Send to {} failed: {}", name, e), cx),
+                    }
+                });
+            },
+        )
+        .detach();
     }
-}
 
-impl AppState {
-    fn render_sidebar(&self) -> impl IntoElement {
+    fn render_reader_page(
+        &self,
+        reader: &ReaderSession,
+        cx: &mut ViewContext<Self>,
+    ) -> impl IntoElement {
         let theme = &self.theme;
+        let text_secondary = theme.text_secondary;
+        let text_primary = theme.text_primary;
+        let accent = theme.accent;
+        let accent_hover = theme.accent_hover;
+        let url = reader.browser_url().to_string();
+        let send_to = self
+            .settings
+            .send_to
+            .as_ref()
+            .filter(|integration| integration.is_configured())
+            .map(|integration| (integration.label(), url.clone(), reader.title()));
+        // 同时有链接和正文时，在顶栏提供切换
+        let source_toggle = self
+            .selected_story()
+            .filter(|story| story.has_both_reader_sources())
+            .map(|story| {
+                let next = if reader.url == story.item_url() {
+                    ReaderSource::Link
+                } else {
+                    ReaderSource::SelfText
+                };
+                (story.clone(), next)
+            });
+        let header_collapsed = self.settings.collapse_reader_header && self.reader_header_collapsed;
+        let compact_header = self.settings.compact_header;
+        let debug_reader_scroll = self.debug_reader_scroll;
+        let scroll_debug = debug_reader_scroll.then(|| {
+            let offset_y = self.reader_scroll_handle.offset().y;
+            let viewport_h = self.reader_scroll_handle.bounds().size.height;
+            let content_h = self
+                .reader_scroll_handle
+                .bounds_for_item(0)
+                .map(|b| b.size.height)
+                .unwrap_or_else(|| px(0.));
+            let max_scroll = (content_h - viewport_h).max(px(0.));
+            format!(
+                "y:{:.0} max:{:.0} children:{}",
+                offset_y.0,
+                max_scroll.0,
+                self.reader_scroll_handle.children_count()
+            )
+        });
 
-        div()
-            .w(px(SIDEBAR_WIDTH))
-            .h_full()
-            .flex()
-            .flex_col()
-            .items_center()
+        let title = reader.title();
+        // 加载中、出错和没有正文时不显示进度条
+        let progress = match &reader.state {
+            ReaderLoadState::Ready(article) if article.has_readable_content() => {
+                Some(self.reader_progress().unwrap_or(0.))
+            }
+            _ => None,
+        };
+
+        let content = match &reader.state {
+            ReaderLoadState::Loading => self
+                .render_reader_loading(cx.viewport_size().height.0)
+                .into_any_element(),
+            ReaderLoadState::Error(message) => self
+                .render_reader_error(message, reader, cx)
+                .into_any_element(),
+            ReaderLoadState::Pdf => self.render_reader_pdf(reader, cx).into_any_element(),
+            ReaderLoadState::Ready(article) if !article.has_readable_content() => self
+                .render_reader_empty(article, reader, cx)
+                .into_any_element(),
+            ReaderLoadState::Ready(article) => {
+                self.render_reader_article(article, cx).into_any_element()
+            }
+        };
+
+        let header = div()
+            .w_full()
+            .flex_shrink_0()
+            .map(|this| {
+                if header_collapsed {
+                    this.h(px(reader_view::READER_HEADER_COLLAPSED_HEIGHT))
+                        .px_4()
+                        .flex()
+                        .items_center()
+                        .text_sm()
+                } else if compact_header {
+                    this.min_h(px(TITLEBAR_HEIGHT))
+                        .px_6()
+                        .py_2()
+                        .flex()
+                        .items_center()
+                } else {
+                    this.p_6()
+                }
+            })
             .bg(theme.bg_secondary)
-            .border_r_1()
-            .border_color(theme.border_subtle)
-            // 顶部留空给 traffic lights
-            .child(div().h(px(TITLEBAR_HEIGHT)).w_full().flex_shrink_0())
-            // Channel icon
+            .border_b_1()
+            .border_color(theme.border)
             .child(
                 div()
-                    .mt_2()
-                    .w(px(40.))
-                    .h(px(40.))
+                    .w_full()
+                    .min_w(px(0.))
                     .flex()
                     .items_center()
-                    .justify_center()
-                    .rounded_lg()
-                    .bg(theme.accent)
-                    .text_color(hsla(0., 0., 1., 1.0))
-                    .text_lg()
-                    .font_weight(FontWeight::BOLD)
-                    .child(self.selected_channel.icon()),
-            )
+                    .justify_between()
+                    .gap_4()
+                    .child(
+                        div()
+                            .min_w(px(0.))
+                            .flex()
+                            .items_center()
+                            .gap_3()
+                            .child(
+                                div()
+                                    .id("reader-back")
+                                    .cursor_pointer()
+                                    .text_color(text_secondary)
+                                    .hover(move |s| s.text_color(text_primary))
+                                    .on_click(cx.listener(|this, _event, cx| {
+                                        this.reader_back(cx);
+                                    }))
+                                    .child("← Back"),
+                            )
+                            .child(
+                                div()
+                                    .min_w(px(0.))
+                                    .text_sm()
+                                    .text_color(theme.text_muted)
+                                    .overflow_hidden()
+                                    .child(title),
+                            ),
+                    )
+                    .when(!header_collapsed, |this| {
+                        this.child(
+                            div()
+                                .flex()
+                                .items_center()
+                                .gap_3()
+                                .when_some(scroll_debug, |this, debug| {
+                                    this.child(
+                                        div().text_xs().text_color(theme.text_muted).child(debug),
+                                    )
+                                })
+                                .when_some(source_toggle, |this, (story, next)| {
+                                    let label = match next {
+                                        ReaderSource::Link => "Show Article",
+                                        ReaderSource::SelfText => "Show Post Text",
+                                    };
+                                    this.child(
+                                        div()
+                                            .id("reader-source-toggle")
+                                            .cursor_pointer()
+                                            .text_color(text_secondary)
+                                            .hover(move |s| s.text_color(text_primary))
+                                            .on_click(cx.listener(move |this, _event, cx| {
+                                                this.open_story_reader(&story, next, cx);
+                                            }))
+                                            .child(label),
+                                    )
+                                })
+                                .child(
+                                    div()
+                                        .id("reader-settings-toggle")
+                                        .cursor_pointer()
+                                        .text_color(if self.reader_quick_bar_open {
+                                            text_primary
+                                        } else {
+                                            text_secondary
+                                        })
+                                        .hover(move |s| s.text_color(text_primary))
+                                        .on_click(cx.listener(|this, _event, cx| {
+                                            this.reader_quick_bar_open =
+                                                !this.reader_quick_bar_open;
+                                            cx.notify();
+                                        }))
+                                        .child("Aa"),
+                                )
+                                .when_some(send_to, |this, (label, url, title)| {
+                                    this.child(
+                                        div()
+                                            .id("reader-send-to")
+                                            .cursor_pointer()
+                                            .text_color(text_secondary)
+                                            .hover(move |s| s.text_color(text_primary))
+                                            .on_click(cx.listener(move |this, _event, cx| {
+                                                this.send_to_read_later(
+                                                    url.clone(),
+                                                    title.clone(),
+                                                    cx,
+                                                );
+                                            }))
+                                            .child(label),
+                                    )
+                                })
+                                .child(
+                                    div()
+                                        .id("reader-open-external")
+                                        .cursor_pointer()
+                                        .text_color(accent)
+                                        .hover(move |s| s.text_color(accent_hover))
+                                        .on_click(cx.listener(move |_this, _event, _cx| {
+                                            let _ = open::that(&url);
+                                        }))
+                                        .child("Open in Browser ↗"),
+                                ),
+                        )
+                    }),
+            );
+
+        div()
+            .id("reader-page")
+            .track_focus(&self.reader_focus_handle)
+            .flex_1()
+            .min_h(px(0.))
+            .w_full()
+            .min_w(px(0.))
+            .flex()
+            .flex_col()
+            .overflow_hidden()
+            .child(self.fade_in(
+                ElementId::NamedInteger("reader-header".into(), header_collapsed as usize),
+                header,
+            ))
+            .when_some(progress, |this, progress| {
+                this.child(
+                    div()
+                        .w_full()
+                        .h(px(2.))
+                        .flex_shrink_0()
+                        .child(div().h_full().w(relative(progress)).bg(accent)),
+                )
+            })
+            .when(self.reader_quick_bar_open, |this| {
+                this.child(self.render_reader_quick_bar(cx))
+            })
+            .children(self.render_reader_tabs(cx))
+            .child(content)
     }
 
-    fn render_story_list(&self, cx: &mut ViewContext<Self>) -> impl IntoElement {
+    /// 顶栏下方的阅读设置：字号、字体、栏宽、主题和图片，改动立即生效并保存
+    fn render_reader_quick_bar(&self, cx: &mut ViewContext<Self>) -> impl IntoElement {
         let theme = &self.theme;
+        let text_muted = theme.text_muted;
+        let text_primary = theme.text_primary;
+        let text_secondary = theme.text_secondary;
+        let selected_bg = theme.bg_selected;
+        let hover_bg = theme.bg_hover;
+        let option = move |id: String, label: &'static str, selected: bool| {
+            div()
+                .id(ElementId::Name(id.into()))
+                .px_2()
+                .py(px(2.))
+                .rounded_md()
+                .cursor_pointer()
+                .map(|this| {
+                    if selected {
+                        this.bg(selected_bg).text_color(text_primary)
+                    } else {
+                        this.text_color(text_secondary)
+                            .hover(move |s| s.bg(hover_bg))
+                    }
+                })
+                .child(label)
+        };
+        let group = |label: &'static str| {
+            div()
+                .flex()
+                .items_center()
+                .gap_1()
+                .child(div().mr_1().text_color(text_muted).child(label))
+        };
+        let font_percent = format!("{:.0}%", self.reader_font_scale() * 100.);
+        let images_on = self.settings.reader_image_mode == ImageMode::Images;
+        let sepia = self.settings.reader_theme == ReaderTheme::Sepia;
 
         div()
-            .w(px(self.story_list_width))
-            .flex_shrink()
-            .h_full()
+            .w_full()
+            .flex_shrink_0()
+            .px_6()
+            .py_2()
             .flex()
-            .flex_col()
+            .flex_wrap()
+            .items_center()
+            .gap_x_6()
+            .gap_y_2()
+            .text_xs()
             .bg(theme.bg_secondary)
-            // Header with titlebar spacing
+            .border_b_1()
+            .border_color(theme.border_subtle)
             .child(
-                div()
-                    .w_full()
-                    .h(px(TITLEBAR_HEIGHT + 52.))
-                    .flex()
-                    .flex_col()
-                    .border_b_1()
-                    .border_color(theme.border_subtle)
-                    // Titlebar spacer
-                    .child(div().h(px(TITLEBAR_HEIGHT)).w_full().flex_shrink_0())
-                    // Title
+                group("Size")
+                    .child(self.render_reader_font_controls(cx))
+                    .child(div().ml_1().text_color(text_muted).child(font_percent)),
+            )
+            .child(group("Font").children(ReaderFont::all().map(|font| {
+                option(
+                    format!("reader-font-{}", font.label()),
+                    font.label(),
+                    self.settings.reader_font == font,
+                )
+                .on_click(cx.listener(move |this, _event, cx| {
+                    this.set_reader_font(font, cx);
+                }))
+            })))
+            .child(group("Width").children(ReaderWidth::all().map(|width| {
+                option(
+                    format!("reader-width-{}", width.label()),
+                    width.label(),
+                    self.settings.reader_width == width,
+                )
+                .on_click(cx.listener(move |this, _event, cx| {
+                    this.set_reader_width(width, cx);
+                }))
+            })))
+            .child(
+                group("Theme")
                     .child(
-                        div().flex_1().flex().items_center().px_4().child(
-                            div()
-                                .text_base()
-                                .font_weight(FontWeight::SEMIBOLD)
-                                .child(self.selected_channel.name()),
+                        option(
+                            "reader-theme-toggle".to_string(),
+                            if self.is_dark { "Dark" } else { "Light" },
+                            false,
+                        )
+                        .on_click(cx.listener(|this, _event, cx| this.toggle_theme(cx))),
+                    )
+                    .child(
+                        option("reader-theme-sepia".to_string(), "Sepia", sepia).on_click(
+                            cx.listener(move |this, _event, cx| {
+                                let theme = if sepia {
+                                    ReaderTheme::App
+                                } else {
+                                    ReaderTheme::Sepia
+                                };
+                                this.set_reader_theme(theme, cx);
+                            }),
                         ),
                     ),
             )
-            // Error message
-            .when_some(self.error_message.clone(), |this, msg| {
-                this.child(
-                    div()
-                        .w_full()
-                        .px_4()
-                        .py_2()
-                        .bg(theme.error)
-                        .text_color(hsla(0., 0., 1., 1.0))
-                        .text_sm()
-                        .child(msg),
-                )
-            })
-            // Stories
             .child(
-                div()
-                    .id("story-list")
-                    .flex_1()
-                    .overflow_y_scroll()
-                    .children(if self.is_loading {
-                        vec![self.render_loading_indicator().into_any_element()]
-                    } else {
-                        self.stories
-                            .iter()
-                            .map(|story| self.render_story_row(story, cx).into_any_element())
-                            .collect()
-                    }),
+                group("Images").child(
+                    option(
+                        "reader-images-toggle".to_string(),
+                        if images_on { "On" } else { "Off" },
+                        images_on,
+                    )
+                    .on_click(cx.listener(|this, _event, cx| this.toggle_reader_images(cx))),
+                ),
             )
     }
 
-    fn render_story_splitter(&self, cx: &mut ViewContext<Self>) -> impl IntoElement {
-        let theme = &self.theme;
-        let is_resizing = self.is_resizing_story_list;
-        let divider_color = if is_resizing {
-            theme.border
-        } else {
-            theme.border_subtle
+    fn set_reader_font(&mut self, font: ReaderFont, cx: &mut ViewContext<Self>) {
+        self.settings.reader_font = font;
+        self.save_settings();
+        cx.notify();
+    }
+
+    fn set_reader_width(&mut self, width: ReaderWidth, cx: &mut ViewContext<Self>) {
+        self.settings.reader_width = width;
+        self.save_settings();
+        cx.notify();
+    }
+
+    fn set_reader_theme(&mut self, theme: ReaderTheme, cx: &mut ViewContext<Self>) {
+        self.settings.reader_theme = theme;
+        self.save_settings();
+        cx.notify();
+    }
+
+    /// 关掉图片时只显示图片说明；重新打开时也不再沿用“图片整体不可用”的判断
+    fn toggle_reader_images(&mut self, cx: &mut ViewContext<Self>) {
+        self.settings.reader_image_mode = match self.settings.reader_image_mode {
+            ImageMode::Images => ImageMode::Text,
+            ImageMode::Text => {
+                self.images_unavailable = false;
+                ImageMode::Images
+            }
         };
+        self.save_settings();
+        cx.notify();
+    }
 
-        div()
-            .id("story-splitter")
-            .w(px(SPLITTER_WIDTH))
-            .h_full()
-            .flex()
-            .flex_row()
-            .cursor_col_resize()
-            .on_mouse_down(
-                MouseButton::Left,
-                cx.listener(Self::start_story_list_resize),
-            )
-            // Left half blends with story list background; right half blends with detail background.
-            .child(div().flex_1().h_full().bg(theme.bg_secondary))
-            .child(div().w(px(1.)).h_full().bg(divider_color))
-            .child(div().flex_1().h_full().bg(theme.bg_primary))
+    /// 同时打开多篇文章时，顶栏下方的标签栏
+    fn render_reader_tabs(&self, cx: &mut ViewContext<Self>) -> Option<AnyElement> {
+        let tabs = self.reader_tabs.tabs();
+        if tabs.len() < 2 {
+            return None;
+        }
+        let theme = &self.theme;
+        let active = self.reader_tabs.active_index();
+        let hover_bg = theme.bg_hover;
+        let text_primary = theme.text_primary;
+        let text_muted = theme.text_muted;
+
+        Some(
+            div()
+                .w_full()
+                .flex_shrink_0()
+                .px_4()
+                .py_1()
+                .flex()
+                .items_center()
+                .gap_1()
+                .overflow_hidden()
+                .bg(theme.bg_secondary)
+                .border_b_1()
+                .border_color(theme.border_subtle)
+                .children(tabs.iter().enumerate().map(|(index, tab)| {
+                    let selected = active == Some(index);
+                    div()
+                        .id(ElementId::Name(format!("reader-tab-{index}").into()))
+                        .flex_1()
+                        .min_w(px(0.))
+                        .max_w(px(200.))
+                        .px_2()
+                        .py_1()
+                        .flex()
+                        .items_center()
+                        .gap_2()
+                        .rounded_md()
+                        .text_xs()
+                        .cursor_pointer()
+                        .map(|this| {
+                            if selected {
+                                this.bg(theme.bg_primary).text_color(text_primary)
+                            } else {
+                                this.text_color(text_muted).hover(move |s| s.bg(hover_bg))
+                            }
+                        })
+                        .on_click(cx.listener(move |this, _event, cx| {
+                            this.activate_reader_tab(index, cx);
+                        }))
+                        .child(
+                            div()
+                                .flex_1()
+                                .min_w(px(0.))
+                                .overflow_hidden()
+                                .whitespace_nowrap()
+                                .child(tab.title()),
+                        )
+                        .child(
+                            div()
+                                .id(ElementId::Name(format!("reader-tab-close-{index}").into()))
+                                .flex_shrink_0()
+                                .text_color(text_muted)
+                                .hover(move |s| s.text_color(text_primary))
+                                .on_click(cx.listener(move |this, _event, cx| {
+                                    cx.stop_propagation();
+                                    this.close_reader_tab(index, cx);
+                                }))
+                                .child("✕"),
+                        )
+                }))
+                .into_any_element(),
+        )
     }
 
-    fn render_loading_indicator(&self) -> impl IntoElement {
+    fn render_reader_loading(&self, viewport_height: f32) -> impl IntoElement {
         let theme = &self.theme;
 
         let skeleton_bar = |max_w: f32, h: f32| {
@@ -407,252 +4835,504 @@ impl AppState {
                 .bg(theme.bg_tertiary)
         };
 
-        let placeholders: Vec<_> = (0..10)
+        let count = skeleton::skeleton_count(viewport_height, skeleton::READER_LINES);
+        let placeholders: Vec<_> = (0..count)
             .map(|i| {
-                let title_max_w = match i % 3 {
-                    0 => 280.0,
-                    1 => 240.0,
-                    _ => 200.0,
+                let line_w = match i % 4 {
+                    0 => 640.0,
+                    1 => 720.0,
+                    2 => 680.0,
+                    _ => 560.0,
                 };
+                skeleton_bar(line_w, 12.0).into_any_element()
+            })
+            .collect();
+
+        div()
+            .id("reader-loading-scroll")
+            .flex_1()
+            .w_full()
+            .overflow_y_scroll()
+            .child(
+                div().w_full().flex().justify_center().child(
+                    div()
+                        .w_full()
+                        .max_w(px(760.))
+                        .px_8()
+                        .py_10()
+                        .flex()
+                        .flex_col()
+                        .gap_6()
+                        .child(
+                            div()
+                                .flex()
+                                .items_center()
+                                .gap_2()
+                                .text_color(theme.text_muted)
+                                .child("⏳")
+                                .child("Loading article…"),
+                        )
+                        .child(
+                            self.pulse_skeleton(
+                                "reader-skeleton",
+                                div()
+                                    .w_full()
+                                    .flex()
+                                    .flex_col()
+                                    .gap_3()
+                                    .children(placeholders),
+                            ),
+                        ),
+                ),
+            )
+    }
+
+    /// 抓取成功但没有可读内容时的提示，区别于错误状态
+    fn render_reader_empty(
+        &self,
+        article: &reader::ReaderArticle,
+        reader: &ReaderSession,
+        cx: &mut ViewContext<Self>,
+    ) -> impl IntoElement {
+        let theme = &self.theme;
+        let accent_hover = theme.accent_hover;
+        let url = reader.browser_url().to_string();
+        let title = Some(article.title.clone())
+            .filter(|t| !t.is_empty())
+            .or_else(|| reader.title_hint.clone())
+            .unwrap_or_else(|| reader.url.clone());
 
+        div()
+            .flex_1()
+            .w_full()
+            .flex()
+            .items_center()
+            .justify_center()
+            .child(
                 div()
                     .w_full()
-                    .px_4()
-                    .py_3()
-                    .border_b_1()
+                    .max_w(px(480.))
+                    .p_8()
+                    .bg(theme.bg_secondary)
+                    .rounded_xl()
+                    .border_1()
                     .border_color(theme.border_subtle)
+                    .flex()
+                    .flex_col()
+                    .items_center()
+                    .gap_4()
                     .child(
                         div()
                             .w_full()
                             .flex()
-                            .flex_col()
-                            .gap_2()
-                            .child(skeleton_bar(title_max_w, 14.0))
-                            .child(div().w_full().flex().gap_2().children(vec![
-                                skeleton_bar(96.0, 10.0).into_any_element(),
-                                skeleton_bar(72.0, 10.0).into_any_element(),
-                                skeleton_bar(56.0, 10.0).into_any_element(),
-                            ])),
+                            .justify_center()
+                            .text_lg()
+                            .font_weight(FontWeight::SEMIBOLD)
+                            .child("No readable content found"),
                     )
-                    .into_any_element()
-            })
-            .collect();
+                    .child(
+                        div()
+                            .w_full()
+                            .text_sm()
+                            .text_color(theme.text_secondary)
+                            .whitespace_normal()
+                            .child(format!(
+                                "\"{}\" loaded, but reader mode couldn't find any text to show. The page may be mostly video, images or interactive content.",
+                                title
+                            )),
+                    )
+                    .child(
+                        div()
+                            .id("reader-empty-open-browser")
+                            .cursor_pointer()
+                            .rounded_md()
+                            .px_4()
+                            .py_2()
+                            .bg(theme.accent)
+                            .text_color(hsla(0., 0., 1., 1.0))
+                            .text_sm()
+                            .font_weight(FontWeight::MEDIUM)
+                            .hover(move |s| s.bg(accent_hover))
+                            .on_click(cx.listener(move |_this, _event, _cx| {
+                                let _ = open::that(&url);
+                            }))
+                            .child("Open in Browser"),
+                    ),
+            )
+    }
+
+    fn render_reader_error(
+        &self,
+        message: &str,
+        reader: &ReaderSession,
+        cx: &mut ViewContext<Self>,
+    ) -> impl IntoElement {
+        let theme = &self.theme;
+        let accent = theme.accent;
+        let accent_hover = theme.accent_hover;
+        let url = reader.url.clone();
+        let url_for_open = reader.url.clone();
+        let title_hint = reader.title_hint.clone();
+
+        // Convert technical error messages to user-friendly descriptions
+        let (friendly_title, friendly_message, suggestion) = Self::parse_error_message(message);
 
         div()
+            .flex_1()
             .w_full()
-            .h_full()
             .flex()
-            .flex_col()
+            .items_center()
+            .justify_center()
             .child(
                 div()
                     .w_full()
-                    .px_4()
-                    .py_4()
+                    .max_w(px(480.))
+                    .p_8()
+                    .bg(theme.bg_secondary)
+                    .rounded_xl()
+                    .border_1()
+                    .border_color(theme.border_subtle)
                     .flex()
+                    .flex_col()
                     .items_center()
-                    .gap_2()
-                    .text_color(theme.text_muted)
-                    .child("⏳")
-                    .child("Loading stories…"),
-            )
-            .children(placeholders)
-    }
-
-    fn render_story_row(&self, story: &Story, cx: &mut ViewContext<Self>) -> impl IntoElement {
-        let theme = &self.theme;
-        let is_selected = self.selected_story_id == Some(story.id);
-
-        let bg_color = if is_selected {
-            theme.bg_selected
-        } else {
-            theme.bg_secondary
-        };
+                    .gap_5()
+                    // Error icon
+                    .child(
+                        div()
+                            .w(px(64.))
+                            .h(px(64.))
+                            .flex()
+                            .items_center()
+                            .justify_center()
+                            .rounded_full()
+                            .bg(hsla(0., 0.8, 0.95, 1.0))
+                            .text_2xl()
+                            .child("⚠️"),
+                    )
+                    // Title
+                    .child(
+                        div()
+                            .w_full()
+                            .flex()
+                            .justify_center()
+                            .text_lg()
+                            .font_weight(FontWeight::SEMIBOLD)
+                            .child(friendly_title),
+                    )
+                    // Description
+                    .child(
+                        div()
+                            .w_full()
+                            .flex()
+                            .flex_col()
+                            .items_center()
+                            .gap_2()
+                            .child(
+                                div()
+                                    .text_sm()
+                                    .text_color(theme.text_secondary)
+                                    .whitespace_normal()
+                                    .child(friendly_message),
+                            )
+                            .when_some(suggestion, |this, suggestion| {
+                                this.child(
+                                    div()
+                                        .text_sm()
+                                        .text_color(theme.text_muted)
+                                        .whitespace_normal()
+                                        .child(suggestion),
+                                )
+                            }),
+                    )
+                    // URL display
+                    .child(
+                        div()
+                            .w_full()
+                            .px_3()
+                            .py_2()
+                            .bg(theme.bg_tertiary)
+                            .rounded_md()
+                            .text_xs()
+                            .text_color(theme.text_muted)
+                            .overflow_hidden()
+                            .text_ellipsis()
+                            .child(reader.url.clone()),
+                    )
+                    // Action buttons
+                    .child(
+                        div()
+                            .flex()
+                            .items_center()
+                            .gap_3()
+                            .child(
+                                div()
+                                    .id("reader-retry")
+                                    .cursor_pointer()
+                                    .rounded_md()
+                                    .px_4()
+                                    .py_2()
+                                    .bg(theme.accent)
+                                    .text_color(hsla(0., 0., 1., 1.0))
+                                    .text_sm()
+                                    .font_weight(FontWeight::MEDIUM)
+                                    .hover(move |s| s.bg(accent_hover))
+                                    .on_click(cx.listener(move |this, _event, cx| {
+                                        this.open_reader(url.clone(), title_hint.clone(), cx);
+                                    }))
+                                    .child("Try Again"),
+                            )
+                            .child(
+                                div()
+                                    .id("reader-open-browser")
+                                    .cursor_pointer()
+                                    .rounded_md()
+                                    .px_4()
+                                    .py_2()
+                                    .border_1()
+                                    .border_color(theme.border)
+                                    .text_color(accent)
+                                    .text_sm()
+                                    .font_weight(FontWeight::MEDIUM)
+                                    .hover(move |s| s.bg(theme.bg_hover))
+                                    .on_click(cx.listener(move |_this, _event, _cx| {
+                                        let _ = open::that(&url_for_open);
+                                    }))
+                                    .child("Open in Browser"),
+                            ),
+                    ),
+            )
+    }
 
-        let story_id = story.id;
-        let title = story.title.clone();
-        let score = story.score;
-        let by = story.by.clone();
-        let domain = story.domain();
-        let formatted_time = story.formatted_time();
-        let comment_count = story.comment_count();
-        let hover_bg = theme.bg_hover;
-        let accent = theme.accent;
-        let text_muted = theme.text_muted;
-        let text_secondary = theme.text_secondary;
-        let border_subtle = theme.border_subtle;
+    /// PDF 链接：不下载正文，只提示用浏览器或系统的阅读器打开
+    fn render_reader_pdf(
+        &self,
+        reader: &ReaderSession,
+        cx: &mut ViewContext<Self>,
+    ) -> impl IntoElement {
+        let theme = &self.theme;
+        let accent_hover = theme.accent_hover;
+        let url = reader.url.clone();
 
         div()
-            .id(ElementId::Name(format!("story-{}", story_id).into()))
+            .flex_1()
             .w_full()
-            .px_4()
-            .py_3()
-            .cursor_pointer()
-            .bg(bg_color)
-            .hover(move |s| s.bg(hover_bg))
-            .border_b_1()
-            .border_color(border_subtle)
-            .on_click(cx.listener(move |this, _event, cx| {
-                this.select_story(story_id, cx);
-            }))
+            .flex()
+            .items_center()
+            .justify_center()
             .child(
                 div()
                     .w_full()
-                    .overflow_hidden()
+                    .max_w(px(480.))
+                    .p_8()
+                    .bg(theme.bg_secondary)
+                    .rounded_xl()
+                    .border_1()
+                    .border_color(theme.border_subtle)
                     .flex()
                     .flex_col()
-                    .gap_1()
-                    // Title
+                    .items_center()
+                    .gap_5()
+                    .child(
+                        div()
+                            .w(px(64.))
+                            .h(px(64.))
+                            .flex()
+                            .items_center()
+                            .justify_center()
+                            .rounded_full()
+                            .bg(theme.bg_tertiary)
+                            .text_2xl()
+                            .child("📄"),
+                    )
+                    .child(
+                        div()
+                            .text_lg()
+                            .font_weight(FontWeight::SEMIBOLD)
+                            .child("PDF document"),
+                    )
                     .child(
                         div()
-                            .w_full()
                             .text_sm()
-                            .font_weight(FontWeight::MEDIUM)
-                            .line_height(rems(1.4))
+                            .text_color(theme.text_secondary)
                             .whitespace_normal()
-                            .child(title),
+                            .child("Reader mode can't display PDFs. Open it in your browser or PDF viewer instead."),
                     )
-                    // Meta row
-                    .child(self.render_story_meta(
-                        score,
-                        domain,
-                        &by,
-                        &formatted_time,
-                        comment_count,
-                        accent,
-                        text_muted,
-                        text_secondary,
-                    )),
+                    .child(
+                        div()
+                            .w_full()
+                            .px_3()
+                            .py_2()
+                            .bg(theme.bg_tertiary)
+                            .rounded_md()
+                            .text_xs()
+                            .text_color(theme.text_muted)
+                            .overflow_hidden()
+                            .text_ellipsis()
+                            .child(reader.url.clone()),
+                    )
+                    .child(
+                        div()
+                            .id("reader-open-pdf")
+                            .cursor_pointer()
+                            .rounded_md()
+                            .px_6()
+                            .py_2()
+                            .bg(theme.accent)
+                            .text_color(hsla(0., 0., 1., 1.0))
+                            .text_sm()
+                            .font_weight(FontWeight::MEDIUM)
+                            .hover(move |s| s.bg(accent_hover))
+                            .on_click(cx.listener(move |_this, _event, _cx| {
+                                let _ = open::that(&url);
+                            }))
+                            .child("Open in Browser"),
+                    ),
             )
     }
 
-    #[allow(clippy::too_many_arguments)]
-    fn render_story_meta(
-        &self,
-        score: i32,
-        domain: Option<String>,
-        by: &str,
-        formatted_time: &str,
-        comment_count: i32,
-        accent: Hsla,
-        text_muted: Hsla,
-        text_secondary: Hsla,
-    ) -> impl IntoElement {
-        div()
-            .min_w(px(0.))
-            .flex()
-            .flex_row()
-            .items_center()
-            .flex_wrap()
-            .gap_3()
-            .text_xs()
-            .text_color(text_muted)
-            // Score
-            .child(
-                div()
-                    .flex()
-                    .items_center()
-                    .gap_1()
-                    .text_color(accent)
-                    .child("▲")
-                    .child(score.to_string()),
+    fn parse_error_message(message: &str) -> (String, String, Option<String>) {
+        let msg_lower = message.to_lowercase();
+
+        if msg_lower.contains("error sending request") || msg_lower.contains("connection") {
+            (
+                "Unable to connect".to_string(),
+                "The page couldn't be reached. This might be a network issue or the website may be unavailable.".to_string(),
+                Some("Check your internet connection and try again.".to_string()),
             )
-            // Domain
-            .when_some(domain, |this, domain| {
-                this.child(div().text_color(text_secondary).child(domain))
-            })
-            // Author
-            .child(format!("by {}", by))
-            // Time
-            .child(formatted_time.to_string())
-            // Comments
-            .when(comment_count > 0, |this| {
-                this.child(
-                    div()
-                        .flex()
-                        .items_center()
-                        .gap_1()
-                        .child("💬")
-                        .child(comment_count.to_string()),
-                )
-            })
+        } else if msg_lower.contains("timeout") {
+            (
+                "Request timed out".to_string(),
+                "The server took too long to respond.".to_string(),
+                Some(
+                    "The website might be experiencing high traffic. Try again later.".to_string(),
+                ),
+            )
+        } else if msg_lower.contains("http 404") {
+            (
+                "Page not found".to_string(),
+                "The requested page doesn't exist or has been moved.".to_string(),
+                None,
+            )
+        } else if msg_lower.contains("http 403") {
+            (
+                "Access denied".to_string(),
+                "You don't have permission to view this page.".to_string(),
+                Some("Try opening it in your browser instead.".to_string()),
+            )
+        } else if msg_lower.contains("http 5") {
+            (
+                "Server error".to_string(),
+                "The website is experiencing technical difficulties.".to_string(),
+                Some("Try again later or open in browser.".to_string()),
+            )
+        } else if msg_lower.contains("unsupported content type") {
+            (
+                "Unsupported content".to_string(),
+                "This type of content can't be displayed in reader mode.".to_string(),
+                Some("Try opening it in your browser instead.".to_string()),
+            )
+        } else if msg_lower.contains("invalid url") {
+            (
+                "Invalid URL".to_string(),
+                "The link appears to be malformed or invalid.".to_string(),
+                None,
+            )
+        } else if msg_lower.contains("too large") {
+            (
+                "Page too large".to_string(),
+                "This page is too large to load in reader mode.".to_string(),
+                Some("Try opening it in your browser instead.".to_string()),
+            )
+        } else {
+            (
+                "Couldn't load this page".to_string(),
+                message.to_string(),
+                Some("Try opening it in your browser instead.".to_string()),
+            )
+        }
     }
 
-    fn render_detail_panel(&self, cx: &mut ViewContext<Self>) -> impl IntoElement {
-        let theme = &self.theme;
-
-        div()
-            .flex_1()
-            .min_w(px(0.))
-            .h_full()
-            .flex()
-            .flex_col()
-            .bg(theme.bg_primary)
-            .overflow_hidden()
-            // Titlebar spacer
-            .child(div().h(px(TITLEBAR_HEIGHT)).w_full().flex_shrink_0())
-            .child(if let Some(reader) = self.reader.as_ref() {
-                self.render_reader_page(reader, cx).into_any_element()
-            } else if let Some(story) = self.selected_story() {
-                self.render_story_detail(story, cx).into_any_element()
-            } else {
-                self.render_empty_state().into_any_element()
-            })
+    /// 文章和整洁评论里的链接：点击在浏览器打开并记为已访问
+    fn inline_links(&self, cx: &mut ViewContext<Self>) -> reader_view::InlineLinks<'_> {
+        let view = cx.view().downgrade();
+        reader_view::InlineLinks {
+            visited: &self.visited_urls,
+            visited_color: self
+                .settings
+                .visited_link_color
+                .as_deref()
+                .and_then(theme::parse_hex_color)
+                .unwrap_or(self.theme.link_visited),
+            on_click: Rc::new(move |url: &str, cx: &mut WindowContext| {
+                let _ = view.update(cx, |this, cx| this.open_inline_link(url, cx));
+            }),
+        }
     }
 
-    fn render_empty_state(&self) -> impl IntoElement {
-        let theme = &self.theme;
-
-        div()
-            .size_full()
-            .flex()
-            .items_center()
-            .justify_center()
-            .text_color(theme.text_muted)
-            .child("Select a story to read")
+    /// 文章正文里的链接：点击按 `reader_link_target` 在阅读器里打开
+    fn article_links(&self, cx: &mut ViewContext<Self>) -> reader_view::InlineLinks<'_> {
+        let view = cx.view().downgrade();
+        reader_view::InlineLinks {
+            on_click: Rc::new(move |url: &str, cx: &mut WindowContext| {
+                let _ = view.update(cx, |this, cx| this.open_article_link(url, cx));
+            }),
+            ..self.inline_links(cx)
+        }
     }
 
-    fn open_reader(&mut self, url: String, title_hint: Option<String>, cx: &mut ViewContext<Self>) {
-        self.reader_scroll_handle.set_offset(point(px(0.), px(0.)));
+    fn reader_images(&self, cx: &mut ViewContext<Self>) -> reader_view::ReaderImages<'_> {
+        let view = cx.view().downgrade();
+        reader_view::ReaderImages {
+            reloads: &self.image_reloads,
+            on_reload: Rc::new(move |url: &str, cx: &mut WindowContext| {
+                let url = url.to_string();
+                let _ = view.update(cx, |this, cx| this.reload_image(url, cx));
+            }),
+            failures: self.image_failures.clone(),
+            text_only: self.settings.reader_image_mode == ImageMode::Text
+                || self.images_unavailable,
+        }
+    }
 
-        if let Some(article) = self.cached_reader_article(&url) {
-            self.reader = Some(ReaderSession {
-                url,
-                title_hint,
-                state: ReaderLoadState::Ready(article),
-            });
-            cx.notify();
+    /// 当前文章的图片全部加载失败时，本次运行改为显示图片说明，并提示一次。
+    /// 失败是上一帧绘制时记录的，所以在下一次渲染开始时检查
+    fn check_broken_images(&mut self, cx: &mut ViewContext<Self>) {
+        if self.images_unavailable || self.settings.reader_image_mode == ImageMode::Text {
             return;
         }
-
-        self.reader = Some(ReaderSession {
-            url: url.clone(),
-            title_hint: title_hint.clone(),
-            state: ReaderLoadState::Loading,
+        let Some(ReaderLoadState::Ready(article)) = self.reader().map(|reader| &reader.state)
+        else {
+            return;
+        };
+        let urls = article.blocks.iter().filter_map(|block| match block {
+            reader::ReaderBlock::Image { url, .. } => Some(url.as_str()),
+            _ => None,
         });
-        cx.notify();
-
-        let http_client = self.http_client.clone();
-
-        cx.spawn(
-            |this: WeakView<Self>, mut cx: AsyncWindowContext| async move {
-                let result = reader::load_article(http_client, &url, title_hint.as_deref()).await;
-                let _ = this.update(&mut cx, |this: &mut Self, cx: &mut ViewContext<Self>| {
-                    let Some(session) = this.reader.as_mut() else {
-                        return;
-                    };
-                    if session.url != url {
-                        return;
-                    }
+        if self.image_failures.all_failed(urls) {
+            self.images_unavailable = true;
+            self.show_toast(
+                "Images aren't loading; showing their descriptions instead",
+                cx,
+            );
+        }
+    }
 
-                    match result {
-                        Ok(article) => {
-                            session.state = ReaderLoadState::Ready(article.clone());
-                            this.cache_reader_article(url.clone(), article);
-                            // Reset scroll position when article finishes loading
-                            this.reader_scroll_handle.set_offset(point(px(0.), px(0.)));
-                        }
-                        Err(message) => session.state = ReaderLoadState::Error(message),
-                    }
+    /// 自己下载失败的图片（带重试），成功后替换占位
+    fn reload_image(&mut self, url: String, cx: &mut ViewContext<Self>) {
+        if !self.image_reloads.begin(&url) {
+            return;
+        }
+        cx.notify();
+        let http_client = self.http_client.clone();
+        let executor = cx.background_executor().clone();
+
+        cx.spawn(
+            |this: WeakView<Self>, mut cx: AsyncWindowContext| async move {
+                let result = images::fetch_image(http_client, &url, executor)
+                    .await
+                    .and_then(|image| images::displayable_image(image, &url));
+                let _ = this.update(&mut cx, |this: &mut Self, cx: &mut ViewContext<Self>| {
+                    this.image_reloads.finish(&url, result);
                     cx.notify();
                 });
             },
@@ -660,728 +5340,1418 @@ impl AppState {
         .detach();
     }
 
-    fn close_reader(&mut self, cx: &mut ViewContext<Self>) {
-        self.reader = None;
+    fn image_height_caps(&self) -> reader_view::ImageHeightCaps {
+        reader_view::ImageHeightCaps {
+            landscape: self.settings.image_max_height_landscape,
+            portrait: self.settings.image_max_height_portrait,
+        }
+    }
+
+    fn reader_layout(&self) -> reader_view::ReaderLayout {
+        reader_view::ReaderLayout {
+            image_caps: self.image_height_caps(),
+            font_scale: self.reader_font_scale(),
+            text_color: None,
+        }
+    }
+
+    fn reader_font_scale(&self) -> f32 {
+        reader_view::clamp_font_scale(self.settings.reader_font_scale)
+    }
+
+    fn render_reader_font_controls(&self, cx: &mut ViewContext<Self>) -> impl IntoElement {
+        let text_secondary = self.theme.text_secondary;
+        let text_primary = self.theme.text_primary;
+        div()
+            .flex()
+            .items_center()
+            .gap_2()
+            .children([(false, "A−"), (true, "A+")].map(|(larger, label)| {
+                div()
+                    .id(label)
+                    .cursor_pointer()
+                    .text_color(text_secondary)
+                    .hover(move |s| s.text_color(text_primary))
+                    .on_click(cx.listener(move |this, _event, cx| {
+                        this.step_reader_font_scale(larger, cx);
+                    }))
+                    .child(label)
+            }))
+    }
+
+    /// 阅读器顶部的 A−/A+，选择保存在设置里
+    fn step_reader_font_scale(&mut self, larger: bool, cx: &mut ViewContext<Self>) {
+        let scale = reader_view::step_font_scale(self.settings.reader_font_scale, larger);
+        if scale == self.settings.reader_font_scale {
+            return;
+        }
+        self.settings.reader_font_scale = scale;
+        self.save_settings();
         cx.notify();
     }
 
-    fn render_reader_page(
+    fn render_reader_block(
         &self,
-        reader: &ReaderSession,
+        index: usize,
+        block: &reader::ReaderBlock,
+        links: &reader_view::InlineLinks<'_>,
         cx: &mut ViewContext<Self>,
-    ) -> impl IntoElement {
-        let theme = &self.theme;
-        let text_secondary = theme.text_secondary;
-        let text_primary = theme.text_primary;
-        let accent = theme.accent;
-        let accent_hover = theme.accent_hover;
-        let url = reader.url.clone();
-        let debug_reader_scroll = self.debug_reader_scroll;
-        let scroll_debug = debug_reader_scroll.then(|| {
-            let offset_y = self.reader_scroll_handle.offset().y;
-            let viewport_h = self.reader_scroll_handle.bounds().size.height;
-            let content_h = self
-                .reader_scroll_handle
-                .bounds_for_item(0)
-                .map(|b| b.size.height)
-                .unwrap_or_else(|| px(0.));
-            let max_scroll = (content_h - viewport_h).max(px(0.));
-            format!(
-                "y:{:.0} max:{:.0} children:{}",
-                offset_y.0,
-                max_scroll.0,
-                self.reader_scroll_handle.children_count()
+    ) -> AnyElement {
+        let element = reader_view::render_reader_block(
+            &self.settings.reader_theme.resolve(&self.theme),
+            index,
+            block,
+            links,
+            &self.reader_images(cx),
+            self.reader_layout(),
+        );
+        let reader::ReaderBlock::Image { url, .. } = block else {
+            return element;
+        };
+
+        // 右键图片弹出复制/保存菜单，点击别处关闭
+        let view = cx.view().downgrade();
+        let url = url.clone();
+        div()
+            .id(ElementId::Name(format!("reader-image-{index}").into()))
+            .relative()
+            .w_full()
+            .on_mouse_down(
+                MouseButton::Right,
+                cx.listener(move |this, _event, cx| {
+                    this.set_reader_image_menu(Some(index), cx);
+                }),
             )
-        });
+            .child(element)
+            .when(self.reader_image_menu == Some(index), |this| {
+                this.on_mouse_down_out(cx.listener(|this, _event, cx| {
+                    this.set_reader_image_menu(None, cx);
+                }))
+                .child(reader_view::render_image_menu(
+                    &self.theme,
+                    Rc::new(move |action: ImageAction, cx: &mut WindowContext| {
+                        let url = url.clone();
+                        let _ = view.update(cx, |this, cx| this.run_image_action(url, action, cx));
+                    }),
+                ))
+            })
+            .into_any_element()
+    }
 
-        let title = match &reader.state {
-            ReaderLoadState::Ready(article) if !article.title.is_empty() => article.title.clone(),
-            _ => reader.title_hint.clone().unwrap_or_else(|| url.clone()),
-        };
+    fn render_reader_article(
+        &self,
+        article: &reader::ReaderArticle,
+        cx: &mut ViewContext<Self>,
+    ) -> impl IntoElement {
+        let theme = self.settings.reader_theme.resolve(&self.theme);
+        let links = self.article_links(cx);
 
-        let content = match &reader.state {
-            ReaderLoadState::Loading => self.render_reader_loading().into_any_element(),
-            ReaderLoadState::Error(message) => self
-                .render_reader_error(message, reader, cx)
-                .into_any_element(),
-            ReaderLoadState::Ready(article) => {
-                self.render_reader_article(article).into_any_element()
-            }
-        };
+        let meta = [
+            article.site_name.clone().unwrap_or_default(),
+            article.byline.clone().unwrap_or_default(),
+            article.reading_time.clone().unwrap_or_default(),
+        ]
+        .into_iter()
+        .filter(|s| !s.is_empty())
+        .collect::<Vec<_>>()
+        .join(" · ");
+
+        let minimap = self.render_reader_minimap(article, cx);
+        let toc = self.render_reader_toc(article, cx);
+        let font_scale = self.reader_font_scale();
+        let heading_tops = self.reader_heading_tops(article);
 
         div()
-            .id("reader-page")
+            .relative()
             .flex_1()
             .min_h(px(0.))
             .w_full()
             .min_w(px(0.))
             .flex()
             .flex_col()
-            .overflow_hidden()
+            .bg(theme.bg_primary)
+            .text_color(theme.text_primary)
             .child(
                 div()
+                    .id("reader-article-scroll")
+                    .flex_1()
+                    .min_h(px(0.))
                     .w_full()
-                    .flex_shrink_0()
-                    .p_6()
-                    .bg(theme.bg_secondary)
-                    .border_b_1()
-                    .border_color(theme.border)
+                    .min_w(px(0.))
+                    .overflow_y_scroll()
+                    .overflow_x_hidden()
+                    .track_scroll(&self.reader_scroll_handle)
+                    .on_scroll_wheel(cx.listener(move |this, _event: &ScrollWheelEvent, cx| {
+                        this.on_reader_scrolled(cx);
+                        // 小地图的视口位置和目录的当前章节跟随滚动
+                        let position = this.reader_outline_position(&heading_tops);
+                        if position != this.reader_outline_position {
+                            this.reader_outline_position = position;
+                            cx.notify();
+                        }
+                    }))
                     .child(
                         div()
                             .w_full()
                             .min_w(px(0.))
                             .flex()
-                            .items_center()
-                            .justify_between()
-                            .gap_4()
+                            .justify_center()
+                            .overflow_hidden()
                             .child(
                                 div()
+                                    .w_full()
                                     .min_w(px(0.))
+                                    .max_w(px(self.settings.reader_width.max_width()))
+                                    .font_family(self.settings.reader_font.family())
+                                    .px_8()
+                                    .py_10()
                                     .flex()
-                                    .items_center()
-                                    .gap_3()
+                                    .flex_col()
+                                    .gap_6()
+                                    .overflow_hidden()
+                                    .when_some(
+                                        article
+                                            .hero_image
+                                            .clone()
+                                            .filter(|_| self.settings.show_hero_image),
+                                        |this, url| {
+                                            this.child(
+                                                img(url)
+                                                    .w_full()
+                                                    .max_h(px(360.))
+                                                    .rounded_md()
+                                                    .object_fit(ObjectFit::Cover),
+                                            )
+                                        },
+                                    )
                                     .child(
                                         div()
-                                            .id("reader-back")
-                                            .cursor_pointer()
-                                            .text_color(text_secondary)
-                                            .hover(move |s| s.text_color(text_primary))
-                                            .on_click(cx.listener(|this, _event, cx| {
-                                                this.close_reader(cx);
-                                            }))
-                                            .child("← Back"),
+                                            .flex()
+                                            .flex_col()
+                                            .gap_2()
+                                            .child(
+                                                div()
+                                                    .text_size(rems(1.25 * font_scale))
+                                                    .font_weight(FontWeight::SEMIBOLD)
+                                                    .line_height(rems(1.3 * font_scale))
+                                                    .whitespace_normal()
+                                                    .child(article.title.clone()),
+                                            )
+                                            .when(!meta.is_empty(), |this| {
+                                                this.child(
+                                                    div().text_sm().text_color(theme.text_muted).child(meta),
+                                                )
+                                            })
+                                            .when(article.low_confidence, |this| {
+                                                this.child(
+                                                    div()
+                                                        .text_xs()
+                                                        .text_color(theme.warning)
+                                                        .whitespace_normal()
+                                                        .child("Reader mode couldn't find a clear article body on this page; some content may be missing."),
+                                                )
+                                            })
+                                            .when(article.truncated, |this| {
+                                                this.child(self.render_reader_truncated_banner(cx))
+                                            }),
                                     )
                                     .child(
                                         div()
+                                            .id("reader-blocks")
+                                            .track_scroll(&self.reader_blocks_scroll_handle)
+                                            .w_full()
                                             .min_w(px(0.))
-                                            .text_sm()
-                                            .text_color(theme.text_muted)
-                                            .overflow_hidden()
-                                            .child(title),
-                                    ),
-                            )
-                            .child(
-                                div()
-                                    .flex()
-                                    .items_center()
-                                    .gap_3()
-                                    .when_some(scroll_debug, |this, debug| {
-                                        this.child(
-                                            div()
-                                                .text_xs()
-                                                .text_color(theme.text_muted)
-                                                .child(debug),
-                                        )
-                                    })
-                                    .child(
-                                        div()
-                                            .id("reader-open-external")
-                                            .cursor_pointer()
-                                            .text_color(accent)
-                                            .hover(move |s| s.text_color(accent_hover))
-                                            .on_click(cx.listener(move |_this, _event, _cx| {
-                                                let _ = open::that(&url);
-                                            }))
-                                            .child("Open in Browser ↗"),
+                                            .flex()
+                                            .flex_col()
+                                            .gap_6()
+                                            .children(
+                                                article
+                                                    .blocks
+                                                    .iter()
+                                                    .enumerate()
+                                                    .map(|(index, block)| {
+                                                        self.render_reader_block(index, block, &links, cx)
+                                                    })
+                                                    .collect::<Vec<_>>(),
+                                            ),
                                     ),
                             ),
                     ),
             )
-            .child(content)
+            .children(minimap)
+            .children(toc)
+    }
+
+    fn toggle_reader_minimap(&mut self, cx: &mut ViewContext<Self>) {
+        self.settings.show_reader_minimap = !self.settings.show_reader_minimap;
+        if let Err(e) = self.settings.save(&self.dirs.data) {
+            self.show_toast(format!("Failed to save settings: {}", e), cx);
+        }
+        cx.notify();
     }
 
-    fn render_reader_loading(&self) -> impl IntoElement {
+    fn toggle_clean_comment_layout(&mut self, cx: &mut ViewContext<Self>) {
+        self.settings.clean_comment_layout = !self.settings.clean_comment_layout;
+        if let Err(e) = self.settings.save(&self.dirs.data) {
+            self.show_toast(format!("Failed to save settings: {}", e), cx);
+        }
+        // 已加载的评论也要换成块，否则每帧都得重新解析
+        self.rebuild_comment_blocks();
+        self.sync_comment_list();
+        cx.notify();
+    }
+
+    /// 长文右侧的大纲小地图：标题位置和当前视口，点击标题跳转。
+    /// 位置取自上一帧的布局，首帧还没有布局时不显示
+    fn render_reader_minimap(
+        &self,
+        article: &reader::ReaderArticle,
+        cx: &mut ViewContext<Self>,
+    ) -> Option<AnyElement> {
+        if !self.settings.show_reader_minimap
+            || article.reading_minutes().unwrap_or(0) < reader_view::MINIMAP_MIN_READING_MINUTES
+        {
+            return None;
+        }
+
+        let content_height = self.reader_scroll_handle.bounds_for_item(0)?.size.height.0;
+        let headings = self.reader_heading_tops(article);
+        if headings.is_empty() {
+            return None;
+        }
+
         let theme = &self.theme;
+        let accent = theme.accent;
+        let markers = reader_view::minimap_markers(&headings, content_height);
+        let (viewport_start, viewport_length) = reader_view::minimap_viewport(
+            -self.reader_scroll_handle.offset().y.0,
+            self.reader_scroll_handle.bounds().size.height.0,
+            content_height,
+        );
 
-        let skeleton_bar = |max_w: f32, h: f32| {
+        Some(
             div()
-                .h(px(h))
-                .w_full()
-                .max_w(px(max_w))
-                .rounded(px(3.))
-                .bg(theme.bg_tertiary)
+                .absolute()
+                .top_2()
+                .bottom_2()
+                .right_1()
+                .w(px(12.))
+                .child(
+                    div()
+                        .absolute()
+                        .left_0()
+                        .right_0()
+                        .top(relative(viewport_start))
+                        .h(relative(viewport_length))
+                        .rounded(px(3.))
+                        .bg(theme.bg_hover),
+                )
+                .children(markers.into_iter().map(|(index, position)| {
+                    div()
+                        .id(ElementId::Name(format!("minimap-heading-{index}").into()))
+                        .absolute()
+                        .left_0()
+                        .right_0()
+                        .top(relative(position))
+                        .h(px(3.))
+                        .rounded(px(1.))
+                        .bg(theme.text_muted)
+                        .cursor_pointer()
+                        .hover(move |s| s.bg(accent))
+                        .on_click(cx.listener(move |this, _event, cx| {
+                            this.scroll_reader_to_block(index, cx);
+                        }))
+                }))
+                .into_any_element(),
+        )
+    }
+
+    /// 目录的当前章节和小地图视口顶部（按像素取整），没显示的那一项不参与比较
+    fn reader_outline_position(&self, heading_tops: &[(usize, f32)]) -> (Option<usize>, i32) {
+        let scroll_y = -self.reader_scroll_handle.offset().y.0;
+        let current = if self.reader_toc_open {
+            reader_view::current_toc_entry(heading_tops, scroll_y)
+        } else {
+            None
+        };
+        let viewport_top = if self.settings.show_reader_minimap {
+            let viewport_height = self.reader_scroll_handle.bounds().size.height.0;
+            let content_height = self
+                .reader_scroll_handle
+                .bounds_for_item(0)
+                .map_or(0.0, |bounds| bounds.size.height.0);
+            let (start, _) =
+                reader_view::minimap_viewport(scroll_y, viewport_height, content_height);
+            // 小地图和视口差不多高
+            (start * viewport_height).round() as i32
+        } else {
+            0
         };
+        (current, viewport_top)
+    }
 
-        let placeholders: Vec<_> = (0..10)
-            .map(|i| {
-                let line_w = match i % 4 {
-                    0 => 640.0,
-                    1 => 720.0,
-                    2 => 680.0,
-                    _ => 560.0,
-                };
-                skeleton_bar(line_w, 12.0).into_any_element()
+    /// 各标题距文章顶部的位置 `(block 下标, top)`，取自上一帧的布局
+    fn reader_heading_tops(&self, article: &reader::ReaderArticle) -> Vec<(usize, f32)> {
+        let Some(content) = self.reader_scroll_handle.bounds_for_item(0) else {
+            return Vec::new();
+        };
+        article
+            .table_of_contents()
+            .into_iter()
+            .filter_map(|entry| {
+                let bounds = self
+                    .reader_blocks_scroll_handle
+                    .bounds_for_item(entry.index)?;
+                Some((entry.index, (bounds.top() - content.top()).0))
             })
-            .collect();
+            .collect()
+    }
+
+    /// 文章目录：右上角的 Contents 按钮，展开后按标题层级缩进列出，
+    /// 点击跳转，正在阅读的章节高亮
+    fn render_reader_toc(
+        &self,
+        article: &reader::ReaderArticle,
+        cx: &mut ViewContext<Self>,
+    ) -> Option<AnyElement> {
+        let entries = article.table_of_contents();
+        if entries.len() < reader_view::TOC_MIN_HEADINGS {
+            return None;
+        }
+
+        let theme = &self.theme;
+        let hover_bg = theme.bg_hover;
+        let accent = theme.accent;
+        let open = self.reader_toc_open;
+        let current = reader_view::current_toc_entry(
+            &self.reader_heading_tops(article),
+            -self.reader_scroll_handle.offset().y.0,
+        );
+        let top_level = entries.iter().map(|entry| entry.level).min().unwrap_or(1);
+
+        let toggle = div()
+            .id("reader-toc-toggle")
+            .px_2()
+            .py_1()
+            .rounded_md()
+            .text_xs()
+            .text_color(theme.text_secondary)
+            .cursor_pointer()
+            .hover(move |s| s.bg(hover_bg))
+            .on_click(cx.listener(|this, _event, cx| {
+                this.reader_toc_open = !this.reader_toc_open;
+                cx.notify();
+            }))
+            .child(if open { "Contents ▾" } else { "Contents ▸" });
+
+        Some(
+            div()
+                .absolute()
+                .top_2()
+                .right(px(20.))
+                .flex()
+                .flex_col()
+                .items_end()
+                .gap_1()
+                .child(toggle)
+                .when(open, |this| {
+                    this.child(
+                        div()
+                            .id("reader-toc")
+                            .w(px(260.))
+                            .max_h(px(420.))
+                            .overflow_y_scroll()
+                            .p_2()
+                            .flex()
+                            .flex_col()
+                            .rounded_md()
+                            .border_1()
+                            .border_color(theme.border_subtle)
+                            .bg(theme.bg_secondary)
+                            .shadow_md()
+                            .children(entries.into_iter().map(|entry| {
+                                let index = entry.index;
+                                let indent = reader_view::toc_indent(entry.level, top_level);
+                                let is_current = current == Some(index);
+                                div()
+                                    .id(ElementId::Name(format!("reader-toc-{index}").into()))
+                                    .pl(px(8. + 12. * f32::from(indent)))
+                                    .pr_2()
+                                    .py_1()
+                                    .rounded_md()
+                                    .text_xs()
+                                    .whitespace_normal()
+                                    .cursor_pointer()
+                                    .text_color(if is_current {
+                                        accent
+                                    } else {
+                                        theme.text_secondary
+                                    })
+                                    .when(is_current, |this| this.font_weight(FontWeight::SEMIBOLD))
+                                    .hover(move |s| s.bg(hover_bg))
+                                    .on_click(cx.listener(move |this, _event, cx| {
+                                        this.scroll_reader_to_block(index, cx);
+                                    }))
+                                    .child(entry.text)
+                            })),
+                    )
+                })
+                .into_any_element(),
+        )
+    }
+
+    fn scroll_reader_to_block(&mut self, index: usize, cx: &mut ViewContext<Self>) {
+        let (Some(content), Some(block)) = (
+            self.reader_scroll_handle.bounds_for_item(0),
+            self.reader_blocks_scroll_handle.bounds_for_item(index),
+        ) else {
+            return;
+        };
+        let offset = self.reader_scroll_handle.offset();
+        let y = -(block.top() - content.top()) + px(READER_HEADING_SCROLL_MARGIN);
+        self.reader_scroll_handle
+            .set_offset(point(offset.x, y.min(px(0.))));
+        self.on_reader_scrolled(cx);
+        cx.notify();
+    }
+
+    /// 文章停在需要 JS 展开的 "Continue reading" 处时，提示去浏览器看全文
+    fn render_reader_truncated_banner(&self, cx: &mut ViewContext<Self>) -> impl IntoElement {
+        let theme = &self.theme;
+        let accent = theme.accent;
+        let accent_hover = theme.accent_hover;
+        let url = self.reader().map(|reader| reader.browser_url().to_string());
 
         div()
-            .id("reader-loading-scroll")
+            .flex()
+            .items_center()
+            .justify_between()
+            .gap_3()
+            .px_3()
+            .py_2()
+            .rounded_md()
+            .bg(theme.bg_secondary)
+            .border_1()
+            .border_color(theme.border_subtle)
+            .text_xs()
+            .child(
+                div()
+                    .flex_1()
+                    .min_w(px(0.))
+                    .text_color(theme.warning)
+                    .whitespace_normal()
+                    .child("This article appears to be cut off at a \"Continue reading\" link that only works in a browser."),
+            )
+            .when_some(url, |this, url| {
+                this.child(
+                    div()
+                        .id("reader-truncated-open")
+                        .flex_shrink_0()
+                        .cursor_pointer()
+                        .text_color(accent)
+                        .hover(move |s| s.text_color(accent_hover))
+                        .on_click(cx.listener(move |_this, _event, _cx| {
+                            let _ = open::that(&url);
+                        }))
+                        .child("Open in browser"),
+                )
+            })
+    }
+
+    /// 标题、正文和评论都是 `comment_list` 里的项，长讨论只渲染视口附近的评论
+    fn render_story_detail(&self) -> impl IntoElement {
+        div()
+            .id("story-detail")
             .flex_1()
+            .min_h(px(0.))
             .w_full()
-            .overflow_y_scroll()
-            .child(
-                div().w_full().flex().justify_center().child(
+            .min_w(px(0.))
+            .flex()
+            .flex_col()
+            .child(list(self.comment_list.clone()).flex_1().w_full())
+    }
+
+    /// 虚拟列表按下标渲染：0 是评论区上方的内容，最后一项是“加载更多”
+    fn render_comment_list_item(&self, ix: usize, cx: &mut ViewContext<Self>) -> AnyElement {
+        let rows = self.comment_rows.indices.len();
+        if ix == 0 {
+            self.render_comment_list_top(cx)
+        } else if ix <= rows {
+            self.render_comment_row(ix - 1, cx)
+        } else {
+            div()
+                .w_full()
+                .px_6()
+                .pb_6()
+                .when(!self.is_loading_comments, |this| {
+                    this.children(self.render_load_more_comments(cx))
+                })
+                .into_any_element()
+        }
+    }
+
+    /// 详情页的标题和正文（分栏时正文在左侧），然后是评论区标题
+    fn render_comment_list_top(&self, cx: &mut ViewContext<Self>) -> AnyElement {
+        let story = self
+            .selected_story()
+            .filter(|_| !self.settings.split_view_on_select);
+        let story_text = story.and_then(|story| story.text.clone());
+        let text_primary = self.theme.text_primary;
+
+        div()
+            .w_full()
+            .min_w(px(0.))
+            .flex()
+            .flex_col()
+            // Header
+            .when_some(story, |this, story| {
+                this.child(self.render_story_header(story, cx))
+            })
+            // Story text if available
+            .when_some(story_text, move |this: Div, text: String| {
+                let clean_text = html_escape::decode_html_entities(&text).to_string();
+                this.child(
                     div()
                         .w_full()
-                        .max_w(px(760.))
-                        .px_8()
-                        .py_10()
-                        .flex()
-                        .flex_col()
-                        .gap_6()
-                        .child(
-                            div()
-                                .flex()
-                                .items_center()
-                                .gap_2()
-                                .text_color(theme.text_muted)
-                                .child("⏳")
-                                .child("Loading article…"),
-                        )
-                        .child(
-                            div()
-                                .w_full()
-                                .flex()
-                                .flex_col()
-                                .gap_3()
-                                .children(placeholders),
-                        ),
-                ),
-            )
+                        .p_6()
+                        .text_sm()
+                        .line_height(rems(1.6))
+                        .text_color(text_primary)
+                        .whitespace_normal()
+                        .child(clean_text),
+                )
+            })
+            .when(story.is_some_and(Story::is_poll), |this| {
+                this.child(self.render_poll_options())
+            })
+            .child(self.render_comments_header(cx))
+            .into_any_element()
     }
 
-    fn render_reader_error(
-        &self,
-        message: &str,
-        reader: &ReaderSession,
-        cx: &mut ViewContext<Self>,
-    ) -> impl IntoElement {
+    /// 投票帖的选项：文字、票数和按最高票数比例画的条
+    fn render_poll_options(&self) -> AnyElement {
         let theme = &self.theme;
-        let accent = theme.accent;
-        let accent_hover = theme.accent_hover;
-        let url = reader.url.clone();
-        let url_for_open = reader.url.clone();
-        let title_hint = reader.title_hint.clone();
-
-        // Convert technical error messages to user-friendly descriptions
-        let (friendly_title, friendly_message, suggestion) = Self::parse_error_message(message);
+        let Some(options) = self.poll_options.as_ref() else {
+            return div()
+                .w_full()
+                .px_6()
+                .pb_2()
+                .text_sm()
+                .text_color(theme.text_muted)
+                .child("Loading poll…")
+                .into_any_element();
+        };
+        let max_score = options
+            .iter()
+            .map(|option| option.score)
+            .max()
+            .unwrap_or(0)
+            .max(1);
 
         div()
-            .flex_1()
             .w_full()
+            .px_6()
+            .pb_2()
             .flex()
-            .items_center()
-            .justify_center()
-            .child(
+            .flex_col()
+            .gap_3()
+            .children(options.iter().map(|option| {
+                let share = option.score.max(0) as f32 / max_score as f32;
                 div()
                     .w_full()
-                    .max_w(px(480.))
-                    .p_8()
-                    .bg(theme.bg_secondary)
-                    .rounded_xl()
-                    .border_1()
-                    .border_color(theme.border_subtle)
                     .flex()
                     .flex_col()
-                    .items_center()
-                    .gap_5()
-                    // Error icon
-                    .child(
-                        div()
-                            .w(px(64.))
-                            .h(px(64.))
-                            .flex()
-                            .items_center()
-                            .justify_center()
-                            .rounded_full()
-                            .bg(hsla(0., 0.8, 0.95, 1.0))
-                            .text_2xl()
-                            .child("⚠️"),
-                    )
-                    // Title
-                    .child(
-                        div()
-                            .w_full()
-                            .flex()
-                            .justify_center()
-                            .text_lg()
-                            .font_weight(FontWeight::SEMIBOLD)
-                            .child(friendly_title),
-                    )
-                    // Description
+                    .gap_1()
                     .child(
                         div()
                             .w_full()
                             .flex()
-                            .flex_col()
                             .items_center()
-                            .gap_2()
+                            .gap_3()
+                            .text_sm()
                             .child(
                                 div()
-                                    .text_sm()
-                                    .text_color(theme.text_secondary)
-                                    .whitespace_normal()
-                                    .child(friendly_message),
+                                    .flex_1()
+                                    .min_w(px(0.))
+                                    .text_color(theme.text_primary)
+                                    .child(option.label()),
                             )
-                            .when_some(suggestion, |this, suggestion| {
-                                this.child(
-                                    div()
-                                        .text_sm()
-                                        .text_color(theme.text_muted)
-                                        .whitespace_normal()
-                                        .child(suggestion),
-                                )
-                            }),
+                            .child(
+                                div()
+                                    .text_xs()
+                                    .text_color(theme.text_muted)
+                                    .child(format!("{} points", option.score)),
+                            ),
                     )
-                    // URL display
                     .child(
                         div()
                             .w_full()
-                            .px_3()
-                            .py_2()
+                            .h(px(6.))
+                            .rounded_full()
                             .bg(theme.bg_tertiary)
-                            .rounded_md()
-                            .text_xs()
-                            .text_color(theme.text_muted)
-                            .overflow_hidden()
-                            .text_ellipsis()
-                            .child(reader.url.clone()),
-                    )
-                    // Action buttons
-                    .child(
-                        div()
-                            .flex()
-                            .items_center()
-                            .gap_3()
-                            .child(
-                                div()
-                                    .id("reader-retry")
-                                    .cursor_pointer()
-                                    .rounded_md()
-                                    .px_4()
-                                    .py_2()
-                                    .bg(theme.accent)
-                                    .text_color(hsla(0., 0., 1., 1.0))
-                                    .text_sm()
-                                    .font_weight(FontWeight::MEDIUM)
-                                    .hover(move |s| s.bg(accent_hover))
-                                    .on_click(cx.listener(move |this, _event, cx| {
-                                        this.open_reader(url.clone(), title_hint.clone(), cx);
-                                    }))
-                                    .child("Try Again"),
-                            )
                             .child(
                                 div()
-                                    .id("reader-open-browser")
-                                    .cursor_pointer()
-                                    .rounded_md()
-                                    .px_4()
-                                    .py_2()
-                                    .border_1()
-                                    .border_color(theme.border)
-                                    .text_color(accent)
-                                    .text_sm()
-                                    .font_weight(FontWeight::MEDIUM)
-                                    .hover(move |s| s.bg(theme.bg_hover))
-                                    .on_click(cx.listener(move |_this, _event, _cx| {
-                                        let _ = open::that(&url_for_open);
-                                    }))
-                                    .child("Open in Browser"),
+                                    .h_full()
+                                    .w(relative(share))
+                                    .rounded_full()
+                                    .bg(theme.accent),
                             ),
-                    ),
-            )
+                    )
+            }))
+            .into_any_element()
     }
 
-    fn parse_error_message(message: &str) -> (String, String, Option<String>) {
-        let msg_lower = message.to_lowercase();
+    fn render_story_header(&self, story: &Story, cx: &mut ViewContext<Self>) -> impl IntoElement {
+        let theme = &self.theme;
+        let action = story.primary_action(self.settings.reader_default_source);
+        // 完整 story 到达前没有正文，别把文字帖先显示成“Discuss on HN”
+        let waiting_for_story = self.is_loading_comments && story.kids.is_none();
+        let action =
+            (action != StoryAction::Discuss || !waiting_for_story).then(|| (action, story.clone()));
+        let accent = theme.accent;
+        let accent_hover = theme.accent_hover;
+        let story_id = story.id;
+        let bookmarked = self.bookmarks.contains(story_id);
 
-        if msg_lower.contains("error sending request") || msg_lower.contains("connection") {
-            (
-                "Unable to connect".to_string(),
-                "The page couldn't be reached. This might be a network issue or the website may be unavailable.".to_string(),
-                Some("Check your internet connection and try again.".to_string()),
-            )
-        } else if msg_lower.contains("timeout") {
-            (
-                "Request timed out".to_string(),
-                "The server took too long to respond.".to_string(),
-                Some("The website might be experiencing high traffic. Try again later.".to_string()),
-            )
-        } else if msg_lower.contains("http 404") {
-            (
-                "Page not found".to_string(),
-                "The requested page doesn't exist or has been moved.".to_string(),
-                None,
-            )
-        } else if msg_lower.contains("http 403") {
-            (
-                "Access denied".to_string(),
-                "You don't have permission to view this page.".to_string(),
-                Some("Try opening it in your browser instead.".to_string()),
-            )
-        } else if msg_lower.contains("http 5") {
-            (
-                "Server error".to_string(),
-                "The website is experiencing technical difficulties.".to_string(),
-                Some("Try again later or open in browser.".to_string()),
-            )
-        } else if msg_lower.contains("unsupported content type") {
-            (
-                "Unsupported content".to_string(),
-                "This type of content can't be displayed in reader mode.".to_string(),
-                Some("Try opening it in your browser instead.".to_string()),
-            )
-        } else if msg_lower.contains("invalid url") {
-            (
-                "Invalid URL".to_string(),
-                "The link appears to be malformed or invalid.".to_string(),
-                None,
-            )
-        } else if msg_lower.contains("too large") {
-            (
-                "Page too large".to_string(),
-                "This page is too large to load in reader mode.".to_string(),
-                Some("Try opening it in your browser instead.".to_string()),
-            )
-        } else {
-            (
-                "Couldn't load this page".to_string(),
-                message.to_string(),
-                Some("Try opening it in your browser instead.".to_string()),
+        div()
+            .w_full()
+            .p_6()
+            .bg(theme.bg_secondary)
+            .border_b_1()
+            .border_color(theme.border)
+            .child(
+                div()
+                    .w_full()
+                    .flex()
+                    .flex_col()
+                    .gap_3()
+                    .overflow_hidden()
+                    // Title
+                    .child(
+                        div()
+                            .w_full()
+                            .text_xl()
+                            .font_weight(FontWeight::SEMIBOLD)
+                            .line_height(rems(1.4))
+                            .whitespace_normal()
+                            .child(story.title.clone()),
+                    )
+                    // Meta
+                    .child(
+                        div()
+                            .w_full()
+                            .min_w(px(0.))
+                            .flex()
+                            .flex_row()
+                            .items_center()
+                            .flex_wrap()
+                            .gap_4()
+                            .text_sm()
+                            // Score
+                            .child(
+                                div()
+                                    .flex()
+                                    .items_center()
+                                    .gap_1()
+                                    .text_color(theme.accent)
+                                    .child("▲")
+                                    .child(format!("{} points", story.score)),
+                            )
+                            // Author
+                            .child(
+                                div()
+                                    .text_color(theme.text_secondary)
+                                    .child(format!("by {}", story.by)),
+                            )
+                            // Time
+                            .child(self.render_timestamp(story.time, theme.text_muted))
+                            // Read / Discuss
+                            .when_some(action, |this: Div, (action, story)| {
+                                this.child(
+                                    div()
+                                        .id("open-link-btn")
+                                        .cursor_pointer()
+                                        .text_color(accent)
+                                        .hover(move |s| s.text_color(accent_hover))
+                                        .on_click(cx.listener(move |this, _event, cx| {
+                                            this.run_story_action(&story, action, cx);
+                                        }))
+                                        .child(action.label()),
+                                )
+                            })
+                            // Bookmark
+                            .child(
+                                div()
+                                    .id("bookmark-story")
+                                    .cursor_pointer()
+                                    .text_color(if bookmarked { accent } else { theme.text_muted })
+                                    .hover(move |s| s.text_color(accent_hover))
+                                    .on_click(cx.listener(move |this, _event, cx| {
+                                        this.toggle_bookmark(story_id, cx);
+                                    }))
+                                    .child(if bookmarked { "★ Saved" } else { "☆ Save" }),
+                            )
+                            // Snooze
+                            .children(
+                                [
+                                    SnoozeDuration::Hours(snooze::SNOOZE_HOURS),
+                                    SnoozeDuration::UntilTomorrow,
+                                ]
+                                .into_iter()
+                                .enumerate()
+                                .map(|(index, duration)| {
+                                    let story = story.clone();
+                                    div()
+                                        .id(ElementId::Name(format!("snooze-{}", index).into()))
+                                        .cursor_pointer()
+                                        .text_color(theme.text_muted)
+                                        .hover(move |s| s.text_color(accent_hover))
+                                        .on_click(cx.listener(move |this, _event, cx| {
+                                            this.snooze_story(&story, duration, cx);
+                                        }))
+                                        .child(duration.label())
+                                }),
+                            ),
+                    ),
             )
-        }
     }
 
-    fn render_reader_block(&self, block: &reader::ReaderBlock) -> AnyElement {
-        reader_view::render_reader_block(&self.theme, block)
-    }
-
-    fn render_reader_article(&self, article: &reader::ReaderArticle) -> impl IntoElement {
+    fn render_comments_loading_indicator(&self, viewport_height: f32) -> Div {
         let theme = &self.theme;
 
-        let meta = [
-            article.site_name.clone().unwrap_or_default(),
-            article.byline.clone().unwrap_or_default(),
-            article.reading_time.clone().unwrap_or_default(),
-        ]
-        .into_iter()
-        .filter(|s| !s.is_empty())
-        .collect::<Vec<_>>()
-        .join(" · ");
+        let skeleton_bar = |max_w: f32, h: f32| {
+            div()
+                .h(px(h))
+                .w_full()
+                .max_w(px(max_w))
+                .rounded(px(3.))
+                .bg(theme.bg_tertiary)
+        };
+
+        let count = skeleton::skeleton_count(viewport_height, skeleton::COMMENT_CARDS);
+        let placeholders: Vec<_> = (0..count)
+            .map(|i| {
+                let indent = (i.min(2) * 16) as f32;
+                let line_1 = match i % 3 {
+                    0 => 360.0,
+                    1 => 300.0,
+                    _ => 260.0,
+                };
 
-        div()
-            .id("reader-article-scroll")
-            .flex_1()
-            .min_h(px(0.))
-            .w_full()
-            .min_w(px(0.))
-            .overflow_y_scroll()
-            .overflow_x_hidden()
-            .track_scroll(&self.reader_scroll_handle)
-            .child(
                 div()
                     .w_full()
                     .min_w(px(0.))
-                    .flex()
-                    .justify_center()
-                    .overflow_hidden()
+                    .pl(px(indent))
                     .child(
                         div()
                             .w_full()
                             .min_w(px(0.))
-                            .max_w(px(760.))
-                            .px_8()
-                            .py_10()
-                            .flex()
-                            .flex_col()
-                            .gap_6()
-                            .overflow_hidden()
+                            .py_2()
+                            .px_3()
+                            .bg(theme.bg_secondary)
+                            .border_l_2()
+                            .border_color(theme.border_subtle)
                             .child(
                                 div()
+                                    .w_full()
+                                    .min_w(px(0.))
                                     .flex()
                                     .flex_col()
                                     .gap_2()
-                                    .child(
-                                        div()
-                                            .text_xl()
-                                            .font_weight(FontWeight::SEMIBOLD)
-                                            .line_height(rems(1.3))
-                                            .whitespace_normal()
-                                            .child(article.title.clone()),
-                                    )
-                                    .when(!meta.is_empty(), |this| {
-                                        this.child(
-                                            div().text_sm().text_color(theme.text_muted).child(meta),
-                                        )
-                                    }),
-                            )
-                            .children(
-                                article
-                                    .blocks
-                                    .iter()
-                                    .map(|block| self.render_reader_block(block))
-                                    .collect::<Vec<_>>(),
+                                    .child(skeleton_bar(140.0, 10.0))
+                                    .child(skeleton_bar(line_1, 10.0))
+                                    .child(skeleton_bar(240.0, 10.0)),
                             ),
-                    ),
+                    )
+                    .into_any_element()
+            })
+            .collect();
+
+        div()
+            .w_full()
+            .py_8()
+            .flex()
+            .flex_col()
+            .items_center()
+            .gap_4()
+            .text_color(theme.text_muted)
+            .child(
+                div()
+                    .flex()
+                    .items_center()
+                    .gap_2()
+                    .child("💬")
+                    .child("Loading comments…"),
+            )
+            .child(
+                self.pulse_skeleton(
+                    "comments-skeleton",
+                    div()
+                        .w_full()
+                        .flex()
+                        .flex_col()
+                        .gap_2()
+                        .children(placeholders),
+                ),
             )
     }
 
-    fn render_story_detail(&self, story: &Story, cx: &mut ViewContext<Self>) -> impl IntoElement {
+    /// 评论区标题和查找框；评论还没到或者没有评论时，提示也在这里
+    fn render_comments_header(&self, cx: &mut ViewContext<Self>) -> impl IntoElement {
         let theme = &self.theme;
 
-        // Clone values needed for closures
-        let story_text = story.text.clone();
-        let text_primary = theme.text_primary;
-
         div()
-            .id("story-detail")
-            .flex_1()
             .w_full()
             .min_w(px(0.))
             .flex()
             .flex_col()
-            .overflow_y_scroll()
-            // Header
-            .child(self.render_story_header(story, cx))
-            // Story text if available
-            .when_some(story_text, move |this: Stateful<Div>, text: String| {
-                let clean_text = html_escape::decode_html_entities(&text).to_string();
-                this.child(
+            .px_6()
+            .pt_6()
+            .overflow_x_hidden()
+            // Comments header
+            .child(
+                div()
+                    .flex()
+                    .items_center()
+                    .gap_2()
+                    .mb_4()
+                    .text_base()
+                    .font_weight(FontWeight::SEMIBOLD)
+                    .child("Comments")
+                    .child(
+                        div()
+                            .text_sm()
+                            .text_color(theme.text_muted)
+                            .child(format!("({})", self.comments.len())),
+                    )
+                    .when(!self.comments.is_empty(), |this| {
+                        this.child(div().flex_1())
+                            .child(self.render_comment_sort_control(cx))
+                            .child(self.render_collapse_all_buttons(cx))
+                    }),
+            )
+            .when_some(self.comment_search.as_ref(), |this, search| {
+                this.child(self.render_comment_search_bar(search, cx))
+            })
+            .children(if self.is_loading_comments {
+                Some(
+                    self.render_comments_loading_indicator(cx.viewport_size().height.0)
+                        .into_any_element(),
+                )
+            } else if self.comments.is_empty() {
+                // 有评论数却一条也没拿到：评论已被删除（常见于很早收藏的 story）
+                let gone = self
+                    .selected_story
+                    .as_ref()
+                    .is_some_and(|story| story.comment_count() > 0);
+                Some(
                     div()
                         .w_full()
-                        .p_6()
-                        .text_sm()
-                        .line_height(rems(1.6))
-                        .text_color(text_primary)
-                        .whitespace_normal()
-                        .child(clean_text),
+                        .py_8()
+                        .flex()
+                        .justify_center()
+                        .text_color(theme.text_muted)
+                        .child(if gone {
+                            "Comments are no longer available"
+                        } else {
+                            "No comments yet"
+                        })
+                        .into_any_element(),
                 )
+            } else {
+                None
             })
-            // Comments section
-            .child(self.render_comments_section(cx))
     }
 
-    fn render_story_header(&self, story: &Story, cx: &mut ViewContext<Self>) -> impl IntoElement {
+    /// 一条可见评论；卡片布局的底色和边框画在每一行上，首尾两行带圆角
+    fn render_comment_row(&self, row: usize, cx: &mut ViewContext<Self>) -> AnyElement {
+        let rows = &self.comment_rows;
+        let comment = &self.comments[rows.indices[row]];
+        let first = row == 0;
+        let last = row + 1 == rows.indices.len();
+        let missing = rows.missing.get(&comment.id).copied();
+
+        if self.settings.clean_comment_layout {
+            return div()
+                .w_full()
+                .min_w(px(0.))
+                .px_6()
+                .flex()
+                .justify_center()
+                .child(
+                    div()
+                        .w_full()
+                        .min_w(px(0.))
+                        .max_w(px(760.))
+                        .when(first, |this| this.pt_4())
+                        .when(last, |this| this.pb_4())
+                        .when(!last, |this| this.pb_8())
+                        .child(self.render_clean_comment(comment, missing, cx)),
+                )
+                .into_any_element();
+        }
+
+        let total = rows
+            .totals
+            .as_ref()
+            .and_then(|totals| totals.get(&comment.id).copied());
+        div()
+            .w_full()
+            .min_w(px(0.))
+            .px_6()
+            .child(
+                div()
+                    .w_full()
+                    .min_w(px(0.))
+                    .px_2()
+                    .pb_2()
+                    .bg(self.theme.bg_secondary)
+                    .border_x_1()
+                    .border_color(self.theme.border_subtle)
+                    .when(first, |this| this.pt_2().border_t_1().rounded_t_md())
+                    .when(last, |this| this.border_b_1().rounded_b_md())
+                    .child(self.render_comment(comment, total, missing, cx)),
+            )
+            .into_any_element()
+    }
+
+    /// 顶级评论超过每层上限时，列表末尾的“加载更多”
+    fn render_load_more_comments(&self, cx: &mut ViewContext<Self>) -> Option<AnyElement> {
+        let remaining = self.remaining_root_comments();
+        if remaining == 0 {
+            return None;
+        }
         let theme = &self.theme;
-        let url = story.url.clone();
-        let title_hint = story.title.clone();
-        let accent = theme.accent;
         let accent_hover = theme.accent_hover;
 
+        let button = div()
+            .id("load-more-comments")
+            .w_full()
+            .py_3()
+            .flex()
+            .justify_center()
+            .text_xs();
+        Some(if self.is_loading_more_comments {
+            button
+                .text_color(theme.text_muted)
+                .child("Loading more comments…")
+                .into_any_element()
+        } else {
+            button
+                .cursor_pointer()
+                .text_color(theme.accent)
+                .hover(move |s| s.text_color(accent_hover))
+                .on_click(cx.listener(|this, _event, cx| {
+                    this.load_more_root_comments(cx);
+                }))
+                .child(format!("Load more top-level comments ({} left)", remaining))
+                .into_any_element()
+        })
+    }
+
+    /// 评论区标题右侧的“全部折叠/全部展开”
+    fn render_comment_sort_control(&self, cx: &mut ViewContext<Self>) -> impl IntoElement {
+        let text_secondary = self.theme.text_secondary;
+        let text_primary = self.theme.text_primary;
+        let accent = self.theme.accent;
+        let current = self.comment_sort;
+
+        div()
+            .flex()
+            .items_center()
+            .gap_2()
+            .mr_2()
+            .text_xs()
+            .font_weight(FontWeight::NORMAL)
+            .children(CommentSort::ALL.map(|sort| {
+                let selected = sort == current;
+                div()
+                    .id(ElementId::Name(
+                        format!("comment-sort-{}", sort.label()).into(),
+                    ))
+                    .cursor_pointer()
+                    .text_color(if selected { accent } else { text_secondary })
+                    .when(!selected, |this| {
+                        this.hover(move |s| s.text_color(text_primary))
+                    })
+                    .on_click(cx.listener(move |this, _event, cx| {
+                        this.set_comment_sort(sort, cx);
+                    }))
+                    .child(sort.label())
+            }))
+    }
+
+    fn render_collapse_all_buttons(&self, cx: &mut ViewContext<Self>) -> impl IntoElement {
+        let text_secondary = self.theme.text_secondary;
+        let text_primary = self.theme.text_primary;
+
+        div()
+            .flex()
+            .items_center()
+            .gap_3()
+            .text_xs()
+            .font_weight(FontWeight::NORMAL)
+            .children(
+                [("Collapse all", true), ("Expand all", false)].map(|(label, collapse)| {
+                    div()
+                        .id(label)
+                        .cursor_pointer()
+                        .text_color(text_secondary)
+                        .hover(move |s| s.text_color(text_primary))
+                        .on_click(cx.listener(move |this, _event, cx| {
+                            if collapse {
+                                this.collapse_all_comments(cx);
+                            } else {
+                                this.expand_all_comments(cx);
+                            }
+                        }))
+                        .child(label)
+                }),
+            )
+    }
+
+    /// 整洁布局：评论用阅读模式的排版居中显示，层级只用细线和少量缩进表示
+    fn render_clean_comment(
+        &self,
+        comment: &Comment,
+        missing_replies: Option<usize>,
+        cx: &mut ViewContext<Self>,
+    ) -> impl IntoElement {
+        let comment_id = comment.id;
+        let links = self.inline_links(cx);
+        // 段落跟随评论的颜色，看过的评论照样变暗
+        let layout = reader_view::ReaderLayout {
+            text_color: Some(self.comment_text_color(comment_id)),
+            ..self.reader_layout()
+        };
+        let reader_images = self.reader_images(cx);
+        let theme = &self.theme;
+        let text_muted = theme.text_muted;
+        let text_primary = theme.text_primary;
+
+        let is_collapsed = self.is_collapsed(comment_id);
+        let arrow = if is_collapsed { "▸" } else { "▾" };
+        let fallback;
+        let blocks = match self.comment_blocks.get(&comment_id) {
+            Some(blocks) => blocks,
+            None => {
+                fallback = reader_view::comment_blocks(comment);
+                &fallback
+            }
+        };
+
         div()
+            .id(ElementId::Name(format!("comment-{}", comment_id).into()))
+            .on_hover(cx.listener(move |this, hovered: &bool, _cx| {
+                if *hovered {
+                    this.hovered_comment = Some(comment_id);
+                } else if this.hovered_comment == Some(comment_id) {
+                    this.hovered_comment = None;
+                }
+            }))
             .w_full()
-            .p_6()
-            .bg(theme.bg_secondary)
-            .border_b_1()
-            .border_color(theme.border)
+            .min_w(px(0.))
+            .flex_shrink_0()
+            .pl(px((comment.depth.min(5) * 12) as f32))
             .child(
                 div()
                     .w_full()
+                    .min_w(px(0.))
                     .flex()
                     .flex_col()
                     .gap_3()
-                    .overflow_hidden()
-                    // Title
-                    .child(
-                        div()
-                            .w_full()
-                            .text_xl()
-                            .font_weight(FontWeight::SEMIBOLD)
-                            .line_height(rems(1.4))
-                            .whitespace_normal()
-                            .child(story.title.clone()),
-                    )
-                    // Meta
+                    .when(comment.depth > 0, |this| {
+                        this.pl_4().border_l_1().border_color(theme.border_subtle)
+                    })
                     .child(
                         div()
-                            .w_full()
-                            .min_w(px(0.))
+                            .id(ElementId::Name(
+                                format!("comment-header-{}", comment_id).into(),
+                            ))
+                            .group(format!("comment-header-{}", comment_id))
                             .flex()
-                            .flex_row()
                             .items_center()
                             .flex_wrap()
-                            .gap_4()
+                            .gap_2()
                             .text_sm()
-                            // Score
-                            .child(
-                                div()
-                                    .flex()
-                                    .items_center()
-                                    .gap_1()
-                                    .text_color(theme.accent)
-                                    .child("▲")
-                                    .child(format!("{} points", story.score)),
-                            )
-                            // Author
-                            .child(
-                                div()
-                                    .text_color(theme.text_secondary)
-                                    .child(format!("by {}", story.by)),
-                            )
-                            // Time
+                            .when(comment.has_replies(), |this| {
+                                this.cursor_pointer()
+                                    .on_click(cx.listener(move |this, _event, cx| {
+                                        this.toggle_collapse(comment_id, cx);
+                                    }))
+                                    .child(
+                                        div()
+                                            .text_color(text_muted)
+                                            .child(format!("{} {}", arrow, comment.reply_count)),
+                                    )
+                            })
                             .child(
                                 div()
-                                    .text_color(theme.text_muted)
-                                    .child(story.formatted_time()),
+                                    .font_weight(FontWeight::SEMIBOLD)
+                                    .text_color(text_primary)
+                                    .child(comment.author().to_string()),
                             )
-                            // Link
-                            .when_some(url, |this: Div, url: String| {
-                                let title_hint = title_hint.clone();
-                                this.child(
-                                    div()
-                                        .id("open-link-btn")
-                                        .cursor_pointer()
-                                        .text_color(accent)
-                                        .hover(move |s| s.text_color(accent_hover))
-                                        .on_click(cx.listener(move |this, _event, cx| {
-                                            this.open_reader(
-                                                url.clone(),
-                                                Some(title_hint.clone()),
-                                                cx,
-                                            );
-                                        }))
-                                        .child("Read"),
+                            .child(self.render_timestamp(comment.time, text_muted))
+                            .child(self.render_comment_actions(comment_id, cx)),
+                    )
+                    .when(!is_collapsed, |this| {
+                        this.text_color(self.comment_text_color(comment_id))
+                            .children(blocks.iter().enumerate().map(|(index, block)| {
+                                reader_view::render_reader_block(
+                                    theme,
+                                    index,
+                                    block,
+                                    &links,
+                                    &reader_images,
+                                    layout,
                                 )
-                            }),
-                    ),
+                            }))
+                            .children(self.render_comment_unfurls(comment, cx))
+                            .children(self.render_load_more_replies(
+                                comment_id,
+                                missing_replies,
+                                cx,
+                            ))
+                    }),
             )
     }
 
-    fn render_comments_loading_indicator(&self) -> Div {
+    /// 列表顶部的搜索框；输入时显示 Algolia 的搜索结果，清空恢复频道列表
+    fn render_story_search_box(&self, cx: &mut ViewContext<Self>) -> impl IntoElement {
         let theme = &self.theme;
+        let text_primary = theme.text_primary;
+        let focused = self.story_search_focus_handle.is_focused(cx);
+        let query = &self.story_search.query;
 
-        let skeleton_bar = |max_w: f32, h: f32| {
+        div().w_full().px_3().py_2().child(
             div()
-                .h(px(h))
+                .id("story-search")
+                .track_focus(&self.story_search_focus_handle)
+                .on_key_down(cx.listener(Self::on_story_search_key))
+                .on_click(cx.listener(|this, _event, cx| {
+                    cx.focus(&this.story_search_focus_handle);
+                }))
                 .w_full()
-                .max_w(px(max_w))
-                .rounded(px(3.))
-                .bg(theme.bg_tertiary)
-        };
-
-        let placeholders: Vec<_> = (0..6)
-            .map(|i| {
-                let indent = (i.min(2) * 16) as f32;
-                let line_1 = match i % 3 {
-                    0 => 360.0,
-                    1 => 300.0,
-                    _ => 260.0,
-                };
-
-                div()
-                    .w_full()
-                    .min_w(px(0.))
-                    .pl(px(indent))
-                    .child(
+                .px_3()
+                .py_1()
+                .flex()
+                .items_center()
+                .gap_2()
+                .rounded_md()
+                .border_1()
+                .border_color(if focused { theme.accent } else { theme.border })
+                .bg(theme.bg_primary)
+                .text_sm()
+                .child(
+                    div()
+                        .flex_1()
+                        .min_w(px(0.))
+                        .overflow_hidden()
+                        .when(query.is_empty() && !focused, |this| {
+                            this.text_color(theme.text_muted).child("Search stories")
+                        })
+                        .when(!query.is_empty() || focused, |this| {
+                            this.text_color(text_primary).child(format!("{}▏", query))
+                        }),
+                )
+                .when(!query.is_empty(), |this| {
+                    this.child(
                         div()
-                            .w_full()
-                            .min_w(px(0.))
-                            .py_2()
-                            .px_3()
-                            .bg(theme.bg_secondary)
-                            .border_l_2()
-                            .border_color(theme.border_subtle)
-                            .child(
-                                div()
-                                    .w_full()
-                                    .min_w(px(0.))
-                                    .flex()
-                                    .flex_col()
-                                    .gap_2()
-                                    .child(skeleton_bar(140.0, 10.0))
-                                    .child(skeleton_bar(line_1, 10.0))
-                                    .child(skeleton_bar(240.0, 10.0)),
-                            ),
+                            .id("story-search-clear")
+                            .cursor_pointer()
+                            .text_color(theme.text_muted)
+                            .hover(move |s| s.text_color(text_primary))
+                            .on_click(cx.listener(|this, _event, cx| {
+                                this.clear_story_search(cx);
+                            }))
+                            .child("✕"),
                     )
-                    .into_any_element()
-            })
-            .collect();
+                }),
+        )
+    }
+
+    /// Feeds 频道顶部：已订阅的源（可以删除）和添加订阅的输入框
+    fn render_feed_manager(&self, cx: &mut ViewContext<Self>) -> impl IntoElement {
+        let theme = &self.theme;
+        let text_primary = theme.text_primary;
+        let focused = self.feed_input_focus_handle.is_focused(cx);
+        let input = &self.feed_input;
 
         div()
             .w_full()
-            .py_8()
+            .px_3()
+            .pb_2()
             .flex()
             .flex_col()
-            .items_center()
-            .gap_4()
+            .gap_1()
+            .text_xs()
             .text_color(theme.text_muted)
-            .child(
+            .children(self.settings.feeds.iter().enumerate().map(|(ix, url)| {
+                let label = self.feeds.title(url);
+                let url = url.clone();
                 div()
                     .flex()
                     .items_center()
+                    .justify_between()
                     .gap_2()
-                    .child("💬")
-                    .child("Loading comments…"),
-            )
+                    .child(
+                        div()
+                            .flex_1()
+                            .min_w(px(0.))
+                            .overflow_hidden()
+                            .whitespace_nowrap()
+                            .text_ellipsis()
+                            .child(label),
+                    )
+                    .child(
+                        div()
+                            .id(ElementId::Name(format!("remove-feed-{}", ix).into()))
+                            .cursor_pointer()
+                            .hover(move |s| s.text_color(text_primary))
+                            .on_click(cx.listener(move |this, _event, cx| {
+                                this.remove_feed(&url, cx);
+                            }))
+                            .child("✕"),
+                    )
+            }))
             .child(
                 div()
+                    .id("feed-input")
+                    .track_focus(&self.feed_input_focus_handle)
+                    .on_key_down(cx.listener(Self::on_feed_input_key))
+                    .on_click(cx.listener(|this, _event, cx| {
+                        cx.focus(&this.feed_input_focus_handle);
+                    }))
                     .w_full()
-                    .flex()
-                    .flex_col()
-                    .gap_2()
-                    .children(placeholders),
+                    .px_3()
+                    .py_1()
+                    .rounded_md()
+                    .border_1()
+                    .border_color(if focused { theme.accent } else { theme.border })
+                    .bg(theme.bg_primary)
+                    .overflow_hidden()
+                    .whitespace_nowrap()
+                    .when(input.is_empty() && !focused, |this| {
+                        this.child("Add a feed URL (RSS or Atom)")
+                    })
+                    .when(!input.is_empty() || focused, |this| {
+                        this.text_color(text_primary).child(format!("{}▏", input))
+                    }),
             )
+            .when(!self.settings.feeds.is_empty(), |this| {
+                this.child(
+                    div()
+                        .id("export-feeds-opml")
+                        .cursor_pointer()
+                        .hover(move |s| s.text_color(text_primary))
+                        .on_click(cx.listener(|this, _event, cx| {
+                            this.export_feeds_opml(cx);
+                        }))
+                        .child("Export as OPML"),
+                )
+            })
     }
 
-    fn render_comments_section(&self, cx: &mut ViewContext<Self>) -> impl IntoElement {
+    fn render_comment_search_bar(
+        &self,
+        search: &CommentSearch,
+        cx: &mut ViewContext<Self>,
+    ) -> impl IntoElement {
         let theme = &self.theme;
+        let text_primary = theme.text_primary;
+        let focused = self.comment_search_focus_handle.is_focused(cx);
 
         div()
+            .id("comment-search")
+            .track_focus(&self.comment_search_focus_handle)
+            .on_key_down(cx.listener(Self::on_comment_search_key))
+            .on_click(cx.listener(|this, _event, cx| {
+                cx.focus(&this.comment_search_focus_handle);
+            }))
             .w_full()
-            .min_w(px(0.))
+            .mb_3()
+            .px_3()
+            .py_2()
             .flex()
-            .flex_col()
-            .p_6()
-            .overflow_x_hidden()
-            // Comments header
+            .items_center()
+            .gap_3()
+            .rounded_md()
+            .border_1()
+            .border_color(if focused { theme.accent } else { theme.border })
+            .bg(theme.bg_primary)
+            .text_sm()
+            .child(div().text_color(theme.text_muted).child("Find"))
             .child(
                 div()
-                    .flex()
-                    .items_center()
-                    .gap_2()
-                    .mb_4()
-                    .text_base()
-                    .font_weight(FontWeight::SEMIBOLD)
-                    .child("Comments")
-                    .child(
-                        div()
-                            .text_sm()
-                            .text_color(theme.text_muted)
-                            .child(format!("({})", self.comments.len())),
-                    ),
+                    .flex_1()
+                    .min_w(px(0.))
+                    .overflow_hidden()
+                    .text_color(text_primary)
+                    .child(format!("{}▏", search.query)),
             )
-            // Comments list or loading
-            .child(if self.is_loading_comments {
-                self.render_comments_loading_indicator()
-            } else if self.comments.is_empty() {
+            .child(
                 div()
-                    .w_full()
-                    .py_8()
-                    .flex()
-                    .justify_center()
+                    .text_xs()
                     .text_color(theme.text_muted)
-                    .child("No comments yet")
-            } else {
+                    .child(search.status()),
+            )
+            .child(
                 div()
-                    .w_full()
-                    .min_w(px(0.))
-                    .flex()
-                    .flex_col()
-                    .gap_2()
-                    .p_2()
-                    .bg(theme.bg_secondary)
-                    .rounded_md()
-                    .border_1()
-                    .border_color(theme.border_subtle)
-                    .children(
-                        self.visible_comments()
-                            .into_iter()
-                            .map(|c| self.render_comment(c, cx)),
-                    )
+                    .id("comment-search-close")
+                    .cursor_pointer()
+                    .text_color(theme.text_muted)
+                    .hover(move |s| s.text_color(text_primary))
+                    .on_click(cx.listener(|this, _event, cx| {
+                        this.clear_comment_search(cx);
+                    }))
+                    .child("✕"),
+            )
+    }
+
+    /// 查找时给命中的文字加背景，当前命中更醒目
+    fn render_comment_text(&self, comment_id: i64, text: String, color: Hsla) -> AnyElement {
+        let Some(search) = self
+            .comment_search
+            .as_ref()
+            .filter(|search| search.matches.contains(&comment_id))
+        else {
+            return text.into_any_element();
+        };
+
+        let background = if search.current_match() == Some(comment_id) {
+            self.theme.search_match_current
+        } else {
+            self.theme.search_match
+        };
+        let highlights = comment_search::match_ranges(&text, search.query.trim())
+            .into_iter()
+            .map(|range| {
+                (
+                    range,
+                    HighlightStyle {
+                        background_color: Some(background),
+                        ..Default::default()
+                    },
+                )
             })
+            .collect::<Vec<_>>();
+        let style = TextStyle {
+            color,
+            font_family: ".SystemUIFont".into(),
+            ..Default::default()
+        };
+        StyledText::new(text)
+            .with_highlights(&style, highlights)
+            .into_any_element()
     }
 
-    fn render_comment(&self, comment: &Comment, cx: &mut ViewContext<Self>) -> impl IntoElement {
+    fn render_comment(
+        &self,
+        comment: &Comment,
+        total_replies: Option<usize>,
+        missing_replies: Option<usize>,
+        cx: &mut ViewContext<Self>,
+    ) -> impl IntoElement {
         let theme = &self.theme;
         let depth = comment.depth;
         let comment_id = comment.id;
@@ -1404,19 +6774,31 @@ impl AppState {
         let border_color = border_colors[depth.min(border_colors.len() - 1)];
 
         let author = comment.author().to_string();
-        let time = comment.formatted_time();
+        let avatar = self
+            .settings
+            .show_comment_avatars
+            .then(|| comment.avatar())
+            .flatten();
         let text = comment.clean_text();
         let text_muted = theme.text_muted;
         let text_primary = theme.text_primary;
+        let body_color = self.comment_text_color(comment_id);
         let header_hover_bg = hsla(0., 0., 0.5, 0.06);
-        let collapse_label = if is_collapsed {
-            format!("▸ {}", reply_count)
-        } else {
-            format!("▾ {}", reply_count)
+        let arrow = if is_collapsed { "▸" } else { "▾" };
+        let collapse_label = match total_replies {
+            Some(total) if total > reply_count => format!("{} {} ({})", arrow, reply_count, total),
+            _ => format!("{} {}", arrow, reply_count),
         };
 
         div()
             .id(ElementId::Name(format!("comment-{}", comment_id).into()))
+            .on_hover(cx.listener(move |this, hovered: &bool, _cx| {
+                if *hovered {
+                    this.hovered_comment = Some(comment_id);
+                } else if this.hovered_comment == Some(comment_id) {
+                    this.hovered_comment = None;
+                }
+            }))
             .w_full()
             .min_w(px(0.))
             .flex_shrink_0()
@@ -1456,6 +6838,7 @@ impl AppState {
                                     .id(ElementId::Name(
                                         format!("comment-header-{}", comment_id).into(),
                                     ))
+                                    .group(format!("comment-header-{}", comment_id))
                                     .min_w(px(0.))
                                     .flex()
                                     .items_center()
@@ -1481,13 +6864,17 @@ impl AppState {
                                                 .child(collapse_label),
                                         )
                                     })
+                                    .when_some(avatar, |this, avatar| {
+                                        this.child(self.render_comment_avatar(avatar))
+                                    })
                                     .child(
                                         div()
                                             .font_weight(FontWeight::MEDIUM)
                                             .text_color(text_primary)
-                                            .child(author),
+                                            .child(author.clone()),
                                     )
-                                    .child(div().text_color(text_muted).child(time)),
+                                    .child(self.render_timestamp(comment.time, text_muted))
+                                    .child(self.render_comment_actions(comment_id, cx)),
                             )
                             // Comment text
                             .when(!is_collapsed, |this| {
@@ -1497,27 +6884,193 @@ impl AppState {
                                         .min_w(px(0.))
                                         .text_sm()
                                         .line_height(rems(1.5))
-                                        .text_color(text_primary)
+                                        .text_color(body_color)
                                         .whitespace_normal()
                                         .overflow_x_hidden()
-                                        .child(text),
+                                        .child(
+                                            self.render_comment_text(comment_id, text, body_color),
+                                        ),
+                                )
+                                .children(self.render_comment_unfurls(comment, cx))
+                                .children(
+                                    self.render_load_more_replies(comment_id, missing_replies, cx),
                                 )
                             }),
                     ),
             )
     }
+
+    /// 有回复没加载时显示在评论下方；已经补充获取过的不再显示
+    fn render_load_more_replies(
+        &self,
+        comment_id: i64,
+        missing: Option<usize>,
+        cx: &mut ViewContext<Self>,
+    ) -> Option<AnyElement> {
+        let theme = &self.theme;
+        if self.loading_replies.contains(&comment_id) {
+            return Some(
+                div()
+                    .text_xs()
+                    .text_color(theme.text_muted)
+                    .child("Loading replies…")
+                    .into_any_element(),
+            );
+        }
+        let missing = missing.filter(|_| !self.expanded_replies.contains(&comment_id))?;
+        let accent_hover = theme.accent_hover;
+        let label = if missing == 1 {
+            "Load 1 more reply".to_string()
+        } else {
+            format!("Load {} more replies", missing)
+        };
+
+        Some(
+            div()
+                .id(ElementId::Name(
+                    format!("comment-more-replies-{}", comment_id).into(),
+                ))
+                .text_xs()
+                .cursor_pointer()
+                .text_color(theme.accent)
+                .hover(move |s| s.text_color(accent_hover))
+                .on_click(cx.listener(move |this, _event, cx| {
+                    cx.stop_propagation();
+                    this.load_more_replies(comment_id, cx);
+                }))
+                .child(label)
+                .into_any_element(),
+        )
+    }
+
+    /// 评论头部悬停时出现的复制/引用按钮，折叠的评论同样可用
+    fn render_comment_actions(
+        &self,
+        comment_id: i64,
+        cx: &mut ViewContext<Self>,
+    ) -> impl IntoElement {
+        let text_muted = self.theme.text_muted;
+        let text_primary = self.theme.text_primary;
+        let group = format!("comment-header-{}", comment_id);
+
+        div()
+            .flex()
+            .items_center()
+            .gap_2()
+            .opacity(0.)
+            .group_hover(group, |s| s.opacity(1.))
+            .children([("Copy", false), ("Quote", true)].map(|(label, quote)| {
+                div()
+                    .id(ElementId::Name(
+                        format!("comment-{}-{}", label.to_lowercase(), comment_id).into(),
+                    ))
+                    .cursor_pointer()
+                    .text_color(text_muted)
+                    .hover(move |s| s.text_color(text_primary))
+                    .on_click(cx.listener(move |this, _event, cx| {
+                        cx.stop_propagation();
+                        this.copy_comment(comment_id, quote, cx);
+                    }))
+                    .child(label)
+            }))
+            .child(
+                div()
+                    .id(ElementId::Name(
+                        format!("comment-collapse-thread-{}", comment_id).into(),
+                    ))
+                    .cursor_pointer()
+                    .text_color(text_muted)
+                    .hover(move |s| s.text_color(text_primary))
+                    .on_click(cx.listener(move |this, _event, cx| {
+                        cx.stop_propagation();
+                        this.collapse_thread(comment_id, cx);
+                    }))
+                    .child("Collapse thread"),
+            )
+    }
+
+    fn copy_comment(&mut self, comment_id: i64, quote: bool, cx: &mut ViewContext<Self>) {
+        let Some(comment) = self.comments.iter().find(|c| c.id == comment_id) else {
+            return;
+        };
+        let (text, message) = if quote {
+            (comment.quote_text(), "Quoted comment copied")
+        } else {
+            (comment.copy_text(), "Comment copied")
+        };
+        cx.write_to_clipboard(ClipboardItem::new_string(text));
+        self.show_toast(message, cx);
+    }
+
+    fn render_comment_avatar(&self, avatar: CommentAvatar) -> AnyElement {
+        let theme = &self.theme;
+        let bg = theme.bg_tertiary;
+        let text_secondary = theme.text_secondary;
+        let chip = move |initial: char| {
+            div()
+                .size(px(16.))
+                .flex_shrink_0()
+                .flex()
+                .items_center()
+                .justify_center()
+                .rounded_full()
+                .bg(bg)
+                .text_color(text_secondary)
+                .text_size(px(9.))
+                .font_weight(FontWeight::SEMIBOLD)
+                .child(initial.to_string())
+        };
+
+        match avatar {
+            CommentAvatar::Image { url, initial } => img(url)
+                .size(px(16.))
+                .flex_shrink_0()
+                .rounded_full()
+                .with_fallback(move || chip(initial).into_any_element())
+                .into_any_element(),
+            CommentAvatar::Initial(initial) => chip(initial).into_any_element(),
+        }
+    }
 }
 
 fn main() {
+    let dirs = AppDirs::from_env();
+    dirs.migrate_from_cache(&[
+        settings::SETTINGS_FILE,
+        theme::CUSTOM_THEME_FILE,
+        bookmarks::BOOKMARKS_FILE,
+        history::SEEN_COMMENTS_FILE,
+        history::VISITED_URLS_FILE,
+        history::READ_STORIES_FILE,
+        snooze::SNOOZED_FILE,
+        stats::STATS_FILE,
+    ]);
+    let settings = Settings::load(&dirs.data);
+    let ca_path = tls::configured_ca_path(
+        settings.extra_ca_certificate.as_deref(),
+        std::env::var(tls::EXTRA_CA_ENV).ok().as_deref(),
+    );
+    let (http_client, custom_ca) = tls::build_http_client(ca_path);
+
     App::new()
-        .with_http_client(Arc::new(ReqwestClient::new()))
-        .run(|cx: &mut AppContext| {
+        .with_http_client(Arc::new(http_client))
+        .run(move |cx: &mut AppContext| {
+            let startup_size = settings.startup_window_size;
+            let default_bounds = Bounds::centered(
+                None,
+                size(px(startup_size.width), px(startup_size.height)),
+                cx,
+            );
+            let displays = cx
+                .displays()
+                .iter()
+                .map(|display| display.bounds())
+                .collect::<Vec<_>>();
+            let window_bounds =
+                settings::initial_window_bounds(settings.window, &displays, default_bounds);
+
             let options = WindowOptions {
-                window_bounds: Some(WindowBounds::Windowed(Bounds::centered(
-                    None,
-                    size(px(1200.), px(800.)),
-                    cx,
-                ))),
+                window_bounds: Some(window_bounds),
                 titlebar: Some(TitlebarOptions {
                     title: Some("OneRss".into()),
                     appears_transparent: true,
@@ -1526,10 +7079,123 @@ fn main() {
                 ..Default::default()
             };
 
+            // macOS 用 cmd-1..9，其他平台用 ctrl-1..9
+            let modifier = if cfg!(target_os = "macos") {
+                "cmd"
+            } else {
+                "ctrl"
+            };
+            let mut bindings = (0..NewsChannel::all().len().min(9))
+                .map(|index| {
+                    KeyBinding::new(
+                        &format!("{modifier}-{}", index + 1),
+                        SelectChannel(index),
+                        None,
+                    )
+                })
+                .collect::<Vec<_>>();
+            bindings.push(KeyBinding::new("ctrl-tab", NextChannel, None));
+            bindings.push(KeyBinding::new(
+                &format!("{modifier}-shift-p"),
+                TogglePinnedChannel,
+                None,
+            ));
+            bindings.push(KeyBinding::new(
+                &format!("{modifier}-alt-up"),
+                MoveChannelUp,
+                None,
+            ));
+            bindings.push(KeyBinding::new(
+                &format!("{modifier}-f"),
+                FindInComments,
+                None,
+            ));
+            bindings.push(KeyBinding::new(
+                &format!("{modifier}-shift-c"),
+                ToggleThreadCollapse,
+                None,
+            ));
+            bindings.push(KeyBinding::new(
+                &format!("{modifier}-shift-v"),
+                OpenFromClipboard,
+                None,
+            ));
+            bindings.push(KeyBinding::new(
+                &format!("{modifier}-shift-m"),
+                ToggleReaderMinimap,
+                None,
+            ));
+            bindings.push(KeyBinding::new(
+                &format!("{modifier}-alt-l"),
+                ToggleCleanComments,
+                None,
+            ));
+            bindings.push(KeyBinding::new(
+                &format!("{modifier}-alt-j"),
+                ShowRawJson,
+                None,
+            ));
+            bindings.push(KeyBinding::new(
+                &format!("{modifier}-alt-down"),
+                MoveChannelDown,
+                None,
+            ));
+            bindings.push(KeyBinding::new(
+                &format!("{modifier}-alt-e"),
+                ExportTheme,
+                None,
+            ));
+            bindings.push(KeyBinding::new(
+                &format!("{modifier}-alt-i"),
+                ImportTheme,
+                None,
+            ));
+            bindings.push(KeyBinding::new(
+                &format!("{modifier}-alt-o"),
+                ExportFeedsOpml,
+                None,
+            ));
+            bindings.push(KeyBinding::new(
+                &format!("{modifier}-\\"),
+                CycleStoryListWidth,
+                None,
+            ));
+            bindings.push(KeyBinding::new(
+                &format!("{modifier}-shift-r"),
+                MarkAllStoriesRead,
+                None,
+            ));
+            bindings.push(KeyBinding::new(
+                &format!("{modifier}-alt-r"),
+                ClearReadStories,
+                None,
+            ));
+            bindings.push(KeyBinding::new(
+                &format!("{modifier}-alt-k"),
+                ClearReaderCache,
+                None,
+            ));
+            bindings.push(KeyBinding::new(
+                &format!("{modifier}-r"),
+                RefreshStories,
+                None,
+            ));
+            bindings.push(KeyBinding::new(
+                &format!("{modifier}-shift-d"),
+                ToggleReaderScrollDebug,
+                None,
+            ));
+            cx.bind_keys(bindings);
+
             cx.open_window(options, |cx| {
                 cx.new_view(|cx| {
-                    let mut state = AppState::new(cx);
+                    let mut state = AppState::new(settings, dirs, cx);
+                    state.custom_ca = custom_ca;
+                    cx.focus(&state.focus_handle);
                     state.load_stories(cx);
+                    state.start_clock(cx);
+                    state.repair_reader_cache(cx);
+                    state.detect_reduce_motion(cx);
                     state
                 })
             })
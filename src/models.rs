@@ -1,13 +1,26 @@
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::ops::Range;
 use std::sync::LazyLock;
 
 /// 缓存的 HTML 标签正则表达式
 static HTML_TAG_RE: LazyLock<regex::Regex> =
     LazyLock::new(|| regex::Regex::new(r"<[^>]+>").expect("Invalid regex pattern"));
 
+/// HN 评论里的链接，显示文字可能被截断，复制时改用 href
+static HTML_LINK_RE: LazyLock<regex::Regex> = LazyLock::new(|| {
+    regex::Regex::new(r#"(?is)<a\s[^>]*href="([^"]*)"[^>]*>(.*?)</a>"#)
+        .expect("Invalid regex pattern")
+});
+
 /// 格式化相对时间
 pub fn format_relative_time(timestamp: i64) -> String {
-    let now = chrono::Utc::now().timestamp();
+    format_relative_time_at(timestamp, chrono::Utc::now().timestamp())
+}
+
+/// 相对 `now` 的时间；本机时钟比服务器慢、时间在未来时显示 "just now"
+pub fn format_relative_time_at(timestamp: i64, now: i64) -> String {
+    const DAY: i64 = 86400;
     let diff = now - timestamp;
 
     if diff < 0 {
@@ -16,13 +29,28 @@ pub fn format_relative_time(timestamp: i64) -> String {
         format!("{}s ago", diff)
     } else if diff < 3600 {
         format!("{}m ago", diff / 60)
-    } else if diff < 86400 {
+    } else if diff < DAY {
         format!("{}h ago", diff / 3600)
+    } else if diff < 7 * DAY {
+        format!("{}d ago", diff / DAY)
+    } else if diff < 365 * DAY {
+        format!("{}w ago", diff / (7 * DAY))
     } else {
-        format!("{}d ago", diff / 86400)
+        format!("{}y ago", diff / (365 * DAY))
     }
 }
 
+/// 本地时区的完整日期和时间，悬停在相对时间上时显示
+pub fn format_absolute_time(timestamp: i64) -> String {
+    chrono::DateTime::from_timestamp(timestamp, 0)
+        .map(|time| {
+            time.with_timezone(&chrono::Local)
+                .format("%Y-%m-%d %H:%M")
+                .to_string()
+        })
+        .unwrap_or_default()
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub struct Story {
     pub id: i64,
@@ -34,16 +62,150 @@ pub struct Story {
     pub descendants: Option<i32>,
     pub kids: Option<Vec<i64>>,
     pub text: Option<String>,
+    /// 投票帖的选项（`pollopt` item 的 id），按显示顺序
+    #[serde(default)]
+    pub parts: Option<Vec<i64>>,
     #[serde(rename = "type")]
     pub story_type: String,
 }
 
+/// 投票帖的一个选项（HN 的 `pollopt` item）
+#[derive(Debug, Clone, Deserialize, PartialEq)]
+pub struct PollOption {
+    pub id: i64,
+    #[serde(default)]
+    pub text: Option<String>,
+    #[serde(default)]
+    pub score: i32,
+}
+
+impl PollOption {
+    /// 选项文字是转义过的 HTML
+    #[must_use]
+    pub fn label(&self) -> String {
+        self.text
+            .as_deref()
+            .map(|text| html_escape::decode_html_entities(text).trim().to_string())
+            .unwrap_or_default()
+    }
+}
+
 impl Story {
     #[must_use]
-    pub fn formatted_time(&self) -> String {
-        format_relative_time(self.time)
+    pub fn domain(&self) -> Option<String> {
+        self.url.as_ref().and_then(|url| {
+            url::Url::parse(url)
+                .ok()
+                .and_then(|u| u.host_str().map(|h| h.replace("www.", "")))
+        })
+    }
+
+    #[must_use]
+    pub fn comment_count(&self) -> i32 {
+        self.descendants.unwrap_or(0)
+    }
+
+    #[must_use]
+    pub fn item_url(&self) -> String {
+        if let Some(url) = crate::lobsters::story_page_url(self.id) {
+            return url;
+        }
+        // 订阅源没有讨论页，用文章本身
+        if crate::feeds::is_feed_id(self.id) {
+            return self.url.clone().unwrap_or_default();
+        }
+        format!("https://news.ycombinator.com/item?id={}", self.id)
+    }
+
+    #[must_use]
+    pub fn is_poll(&self) -> bool {
+        self.story_type == "poll"
+    }
+
+    #[must_use]
+    pub fn has_self_text(&self) -> bool {
+        self.text.as_ref().is_some_and(|t| !t.trim().is_empty())
+    }
+
+    #[must_use]
+    pub fn has_both_reader_sources(&self) -> bool {
+        self.url.is_some() && self.has_self_text()
+    }
+
+    /// 优先使用 `preferred`，不可用时退回另一个来源；两者都没有时返回 None
+    #[must_use]
+    pub fn reader_source(&self, preferred: ReaderSource) -> Option<ReaderSource> {
+        pick_reader_source(self.url.is_some(), self.has_self_text(), preferred)
+    }
+
+    /// 详情头部主按钮的行为：有链接或正文时在阅读器里读，都没有时去 HN 上看讨论
+    #[must_use]
+    pub fn primary_action(&self, preferred: ReaderSource) -> StoryAction {
+        self.reader_source(preferred)
+            .map_or(StoryAction::Discuss, StoryAction::Read)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StoryAction {
+    Read(ReaderSource),
+    /// 打开 HN 上的讨论页
+    Discuss,
+}
+
+impl StoryAction {
+    #[must_use]
+    pub fn label(self) -> &'static str {
+        match self {
+            StoryAction::Read(_) => "Read",
+            StoryAction::Discuss => "Discuss on HN",
+        }
     }
+}
 
+fn pick_reader_source(
+    has_link: bool,
+    has_text: bool,
+    preferred: ReaderSource,
+) -> Option<ReaderSource> {
+    let available = |source: ReaderSource| match source {
+        ReaderSource::Link => has_link,
+        ReaderSource::SelfText => has_text,
+    };
+    [preferred, preferred.other()]
+        .into_iter()
+        .find(|&source| available(source))
+}
+
+/// 列表用的精简 story：不保留 `kids` 和正文，选中时再获取完整的 `Story`
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct StorySummary {
+    pub id: i64,
+    pub title: String,
+    pub url: Option<String>,
+    pub score: i32,
+    pub by: String,
+    pub time: i64,
+    pub descendants: Option<i32>,
+    #[serde(rename = "type")]
+    pub story_type: String,
+    /// 是否有正文（Ask HN 等），只记录有没有，不保留内容
+    #[serde(
+        rename(deserialize = "text"),
+        default,
+        deserialize_with = "deserialize_present"
+    )]
+    pub has_text: bool,
+}
+
+fn deserialize_present<'de, D>(deserializer: D) -> Result<bool, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    Option::<serde::de::IgnoredAny>::deserialize(deserializer).map(|value| value.is_some())
+}
+
+impl StorySummary {
     #[must_use]
     pub fn domain(&self) -> Option<String> {
         self.url.as_ref().and_then(|url| {
@@ -57,6 +219,102 @@ impl Story {
     pub fn comment_count(&self) -> i32 {
         self.descendants.unwrap_or(0)
     }
+
+    #[must_use]
+    pub fn reader_source(&self, preferred: ReaderSource) -> Option<ReaderSource> {
+        pick_reader_source(self.url.is_some(), self.has_text, preferred)
+    }
+
+    /// 用刚获取的完整 story 更新会变化的分数和评论数，返回是否有变化
+    pub fn refresh_from(&mut self, story: &Story) -> bool {
+        if self.id != story.id {
+            return false;
+        }
+        let changed = self.score != story.score || self.descendants != story.descendants;
+        self.score = story.score;
+        self.descendants = story.descendants;
+        changed
+    }
+
+    /// 完整数据到达前先用摘要顶上，`kids` 和正文为空
+    #[must_use]
+    pub fn to_story(&self) -> Story {
+        Story {
+            id: self.id,
+            title: self.title.clone(),
+            url: self.url.clone(),
+            score: self.score,
+            by: self.by.clone(),
+            time: self.time,
+            descendants: self.descendants,
+            kids: None,
+            text: None,
+            parts: None,
+            story_type: self.story_type.clone(),
+        }
+    }
+}
+
+impl From<&Story> for StorySummary {
+    fn from(story: &Story) -> Self {
+        Self {
+            id: story.id,
+            title: story.title.clone(),
+            url: story.url.clone(),
+            score: story.score,
+            by: story.by.clone(),
+            time: story.time,
+            descendants: story.descendants,
+            story_type: story.story_type.clone(),
+            has_text: story.has_self_text(),
+        }
+    }
+}
+
+/// 阅读模式的内容来源：链接指向的文章，或帖子自身的正文（Ask/Show HN）
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ReaderSource {
+    #[default]
+    Link,
+    SelfText,
+}
+
+impl ReaderSource {
+    #[must_use]
+    pub fn other(self) -> Self {
+        match self {
+            ReaderSource::Link => ReaderSource::SelfText,
+            ReaderSource::SelfText => ReaderSource::Link,
+        }
+    }
+}
+
+/// 顶级评论串的排列方式，串内回复的顺序不变
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum CommentSort {
+    /// 来源给出的顺序（HN 按热度）
+    #[default]
+    Default,
+    Newest,
+    Oldest,
+}
+
+impl CommentSort {
+    pub const ALL: [CommentSort; 3] = [
+        CommentSort::Default,
+        CommentSort::Newest,
+        CommentSort::Oldest,
+    ];
+
+    #[must_use]
+    pub fn label(self) -> &'static str {
+        match self {
+            CommentSort::Default => "Default",
+            CommentSort::Newest => "Newest",
+            CommentSort::Oldest => "Oldest",
+        }
+    }
 }
 
 /// 原始评论数据（从 API 获取）
@@ -82,8 +340,21 @@ pub struct Comment {
     pub kids: Option<Vec<i64>>,
     pub parent: i64,
     pub depth: usize,
-    /// 子评论数量（包括嵌套的）
+    /// 直接回复的数量（`kids` 的个数），嵌套回复见 `descendant_counts`
     pub reply_count: usize,
+    /// 评论者头像，由提供头像的来源填充（HN 没有头像，始终为 None）
+    pub avatar_url: Option<String>,
+}
+
+/// 评论头像的显示方式
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CommentAvatar {
+    /// 头像图片，加载失败时显示 `initial`
+    Image {
+        url: String,
+        initial: char,
+    },
+    Initial(char),
 }
 
 impl From<RawComment> for Comment {
@@ -97,6 +368,7 @@ impl From<RawComment> for Comment {
             parent: raw.parent,
             depth: 0,
             reply_count: 0,
+            avatar_url: None,
         }
     }
 }
@@ -108,11 +380,6 @@ impl Comment {
         self
     }
 
-    #[must_use]
-    pub fn formatted_time(&self) -> String {
-        format_relative_time(self.time)
-    }
-
     #[must_use]
     pub fn author(&self) -> &str {
         self.by.as_deref().unwrap_or("[deleted]")
@@ -136,22 +403,405 @@ impl Comment {
         )
     }
 
+    /// 复制用的正文：段落间空一行，代码块保留换行，链接写成完整地址
+    #[must_use]
+    pub fn copy_text(&self) -> String {
+        let Some(text) = self.text.as_deref() else {
+            return "[deleted]".to_string();
+        };
+        let text = HTML_LINK_RE.replace_all(text, "$1");
+        let text = text
+            .replace("<p>", "\n\n")
+            .replace("</p>", "")
+            .replace("<br>", "\n")
+            .replace("<br/>", "\n")
+            .replace("<br />", "\n");
+        let text = HTML_TAG_RE.replace_all(&text, "");
+        html_escape::decode_html_entities(&text).trim().to_string()
+    }
+
+    /// 正文里直接贴出的链接（显示文字就是地址，HN 会把过长的截断成 `...`），
+    /// 不含写成文字说明的链接
+    #[must_use]
+    pub fn bare_links(&self) -> Vec<String> {
+        let Some(text) = self.text.as_deref() else {
+            return Vec::new();
+        };
+        let mut links = Vec::new();
+        for captures in HTML_LINK_RE.captures_iter(text) {
+            let href = html_escape::decode_html_entities(&captures[1])
+                .trim()
+                .to_string();
+            let label = HTML_TAG_RE.replace_all(&captures[2], "");
+            let label = html_escape::decode_html_entities(&label);
+            let label = label.trim();
+            let is_bare = label == href
+                || label
+                    .strip_suffix("...")
+                    .is_some_and(|prefix| !prefix.is_empty() && href.starts_with(prefix));
+            if is_bare && !links.contains(&href) {
+                links.push(href);
+            }
+        }
+        links
+    }
+
+    /// 引用格式：署名一行，正文每行加 `> ` 前缀，方便贴到别处回复
+    #[must_use]
+    pub fn quote_text(&self) -> String {
+        quote_lines(self.author(), &self.copy_text())
+    }
+
+    /// 来源没有头像时返回 None；来源支持头像但地址为空时退化为首字母
+    #[must_use]
+    pub fn avatar(&self) -> Option<CommentAvatar> {
+        let url = self.avatar_url.as_deref()?.trim();
+        let initial = self
+            .author()
+            .chars()
+            .find(|c| c.is_alphanumeric())
+            .map_or('?', |c| c.to_ascii_uppercase());
+        if url.is_empty() {
+            Some(CommentAvatar::Initial(initial))
+        } else {
+            Some(CommentAvatar::Image {
+                url: url.to_string(),
+                initial,
+            })
+        }
+    }
+
     #[must_use]
     pub fn has_replies(&self) -> bool {
         self.kids.as_ref().is_some_and(|k| !k.is_empty())
     }
 }
 
+/// 只用来追踪点击来源的查询参数，`utm_` 开头的全部算在内
+const TRACKING_PARAMS: &[&str] = &[
+    "fbclid", "gclid", "dclid", "gbraid", "wbraid", "msclkid", "yclid", "twclid", "igshid",
+    "mc_cid", "mc_eid", "_hsenc", "_hsmi", "mkt_tok", "ref_src", "ref_url",
+];
+
+fn is_tracking_param(key: &str) -> bool {
+    let key = key.to_ascii_lowercase();
+    key.starts_with("utm_") || TRACKING_PARAMS.contains(&key.as_str())
+}
+
+/// 去掉链接里的追踪参数，其余参数原样保留（不重新编码、不调整顺序）。
+/// 同一篇文章从不同渠道分享出来也能对上缓存，打开浏览器时也不会带出去
+#[must_use]
+pub fn clean_url(url: &str) -> String {
+    let Ok(mut parsed) = url::Url::parse(url) else {
+        return url.to_string();
+    };
+    let Some(query) = parsed.query() else {
+        return url.to_string();
+    };
+    let kept = query
+        .split('&')
+        .filter(|pair| {
+            let key = pair.split('=').next().unwrap_or_default();
+            !key.is_empty() && !is_tracking_param(key)
+        })
+        .collect::<Vec<_>>();
+    if kept.len() == query.split('&').count() {
+        return url.to_string();
+    }
+    let kept = kept.join("&");
+    parsed.set_query((!kept.is_empty()).then_some(kept.as_str()));
+    parsed.to_string()
+}
+
+#[must_use]
+pub fn quote_lines(author: &str, text: &str) -> String {
+    let mut quoted = format!("{author} wrote:\n");
+    for line in text.lines() {
+        let line = line.trim_end();
+        if line.is_empty() {
+            quoted.push_str(">\n");
+        } else {
+            quoted.push_str("> ");
+            quoted.push_str(line);
+            quoted.push('\n');
+        }
+    }
+    quoted
+}
+
+/// 每条评论子树里已加载的回复总数；`comments` 是按深度优先排列的扁平列表
+#[must_use]
+pub fn descendant_counts(comments: &[Comment]) -> HashMap<i64, usize> {
+    let mut counts = HashMap::new();
+    let mut ancestors: Vec<i64> = Vec::new();
+    for comment in comments {
+        ancestors.truncate(comment.depth);
+        for id in &ancestors {
+            *counts.entry(*id).or_default() += 1;
+        }
+        counts.entry(comment.id).or_default();
+        ancestors.push(comment.id);
+    }
+    counts
+}
+
+/// 按 `sort` 重排顶级评论串，每串连同全部回复整体移动。
+/// `Default` 按 `ranking`（story 的 `kids`）里的位置排，不在其中的保持原顺序排在最后
+pub fn sort_threads(comments: &mut Vec<Comment>, sort: CommentSort, ranking: &[i64]) {
+    let mut threads: Vec<Vec<Comment>> = Vec::new();
+    for comment in comments.drain(..) {
+        match threads.last_mut() {
+            Some(thread) if comment.depth > 0 => thread.push(comment),
+            _ => threads.push(vec![comment]),
+        }
+    }
+    match sort {
+        CommentSort::Default => {
+            let rank: HashMap<i64, usize> = ranking
+                .iter()
+                .enumerate()
+                .map(|(ix, &id)| (id, ix))
+                .collect();
+            threads.sort_by_key(|thread| rank.get(&thread[0].id).copied().unwrap_or(usize::MAX));
+        }
+        CommentSort::Newest => threads.sort_by_key(|thread| std::cmp::Reverse(thread[0].time)),
+        CommentSort::Oldest => threads.sort_by_key(|thread| thread[0].time),
+    }
+    comments.extend(threads.into_iter().flatten());
+}
+
+/// 评论所在的顶级评论
+#[must_use]
+pub fn thread_root(comments: &[Comment], id: i64) -> Option<i64> {
+    let index = comments.iter().position(|c| c.id == id)?;
+    comments[..=index]
+        .iter()
+        .rev()
+        .find(|c| c.depth == 0)
+        .or(comments.first())
+        .map(|c| c.id)
+}
+
+/// 把纵向位置映射到所在的顶级评论：`rows` 是按显示顺序排列的可见评论及其底边位置，
+/// 位置在第一条之前时取第一条，超出最后一条时返回 None
+#[must_use]
+pub fn thread_root_at(rows: &[(&Comment, f32)], y: f32) -> Option<i64> {
+    let mut root = None;
+    for (comment, bottom) in rows {
+        if comment.depth == 0 || root.is_none() {
+            root = Some(comment.id);
+        }
+        if *bottom > y {
+            return root;
+        }
+    }
+    None
+}
+
+/// 可见评论从 `old` 变成 `new` 时需要替换的区间和替换后的条数，
+/// 只比较首尾相同的部分，没有变化时返回 None
+#[must_use]
+pub fn rows_splice(old: &[i64], new: &[i64]) -> Option<(Range<usize>, usize)> {
+    if old == new {
+        return None;
+    }
+    let prefix = old.iter().zip(new).take_while(|(a, b)| a == b).count();
+    let suffix = old[prefix..]
+        .iter()
+        .rev()
+        .zip(new[prefix..].iter().rev())
+        .take_while(|(a, b)| a == b)
+        .count();
+    Some((prefix..old.len() - suffix, new.len() - prefix - suffix))
+}
+
+/// 还有直接回复没加载的评论（被层级或每层数量上限截掉）及缺少的条数
+#[must_use]
+pub fn missing_reply_counts(comments: &[Comment]) -> HashMap<i64, usize> {
+    let mut loaded: HashMap<i64, usize> = HashMap::new();
+    for comment in comments {
+        *loaded.entry(comment.parent).or_default() += 1;
+    }
+    comments
+        .iter()
+        .filter_map(|comment| {
+            let loaded = loaded.get(&comment.id).copied().unwrap_or_default();
+            let missing = comment.reply_count.saturating_sub(loaded);
+            (missing > 0).then_some((comment.id, missing))
+        })
+        .collect()
+}
+
+/// 把补充获取的回复放进 `parent_id` 的子树：按新结果里的顺序排列直接回复，
+/// 两边都有的回复保留加载得更多的那棵子树，列表仍然是深度优先顺序
+pub fn splice_replies(comments: &mut Vec<Comment>, parent_id: i64, replies: Vec<Comment>) {
+    let Some(parent_index) = comments.iter().position(|c| c.id == parent_id) else {
+        return;
+    };
+    let depth = comments[parent_index].depth;
+    let start = parent_index + 1;
+    let end = comments[start..]
+        .iter()
+        .position(|c| c.depth <= depth)
+        .map_or(comments.len(), |i| start + i);
+
+    let mut existing = split_subtrees(comments.drain(start..end), depth + 1);
+    let mut merged = Vec::new();
+    for tree in split_subtrees(replies, depth + 1) {
+        match existing.iter().position(|old| old[0].id == tree[0].id) {
+            Some(i) => {
+                let old = existing.remove(i);
+                merged.extend(if old.len() >= tree.len() { old } else { tree });
+            }
+            None => merged.extend(tree),
+        }
+    }
+    // 新结果里没有的旧回复（比如刚被删除）放在最后
+    merged.extend(existing.into_iter().flatten());
+    comments.splice(start..start, merged);
+}
+
+/// 按深度为 `depth` 的评论切成一棵棵子树
+fn split_subtrees(comments: impl IntoIterator<Item = Comment>, depth: usize) -> Vec<Vec<Comment>> {
+    let mut trees: Vec<Vec<Comment>> = Vec::new();
+    for comment in comments {
+        match trees.last_mut() {
+            Some(tree) if comment.depth > depth => tree.push(comment),
+            _ => trees.push(vec![comment]),
+        }
+    }
+    trees
+}
+
+/// 评论抓取的深度和每层数量上限
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CommentLimits {
+    /// 最深的评论层级，顶级评论为 0
+    pub max_depth: usize,
+    /// 每个父节点下最多抓取的回复数
+    pub max_per_level: usize,
+}
+
+impl CommentLimits {
+    /// 用户设置优先，未设置的项沿用来源默认值
+    #[must_use]
+    pub fn with_overrides(self, max_depth: Option<usize>, max_per_level: Option<usize>) -> Self {
+        Self {
+            max_depth: max_depth.unwrap_or(self.max_depth),
+            max_per_level: max_per_level.unwrap_or(self.max_per_level).max(1),
+        }
+    }
+}
+
+/// story 列表的排序方式，按频道记在设置里
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum StorySort {
+    #[default]
+    Score,
+    Newest,
+    Comments,
+}
+
+impl StorySort {
+    #[must_use]
+    pub fn all() -> &'static [StorySort] {
+        &[StorySort::Score, StorySort::Newest, StorySort::Comments]
+    }
+
+    #[must_use]
+    pub fn label(&self) -> &'static str {
+        match self {
+            StorySort::Score => "Top",
+            StorySort::Newest => "Newest",
+            StorySort::Comments => "Most discussed",
+        }
+    }
+
+    /// 下一个排序方式，列表顶部的按钮循环切换
+    #[must_use]
+    pub fn next(&self) -> StorySort {
+        let all = Self::all();
+        let index = all.iter().position(|sort| sort == self).unwrap_or(0);
+        all[(index + 1) % all.len()]
+    }
+
+    /// 稳定排序，同分时保持榜单原来的顺序
+    pub fn sort(&self, stories: &mut [&StorySummary]) {
+        match self {
+            StorySort::Score => stories.sort_by(|a, b| b.score.cmp(&a.score)),
+            StorySort::Newest => stories.sort_by(|a, b| b.time.cmp(&a.time)),
+            StorySort::Comments => {
+                stories.sort_by(|a, b| b.comment_count().cmp(&a.comment_count()))
+            }
+        }
+    }
+}
+
+/// 侧边栏的频道：HN 的各个榜单（`HackerNews` 是首页 Top）、Lobsters 的首页，
+/// 以及用户添加的 RSS/Atom 订阅源合在一起的 Feeds
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum NewsChannel {
     HackerNews,
+    New,
+    Best,
+    Ask,
+    Show,
+    Jobs,
+    Lobsters,
+    Feeds,
 }
 
 impl NewsChannel {
+    /// 侧边栏和快捷键使用的频道顺序
+    #[must_use]
+    pub fn all() -> &'static [NewsChannel] {
+        &[
+            NewsChannel::HackerNews,
+            NewsChannel::New,
+            NewsChannel::Best,
+            NewsChannel::Ask,
+            NewsChannel::Show,
+            NewsChannel::Jobs,
+            NewsChannel::Lobsters,
+            NewsChannel::Feeds,
+        ]
+    }
+
+    /// 持久化用的稳定标识
+    #[must_use]
+    pub fn key(&self) -> &'static str {
+        match self {
+            NewsChannel::HackerNews => "hacker_news",
+            NewsChannel::New => "hn_new",
+            NewsChannel::Best => "hn_best",
+            NewsChannel::Ask => "hn_ask",
+            NewsChannel::Show => "hn_show",
+            NewsChannel::Jobs => "hn_jobs",
+            NewsChannel::Lobsters => "lobsters",
+            NewsChannel::Feeds => "feeds",
+        }
+    }
+
+    #[must_use]
+    pub fn from_key(key: &str) -> Option<NewsChannel> {
+        Self::all()
+            .iter()
+            .copied()
+            .find(|channel| channel.key() == key)
+    }
+
     #[must_use]
     pub fn name(&self) -> &'static str {
         match self {
             NewsChannel::HackerNews => "Hacker News",
+            NewsChannel::New => "New",
+            NewsChannel::Best => "Best",
+            NewsChannel::Ask => "Ask HN",
+            NewsChannel::Show => "Show HN",
+            NewsChannel::Jobs => "Jobs",
+            NewsChannel::Lobsters => "Lobsters",
+            NewsChannel::Feeds => "Feeds",
         }
     }
 
@@ -159,6 +809,67 @@ impl NewsChannel {
     pub fn icon(&self) -> &'static str {
         match self {
             NewsChannel::HackerNews => "Y",
+            NewsChannel::New => "N",
+            NewsChannel::Best => "B",
+            NewsChannel::Ask => "A",
+            NewsChannel::Show => "S",
+            NewsChannel::Jobs => "J",
+            NewsChannel::Lobsters => "L",
+            NewsChannel::Feeds => "R",
+        }
+    }
+
+    /// 榜单路径：HN 频道在 Firebase API 上，Lobsters 在 lobste.rs 上；
+    /// Feeds 的地址由用户添加，没有固定路径
+    #[must_use]
+    pub fn endpoint(&self) -> &'static str {
+        match self {
+            NewsChannel::HackerNews => "topstories.json",
+            NewsChannel::New => "newstories.json",
+            NewsChannel::Best => "beststories.json",
+            NewsChannel::Ask => "askstories.json",
+            NewsChannel::Show => "showstories.json",
+            NewsChannel::Jobs => "jobstories.json",
+            NewsChannel::Lobsters => crate::lobsters::HOTTEST_PATH,
+            NewsChannel::Feeds => "",
+        }
+    }
+
+    /// 第一次打开频道时的排序：New、Jobs 和订阅源按时间，其余按分数
+    #[must_use]
+    pub fn default_sort(&self) -> StorySort {
+        match self {
+            NewsChannel::New | NewsChannel::Jobs | NewsChannel::Feeds => StorySort::Newest,
+            NewsChannel::HackerNews
+            | NewsChannel::Best
+            | NewsChannel::Ask
+            | NewsChannel::Show
+            | NewsChannel::Lobsters => StorySort::Score,
+        }
+    }
+
+    /// 列表是否按分数排序；New 和 Jobs 保持榜单本身的时间顺序
+    #[must_use]
+    pub fn sorts_by_score(&self) -> bool {
+        self.default_sort() == StorySort::Score
+    }
+
+    /// 来源默认的评论抓取上限：HN 讨论通常很宽但不深
+    #[must_use]
+    pub fn comment_limits(&self) -> CommentLimits {
+        match self {
+            NewsChannel::HackerNews
+            | NewsChannel::New
+            | NewsChannel::Best
+            | NewsChannel::Ask
+            | NewsChannel::Show
+            | NewsChannel::Jobs
+            | NewsChannel::Feeds
+            // 整棵树一次返回，用不到上限
+            | NewsChannel::Lobsters => CommentLimits {
+                max_depth: 3,
+                max_per_level: 10,
+            },
         }
     }
 }
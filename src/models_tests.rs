@@ -0,0 +1,301 @@
+use crate::models::{self, Comment, CommentAvatar, RawComment, ReaderSource, Story, StoryAction};
+
+fn raw_comment(by: &str) -> RawComment {
+    RawComment {
+        id: 1,
+        by: Some(by.to_string()),
+        text: Some("hello".to_string()),
+        time: 1_700_000_000,
+        kids: None,
+        parent: 0,
+        comment_type: "comment".to_string(),
+    }
+}
+
+/// 只关心 id、层级和时间的评论
+fn comment(id: i64, depth: usize, time: i64) -> Comment {
+    Comment {
+        id,
+        time,
+        ..Comment::from(raw_comment("pg")).with_depth(depth)
+    }
+}
+
+#[test]
+fn hn_comments_have_no_avatar_and_sources_with_avatars_do() {
+    let hn = Comment::from(raw_comment("pg"));
+    assert_eq!(hn.avatar(), None);
+
+    let with_image = Comment {
+        avatar_url: Some("https://example.com/a.png".to_string()),
+        ..Comment::from(raw_comment("alice"))
+    };
+    assert_eq!(
+        with_image.avatar(),
+        Some(CommentAvatar::Image {
+            url: "https://example.com/a.png".to_string(),
+            initial: 'A',
+        })
+    );
+
+    let without_image = Comment {
+        avatar_url: Some(String::new()),
+        ..Comment::from(raw_comment("bob"))
+    };
+    assert_eq!(without_image.avatar(), Some(CommentAvatar::Initial('B')));
+}
+
+#[test]
+fn reader_source_prefers_setting_and_falls_back_to_available_source() {
+    let story = |url: Option<&str>, text: Option<&str>| Story {
+        id: 1,
+        title: "Ask HN".to_string(),
+        url: url.map(str::to_string),
+        score: 1,
+        by: "op".to_string(),
+        time: 1_700_000_000,
+        descendants: None,
+        kids: None,
+        text: text.map(str::to_string),
+        parts: None,
+        story_type: "story".to_string(),
+    };
+
+    let both = story(Some("https://example.com"), Some("body"));
+    assert!(both.has_both_reader_sources());
+    assert_eq!(
+        both.reader_source(ReaderSource::Link),
+        Some(ReaderSource::Link)
+    );
+    assert_eq!(
+        both.reader_source(ReaderSource::SelfText),
+        Some(ReaderSource::SelfText)
+    );
+
+    let ask = story(None, Some("body"));
+    assert_eq!(
+        ask.reader_source(ReaderSource::Link),
+        Some(ReaderSource::SelfText)
+    );
+
+    let empty_text = story(Some("https://example.com"), Some("  "));
+    assert_eq!(
+        empty_text.reader_source(ReaderSource::SelfText),
+        Some(ReaderSource::Link)
+    );
+    assert_eq!(story(None, None).reader_source(ReaderSource::Link), None);
+
+    // 头部按钮：链接、只有正文、两者都没有
+    let link = story(Some("https://example.com"), None);
+    assert_eq!(
+        link.primary_action(ReaderSource::SelfText),
+        StoryAction::Read(ReaderSource::Link)
+    );
+    assert_eq!(
+        ask.primary_action(ReaderSource::Link),
+        StoryAction::Read(ReaderSource::SelfText)
+    );
+    assert_eq!(ask.primary_action(ReaderSource::Link).label(), "Read");
+    let neither = story(None, Some(""));
+    assert_eq!(
+        neither.primary_action(ReaderSource::Link),
+        StoryAction::Discuss
+    );
+    assert_eq!(StoryAction::Discuss.label(), "Discuss on HN");
+}
+
+#[test]
+fn quoted_comment_has_attribution_and_prefixed_lines() {
+    let comment = Comment {
+        text: Some(
+            "First point, see <a href=\"https:&#x2F;&#x2F;example.com&#x2F;a&#x2F;long&#x2F;path\" rel=\"nofollow\">https:&#x2F;&#x2F;example.com&#x2F;a&#x2F;l...</a><p>Second &amp; last<br>line"
+                .to_string(),
+        ),
+        ..Comment::from(raw_comment("dang"))
+    };
+
+    assert_eq!(
+        comment.copy_text(),
+        "First point, see https://example.com/a/long/path\n\nSecond & last\nline"
+    );
+    assert_eq!(
+        comment.quote_text(),
+        "dang wrote:\n> First point, see https://example.com/a/long/path\n>\n> Second & last\n> line\n"
+    );
+}
+
+#[test]
+fn descendant_counts_sum_each_subtree() {
+    // 1
+    // ├─ 2
+    // │  ├─ 3
+    // │  │  └─ 4
+    // │  └─ 5
+    // └─ 6
+    // 7
+    let comments = [(1, 0), (2, 1), (3, 2), (4, 3), (5, 2), (6, 1), (7, 0)]
+        .into_iter()
+        .map(|(id, depth)| comment(id, depth, 1_700_000_000))
+        .collect::<Vec<_>>();
+
+    let counts = models::descendant_counts(&comments);
+
+    assert_eq!(counts[&1], 5);
+    assert_eq!(counts[&2], 3);
+    assert_eq!(counts[&3], 1);
+    assert_eq!(counts[&4], 0);
+    assert_eq!(counts[&5], 0);
+    assert_eq!(counts[&6], 0);
+    assert_eq!(counts[&7], 0);
+}
+
+#[test]
+fn viewport_position_maps_to_its_top_level_thread() {
+    let comments = [(1, 0), (2, 1), (3, 2), (4, 0), (5, 1)]
+        .into_iter()
+        .map(|(id, depth)| comment(id, depth, 1_700_000_000))
+        .collect::<Vec<_>>();
+    // 每条评论高 100px，从 y = 200 开始
+    let rows = comments
+        .iter()
+        .enumerate()
+        .map(|(i, c)| (c, 300.0 + 100.0 * i as f32))
+        .collect::<Vec<_>>();
+
+    // 视口顶部还在评论区之上：取第一条
+    assert_eq!(models::thread_root_at(&rows, 0.0), Some(1));
+    // 落在嵌套回复上时返回它的顶级评论
+    assert_eq!(models::thread_root_at(&rows, 450.0), Some(1));
+    assert_eq!(models::thread_root_at(&rows, 550.0), Some(4));
+    assert_eq!(models::thread_root_at(&rows, 650.0), Some(4));
+    // 已经滚过所有评论
+    assert_eq!(models::thread_root_at(&rows, 700.0), None);
+
+    assert_eq!(models::thread_root(&comments, 3), Some(1));
+    assert_eq!(models::thread_root(&comments, 5), Some(4));
+    assert_eq!(models::thread_root(&comments, 99), None);
+}
+
+#[test]
+fn collapsing_a_thread_splices_only_the_hidden_rows() {
+    assert_eq!(models::rows_splice(&[1, 2, 3], &[1, 2, 3]), None);
+    // 折叠 2：它下面的 3、4 被移除
+    assert_eq!(
+        models::rows_splice(&[1, 2, 3, 4, 5], &[1, 2, 5]),
+        Some((2..4, 0))
+    );
+    // 展开时插回原处
+    assert_eq!(
+        models::rows_splice(&[1, 2, 5], &[1, 2, 3, 4, 5]),
+        Some((2..2, 2))
+    );
+    // 加载更多顶级评论追加在末尾
+    assert_eq!(models::rows_splice(&[1, 2], &[1, 2, 8, 9]), Some((2..2, 2)));
+    assert_eq!(models::rows_splice(&[], &[1]), Some((0..0, 1)));
+    // 重复的 id 不会让首尾重叠
+    assert_eq!(models::rows_splice(&[1, 1], &[1]), Some((1..2, 0)));
+}
+
+#[test]
+fn fetched_replies_splice_into_the_parent_subtree() {
+    let reply = |id: i64, parent: i64, depth: usize, kids: &[i64]| Comment {
+        parent,
+        reply_count: kids.len(),
+        kids: (!kids.is_empty()).then(|| kids.to_vec()),
+        ..comment(id, depth, 1_700_000_000)
+    };
+    // 2 只加载了第一条回复，3 下面的回复因为层级上限没有加载
+    let mut comments = vec![
+        reply(1, 100, 0, &[2]),
+        reply(2, 1, 1, &[3, 4, 5]),
+        reply(3, 2, 2, &[6]),
+        reply(7, 100, 0, &[]),
+    ];
+    let missing = models::missing_reply_counts(&comments);
+    assert_eq!(missing.get(&2), Some(&2));
+    assert_eq!(missing.get(&3), Some(&1));
+    assert_eq!(missing.get(&1), None);
+
+    let replies = vec![
+        reply(3, 2, 2, &[6]),
+        reply(6, 3, 3, &[]),
+        reply(4, 2, 2, &[]),
+        reply(5, 2, 2, &[]),
+    ];
+    models::splice_replies(&mut comments, 2, replies);
+
+    let summary = comments.iter().map(|c| (c.id, c.depth)).collect::<Vec<_>>();
+    assert_eq!(
+        summary,
+        vec![(1, 0), (2, 1), (3, 2), (6, 3), (4, 2), (5, 2), (7, 0)]
+    );
+    assert!(models::missing_reply_counts(&comments).is_empty());
+}
+
+#[test]
+fn relative_time_rolls_up_into_weeks_and_years() {
+    let now = 1_700_000_000;
+    let day = 86_400;
+    let cases = [
+        (now + 30, "just now"),
+        (now - 5, "5s ago"),
+        (now - 3 * 60, "3m ago"),
+        (now - 5 * 3600, "5h ago"),
+        (now - 6 * day, "6d ago"),
+        (now - 7 * day, "1w ago"),
+        (now - 100 * day, "14w ago"),
+        (now - 365 * day, "1y ago"),
+        (now - 3 * 365 * day, "3y ago"),
+    ];
+    for (timestamp, expected) in cases {
+        assert_eq!(models::format_relative_time_at(timestamp, now), expected);
+    }
+
+    // 本地时区不固定，只检查格式
+    let absolute = models::format_absolute_time(now);
+    assert_eq!(absolute.len(), "2023-11-14 22:13".len());
+    assert!(absolute.starts_with("2023-11-1"), "{absolute}");
+}
+
+#[test]
+fn clean_url_drops_tracking_params_and_keeps_the_rest() {
+    assert_eq!(
+        models::clean_url("https://example.com/post?utm_source=hn&id=42&UTM_Medium=x&fbclid=abc"),
+        "https://example.com/post?id=42"
+    );
+    assert_eq!(
+        models::clean_url("https://example.com/post?gclid=1&utm_campaign=launch#comments"),
+        "https://example.com/post#comments"
+    );
+    // 没有追踪参数时原样返回，编码和顺序都不动
+    let untouched = "https://example.com/search?q=a+b&page=2&ref=nav";
+    assert_eq!(models::clean_url(untouched), untouched);
+    assert_eq!(
+        models::clean_url("not a url?utm_source=x"),
+        "not a url?utm_source=x"
+    );
+}
+
+#[test]
+fn sorting_moves_whole_threads_by_root_time() {
+    // 回复比顶级评论新也不影响所在串的位置
+    let mut comments = vec![
+        comment(1, 0, 200),
+        comment(2, 1, 900),
+        comment(3, 0, 100),
+        comment(4, 0, 300),
+        comment(5, 1, 400),
+        comment(6, 2, 500),
+    ];
+    let ids = |comments: &[Comment]| comments.iter().map(|c| c.id).collect::<Vec<_>>();
+
+    models::sort_threads(&mut comments, models::CommentSort::Newest, &[]);
+    assert_eq!(ids(&comments), [4, 5, 6, 1, 2, 3]);
+
+    models::sort_threads(&mut comments, models::CommentSort::Oldest, &[]);
+    assert_eq!(ids(&comments), [3, 1, 2, 4, 5, 6]);
+
+    // 默认顺序按 story 的 kids 还原
+    models::sort_threads(&mut comments, models::CommentSort::Default, &[1, 3, 4]);
+    assert_eq!(ids(&comments), [1, 2, 3, 4, 5, 6]);
+}
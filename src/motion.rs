@@ -0,0 +1,60 @@
+use std::time::Duration;
+
+/// 一次状态变化的呈现方式
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Motion {
+    Animated(Duration),
+    /// 直接切换到最终状态
+    Instant,
+}
+
+/// 所有动画都先经过这里：减少动态效果时返回 `Instant`
+#[must_use]
+pub fn motion(reduce_motion: bool, duration: Duration) -> Motion {
+    if reduce_motion || duration.is_zero() {
+        Motion::Instant
+    } else {
+        Motion::Animated(duration)
+    }
+}
+
+/// 用户设置优先，未设置时跟随系统，读不到系统设置时保留动画
+#[must_use]
+pub fn resolve_reduce_motion(setting: Option<bool>, system: Option<bool>) -> bool {
+    setting.or(system).unwrap_or(false)
+}
+
+/// 系统的“减少动态效果”设置（macOS 辅助功能、GNOME 的动画开关）。
+/// 要启动子进程，只在后台线程里调用
+pub fn system_prefers_reduced_motion() -> Option<bool> {
+    if cfg!(target_os = "macos") {
+        read_flag(
+            "defaults",
+            &["read", "com.apple.universalaccess", "reduceMotion"],
+        )
+    } else if cfg!(target_os = "linux") {
+        // GNOME 记录的是“启用动画”，取反
+        read_flag(
+            "gsettings",
+            &["get", "org.gnome.desktop.interface", "enable-animations"],
+        )
+        .map(|enabled| !enabled)
+    } else {
+        None
+    }
+}
+
+fn read_flag(program: &str, args: &[&str]) -> Option<bool> {
+    let output = std::process::Command::new(program)
+        .args(args)
+        .output()
+        .ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    match String::from_utf8_lossy(&output.stdout).trim() {
+        "1" | "true" => Some(true),
+        "0" | "false" => Some(false),
+        _ => None,
+    }
+}
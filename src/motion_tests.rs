@@ -0,0 +1,17 @@
+use crate::motion::{self, Motion};
+use std::time::Duration;
+
+#[test]
+fn reduce_motion_turns_animations_into_instant_changes() {
+    let duration = Duration::from_millis(200);
+
+    assert_eq!(motion::motion(false, duration), Motion::Animated(duration));
+    assert_eq!(motion::motion(true, duration), Motion::Instant);
+    assert_eq!(motion::motion(false, Duration::ZERO), Motion::Instant);
+
+    // 显式设置不看系统，没有设置时跟随系统，都没有时保留动画
+    assert!(motion::resolve_reduce_motion(Some(true), Some(false)));
+    assert!(!motion::resolve_reduce_motion(Some(false), Some(true)));
+    assert!(motion::resolve_reduce_motion(None, Some(true)));
+    assert!(!motion::resolve_reduce_motion(None, None));
+}
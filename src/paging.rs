@@ -0,0 +1,126 @@
+use crate::models::StorySummary;
+use std::collections::HashSet;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::Duration;
+
+/// 每页加载的 story 数
+pub const STORY_PAGE_SIZE: usize = 30;
+/// 覆盖设置里的自动刷新间隔（分钟），`0` 关闭
+pub const AUTO_REFRESH_ENV: &str = "ONEAPP_AUTO_REFRESH_MINUTES";
+
+/// 每个新建的 pager 拿到不同的 epoch，`0` 留给空的默认 pager
+static NEXT_PAGER_EPOCH: AtomicU64 = AtomicU64::new(1);
+
+/// 排行榜分页：记录已经请求到第几条，同一时间只允许一页在加载
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct StoryPager {
+    ids: Vec<i64>,
+    requested: usize,
+    loading: bool,
+    /// 刷新会换掉整个 pager；在途的页用它判断自己是不是还属于当前列表
+    epoch: u64,
+}
+
+impl StoryPager {
+    pub fn new(ids: Vec<i64>) -> Self {
+        Self {
+            ids,
+            requested: 0,
+            loading: false,
+            epoch: NEXT_PAGER_EPOCH.fetch_add(1, Ordering::Relaxed),
+        }
+    }
+
+    pub fn epoch(&self) -> u64 {
+        self.epoch
+    }
+
+    pub fn has_more(&self) -> bool {
+        self.requested < self.ids.len()
+    }
+
+    pub fn is_loading(&self) -> bool {
+        self.loading
+    }
+
+    /// 还没请求过的 story 数
+    pub fn remaining(&self) -> usize {
+        self.ids.len() - self.requested
+    }
+
+    /// 取出下一页的 id 并标记为加载中；已有一页在途或没有更多时返回 None
+    pub fn next_page(&mut self) -> Option<Vec<i64>> {
+        if self.loading || !self.has_more() {
+            return None;
+        }
+        let end = (self.requested + STORY_PAGE_SIZE).min(self.ids.len());
+        let page = self.ids[self.requested..end].to_vec();
+        self.requested = end;
+        self.loading = true;
+        Some(page)
+    }
+
+    /// 最后一行可见的 story 距离已加载末尾不超过 `threshold` 行时预取下一页
+    pub fn prefetch(
+        &mut self,
+        last_visible_row: usize,
+        loaded_rows: usize,
+        threshold: usize,
+    ) -> Option<Vec<i64>> {
+        if last_visible_row + threshold < loaded_rows {
+            return None;
+        }
+        self.next_page()
+    }
+
+    pub fn finish_page(&mut self) {
+        self.loading = false;
+    }
+
+    /// 在 `epoch` 时请求的一页到了。列表期间被刷新过时返回 false，
+    /// 这一页来自旧的排名，不要了，新 pager 自己在途的页也不受影响
+    pub fn finish_page_from(&mut self, epoch: u64) -> bool {
+        if epoch != self.epoch {
+            return false;
+        }
+        self.finish_page();
+        true
+    }
+}
+
+/// 整行都已滚出列表顶部的行数。`scroll_top` 是向下滚动的距离，
+/// `row_heights` 按列表顺序给出每行高度
+pub fn rows_scrolled_past(scroll_top: f32, row_heights: &[f32]) -> usize {
+    let mut bottom = 0.0;
+    row_heights
+        .iter()
+        .take_while(|&&height| {
+            bottom += height;
+            bottom <= scroll_top
+        })
+        .count()
+}
+
+/// 自动刷新的间隔：环境变量优先于设置，没有或为 0 时关闭
+pub fn auto_refresh_interval(setting: Option<u64>, env: Option<&str>) -> Option<Duration> {
+    let minutes = match env.map(|value| value.trim().parse::<u64>()) {
+        Some(Ok(minutes)) => minutes,
+        _ => setting?,
+    };
+    (minutes > 0).then(|| Duration::from_secs(minutes * 60))
+}
+
+/// 把后台刷新拿到的第一页合并进已加载的列表，返回新出现的 story。
+/// 第一页按新排名整个换成 `fresh`；其余已加载的 story 保持原来的顺序接在后面，
+/// 掉出第一页的也留在那里，已加载的后续页不会丢
+pub fn merge_refreshed(list: &mut Vec<StorySummary>, fresh: Vec<StorySummary>) -> Vec<i64> {
+    let fresh_ids = fresh.iter().map(|s| s.id).collect::<HashSet<_>>();
+    let new_ids = fresh
+        .iter()
+        .filter(|story| !list.iter().any(|s| s.id == story.id))
+        .map(|story| story.id)
+        .collect();
+    list.retain(|s| !fresh_ids.contains(&s.id));
+    list.splice(0..0, fresh);
+    new_ids
+}
@@ -0,0 +1,137 @@
+use crate::models::StorySummary;
+use crate::paging::{self, StoryPager, STORY_PAGE_SIZE};
+use crate::test_support::story_summary;
+
+#[test]
+fn crossing_the_threshold_prefetches_exactly_one_page() {
+    let ids = (1..=70).collect::<Vec<i64>>();
+    let mut pager = StoryPager::new(ids);
+
+    let first = pager.next_page().unwrap();
+    assert_eq!(first.len(), STORY_PAGE_SIZE);
+    pager.finish_page();
+
+    // 还没滚到阈值
+    assert_eq!(pager.prefetch(19, 30, 10), None);
+
+    // 越过阈值只触发一次，在途期间继续滚动不会重复请求
+    let second = pager.prefetch(20, 30, 10).unwrap();
+    assert_eq!(second.first(), Some(&31));
+    assert_eq!(second.len(), STORY_PAGE_SIZE);
+    assert!(pager.is_loading());
+    assert_eq!(pager.prefetch(25, 30, 10), None);
+    assert_eq!(pager.prefetch(29, 30, 10), None);
+    pager.finish_page();
+
+    // 新一页到达后阈值跟着末尾后移
+    assert_eq!(pager.prefetch(29, 60, 10), None);
+    let last = pager.prefetch(55, 60, 10).unwrap();
+    assert_eq!(last, (61..=70).collect::<Vec<_>>());
+    pager.finish_page();
+
+    assert!(!pager.has_more());
+    assert_eq!(pager.prefetch(69, 70, 10), None);
+}
+
+#[test]
+fn load_more_is_guarded_by_the_page_in_flight() {
+    let mut pager = StoryPager::new((1..=45).collect());
+    pager.next_page().unwrap();
+    pager.finish_page();
+    assert_eq!(pager.remaining(), 15);
+
+    // 点 "Load more" 时预取还在途，不会再请求同一页
+    let page = pager.prefetch(29, 30, 10).unwrap();
+    assert_eq!(pager.next_page(), None);
+    pager.finish_page();
+
+    assert_eq!(page, (31..=45).collect::<Vec<_>>());
+    assert_eq!(pager.remaining(), 0);
+    assert_eq!(pager.next_page(), None);
+}
+
+#[test]
+fn pages_from_a_replaced_pager_are_dropped() {
+    let mut old = StoryPager::new((1..=60).collect());
+    old.next_page().unwrap();
+    old.finish_page();
+    let stale = old.epoch();
+    assert!(old.next_page().is_some());
+
+    // 刷新换上了新 pager，它自己的第二页也在途
+    let mut pager = StoryPager::new((1..=60).collect());
+    assert_ne!(pager.epoch(), stale);
+    pager.next_page().unwrap();
+    pager.finish_page();
+    pager.next_page().unwrap();
+
+    // 旧的那页到了：不算数，新 pager 仍在加载，不会再请求同一页
+    assert!(!pager.finish_page_from(stale));
+    assert!(pager.is_loading());
+    assert_eq!(pager.next_page(), None);
+    assert!(pager.finish_page_from(pager.epoch()));
+    assert!(!pager.is_loading());
+}
+
+#[test]
+fn only_rows_fully_above_the_viewport_count_as_scrolled_past() {
+    let heights = [60.0, 80.0, 60.0, 60.0];
+
+    assert_eq!(paging::rows_scrolled_past(0.0, &heights), 0);
+    // 第一行还露出一点
+    assert_eq!(paging::rows_scrolled_past(59.0, &heights), 0);
+    assert_eq!(paging::rows_scrolled_past(60.0, &heights), 1);
+    assert_eq!(paging::rows_scrolled_past(199.0, &heights), 2);
+    assert_eq!(paging::rows_scrolled_past(10_000.0, &heights), 4);
+    assert_eq!(paging::rows_scrolled_past(-20.0, &heights), 0);
+}
+
+fn summary(id: i64, score: i32) -> StorySummary {
+    StorySummary {
+        score,
+        ..story_summary(id)
+    }
+}
+
+#[test]
+fn refreshed_first_page_merges_into_the_loaded_list() {
+    // 已经加载了两页（这里简化成 1..=5）
+    let mut list = (1..=5).map(|id| summary(id, 10)).collect::<Vec<_>>();
+    // 新排名：7 冲到第一，2 的分数涨了，6 排在 3 后面，1 掉出了第一页
+    let fresh = vec![
+        summary(7, 50),
+        summary(2, 40),
+        summary(3, 10),
+        summary(6, 5),
+    ];
+
+    let new_ids = paging::merge_refreshed(&mut list, fresh);
+
+    assert_eq!(new_ids, vec![7, 6]);
+    let order = list.iter().map(|s| s.id).collect::<Vec<_>>();
+    // 第一页和新排名一致，掉出去的 1 排在它后面，后续页原样保留
+    assert_eq!(order, vec![7, 2, 3, 6, 1, 4, 5]);
+    assert_eq!(list[1].score, 40);
+}
+
+#[test]
+fn auto_refresh_is_off_unless_configured() {
+    use std::time::Duration;
+
+    assert_eq!(paging::auto_refresh_interval(None, None), None);
+    assert_eq!(
+        paging::auto_refresh_interval(Some(5), None),
+        Some(Duration::from_secs(300))
+    );
+    assert_eq!(paging::auto_refresh_interval(Some(0), None), None);
+    // 环境变量优先，`0` 可以关掉设置里打开的自动刷新
+    assert_eq!(
+        paging::auto_refresh_interval(None, Some("2")),
+        Some(Duration::from_secs(120))
+    );
+    assert_eq!(paging::auto_refresh_interval(Some(5), Some("0")), None);
+    assert_eq!(
+        paging::auto_refresh_interval(Some(5), Some("soon")),
+        Some(Duration::from_secs(300))
+    );
+}
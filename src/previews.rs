@@ -0,0 +1,72 @@
+use crate::models::Comment;
+use std::collections::HashMap;
+use std::time::Duration;
+
+/// 鼠标在同一行停留这么久才去获取预览，快速划过列表时不发请求
+pub const PREVIEW_HOVER_DELAY: Duration = Duration::from_millis(500);
+/// 预览里显示的顶级评论数
+pub const PREVIEW_COMMENT_COUNT: usize = 2;
+/// 每条预览评论最多显示的字符数
+const PREVIEW_MAX_CHARS: usize = 280;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct PreviewComment {
+    pub author: String,
+    pub text: String,
+}
+
+impl PreviewComment {
+    pub fn from_comment(comment: &Comment) -> Self {
+        let text = comment.clean_text();
+        let text = match text.char_indices().nth(PREVIEW_MAX_CHARS) {
+            Some((end, _)) => format!("{}…", text[..end].trim_end()),
+            None => text,
+        };
+        Self {
+            author: comment.author().to_string(),
+            text,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum CommentPreview {
+    Loading,
+    Ready(Vec<PreviewComment>),
+    Failed(String),
+}
+
+/// 按 story 缓存的评论预览，每个 story 只请求一次（失败也不重试，避免悬停时反复请求）
+#[derive(Debug, Default)]
+pub struct CommentPreviews {
+    entries: HashMap<i64, CommentPreview>,
+}
+
+impl CommentPreviews {
+    /// 标记开始获取；已经缓存或正在获取时返回 false，调用方不再发请求
+    pub fn begin(&mut self, story_id: i64) -> bool {
+        if self.entries.contains_key(&story_id) {
+            return false;
+        }
+        self.entries.insert(story_id, CommentPreview::Loading);
+        true
+    }
+
+    pub fn finish(&mut self, story_id: i64, result: Result<Vec<Comment>, String>) {
+        let preview = match result {
+            Ok(comments) => CommentPreview::Ready(
+                comments
+                    .iter()
+                    .take(PREVIEW_COMMENT_COUNT)
+                    .map(PreviewComment::from_comment)
+                    .collect(),
+            ),
+            Err(e) => CommentPreview::Failed(e),
+        };
+        self.entries.insert(story_id, preview);
+    }
+
+    pub fn get(&self, story_id: i64) -> Option<&CommentPreview> {
+        self.entries.get(&story_id)
+    }
+}
@@ -0,0 +1,75 @@
+use crate::api::HackerNewsClient;
+use crate::previews::{CommentPreview, CommentPreviews, PREVIEW_COMMENT_COUNT};
+use gpui::http_client::{AsyncBody, FakeHttpClient, HttpClient, Response};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+
+/// 悬停延迟到期后的流程：没有缓存时才请求
+fn hover(previews: &mut CommentPreviews, client: &HackerNewsClient, story_id: i64) {
+    if previews.begin(story_id) {
+        let result = futures::executor::block_on(
+            client.fetch_comment_preview(story_id, PREVIEW_COMMENT_COUNT),
+        );
+        previews.finish(story_id, result);
+    }
+}
+
+#[test]
+fn hovering_fetches_each_preview_once_and_reuses_the_cache() {
+    let story_fetches = Arc::new(AtomicUsize::new(0));
+    let counter = story_fetches.clone();
+    let http: Arc<dyn HttpClient> = FakeHttpClient::create(move |req| {
+        let counter = counter.clone();
+        async move {
+            let (status, body) = match req.uri().path() {
+                "/v0/item/1.json" => {
+                    counter.fetch_add(1, Ordering::SeqCst);
+                    (
+                        200,
+                        r#"{"id":1,"title":"Story","score":1,"by":"op","time":1700000000,"kids":[10,11,12],"type":"story"}"#,
+                    )
+                }
+                "/v0/item/10.json" => (
+                    200,
+                    r#"{"id":10,"by":"alice","text":"First &amp; <i>best</i>","time":1700000000,"parent":1,"type":"comment"}"#,
+                ),
+                "/v0/item/11.json" => (
+                    200,
+                    r#"{"id":11,"by":"bob","text":"Second","time":1700000000,"parent":1,"type":"comment"}"#,
+                ),
+                _ => (404, ""),
+            };
+            Ok(Response::builder()
+                .status(status)
+                .body(AsyncBody::from(body.to_string()))
+                .unwrap())
+        }
+    });
+    let client = HackerNewsClient::new(http);
+    let mut previews = CommentPreviews::default();
+
+    hover(&mut previews, &client, 1);
+    hover(&mut previews, &client, 1);
+    hover(&mut previews, &client, 1);
+    assert_eq!(story_fetches.load(Ordering::SeqCst), 1);
+
+    let Some(CommentPreview::Ready(comments)) = previews.get(1) else {
+        panic!("expected a ready preview, got {:?}", previews.get(1));
+    };
+    let summary = comments
+        .iter()
+        .map(|c| (c.author.as_str(), c.text.as_str()))
+        .collect::<Vec<_>>();
+    assert_eq!(summary, vec![("alice", "First & best"), ("bob", "Second")]);
+}
+
+#[test]
+fn preview_in_flight_is_not_requested_again() {
+    let mut previews = CommentPreviews::default();
+    assert!(previews.begin(7));
+    assert!(!previews.begin(7));
+    assert_eq!(previews.get(7), Some(&CommentPreview::Loading));
+
+    previews.finish(7, Err("HTTP 500".to_string()));
+    assert!(!previews.begin(7));
+}
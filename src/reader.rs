@@ -1,17 +1,47 @@
+use encoding_rs::{Encoding, UTF_16BE, UTF_16LE, UTF_8};
+use flate2::read::{DeflateDecoder, MultiGzDecoder, ZlibDecoder};
+use futures::future::{self, Either};
 use futures::AsyncReadExt as _;
 use gpui::http_client::{http, AsyncBody, HttpClient, HttpRequestExt, Method, RedirectPolicy};
+use gpui::BackgroundExecutor;
 use readabilityrs::{Readability, ReadabilityOptions};
 use scraper::{ElementRef, Html, Selector};
 use serde::{Deserialize, Serialize};
 use std::collections::hash_map::DefaultHasher;
+use std::collections::HashSet;
+use std::future::Future;
 use std::hash::{Hash, Hasher};
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::sync::Arc;
-use std::time::{SystemTime, UNIX_EPOCH};
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
 
 const MAX_HTML_BYTES: usize = 4 * 1024 * 1024;
+/// Redirect hops followed before a page load gives up.
+const MAX_REDIRECTS: usize = 10;
+/// How long a fetch (headers and body) may take before it is abandoned.
+const DEFAULT_FETCH_TIMEOUT: Duration = Duration::from_secs(15);
+/// Overrides `DEFAULT_FETCH_TIMEOUT`, in whole seconds.
+const FETCH_TIMEOUT_ENV: &str = "ONEAPP_FETCH_TIMEOUT";
+/// How much of a page is searched for a `<meta>` charset declaration.
+const CHARSET_SNIFF_BYTES: usize = 4096;
 const MAX_BLOCKS: usize = 300;
+/// Link density above which a paragraph is treated as navigation rather than prose.
+const NAV_LINK_DENSITY: f32 = 0.5;
+/// Minimum text length for a readabilityrs result we only use as a last resort.
+const MARGINAL_READABILITY_MIN_LEN: usize = 140;
 const DISK_CACHE_TTL_SECS: i64 = 24 * 60 * 60;
+/// Total size the article cache may grow to before the least recently read files go.
+const DEFAULT_DISK_CACHE_BUDGET_BYTES: u64 = 50 * 1024 * 1024;
+/// Overrides `DEFAULT_DISK_CACHE_BUDGET_BYTES`, in whole megabytes.
+const DISK_CACHE_BUDGET_ENV: &str = "ONEAPP_READER_CACHE_MB";
+/// How far in the future a cached `fetched_at` may be before it counts as garbage (clock skew).
+const DISK_CACHE_CLOCK_SKEW_SECS: i64 = 10 * 60;
+/// Most cache files a single repair pass inspects, so startup stays quick on huge caches.
+pub(crate) const CACHE_REPAIR_MAX_FILES: usize = 2_000;
+/// Time budget for a single repair pass; the rest of the cache is checked on a later start.
+pub(crate) const CACHE_REPAIR_BUDGET: Duration = Duration::from_millis(500);
+/// Below this much text an article without images is shown as "no readable content".
+const READABLE_TEXT_FLOOR: usize = 40;
 const POSITIVE_KEYWORDS: &[&str] = &[
     "article", "body", "content", "entry", "main", "page", "post", "read", "story", "text",
 ];
@@ -50,20 +80,252 @@ const NEGATIVE_KEYWORDS: &[&str] = &[
     "widget",
 ];
 
+/// Phrases that mark calls-to-action or "related" sections at the edges of an article.
+const EDGE_BOILERPLATE_PHRASES: &[&str] = &[
+    "advertisement",
+    "follow us",
+    "like us on",
+    "more from",
+    "most popular",
+    "read more",
+    "recommended for you",
+    "related articles",
+    "related posts",
+    "related stories",
+    "share this",
+    "share on",
+    "sign up",
+    "you may also like",
+    "you might also like",
+];
+/// Longest block that can still count as an edge CTA; longer text starting with a phrase is prose.
+const EDGE_BOILERPLATE_MAX_CHARS: usize = 80;
+/// Link text of JS expanders that reveal the rest of a truncated article body.
+const TRUNCATION_MARKERS: &[&str] = &[
+    "continue reading",
+    "keep reading",
+    "read the full article",
+    "read the full story",
+    "read the rest",
+    "read full article",
+    "read full story",
+    "show full article",
+];
+/// Only the last few blocks are checked for a truncation marker.
+const TRUNCATION_TAIL_BLOCKS: usize = 3;
+/// Without `og:image`, an image this close to the top counts as the lead image.
+const HERO_LEAD_BLOCKS: usize = 3;
+/// Quotes longer than this are real quotations, never decorative pull quotes.
+const PULL_QUOTE_MAX_WORDS: usize = 60;
+/// Share of a short quote's word pairs that must appear in one paragraph for it to
+/// count as a pull quote repeating the body.
+const PULL_QUOTE_OVERLAP: f32 = 0.8;
+/// Larger tables are cut down to this many body rows and columns.
+pub(crate) const MAX_TABLE_ROWS: usize = 60;
+const MAX_TABLE_COLUMNS: usize = 8;
+/// How many trailing blocks a "Related" heading may own before we stop treating it as boilerplate.
+const TRAILING_SECTION_MAX_BLOCKS: usize = 4;
+/// Headings at or above this level already read as a section break, so a rule right
+/// before one is dropped.
+const SECTION_HEADING_LEVEL: u8 = 2;
+
+/// Most reader tabs kept open; opening another drops the oldest one that isn't shown.
+pub const MAX_READER_TABS: usize = 6;
+/// Most pages a tab remembers for Back; older ones are forgotten.
+pub const MAX_READER_BACK_PAGES: usize = 20;
+
+/// Where a link clicked inside an article opens.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ReaderLinkTarget {
+    /// Replace the article in the current tab; Back returns to it.
+    #[default]
+    Replace,
+    NewTab,
+    Browser,
+}
+
 #[derive(Debug, Clone)]
 pub struct ReaderSession {
     pub url: String,
     pub title_hint: Option<String>,
     pub state: ReaderLoadState,
+    /// Scroll position saved while another tab is shown.
+    pub scroll_y: f32,
+    /// Pages this tab showed before following links, most recent last.
+    pub back: Vec<ReaderSession>,
+}
+
+impl ReaderSession {
+    pub fn new(url: String, title_hint: Option<String>, state: ReaderLoadState) -> Self {
+        Self {
+            url,
+            title_hint,
+            state,
+            scroll_y: 0.0,
+            back: Vec::new(),
+        }
+    }
+
+    pub fn can_go_back(&self) -> bool {
+        !self.back.is_empty()
+    }
+
+    /// The article title once loaded, otherwise the hint or the URL.
+    pub fn title(&self) -> String {
+        match &self.state {
+            ReaderLoadState::Ready(article) if !article.title.is_empty() => article.title.clone(),
+            _ => self.title_hint.clone().unwrap_or_else(|| self.url.clone()),
+        }
+    }
+
+    /// The page to hand to the browser: where the loaded article was redirected to, if anywhere.
+    pub fn browser_url(&self) -> &str {
+        match &self.state {
+            ReaderLoadState::Ready(article) => article.final_url.as_deref().unwrap_or(&self.url),
+            _ => &self.url,
+        }
+    }
+}
+
+/// Open reader tabs. At most one is shown; hiding the reader keeps the tabs.
+#[derive(Debug, Clone, Default)]
+pub struct ReaderTabs {
+    tabs: Vec<ReaderSession>,
+    active: Option<usize>,
+}
+
+impl ReaderTabs {
+    pub fn tabs(&self) -> &[ReaderSession] {
+        &self.tabs
+    }
+
+    pub fn active_index(&self) -> Option<usize> {
+        self.active
+    }
+
+    pub fn active(&self) -> Option<&ReaderSession> {
+        self.active.and_then(|index| self.tabs.get(index))
+    }
+
+    pub fn active_mut(&mut self) -> Option<&mut ReaderSession> {
+        self.active.and_then(|index| self.tabs.get_mut(index))
+    }
+
+    pub fn position(&self, url: &str) -> Option<usize> {
+        self.tabs.iter().position(|tab| tab.url == url)
+    }
+
+    /// The page for `url`, whether or not it's shown (or waiting in a tab's back
+    /// stack), so a finished load lands in the right place after the user moved on.
+    pub fn find_mut(&mut self, url: &str) -> Option<&mut ReaderSession> {
+        self.tabs.iter_mut().find_map(|tab| {
+            if tab.url == url {
+                Some(tab)
+            } else {
+                tab.back.iter_mut().find(|page| page.url == url)
+            }
+        })
+    }
+
+    /// Shows `session`, replacing the tab for the same URL if there is one.
+    pub fn open(&mut self, mut session: ReaderSession) {
+        let index = match self.position(&session.url) {
+            Some(index) => {
+                // Reloading a page keeps the way back
+                session.back = std::mem::take(&mut self.tabs[index].back);
+                self.tabs[index] = session;
+                index
+            }
+            None => {
+                if self.tabs.len() >= MAX_READER_TABS {
+                    let oldest = if self.active == Some(0) { 1 } else { 0 };
+                    self.close(oldest);
+                }
+                self.tabs.push(session);
+                self.tabs.len() - 1
+            }
+        };
+        self.active = Some(index);
+    }
+
+    /// Shows `session` in place of the shown tab's page, which Back then returns to.
+    /// Without a shown tab, or when another tab already has the URL, this is `open`.
+    pub fn navigate(&mut self, mut session: ReaderSession) {
+        let Some(active) = self.active else {
+            return self.open(session);
+        };
+        if self.position(&session.url).is_some() {
+            return self.open(session);
+        }
+        let tab = &mut self.tabs[active];
+        session.back = std::mem::take(&mut tab.back);
+        let previous = std::mem::replace(tab, session);
+        tab.back.push(previous);
+        if tab.back.len() > MAX_READER_BACK_PAGES {
+            tab.back.remove(0);
+        }
+    }
+
+    /// Returns the shown tab to its previous page; false when there is none.
+    pub fn go_back(&mut self) -> bool {
+        let Some(tab) = self.active_mut() else {
+            return false;
+        };
+        let Some(mut previous) = tab.back.pop() else {
+            return false;
+        };
+        previous.back = std::mem::take(&mut tab.back);
+        *tab = previous;
+        true
+    }
+
+    pub fn activate(&mut self, index: usize) -> bool {
+        if index >= self.tabs.len() {
+            return false;
+        }
+        self.active = Some(index);
+        true
+    }
+
+    /// Closes a tab; closing the shown one shows its neighbour instead.
+    pub fn close(&mut self, index: usize) -> Option<ReaderSession> {
+        if index >= self.tabs.len() {
+            return None;
+        }
+        let session = self.tabs.remove(index);
+        self.active = match self.active {
+            Some(active) if active == index => {
+                (!self.tabs.is_empty()).then(|| index.min(self.tabs.len() - 1))
+            }
+            Some(active) if active > index => Some(active - 1),
+            active => active,
+        };
+        Some(session)
+    }
+
+    pub fn hide(&mut self) {
+        self.active = None;
+    }
 }
 
 #[derive(Debug, Clone)]
 pub enum ReaderLoadState {
     Loading,
     Ready(ReaderArticle),
+    /// The link is a PDF, which reader mode can't show; the UI offers to open it externally.
+    Pdf,
     Error(String),
 }
 
+/// What `load_article` found behind a URL.
+#[derive(Debug, Clone)]
+pub enum LoadedPage {
+    Article(ReaderArticle),
+    /// A PDF, recognised by its extension or `Content-Type` before the body is downloaded.
+    Pdf,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ReaderArticle {
     pub title: String,
@@ -71,15 +333,31 @@ pub struct ReaderArticle {
     pub site_name: Option<String>,
     pub reading_time: Option<String>,
     pub blocks: Vec<ReaderBlock>,
+    /// Set when no clear article body was found and the content is a best-effort guess.
+    #[serde(default)]
+    pub low_confidence: bool,
+    /// Set when the body stops at a "Continue reading" expander we can't run, so
+    /// the rest of the article is probably missing.
+    #[serde(default)]
+    pub truncated: bool,
+    /// Lead image from `og:image` or the top of the body. Its copies stay in `blocks`;
+    /// `for_display` drops them when the hero is drawn above the article.
+    #[serde(default)]
+    pub hero_image: Option<String>,
+    /// Where the page ended up after redirects, when that differs from the requested URL.
+    #[serde(default)]
+    pub final_url: Option<String>,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub enum ReaderBlock {
     Heading {
         level: u8,
         text: String,
     },
     Paragraph(String),
+    /// A paragraph with inline markup (links, abbreviations); plain paragraphs stay `Paragraph`.
+    RichParagraph(Vec<InlineRun>),
     Quote(String),
     List {
         ordered: bool,
@@ -89,66 +367,181 @@ pub enum ReaderBlock {
         text: String,
         language: Option<String>,
     },
+    /// A data table; `headers` is empty when the table has no header row. Every row is
+    /// padded to the same number of cells.
+    Table {
+        headers: Vec<String>,
+        rows: Vec<Vec<String>>,
+    },
     Image {
         url: String,
         alt: Option<String>,
         caption: Option<String>,
+        /// Intrinsic `(width, height)` from the `width`/`height` attributes, when given.
+        #[serde(default)]
+        dimensions: Option<(u32, u32)>,
     },
     Rule,
 }
 
+impl ReaderArticle {
+    /// False when extraction succeeded but left nothing worth showing (e.g. all media was filtered).
+    pub fn has_readable_content(&self) -> bool {
+        self.blocks
+            .iter()
+            .any(|b| matches!(b, ReaderBlock::Image { .. }))
+            || total_text_len(&self.blocks) >= READABLE_TEXT_FLOOR
+    }
+
+    /// Estimated reading time in whole minutes, `None` for an article without text.
+    pub fn reading_minutes(&self) -> Option<usize> {
+        estimate_reading_minutes(&self.blocks)
+    }
+
+    /// Headings in reading order, for the table of contents.
+    pub fn table_of_contents(&self) -> Vec<TocEntry> {
+        self.blocks
+            .iter()
+            .enumerate()
+            .filter_map(|(index, block)| match block {
+                ReaderBlock::Heading { level, text } => Some(TocEntry {
+                    index,
+                    level: *level,
+                    text: text.clone(),
+                }),
+                _ => None,
+            })
+            .collect()
+    }
+
+    /// The article as shown: with the hero drawn above it, the body copies of that image
+    /// are dropped so it isn't shown twice; without it, the body keeps them.
+    pub fn for_display(mut self, show_hero: bool) -> Self {
+        if let Some(hero) = self.hero_image.as_ref().filter(|_| show_hero) {
+            self.blocks
+                .retain(|b| !matches!(b, ReaderBlock::Image { url, .. } if same_image(url, hero)));
+        }
+        self
+    }
+}
+
+/// One heading in an article's table of contents.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TocEntry {
+    /// Index of the heading in `ReaderArticle::blocks`.
+    pub index: usize,
+    pub level: u8,
+    pub text: String,
+}
+
+/// A stretch of paragraph text sharing the same inline markup.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct InlineRun {
+    pub text: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub link: Option<String>,
+    /// Expansion from an enclosing `<abbr title="...">`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub abbr_title: Option<String>,
+    /// Inside `<strong>` or `<b>`.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub bold: bool,
+    /// Inside `<em>` or `<i>`.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub italic: bool,
+}
+
+impl InlineRun {
+    fn has_markup(&self) -> bool {
+        self.is_underlined() || self.bold || self.italic
+    }
+
+    /// Links and abbreviations are drawn with an underline.
+    fn is_underlined(&self) -> bool {
+        self.link.is_some() || self.abbr_title.is_some()
+    }
+
+    fn same_markup(&self, other: &InlineRun) -> bool {
+        self.link == other.link
+            && self.abbr_title == other.abbr_title
+            && self.bold == other.bold
+            && self.italic == other.italic
+    }
+}
+
+pub fn inline_text(runs: &[InlineRun]) -> String {
+    runs.iter().map(|run| run.text.as_str()).collect()
+}
+
+/// `reader_dir` is the on-disk article cache (`AppDirs::reader_cache`).
 pub async fn load_article(
     http_client: Arc<dyn HttpClient>,
+    reader_dir: &Path,
     url: &str,
     title_hint: Option<&str>,
-) -> Result<ReaderArticle, String> {
+    executor: BackgroundExecutor,
+) -> Result<LoadedPage, String> {
     let parsed_url = url::Url::parse(url).map_err(|e| format!("Invalid URL: {e}"))?;
     if parsed_url.scheme() != "http" && parsed_url.scheme() != "https" {
         return Err("Only http(s) URLs are supported.".to_string());
     }
+    if is_pdf_url(&parsed_url) {
+        return Ok(LoadedPage::Pdf);
+    }
 
-    if let Some(mut cached) = read_disk_cache(url) {
+    if let Some(mut cached) = read_disk_cache(reader_dir, url) {
         if cached.title.is_empty() {
             if let Some(title_hint) = title_hint {
                 cached.title = title_hint.to_string();
             }
         }
-        return Ok(cached);
+        return Ok(LoadedPage::Article(cached));
     }
 
-    let request = http::Request::builder()
-        .method(Method::GET)
-        .uri(url)
-        .follow_redirects(RedirectPolicy::FollowAll)
-        .header("User-Agent", "OneApp/0.1 (GPUI Reader Mode)")
-        .header(
-            "Accept",
-            "text/html,application/xhtml+xml,application/xml;q=0.9,*/*;q=0.8",
-        )
-        .body(AsyncBody::empty())
-        .map_err(|e| e.to_string())?;
+    let fetch = async {
+        let (response, final_url) =
+            send_following_redirects(http_client.as_ref(), &parsed_url).await?;
 
-    let response = http_client.send(request).await.map_err(|e| e.to_string())?;
-
-    if !response.status().is_success() {
-        return Err(format!("HTTP {} for {}", response.status(), url));
-    }
+        if !response.status().is_success() {
+            return Err(format!("HTTP {} for {}", response.status(), url));
+        }
 
-    let content_type = response
-        .headers()
-        .get(http::header::CONTENT_TYPE)
-        .and_then(|v| v.to_str().ok())
-        .unwrap_or("")
-        .to_string();
+        let header = |name: http::header::HeaderName| {
+            response
+                .headers()
+                .get(name)
+                .and_then(|v| v.to_str().ok())
+                .unwrap_or("")
+                .to_string()
+        };
+        let content_type = header(http::header::CONTENT_TYPE);
+        let content_encoding = header(http::header::CONTENT_ENCODING);
+        // Stop at the headers: the body would only be thrown away.
+        if is_pdf_content_type(&content_type) {
+            return Ok(None);
+        }
 
-    let mut body = response.into_body();
-    let bytes = read_to_end_limited(&mut body, MAX_HTML_BYTES).await?;
-    let content = String::from_utf8_lossy(&bytes).to_string();
+        let mut body = response.into_body();
+        let bytes = read_to_end_limited(&mut body, MAX_HTML_BYTES).await?;
+        let bytes = decode_body(bytes, &content_encoding, MAX_HTML_BYTES)?;
+        Ok(Some((content_type, final_url, bytes)))
+    };
+    let timeout = fetch_timeout();
+    let Some((content_type, final_url, bytes)) =
+        with_timeout(fetch, timeout, executor.timer(timeout)).await?
+    else {
+        return Ok(LoadedPage::Pdf);
+    };
+    let content = decode_html(&bytes, &content_type);
+    // Relative links and images resolve against the page we landed on, not the one we asked for.
+    let redirected =
+        (final_url != parsed_url).then(|| crate::models::clean_url(final_url.as_str()));
 
     if content_type.contains("text/plain") {
-        let article = plain_text_article(&content, &parsed_url, title_hint.map(str::to_string));
-        let _ = write_disk_cache(url, &article);
-        return Ok(article);
+        let mut article = plain_text_article(&content, &final_url, title_hint.map(str::to_string));
+        article.final_url = redirected;
+        let _ = write_disk_cache(reader_dir, url, &article);
+        return Ok(LoadedPage::Article(article));
     }
 
     if !content_type.is_empty()
@@ -157,50 +550,272 @@ pub async fn load_article(
         return Err(format!("Unsupported content type: {content_type}"));
     }
 
-    let article = extract_html_article(&content, &parsed_url, title_hint.map(str::to_string));
-    let _ = write_disk_cache(url, &article);
-    Ok(article)
+    let mut article = extract_html_article(&content, &final_url, title_hint.map(str::to_string));
+    article.final_url = redirected;
+    let _ = write_disk_cache(reader_dir, url, &article);
+    Ok(LoadedPage::Article(article))
+}
+
+/// GETs `url`, following redirects one hop at a time so we know where the page ended up: the
+/// HTTP client doesn't report the final URL of a redirect it followed itself.
+///
+/// Returns the first non-redirect response together with the URL it came from.
+pub(crate) async fn send_following_redirects(
+    http_client: &dyn HttpClient,
+    url: &url::Url,
+) -> Result<(http::Response<AsyncBody>, url::Url), String> {
+    let mut current = url.clone();
+    for _ in 0..=MAX_REDIRECTS {
+        let request = http::Request::builder()
+            .method(Method::GET)
+            .uri(current.as_str())
+            .follow_redirects(RedirectPolicy::NoFollow)
+            .header("User-Agent", "OneApp/0.1 (GPUI Reader Mode)")
+            .header(
+                "Accept",
+                "text/html,application/xhtml+xml,application/xml;q=0.9,*/*;q=0.8",
+            )
+            .body(AsyncBody::empty())
+            .map_err(|e| e.to_string())?;
+        let response = http_client.send(request).await.map_err(|e| e.to_string())?;
+        let status = response.status();
+        if !status.is_redirection() {
+            return Ok((response, current));
+        }
+
+        let location = response
+            .headers()
+            .get(http::header::LOCATION)
+            .and_then(|v| v.to_str().ok())
+            .ok_or_else(|| format!("HTTP {status} without a Location for {current}"))?;
+        // `Location` may be relative to the URL that sent it.
+        let next = current
+            .join(location.trim())
+            .map_err(|e| format!("Invalid redirect from {current}: {e}"))?;
+        if !matches!(next.scheme(), "http" | "https") {
+            return Err(format!("Redirected to a non-http(s) URL: {next}"));
+        }
+        current = next;
+    }
+    Err(format!("Too many redirects for {url}"))
 }
 
-async fn read_to_end_limited(body: &mut AsyncBody, limit: usize) -> Result<Vec<u8>, String> {
+/// Whether the URL's path names a PDF (`/paper.pdf`, `/Paper.PDF`).
+pub fn is_pdf_url(url: &url::Url) -> bool {
+    url.path().to_ascii_lowercase().ends_with(".pdf")
+}
+
+/// Whether a `Content-Type` header is a PDF, ignoring parameters and case.
+pub fn is_pdf_content_type(content_type: &str) -> bool {
+    let mime = content_type.split(';').next().unwrap_or_default().trim();
+    mime.eq_ignore_ascii_case("application/pdf") || mime.eq_ignore_ascii_case("application/x-pdf")
+}
+
+/// The fetch timeout, taking `ONEAPP_FETCH_TIMEOUT` into account.
+pub(crate) fn fetch_timeout() -> Duration {
+    parse_fetch_timeout(std::env::var(FETCH_TIMEOUT_ENV).ok().as_deref())
+}
+
+/// Parses a timeout in seconds; missing, zero or malformed values fall back to the default.
+pub(crate) fn parse_fetch_timeout(value: Option<&str>) -> Duration {
+    value
+        .and_then(|value| value.trim().parse::<u64>().ok())
+        .filter(|&secs| secs > 0)
+        .map(Duration::from_secs)
+        .unwrap_or(DEFAULT_FETCH_TIMEOUT)
+}
+
+/// Races `fetch` against `sleep` (normally an executor timer for `timeout`).
+///
+/// The error mentions "timeout" so the UI shows it as a timed-out request.
+pub(crate) async fn with_timeout<T>(
+    fetch: impl Future<Output = Result<T, String>>,
+    timeout: Duration,
+    sleep: impl Future<Output = ()>,
+) -> Result<T, String> {
+    futures::pin_mut!(fetch);
+    futures::pin_mut!(sleep);
+    match future::select(fetch, sleep).await {
+        Either::Left((result, _)) => result,
+        Either::Right(((), _)) => Err(format!(
+            "Request timeout after {}s",
+            timeout.as_secs_f32().ceil() as u64
+        )),
+    }
+}
+
+/// Why a response body couldn't be read in full.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) enum BodyError {
+    /// The connection failed partway through.
+    Io(String),
+    /// The body is bigger than the caller's limit.
+    TooLarge { limit: usize },
+}
+
+impl std::fmt::Display for BodyError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            BodyError::Io(message) => f.write_str(message),
+            BodyError::TooLarge { limit } => f.write_str(&too_large(*limit)),
+        }
+    }
+}
+
+impl From<BodyError> for String {
+    fn from(error: BodyError) -> Self {
+        error.to_string()
+    }
+}
+
+pub(crate) async fn read_to_end_limited(
+    body: &mut AsyncBody,
+    limit: usize,
+) -> Result<Vec<u8>, BodyError> {
     let mut bytes = Vec::new();
     let mut total = 0usize;
     let mut buf = [0u8; 8192];
     loop {
-        let n = body.read(&mut buf).await.map_err(|e| e.to_string())?;
+        let n = body
+            .read(&mut buf)
+            .await
+            .map_err(|e| BodyError::Io(e.to_string()))?;
         if n == 0 {
             break;
         }
         total = total.saturating_add(n);
         if total > limit {
-            return Err(format!(
-                "Response too large (>{} MB)",
-                (limit as f32 / (1024.0 * 1024.0)).ceil() as usize
-            ));
+            return Err(BodyError::TooLarge { limit });
         }
         bytes.extend_from_slice(&buf[..n]);
     }
     Ok(bytes)
 }
 
+/// Decodes a page with the charset from `Content-Type`, else one declared in a
+/// `<meta>` tag near the top, else UTF-8. A byte order mark overrides both.
+pub(crate) fn decode_html(bytes: &[u8], content_type: &str) -> String {
+    let encoding = content_type_charset(content_type)
+        .and_then(|label| Encoding::for_label(label.as_bytes()))
+        .or_else(|| meta_charset(bytes))
+        .unwrap_or(UTF_8);
+    let (text, _, _) = encoding.decode(bytes);
+    text.into_owned()
+}
+
+fn content_type_charset(content_type: &str) -> Option<&str> {
+    content_type.split(';').skip(1).find_map(|param| {
+        let (name, value) = param.split_once('=')?;
+        name.trim()
+            .eq_ignore_ascii_case("charset")
+            .then(|| value.trim().trim_matches(|c| c == '"' || c == '\''))
+    })
+}
+
+/// Handles both `<meta charset="x">` and the older
+/// `<meta http-equiv="Content-Type" content="text/html; charset=x">`.
+fn meta_charset(bytes: &[u8]) -> Option<&'static Encoding> {
+    let head = &bytes[..bytes.len().min(CHARSET_SNIFF_BYTES)];
+    let head = String::from_utf8_lossy(head).to_ascii_lowercase();
+    let mut rest = head.as_str();
+    while let Some(start) = rest.find("<meta") {
+        rest = &rest[start + "<meta".len()..];
+        let tag = &rest[..rest.find('>').unwrap_or(rest.len())];
+        let Some(value) = tag
+            .find("charset")
+            .and_then(|i| tag[i + "charset".len()..].trim_start().strip_prefix('='))
+        else {
+            continue;
+        };
+        let label = value
+            .trim_start()
+            .trim_start_matches(['"', '\''])
+            .split(|c: char| !(c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | ':' | '.')))
+            .next()
+            .unwrap_or("");
+        if let Some(encoding) = Encoding::for_label(label.as_bytes()) {
+            // An ASCII-readable tag means the page isn't really UTF-16
+            return Some(if encoding == UTF_16LE || encoding == UTF_16BE {
+                UTF_8
+            } else {
+                encoding
+            });
+        }
+    }
+    None
+}
+
+fn too_large(limit: usize) -> String {
+    format!(
+        "Response too large (>{} MB)",
+        (limit as f32 / (1024.0 * 1024.0)).ceil() as usize
+    )
+}
+
+/// Undoes `Content-Encoding` (gzip, br, deflate), streaming so the decoded body is
+/// capped at `limit` too. Bodies that don't decode are returned as they came, since
+/// some servers label content that a proxy already decompressed.
+pub(crate) fn decode_body(
+    bytes: Vec<u8>,
+    content_encoding: &str,
+    limit: usize,
+) -> Result<Vec<u8>, String> {
+    // Encodings are listed in the order they were applied
+    let mut decoded = bytes;
+    for encoding in content_encoding.rsplit(',') {
+        let encoding = encoding.trim().to_ascii_lowercase();
+        let input = &decoded[..];
+        let result = match encoding.as_str() {
+            "gzip" | "x-gzip" => read_decoded(MultiGzDecoder::new(input), limit),
+            "br" => read_decoded(brotli_decompressor::Decompressor::new(input, 4096), limit),
+            // `deflate` should be zlib-wrapped, but plenty of servers send raw deflate
+            "deflate" => read_decoded(ZlibDecoder::new(input), limit)
+                .or_else(|_| read_decoded(DeflateDecoder::new(input), limit)),
+            _ => continue,
+        };
+        match result {
+            Ok(Some(bytes)) => decoded = bytes,
+            Ok(None) => return Err(too_large(limit)),
+            Err(_) => break,
+        }
+    }
+    Ok(decoded)
+}
+
+/// Reads at most `limit` decoded bytes; `None` when there is more.
+fn read_decoded(decoder: impl std::io::Read, limit: usize) -> std::io::Result<Option<Vec<u8>>> {
+    let mut bytes = Vec::new();
+    decoder.take(limit as u64 + 1).read_to_end(&mut bytes)?;
+    Ok((bytes.len() <= limit).then_some(bytes))
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 struct DiskCacheEntry {
     fetched_at: i64,
     article: ReaderArticle,
 }
 
-fn read_disk_cache(url: &str) -> Option<ReaderArticle> {
-    let path = disk_cache_path(url)?;
-    let bytes = std::fs::read(path).ok()?;
+fn read_disk_cache(reader_dir: &Path, url: &str) -> Option<ReaderArticle> {
+    let path = disk_cache_path(reader_dir, url);
+    let bytes = std::fs::read(&path).ok()?;
     let entry: DiskCacheEntry = serde_json::from_slice(&bytes).ok()?;
     if is_cache_stale(entry.fetched_at) {
         return None;
     }
+    // The mtime doubles as the last access time for eviction
+    let _ = touch(&path);
     Some(entry.article)
 }
 
-fn write_disk_cache(url: &str, article: &ReaderArticle) -> Result<(), String> {
-    let path = disk_cache_path(url).ok_or_else(|| "No cache directory available".to_string())?;
+fn touch(path: &Path) -> std::io::Result<()> {
+    std::fs::File::options()
+        .write(true)
+        .open(path)?
+        .set_modified(SystemTime::now())
+}
+
+fn write_disk_cache(reader_dir: &Path, url: &str, article: &ReaderArticle) -> Result<(), String> {
+    let path = disk_cache_path(reader_dir, url);
     if let Some(parent) = path.parent() {
         std::fs::create_dir_all(parent).map_err(|e| e.to_string())?;
     }
@@ -217,9 +832,56 @@ fn write_disk_cache(url: &str, article: &ReaderArticle) -> Result<(), String> {
         let _ = std::fs::remove_file(&path);
         std::fs::rename(&tmp_path, &path).map_err(|_| error.to_string())?;
     }
+    if let Some(dir) = path.parent() {
+        let _ = evict_disk_cache(dir, disk_cache_budget(), &path);
+    }
     Ok(())
 }
 
+/// The cache budget, taking `ONEAPP_READER_CACHE_MB` into account.
+fn disk_cache_budget() -> u64 {
+    std::env::var(DISK_CACHE_BUDGET_ENV)
+        .ok()
+        .and_then(|value| value.trim().parse::<u64>().ok())
+        .filter(|&mb| mb > 0)
+        .map_or(DEFAULT_DISK_CACHE_BUDGET_BYTES, |mb| mb * 1024 * 1024)
+}
+
+/// Deletes the least recently read cache files under `reader_dir` until the rest fit in
+/// `budget` bytes. `keep` (the file just written) is never removed. Returns how many went.
+pub(crate) fn evict_disk_cache(
+    reader_dir: &Path,
+    budget: u64,
+    keep: &Path,
+) -> std::io::Result<usize> {
+    let mut files = std::fs::read_dir(reader_dir)?
+        .flatten()
+        .filter(|e| e.path().extension().is_some_and(|ext| ext == "json"))
+        .filter_map(|e| {
+            let meta = e.metadata().ok()?;
+            let accessed = meta.modified().unwrap_or(UNIX_EPOCH);
+            Some((accessed, meta.len(), e.path()))
+        })
+        .collect::<Vec<_>>();
+    let mut total = files.iter().map(|(_, len, _)| len).sum::<u64>();
+    if total <= budget {
+        return Ok(0);
+    }
+
+    files.sort_by_key(|(accessed, _, _)| *accessed);
+    let mut removed = 0;
+    for (_, len, path) in files {
+        if total <= budget {
+            break;
+        }
+        if path != keep && std::fs::remove_file(&path).is_ok() {
+            total = total.saturating_sub(len);
+            removed += 1;
+        }
+    }
+    Ok(removed)
+}
+
 fn is_cache_stale(fetched_at: i64) -> bool {
     let Some(now) = now_unix_secs() else {
         return true;
@@ -227,6 +889,52 @@ fn is_cache_stale(fetched_at: i64) -> bool {
     now.saturating_sub(fetched_at) > DISK_CACHE_TTL_SECS
 }
 
+/// Outcome of a cache repair pass.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub(crate) struct CacheRepair {
+    pub scanned: usize,
+    pub removed: usize,
+}
+
+/// Deletes cached articles under `reader_dir` that no longer deserialize (partial writes,
+/// truncation) or whose `fetched_at` is not a plausible timestamp. Stale but intact entries are
+/// left for `read_disk_cache` to refetch. Stops after `max_files` files or once `budget` runs out.
+pub(crate) fn repair_disk_cache(
+    reader_dir: &Path,
+    now: i64,
+    max_files: usize,
+    budget: Duration,
+) -> std::io::Result<CacheRepair> {
+    let entries = match std::fs::read_dir(reader_dir) {
+        Ok(entries) => entries,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(CacheRepair::default()),
+        Err(e) => return Err(e),
+    };
+
+    let started = Instant::now();
+    let mut repair = CacheRepair::default();
+    let paths = entries
+        .flatten()
+        .map(|e| e.path())
+        .filter(|path| path.extension().is_some_and(|ext| ext == "json"));
+    for path in paths.take(max_files) {
+        if started.elapsed() > budget {
+            break;
+        }
+        repair.scanned += 1;
+        let healthy = std::fs::read(&path)
+            .ok()
+            .and_then(|bytes| serde_json::from_slice::<DiskCacheEntry>(&bytes).ok())
+            .is_some_and(|entry| {
+                entry.fetched_at > 0 && entry.fetched_at <= now + DISK_CACHE_CLOCK_SKEW_SECS
+            });
+        if !healthy && std::fs::remove_file(&path).is_ok() {
+            repair.removed += 1;
+        }
+    }
+    Ok(repair)
+}
+
 fn now_unix_secs() -> Option<i64> {
     SystemTime::now()
         .duration_since(UNIX_EPOCH)
@@ -234,62 +942,147 @@ fn now_unix_secs() -> Option<i64> {
         .map(|d| d.as_secs() as i64)
 }
 
-fn disk_cache_path(url: &str) -> Option<PathBuf> {
-    let dir = reader_cache_dir()?;
+fn disk_cache_path(reader_dir: &Path, url: &str) -> PathBuf {
     let key = url_cache_key(url);
-    Some(dir.join("reader").join(format!("{key}.json")))
-}
-
-fn url_cache_key(url: &str) -> String {
-    let mut hasher = DefaultHasher::new();
-    url.hash(&mut hasher);
-    format!("{:016x}", hasher.finish())
+    reader_dir.join(format!("{key}.json"))
 }
 
-fn reader_cache_dir() -> Option<PathBuf> {
-    if let Some(dir) = std::env::var_os("ONEAPP_CACHE_DIR") {
-        return Some(PathBuf::from(dir));
-    }
-
-    if let Some(dir) = std::env::var_os("XDG_CACHE_HOME") {
-        return Some(PathBuf::from(dir).join("oneapp"));
-    }
+/// Removes every file directly under `reader_dir`; a missing directory counts as empty.
+pub(crate) fn clear_cache_files(reader_dir: &Path) -> std::io::Result<usize> {
+    let entries = match std::fs::read_dir(reader_dir) {
+        Ok(entries) => entries,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(0),
+        Err(e) => return Err(e),
+    };
 
-    #[cfg(target_os = "macos")]
-    {
-        if let Some(home) = std::env::var_os("HOME") {
-            return Some(PathBuf::from(home).join("Library/Caches/OneApp"));
+    let mut removed = 0;
+    for entry in entries.flatten() {
+        let is_file = entry.file_type().is_ok_and(|t| t.is_file());
+        if is_file && std::fs::remove_file(entry.path()).is_ok() {
+            removed += 1;
         }
     }
+    Ok(removed)
+}
 
-    if let Some(home) = std::env::var_os("HOME").or_else(|| std::env::var_os("USERPROFILE")) {
-        return Some(PathBuf::from(home).join(".cache/oneapp"));
-    }
+/// Number and total size of cached article files under `reader_dir`.
+pub(crate) fn cache_usage(reader_dir: &Path) -> std::io::Result<(usize, u64)> {
+    let entries = match std::fs::read_dir(reader_dir) {
+        Ok(entries) => entries,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok((0, 0)),
+        Err(e) => return Err(e),
+    };
+
+    Ok(entries
+        .flatten()
+        .filter(|e| e.path().extension().is_some_and(|ext| ext == "json"))
+        .filter_map(|e| e.metadata().ok())
+        .fold((0usize, 0u64), |(count, bytes), meta| {
+            (count + 1, bytes + meta.len())
+        }))
+}
 
-    Some(std::env::temp_dir().join("oneapp-cache"))
+fn url_cache_key(url: &str) -> String {
+    let mut hasher = DefaultHasher::new();
+    url.hash(&mut hasher);
+    format!("{:016x}", hasher.finish())
 }
 
-fn extract_html_article(html: &str, url: &url::Url, title_hint: Option<String>) -> ReaderArticle {
-    let readability_article = extract_with_readabilityrs(html, url, title_hint.clone());
-    let fallback_article = extract_html_article_fallback(html, url, title_hint);
+pub(crate) fn extract_html_article(
+    html: &str,
+    url: &url::Url,
+    title_hint: Option<String>,
+) -> ReaderArticle {
+    let readability_article = extract_with_readabilityrs(html, url, title_hint.clone(), 500);
+    let mut fallback_article = extract_html_article_fallback(html, url, title_hint.clone());
+    let og_image = fallback_article.hero_image.take();
 
     // Compare the two extraction methods and choose the one with more content
-    match readability_article {
+    let mut article = match readability_article {
         Some(ra) => {
             let ra_len = total_text_len(&ra.blocks);
             let fb_len = total_text_len(&fallback_article.blocks);
 
             // Use fallback if it has significantly more content (at least 20% more)
-            if fb_len > ra_len + ra_len / 5 {
+            if !fallback_article.low_confidence && fb_len > ra_len + ra_len / 5 {
                 fallback_article
             } else {
                 ra
             }
         }
+        // Nothing usable from the heuristics either: accept a marginal readabilityrs result.
+        None if fallback_article.low_confidence && fallback_article.blocks.is_empty() => {
+            extract_with_readabilityrs(html, url, title_hint, MARGINAL_READABILITY_MIN_LEN)
+                .map(|article| ReaderArticle {
+                    low_confidence: true,
+                    ..article
+                })
+                .unwrap_or(fallback_article)
+        }
         None => fallback_article,
+    };
+    article.truncated = strip_truncation_marker(&mut article.blocks);
+    article.hero_image = pick_hero_image(&article.blocks, og_image);
+    article
+}
+
+fn extract_og_image(doc: &Html, url: &url::Url) -> Option<String> {
+    extract_meta(doc, "meta[property=\"og:image\"]")
+        .or_else(|| extract_meta(doc, "meta[name=\"twitter:image\"]"))
+        .and_then(|src| resolve_url(url, &src))
+}
+
+/// Picks the hero image: `og:image` wins; otherwise an image among the first few blocks.
+fn pick_hero_image(blocks: &[ReaderBlock], og_image: Option<String>) -> Option<String> {
+    og_image.or_else(|| {
+        blocks.iter().take(HERO_LEAD_BLOCKS).find_map(|b| match b {
+            ReaderBlock::Image { url, .. } => Some(url.clone()),
+            _ => None,
+        })
+    })
+}
+
+/// Sites often serve `og:image` with different size/crop query parameters than the
+/// body copy, so only the host and path are compared.
+fn same_image(a: &str, b: &str) -> bool {
+    match (url::Url::parse(a), url::Url::parse(b)) {
+        (Ok(a), Ok(b)) => a.host_str() == b.host_str() && a.path() == b.path(),
+        _ => a == b,
     }
 }
 
+/// Removes a trailing "Continue reading" style marker and reports whether one was found.
+pub(crate) fn strip_truncation_marker(blocks: &mut Vec<ReaderBlock>) -> bool {
+    let tail_start = blocks.len().saturating_sub(TRUNCATION_TAIL_BLOCKS);
+    let Some(pos) = blocks[tail_start..]
+        .iter()
+        .rposition(is_truncation_marker)
+        .map(|pos| tail_start + pos)
+    else {
+        return false;
+    };
+    blocks.truncate(pos);
+    true
+}
+
+fn is_truncation_marker(block: &ReaderBlock) -> bool {
+    let text = match block {
+        ReaderBlock::Paragraph(text) | ReaderBlock::Heading { text, .. } => text.clone(),
+        ReaderBlock::RichParagraph(runs) => inline_text(runs),
+        _ => return false,
+    };
+    // Expanders are short link labels, sometimes with an arrow or ellipsis.
+    if text.chars().count() > 60 {
+        return false;
+    }
+    let label = text
+        .trim_matches(|c: char| !c.is_alphanumeric())
+        .to_lowercase();
+    TRUNCATION_MARKERS
+        .iter()
+        .any(|marker| label.starts_with(marker))
+}
+
 fn extract_html_article_fallback(
     html: &str,
     url: &url::Url,
@@ -305,8 +1098,16 @@ fn extract_html_article_fallback(
     let byline = extract_meta(&doc, "meta[name=\"author\"]")
         .or_else(|| extract_meta(&doc, "meta[property=\"article:author\"]"));
 
-    let root = select_best_root(&doc).unwrap_or_else(|| doc.root_element());
-    let blocks = extract_blocks(&root, url);
+    // Without a clear candidate the root is the whole document, which on badly
+    // nested pages is mostly navigation; keep only prose-like paragraphs then.
+    let (blocks, low_confidence) = match select_best_root(&doc) {
+        Some(root) => (extract_blocks(&root, url), false),
+        None => {
+            let paragraphs = extract_prose_paragraphs(&doc.root_element());
+            let blocks = paragraphs.into_iter().map(ReaderBlock::Paragraph).collect();
+            (blocks, true)
+        }
+    };
 
     ReaderArticle {
         title,
@@ -314,6 +1115,11 @@ fn extract_html_article_fallback(
         site_name,
         reading_time: estimate_reading_time(&blocks),
         blocks,
+        low_confidence,
+        truncated: false,
+        // Read here so the page isn't parsed a second time just for the meta tag.
+        hero_image: extract_og_image(&doc, url),
+        final_url: None,
     }
 }
 
@@ -321,6 +1127,7 @@ fn extract_with_readabilityrs(
     html: &str,
     url: &url::Url,
     title_hint: Option<String>,
+    min_text_len: usize,
 ) -> Option<ReaderArticle> {
     let options = ReadabilityOptions::default();
     let readability = Readability::new(html, Some(url.as_str()), Some(options)).ok()?;
@@ -335,9 +1142,9 @@ fn extract_with_readabilityrs(
     let root = content_doc.root_element();
     let blocks = extract_blocks(&root, url);
 
-    // Require at least 500 chars to consider readability extraction valid
+    // Require a minimum amount of text to consider readability extraction valid
     // This helps avoid cases where only partial content is extracted
-    if blocks.is_empty() || total_text_len(&blocks) < 500 {
+    if blocks.is_empty() || total_text_len(&blocks) < min_text_len {
         return None;
     }
 
@@ -366,9 +1173,51 @@ fn extract_with_readabilityrs(
         site_name: site_name.or_else(|| host_without_www(url)),
         reading_time: estimate_reading_time(&blocks),
         blocks,
+        low_confidence: false,
+        truncated: false,
+        hero_image: None,
+        final_url: None,
     })
 }
 
+/// Builds an article from a story's own HTML body (Ask/Show HN self-text), reusing the block pipeline.
+pub fn self_text_article(
+    html: &str,
+    title: &str,
+    author: &str,
+    item_url: &url::Url,
+) -> ReaderArticle {
+    let blocks = fragment_blocks(html, item_url);
+
+    ReaderArticle {
+        title: title.to_string(),
+        byline: Some(author.to_string()),
+        site_name: host_without_www(item_url),
+        reading_time: estimate_reading_time(&blocks),
+        blocks,
+        low_confidence: false,
+        truncated: false,
+        hero_image: None,
+        final_url: None,
+    }
+}
+
+/// Blocks for an HN-style HTML fragment (self-text or a comment), through the article pipeline.
+pub fn fragment_blocks(html: &str, base_url: &url::Url) -> Vec<ReaderBlock> {
+    // HN omits the opening <p> of the first paragraph.
+    let doc = Html::parse_fragment(&format!("<p>{html}"));
+    let mut blocks = Vec::new();
+    collect_blocks(&doc.root_element(), base_url, 0, &mut blocks);
+    let blocks = normalize_blocks(blocks);
+    if !blocks.is_empty() {
+        return blocks;
+    }
+    extract_paragraphs(&doc.root_element())
+        .into_iter()
+        .map(ReaderBlock::Paragraph)
+        .collect()
+}
+
 fn plain_text_article(text: &str, url: &url::Url, title_hint: Option<String>) -> ReaderArticle {
     let title = title_hint.unwrap_or_else(|| url.to_string());
     let site_name = host_without_www(url);
@@ -384,10 +1233,14 @@ fn plain_text_article(text: &str, url: &url::Url, title_hint: Option<String>) ->
         site_name,
         reading_time: estimate_reading_time(&blocks),
         blocks,
+        low_confidence: false,
+        truncated: false,
+        hero_image: None,
+        final_url: None,
     }
 }
 
-fn extract_title(doc: &Html) -> Option<String> {
+pub(crate) fn extract_title(doc: &Html) -> Option<String> {
     extract_meta(doc, "meta[property=\"og:title\"]")
         .or_else(|| extract_meta(doc, "meta[name=\"twitter:title\"]"))
         .or_else(|| {
@@ -399,7 +1252,7 @@ fn extract_title(doc: &Html) -> Option<String> {
         })
 }
 
-fn extract_meta(doc: &Html, selector: &str) -> Option<String> {
+pub(crate) fn extract_meta(doc: &Html, selector: &str) -> Option<String> {
     let selector = Selector::parse(selector).ok()?;
     let el = doc.select(&selector).next()?;
     let content = el.value().attr("content")?;
@@ -407,7 +1260,7 @@ fn extract_meta(doc: &Html, selector: &str) -> Option<String> {
     (!content.is_empty()).then_some(content)
 }
 
-fn host_without_www(url: &url::Url) -> Option<String> {
+pub(crate) fn host_without_www(url: &url::Url) -> Option<String> {
     url.host_str()
         .map(|h| h.trim_start_matches("www.").to_string())
         .filter(|h| !h.is_empty())
@@ -462,12 +1315,7 @@ fn score_candidate(candidate: &ElementRef<'_>) -> f32 {
         return 0.0;
     }
 
-    let mut link_text_len = 0usize;
-    for a in candidate.select(&a_selector) {
-        link_text_len = link_text_len.saturating_add(element_text_len(&a));
-    }
-
-    let link_density = (link_text_len as f32 / text_len as f32).min(1.0);
+    let link_density = link_density(candidate, &a_selector);
     if link_density > 0.75 {
         return 0.0;
     }
@@ -498,6 +1346,19 @@ fn score_candidate(candidate: &ElementRef<'_>) -> f32 {
     score
 }
 
+fn link_density(element: &ElementRef<'_>, a_selector: &Selector) -> f32 {
+    let text_len = element_text_len(element);
+    if text_len == 0 {
+        return 0.0;
+    }
+
+    let mut link_text_len = 0usize;
+    for a in element.select(a_selector) {
+        link_text_len = link_text_len.saturating_add(element_text_len(&a));
+    }
+    (link_text_len as f32 / text_len as f32).min(1.0)
+}
+
 fn class_id_weight(element: &ElementRef<'_>) -> i32 {
     let mut weight = 0i32;
     if let Some(id) = element.value().attr("id") {
@@ -556,10 +1417,10 @@ fn count_commas(element: &ElementRef<'_>) -> usize {
         .count()
 }
 
-fn extract_blocks(root: &ElementRef<'_>, base_url: &url::Url) -> Vec<ReaderBlock> {
+pub(crate) fn extract_blocks(root: &ElementRef<'_>, base_url: &url::Url) -> Vec<ReaderBlock> {
     let mut blocks = Vec::new();
     collect_blocks(root, base_url, 0, &mut blocks);
-    let mut blocks = normalize_blocks(blocks);
+    let mut blocks = trim_boilerplate_edges(normalize_blocks(blocks));
 
     if blocks.is_empty() || total_text_len(&blocks) < 200 {
         let paragraphs = extract_paragraphs(root);
@@ -592,7 +1453,12 @@ fn collect_blocks(
             "p" => {
                 if let Some(text) = extract_text(&child) {
                     if !is_noise_paragraph(&text) {
-                        out.push(ReaderBlock::Paragraph(text));
+                        let runs = extract_inline_runs(&child, base_url);
+                        if runs.iter().any(InlineRun::has_markup) {
+                            out.push(ReaderBlock::RichParagraph(runs));
+                        } else {
+                            out.push(ReaderBlock::Paragraph(text));
+                        }
                     }
                 }
             }
@@ -628,6 +1494,17 @@ fn collect_blocks(
                     out.push(ReaderBlock::Code { text, language });
                 }
             }
+            "table" => {
+                if let Some((text, language)) = extract_code_table(&child) {
+                    out.push(ReaderBlock::Code { text, language });
+                } else if is_layout_table(&child) {
+                    collect_blocks(&child, base_url, depth + 1, out);
+                } else if let Some(table) = extract_table(&child) {
+                    out.push(table);
+                } else {
+                    out.extend(table_row_paragraphs(&child));
+                }
+            }
             "figure" => {
                 if let Some(block) = extract_figure_image(&child, base_url) {
                     out.push(block);
@@ -674,6 +1551,71 @@ fn extract_text(element: &ElementRef<'_>) -> Option<String> {
     (!text.is_empty()).then_some(text)
 }
 
+/// Splits an element's text into runs by enclosing link, abbreviation and
+/// emphasis, collapsing whitespace across runs.
+pub(crate) fn extract_inline_runs(element: &ElementRef<'_>, base_url: &url::Url) -> Vec<InlineRun> {
+    let mut runs: Vec<InlineRun> = Vec::new();
+    let mut pending_space = false;
+
+    for node in element.descendants() {
+        let Some(text) = node.value().as_text() else {
+            continue;
+        };
+        let enclosing = |tags: &[&str]| {
+            node.ancestors()
+                .take_while(|ancestor| ancestor.id() != element.id())
+                .filter_map(ElementRef::wrap)
+                .find(|ancestor| tags.contains(&ancestor.value().name()))
+        };
+        let markup = InlineRun {
+            text: String::new(),
+            link: enclosing(&["a"])
+                .and_then(|a| a.value().attr("href"))
+                .and_then(|href| resolve_url(base_url, href))
+                .filter(|url| url.starts_with("http://") || url.starts_with("https://")),
+            abbr_title: enclosing(&["abbr"])
+                .and_then(|abbr| abbr.value().attr("title"))
+                .map(normalize_whitespace)
+                .filter(|title| !title.is_empty()),
+            bold: enclosing(&["strong", "b"]).is_some(),
+            italic: enclosing(&["em", "i"]).is_some(),
+        };
+
+        for ch in text.chars() {
+            if ch.is_whitespace() {
+                pending_space = !runs.is_empty();
+                continue;
+            }
+            let space = std::mem::take(&mut pending_space);
+            match runs.last_mut() {
+                Some(last) if last.same_markup(&markup) => {
+                    if space {
+                        last.text.push(' ');
+                    }
+                    last.text.push(ch);
+                }
+                last => {
+                    // Keep the separating space out of links and abbreviations so
+                    // underlines don't start or end with a gap.
+                    let mut text = String::new();
+                    match last {
+                        Some(last) if space && !last.is_underlined() => last.text.push(' '),
+                        _ if space => text.push(' '),
+                        _ => {}
+                    }
+                    text.push(ch);
+                    runs.push(InlineRun {
+                        text,
+                        ..markup.clone()
+                    });
+                }
+            }
+        }
+    }
+
+    runs
+}
+
 fn extract_blockquote_text(element: &ElementRef<'_>) -> Option<String> {
     let p_selector = Selector::parse("p").ok()?;
     let mut paragraphs = element
@@ -728,6 +1670,182 @@ fn extract_code_block(pre: &ElementRef<'_>) -> Option<(String, Option<String>)>
     Some((text, language))
 }
 
+/// Highlighters such as GitHub, Rouge and Pygments lay code out as a table with a
+/// line-number column. Recover the code column as one block; data tables return `None`.
+fn extract_code_table(table: &ElementRef<'_>) -> Option<(String, Option<String>)> {
+    let row_selector = Selector::parse("tr").ok()?;
+    let code_selector = Selector::parse("pre, code").ok()?;
+
+    let mut lines = Vec::new();
+    let mut saw_line_numbers = false;
+    let mut language = None;
+    for row in table.select(&row_selector) {
+        let cells = row
+            .child_elements()
+            .filter(|cell| matches!(cell.value().name(), "td" | "th"))
+            .collect::<Vec<_>>();
+        let (gutter, code) = match cells.as_slice() {
+            [gutter, code] => (gutter, code),
+            _ => return None,
+        };
+        if !is_line_number_cell(gutter) {
+            return None;
+        }
+        saw_line_numbers |= gutter.value().attr("data-line-number").is_some()
+            || gutter
+                .text()
+                .any(|t| t.chars().any(|ch| ch.is_ascii_digit()));
+
+        if language.is_none() {
+            language = code
+                .select(&code_selector)
+                .find_map(detect_code_language)
+                .or_else(|| detect_code_language(*code));
+        }
+        let text = code.text().collect::<String>();
+        lines.push(text.trim_end_matches('\n').to_string());
+    }
+
+    if lines.is_empty() || !saw_line_numbers || !has_code_table_hint(table, &code_selector) {
+        return None;
+    }
+
+    let text = normalize_code_text(&lines.join("\n"));
+    (!text.is_empty()).then_some((text, language))
+}
+
+/// Old-style pages lay out the whole article with tables; those hold real blocks
+/// (paragraphs, lists, nested tables) in their cells and are read through.
+fn is_layout_table(table: &ElementRef<'_>) -> bool {
+    let Ok(selector) = Selector::parse("p, div, table, ul, ol, pre, blockquote, h1, h2, h3") else {
+        return false;
+    };
+    table.select(&selector).next().is_some()
+}
+
+fn table_rows<'a>(table: &ElementRef<'a>) -> Vec<ElementRef<'a>> {
+    let Ok(selector) = Selector::parse("tr") else {
+        return Vec::new();
+    };
+    table.select(&selector).collect()
+}
+
+fn table_cells(row: &ElementRef<'_>) -> Vec<(String, bool)> {
+    row.child_elements()
+        .filter(|cell| matches!(cell.value().name(), "td" | "th"))
+        .map(|cell| {
+            let text = extract_text(&cell).unwrap_or_default();
+            (text, cell.value().name() == "th")
+        })
+        .collect()
+}
+
+/// Reads a data table into headers and rows. The header row is the first row inside
+/// `<thead>`, or a first row made only of `<th>` cells. Tables with a single column or
+/// no text read better as paragraphs and return `None`.
+fn extract_table(table: &ElementRef<'_>) -> Option<ReaderBlock> {
+    let mut rows = table_rows(table)
+        .iter()
+        .map(|row| {
+            let in_head = row
+                .parent()
+                .and_then(ElementRef::wrap)
+                .is_some_and(|parent| parent.value().name() == "thead");
+            (table_cells(row), in_head)
+        })
+        .filter(|(cells, _)| !cells.is_empty())
+        .collect::<Vec<_>>();
+    if rows.is_empty() {
+        return None;
+    }
+
+    let (first, first_in_head) = &rows[0];
+    let has_header = *first_in_head || first.iter().all(|(_, is_th)| *is_th);
+    let headers = if has_header {
+        rows.remove(0)
+            .0
+            .into_iter()
+            .map(|(text, _)| text)
+            .collect::<Vec<_>>()
+    } else {
+        Vec::new()
+    };
+    let rows = rows
+        .into_iter()
+        .filter(|(_, in_head)| !in_head)
+        .take(MAX_TABLE_ROWS)
+        .map(|(cells, _)| cells.into_iter().map(|(text, _)| text).collect::<Vec<_>>())
+        .collect::<Vec<_>>();
+
+    let columns = std::iter::once(headers.len())
+        .chain(rows.iter().map(Vec::len))
+        .max()
+        .unwrap_or(0)
+        .min(MAX_TABLE_COLUMNS);
+    let has_text = headers
+        .iter()
+        .chain(rows.iter().flatten())
+        .any(|cell| !cell.is_empty());
+    if columns < 2 || rows.is_empty() || !has_text {
+        return None;
+    }
+
+    let pad = |mut cells: Vec<String>| {
+        cells.resize(columns, String::new());
+        cells
+    };
+    Some(ReaderBlock::Table {
+        headers: if headers.is_empty() {
+            headers
+        } else {
+            pad(headers)
+        },
+        rows: rows.into_iter().map(pad).collect(),
+    })
+}
+
+/// Fallback for tables that don't hold up as a grid: one paragraph per row.
+fn table_row_paragraphs(table: &ElementRef<'_>) -> Vec<ReaderBlock> {
+    table_rows(table)
+        .iter()
+        .map(|row| {
+            table_cells(row)
+                .into_iter()
+                .map(|(text, _)| text)
+                .filter(|text| !text.is_empty())
+                .collect::<Vec<_>>()
+                .join(" · ")
+        })
+        .filter(|text| !text.is_empty())
+        .take(MAX_TABLE_ROWS)
+        .map(ReaderBlock::Paragraph)
+        .collect()
+}
+
+fn is_line_number_cell(cell: &ElementRef<'_>) -> bool {
+    cell.text()
+        .flat_map(str::chars)
+        .all(|ch| ch.is_ascii_digit() || ch.is_whitespace())
+}
+
+/// Something code-like besides the numbers, so a ranked data table is not mistaken for code.
+fn has_code_table_hint(table: &ElementRef<'_>, code_selector: &Selector) -> bool {
+    const CLASS_HINTS: &[&str] = &["blob", "code", "gutter", "highlight", "lineno", "rouge"];
+
+    if table.select(code_selector).next().is_some() {
+        return true;
+    }
+    std::iter::once(*table)
+        .chain(table.descendants().filter_map(ElementRef::wrap))
+        .any(|element| {
+            element.value().attr("data-line-number").is_some()
+                || element.value().attr("class").is_some_and(|class| {
+                    let class = class.to_ascii_lowercase();
+                    CLASS_HINTS.iter().any(|hint| class.contains(hint))
+                })
+        })
+}
+
 fn detect_code_language(code: ElementRef<'_>) -> Option<String> {
     let class = code.value().attr("class")?;
     for token in class.split_whitespace() {
@@ -781,7 +1899,24 @@ fn extract_image(
         return None;
     }
 
-    Some(ReaderBlock::Image { url, alt, caption })
+    let dimension = |name: &str| {
+        let value = img.value().attr(name)?.trim();
+        let digits = value.trim_end_matches("px");
+        digits.parse::<u32>().ok()
+    };
+    let (width, height) = (dimension("width"), dimension("height"));
+    // Tracking pixels declare themselves as 1x1 (or 0x0) and slip past the URL heuristics.
+    if width.into_iter().chain(height).any(|v| v <= 2) {
+        return None;
+    }
+    let dimensions = width.zip(height);
+
+    Some(ReaderBlock::Image {
+        url,
+        alt,
+        caption,
+        dimensions,
+    })
 }
 
 fn image_src(img: &ElementRef<'_>) -> Option<String> {
@@ -849,6 +1984,10 @@ fn is_likely_noise_image_url(url: &str, alt: &Option<String>, caption: &Option<S
         "spinner",
         "/ads/",
         "doubleclick",
+        "1x1.",
+        "spacer.gif",
+        "pixel.gif",
+        "/beacon",
     ];
     if always_bad.iter().any(|k| url_lower.contains(k)) {
         return true;
@@ -935,7 +2074,7 @@ fn is_noise_paragraph(text: &str) -> bool {
     noise_tokens.iter().any(|t| lower.contains(t))
 }
 
-fn normalize_blocks(blocks: Vec<ReaderBlock>) -> Vec<ReaderBlock> {
+pub(crate) fn normalize_blocks(blocks: Vec<ReaderBlock>) -> Vec<ReaderBlock> {
     let mut out = Vec::new();
 
     for block in blocks {
@@ -954,6 +2093,12 @@ fn normalize_blocks(blocks: Vec<ReaderBlock>) -> Vec<ReaderBlock> {
                 }
                 ReaderBlock::Paragraph(text)
             }
+            ReaderBlock::RichParagraph(runs) => {
+                if inline_text(&runs).trim().is_empty() {
+                    continue;
+                }
+                ReaderBlock::RichParagraph(runs)
+            }
             ReaderBlock::Quote(text) => {
                 let text = text.trim().to_string();
                 if text.is_empty() {
@@ -980,12 +2125,24 @@ fn normalize_blocks(blocks: Vec<ReaderBlock>) -> Vec<ReaderBlock> {
                 }
                 ReaderBlock::Code { text, language }
             }
-            ReaderBlock::Image { url, alt, caption } => {
+            ReaderBlock::Table { headers, rows } => {
+                if rows.is_empty() {
+                    continue;
+                }
+                ReaderBlock::Table { headers, rows }
+            }
+            ReaderBlock::Image {
+                url,
+                alt,
+                caption,
+                dimensions,
+            } => {
                 if url.trim().is_empty() {
                     continue;
                 }
                 ReaderBlock::Image {
                     url,
+                    dimensions,
                     alt: alt.and_then(|s| {
                         let s = normalize_whitespace(&s);
                         (!s.is_empty()).then_some(s)
@@ -1003,10 +2160,16 @@ fn normalize_blocks(blocks: Vec<ReaderBlock>) -> Vec<ReaderBlock> {
             if matches!(
                 (prev, &block),
                 (ReaderBlock::Paragraph(a), ReaderBlock::Paragraph(b)) if a == b
-            ) {
+            ) || matches!((prev, &block), (ReaderBlock::Rule, ReaderBlock::Rule))
+            {
                 continue;
             }
         }
+        if matches!(block, ReaderBlock::Heading { level, .. } if level <= SECTION_HEADING_LEVEL)
+            && matches!(out.last(), Some(ReaderBlock::Rule))
+        {
+            out.pop();
+        }
 
         out.push(block);
         if out.len() >= MAX_BLOCKS {
@@ -1014,7 +2177,138 @@ fn normalize_blocks(blocks: Vec<ReaderBlock>) -> Vec<ReaderBlock> {
         }
     }
 
-    out
+    drop_pull_quotes(out)
+}
+
+/// Drops short quotes that repeat a paragraph of the article, which is how decorative
+/// pull quotes look once their styling is gone. Matching is on word pairs so trimmed
+/// or lightly edited pull quotes still count.
+fn drop_pull_quotes(blocks: Vec<ReaderBlock>) -> Vec<ReaderBlock> {
+    let paragraphs = blocks
+        .iter()
+        .filter_map(|b| match b {
+            ReaderBlock::Paragraph(text) => Some(word_pairs(text)),
+            ReaderBlock::RichParagraph(runs) => Some(word_pairs(&inline_text(runs))),
+            _ => None,
+        })
+        .collect::<Vec<_>>();
+    if paragraphs.is_empty() {
+        return blocks;
+    }
+
+    blocks
+        .into_iter()
+        .filter(|b| match b {
+            ReaderBlock::Quote(text) => !is_pull_quote(text, &paragraphs),
+            _ => true,
+        })
+        .collect()
+}
+
+fn is_pull_quote(text: &str, paragraphs: &[HashSet<(String, String)>]) -> bool {
+    if text.split_whitespace().count() > PULL_QUOTE_MAX_WORDS {
+        return false;
+    }
+    let pairs = word_pairs(text);
+    if pairs.is_empty() {
+        return false;
+    }
+    paragraphs.iter().any(|paragraph| {
+        let shared = pairs.iter().filter(|p| paragraph.contains(*p)).count();
+        shared as f32 / pairs.len() as f32 >= PULL_QUOTE_OVERLAP
+    })
+}
+
+/// Adjacent word pairs, lowercased with punctuation stripped so curly quotes and
+/// ellipses added around a pull quote don't matter.
+fn word_pairs(text: &str) -> HashSet<(String, String)> {
+    let words = text
+        .split_whitespace()
+        .map(|w| {
+            w.chars()
+                .filter(|c| c.is_alphanumeric())
+                .flat_map(char::to_lowercase)
+                .collect::<String>()
+        })
+        .filter(|w| !w.is_empty())
+        .collect::<Vec<_>>();
+    words
+        .windows(2)
+        .map(|pair| (pair[0].clone(), pair[1].clone()))
+        .collect()
+}
+
+/// Drops call-to-action and "related" blocks at the very start and end of an article.
+/// Mid-article blocks are left alone, and nothing is trimmed if it would empty the article.
+pub(crate) fn trim_boilerplate_edges(blocks: Vec<ReaderBlock>) -> Vec<ReaderBlock> {
+    let start = blocks
+        .iter()
+        .position(|b| !is_edge_boilerplate(b))
+        .unwrap_or(blocks.len());
+    let mut end = blocks.len();
+
+    // A trailing "Related stories" heading takes its short list of links with it.
+    if let Some(pos) = blocks
+        .iter()
+        .rposition(|b| matches!(b, ReaderBlock::Heading { text, .. } if is_boilerplate_text(text)))
+    {
+        let owned = &blocks[pos + 1..];
+        if pos >= start && owned.len() <= TRAILING_SECTION_MAX_BLOCKS && owned.iter().all(|b| {
+            matches!(
+                b,
+                ReaderBlock::List { .. } | ReaderBlock::Image { .. } | ReaderBlock::Rule
+            ) || matches!(b, ReaderBlock::Paragraph(text) if text.len() < 160)
+                || matches!(b, ReaderBlock::RichParagraph(runs) if inline_text(runs).len() < 160)
+        }) {
+            end = pos;
+        }
+    }
+
+    while end > start && is_edge_boilerplate(&blocks[end - 1]) {
+        end -= 1;
+    }
+
+    if start >= end {
+        return blocks;
+    }
+
+    blocks.into_iter().skip(start).take(end - start).collect()
+}
+
+fn is_edge_boilerplate(block: &ReaderBlock) -> bool {
+    match block {
+        ReaderBlock::Paragraph(text) | ReaderBlock::Heading { text, .. } => {
+            is_boilerplate_text(text)
+        }
+        ReaderBlock::RichParagraph(runs) => is_boilerplate_text(&inline_text(runs)),
+        ReaderBlock::Rule => true,
+        _ => false,
+    }
+}
+
+fn is_boilerplate_text(text: &str) -> bool {
+    let text = text.trim();
+    let char_count = text.chars().count();
+    if char_count == 0 || char_count > EDGE_BOILERPLATE_MAX_CHARS {
+        return false;
+    }
+
+    // The whole block is the CTA ("Sign up for our newsletter"), not a sentence that mentions it.
+    let lower = text.to_lowercase();
+    if EDGE_BOILERPLATE_PHRASES.iter().any(|p| {
+        lower
+            .strip_prefix(p)
+            .is_some_and(|rest| !rest.starts_with(|c: char| c.is_alphanumeric()))
+    }) {
+        return true;
+    }
+
+    // Short all-caps CTAs such as "SUBSCRIBE NOW" or "SHARE".
+    let letters = text
+        .chars()
+        .filter(|c| c.is_alphabetic())
+        .collect::<Vec<_>>();
+    char_count <= 40 && letters.len() >= 4 && letters.iter().all(|c| c.is_uppercase())
 }
 
 fn total_text_len(blocks: &[ReaderBlock]) -> usize {
@@ -1023,9 +2317,15 @@ fn total_text_len(blocks: &[ReaderBlock]) -> usize {
         .map(|b| match b {
             ReaderBlock::Heading { text, .. } => text.len(),
             ReaderBlock::Paragraph(text) => text.len(),
+            ReaderBlock::RichParagraph(runs) => runs.iter().map(|run| run.text.len()).sum(),
             ReaderBlock::Quote(text) => text.len(),
             ReaderBlock::List { items, .. } => items.iter().map(|s| s.len()).sum(),
             ReaderBlock::Code { text, .. } => text.len(),
+            ReaderBlock::Table { headers, rows } => headers
+                .iter()
+                .chain(rows.iter().flatten())
+                .map(|cell| cell.len())
+                .sum(),
             ReaderBlock::Image { alt, caption, .. } => {
                 alt.as_ref().map_or(0, |s| s.len()) + caption.as_ref().map_or(0, |s| s.len())
             }
@@ -1061,6 +2361,20 @@ fn extract_paragraphs(root: &ElementRef<'_>) -> Vec<String> {
     paragraphs
 }
 
+/// Paragraphs that read like prose: not mostly links and not obvious noise.
+fn extract_prose_paragraphs(root: &ElementRef<'_>) -> Vec<String> {
+    let (Ok(p_selector), Ok(a_selector)) = (Selector::parse("p"), Selector::parse("a")) else {
+        return Vec::new();
+    };
+
+    root.select(&p_selector)
+        .filter(|p| link_density(p, &a_selector) < NAV_LINK_DENSITY)
+        .filter_map(|p| extract_text(&p))
+        .filter(|text| !is_noise_paragraph(text))
+        .take(200)
+        .collect()
+}
+
 fn split_paragraphs(text: &str) -> Vec<String> {
     text.split("\n\n")
         .map(|p| normalize_whitespace(p))
@@ -1070,6 +2384,10 @@ fn split_paragraphs(text: &str) -> Vec<String> {
 }
 
 fn estimate_reading_time(blocks: &[ReaderBlock]) -> Option<String> {
+    estimate_reading_minutes(blocks).map(|minutes| format!("{minutes} min read"))
+}
+
+fn estimate_reading_minutes(blocks: &[ReaderBlock]) -> Option<usize> {
     let (mut words, mut chars) = (0usize, 0usize);
 
     let mut add_text = |text: &str| {
@@ -1081,6 +2399,7 @@ fn estimate_reading_time(blocks: &[ReaderBlock]) -> Option<String> {
         match block {
             ReaderBlock::Heading { text, .. } => add_text(text),
             ReaderBlock::Paragraph(text) => add_text(text),
+            ReaderBlock::RichParagraph(runs) => add_text(&inline_text(runs)),
             ReaderBlock::Quote(text) => add_text(text),
             ReaderBlock::List { items, .. } => {
                 for item in items {
@@ -1088,6 +2407,11 @@ fn estimate_reading_time(blocks: &[ReaderBlock]) -> Option<String> {
                 }
             }
             ReaderBlock::Code { text, .. } => add_text(text),
+            ReaderBlock::Table { headers, rows } => {
+                for cell in headers.iter().chain(rows.iter().flatten()) {
+                    add_text(cell);
+                }
+            }
             ReaderBlock::Image { alt, caption, .. } => {
                 if let Some(alt) = alt {
                     add_text(alt);
@@ -1106,8 +2430,7 @@ fn estimate_reading_time(blocks: &[ReaderBlock]) -> Option<String> {
 
     let minutes_by_words = (words + 199) / 200;
     let minutes_by_chars = (chars + 999) / 1000;
-    let minutes = minutes_by_words.max(minutes_by_chars).max(1);
-    Some(format!("{minutes} min read"))
+    Some(minutes_by_words.max(minutes_by_chars).max(1))
 }
 
 fn element_text_len(element: &ElementRef<'_>) -> usize {
@@ -0,0 +1,741 @@
+use crate::reader::{self, ReaderBlock};
+
+fn paragraph(text: &str) -> ReaderBlock {
+    ReaderBlock::Paragraph(text.to_string())
+}
+
+fn body_paragraph(i: usize) -> ReaderBlock {
+    paragraph(&format!(
+        "Body paragraph {i} talks about the actual subject, with enough words to be real content."
+    ))
+}
+
+#[test]
+fn boilerplate_is_trimmed_only_at_article_edges() {
+    let blocks = vec![
+        paragraph("SHARE THIS STORY"),
+        paragraph("Sign up for our daily briefing"),
+        body_paragraph(1),
+        paragraph("Read more about the history of the project below."),
+        body_paragraph(2),
+        ReaderBlock::Heading {
+            level: 2,
+            text: "Related stories".to_string(),
+        },
+        ReaderBlock::List {
+            ordered: false,
+            items: vec!["Another story".to_string(), "Yet another".to_string()],
+        },
+        paragraph("Follow us on Mastodon"),
+    ];
+
+    let trimmed = reader::trim_boilerplate_edges(blocks);
+    let texts = trimmed
+        .iter()
+        .map(|b| match b {
+            ReaderBlock::Paragraph(text) => text.as_str(),
+            _ => "<other>",
+        })
+        .collect::<Vec<_>>();
+
+    assert_eq!(trimmed.len(), 3);
+    assert!(texts[0].starts_with("Body paragraph 1"));
+    // Mid-article "read more" stays.
+    assert!(texts[1].starts_with("Read more about"));
+    assert!(texts[2].starts_with("Body paragraph 2"));
+}
+
+#[test]
+fn prose_mentioning_a_boilerplate_phrase_is_kept() {
+    let blocks = vec![
+        paragraph("The most popular editor at the time needed no setup at all."),
+        body_paragraph(1),
+        paragraph("Sign up flows were the worst part of the redesign, so the team rebuilt them from scratch."),
+        paragraph("Critics wanted more from the sequel."),
+    ];
+
+    assert_eq!(reader::trim_boilerplate_edges(blocks).len(), 4);
+}
+
+#[test]
+fn boilerplate_trim_never_empties_an_article() {
+    let blocks = vec![paragraph("SUBSCRIBE NOW"), paragraph("Share on Twitter")];
+    assert_eq!(reader::trim_boilerplate_edges(blocks).len(), 2);
+}
+
+#[test]
+fn malformed_page_without_article_root_keeps_only_prose() {
+    // Unclosed inline tags, a paragraph inside a table, and link-heavy navigation
+    // straight under <body> with no article/main/section/div container.
+    let html = r#"<html><head><title>Broken page</title></head><body>
+        <p><a href="/">Home</a> | <a href="/news">News</a> | <a href="/about">About us</a></p>
+        <p>The first real paragraph explains what happened, <b>with <i>badly nested</b> markup</i>.
+        <table><p>A second real paragraph ended up inside a broken table element.</table>
+        <p><a href="/a">Next</a> <a href="/b">Previous</a> <a href="/c">Archive</a></p>
+        </body></html>"#;
+    let url = url::Url::parse("https://example.com/post").unwrap();
+
+    let article = reader::extract_html_article(html, &url, None);
+
+    assert!(article.low_confidence);
+    let texts = article
+        .blocks
+        .iter()
+        .filter_map(|b| match b {
+            ReaderBlock::Paragraph(text) => Some(text.as_str()),
+            _ => None,
+        })
+        .collect::<Vec<_>>();
+    assert!(texts
+        .iter()
+        .any(|t| t.starts_with("The first real paragraph")));
+    assert!(texts
+        .iter()
+        .any(|t| t.starts_with("A second real paragraph")));
+    assert!(!texts
+        .iter()
+        .any(|t| t.contains("Home") || t.contains("Archive")));
+}
+
+#[test]
+fn line_numbered_code_tables_become_code_blocks() {
+    let html = r#"<article>
+        <p>Highlighters wrap code in tables so the line numbers do not get copied along with it.</p>
+        <table class="highlight">
+          <tr><td class="blob-num" data-line-number="1"></td><td class="blob-code"><span class="pl-k">fn</span> main() {</td></tr>
+          <tr><td class="blob-num" data-line-number="2"></td><td class="blob-code">    println!("hi");</td></tr>
+          <tr><td class="blob-num" data-line-number="3"></td><td class="blob-code">}</td></tr>
+        </table>
+        <p>Rouge puts the whole gutter and the whole listing into a single row of two cells.</p>
+        <table class="rouge-table"><tbody><tr>
+          <td class="rouge-gutter gl"><pre class="lineno">1
+2
+</pre></td>
+          <td class="rouge-code"><pre><code class="language-ruby">puts "one"
+puts "two"
+</code></pre></td>
+        </tr></tbody></table>
+        <p>A ranked data table stays out of code blocks even though it has a number column.</p>
+        <table>
+          <tr><td>1</td><td>Alice</td></tr>
+          <tr><td>2</td><td>Bob</td></tr>
+        </table>
+    </article>"#;
+    let doc = scraper::Html::parse_fragment(html);
+    let url = url::Url::parse("https://example.com/docs").unwrap();
+
+    let blocks = reader::extract_blocks(&doc.root_element(), &url);
+
+    let code = blocks
+        .iter()
+        .filter_map(|b| match b {
+            ReaderBlock::Code { text, language } => Some((text.as_str(), language.as_deref())),
+            _ => None,
+        })
+        .collect::<Vec<_>>();
+    assert_eq!(
+        code,
+        vec![
+            (
+                "fn main() {\n\u{a0}\u{a0}\u{a0}\u{a0}println!(\"hi\");\n}",
+                None
+            ),
+            ("puts \"one\"\nputs \"two\"", Some("ruby")),
+        ]
+    );
+}
+
+#[test]
+fn paragraph_links_become_inline_runs() {
+    let html = r#"<p>Read   the <a href="/docs/intro">intro
+        guide</a> before <a href="javascript:void(0)">this</a> part.</p>"#;
+    let doc = scraper::Html::parse_fragment(html);
+    let p = doc
+        .select(&scraper::Selector::parse("p").unwrap())
+        .next()
+        .unwrap();
+    let url = url::Url::parse("https://example.com/blog/post").unwrap();
+
+    let runs = reader::extract_inline_runs(&p, &url);
+
+    let summary = runs
+        .iter()
+        .map(|run| (run.text.as_str(), run.link.as_deref()))
+        .collect::<Vec<_>>();
+    assert_eq!(
+        summary,
+        vec![
+            ("Read the ", None),
+            ("intro guide", Some("https://example.com/docs/intro")),
+            (" before this part.", None),
+        ]
+    );
+}
+
+#[test]
+fn abbreviation_title_stays_with_its_run() {
+    let html = r#"<p>The <abbr title="  HyperText
+        Markup Language ">HTML</abbr> spec and an <abbr>API</abbr>.</p>"#;
+    let doc = scraper::Html::parse_fragment(html);
+    let p = doc
+        .select(&scraper::Selector::parse("p").unwrap())
+        .next()
+        .unwrap();
+    let url = url::Url::parse("https://example.com/").unwrap();
+
+    let runs = reader::extract_inline_runs(&p, &url);
+
+    let summary = runs
+        .iter()
+        .map(|run| (run.text.as_str(), run.abbr_title.as_deref()))
+        .collect::<Vec<_>>();
+    assert_eq!(
+        summary,
+        vec![
+            ("The ", None),
+            ("HTML", Some("HyperText Markup Language")),
+            (" spec and an API.", None),
+        ]
+    );
+}
+
+#[test]
+fn media_only_page_has_no_readable_content() {
+    let html = r#"<html><head><title>Launch video</title></head><body>
+        <div class="player"><video src="/launch.mp4" controls></video></div>
+        <script>window.player = true;</script>
+        </body></html>"#;
+    let url = url::Url::parse("https://example.com/watch").unwrap();
+
+    let article = reader::extract_html_article(html, &url, None);
+
+    assert_eq!(article.title, "Launch video");
+    assert!(!article.has_readable_content());
+
+    let article = reader::ReaderArticle {
+        blocks: vec![body_paragraph(1)],
+        ..article
+    };
+    assert!(article.has_readable_content());
+}
+
+#[test]
+fn continue_reading_ending_marks_article_truncated() {
+    let html = r#"<html><head><title>Quarterly results</title></head><body><article>
+        <h1>Quarterly results</h1>
+        <p>The company reported results on Tuesday that surprised most of the analysts who follow it closely.</p>
+        <p>Revenue grew faster than expected in every region, led by a strong quarter for the cloud business.</p>
+        <p>Executives said the outlook for next year remains cautious because of rising costs and slower hiring.</p>
+        <p><a href="/quarterly-results?full=1" class="expander">Continue reading →</a></p>
+    </article></body></html>"#;
+    let url = url::Url::parse("https://example.com/quarterly-results").unwrap();
+
+    let article = reader::extract_html_article(html, &url, None);
+
+    assert!(article.truncated);
+    assert!(!article.blocks.iter().any(|b| match b {
+        ReaderBlock::Paragraph(text) => text.contains("Continue reading"),
+        ReaderBlock::RichParagraph(runs) => reader::inline_text(runs).contains("Continue reading"),
+        _ => false,
+    }));
+
+    // A marker in the middle of a long article is left alone.
+    let mut blocks = vec![
+        paragraph("Continue reading"),
+        body_paragraph(1),
+        body_paragraph(2),
+        body_paragraph(3),
+    ];
+    assert!(!reader::strip_truncation_marker(&mut blocks));
+    assert_eq!(blocks.len(), 4);
+}
+
+#[test]
+fn og_image_becomes_the_hero_and_leaves_the_body() {
+    let html = r#"<html><head><title>Launch day</title>
+        <meta property="og:image" content="/images/launch.jpg?w=1200&amp;crop=1">
+        </head><body><article>
+        <h1>Launch day</h1>
+        <figure><img src="https://example.com/images/launch.jpg?w=640" alt="The rocket on the pad"></figure>
+        <p>The launch went ahead on schedule this morning after two weeks of weather delays at the coast.</p>
+        <p>Engineers said the second stage performed exactly as modelled and the payload reached orbit.</p>
+        <figure><img src="https://example.com/images/crowd.jpg" alt="Spectators watching the launch"></figure>
+        <p>Thousands of spectators lined the beaches to watch, many of them arriving before sunrise.</p>
+    </article></body></html>"#;
+    let url = url::Url::parse("https://example.com/news/launch").unwrap();
+
+    let article = reader::extract_html_article(html, &url, None);
+
+    assert_eq!(
+        article.hero_image.as_deref(),
+        Some("https://example.com/images/launch.jpg?w=1200&crop=1")
+    );
+    let images = |article: &reader::ReaderArticle| {
+        article
+            .blocks
+            .iter()
+            .filter_map(|b| match b {
+                ReaderBlock::Image { url, .. } => Some(url.clone()),
+                _ => None,
+            })
+            .collect::<Vec<_>>()
+    };
+    // With the hero turned off the body keeps its copy.
+    assert_eq!(images(&article.clone().for_display(false)).len(), 2);
+
+    assert_eq!(
+        images(&article.for_display(true)),
+        vec!["https://example.com/images/crowd.jpg"]
+    );
+}
+
+#[test]
+fn tracking_pixels_are_dropped_from_the_body() {
+    let html = r#"<html><head><title>Quarterly results</title></head><body><article>
+        <h1>Quarterly results</h1>
+        <p>Revenue grew for the third quarter in a row, driven mostly by the new subscription tier.</p>
+        <img src="https://stats.example.net/t.gif?id=42" width="1" height="1" alt="">
+        <figure><img src="https://example.com/charts/revenue.png" width="800" height="450" alt="Revenue by quarter"></figure>
+        <p>Operating costs stayed flat, which the company attributed to the data centre consolidation.</p>
+        <img src="https://cdn.example.com/img/spacer.gif" alt="">
+        <img src="https://example.com/open.png" width="0" height="0">
+    </article></body></html>"#;
+    let url = url::Url::parse("https://example.com/reports/q3").unwrap();
+
+    let article = reader::extract_html_article(html, &url, None);
+
+    let images = article
+        .blocks
+        .iter()
+        .filter_map(|b| match b {
+            ReaderBlock::Image {
+                url, dimensions, ..
+            } => Some((url.as_str(), *dimensions)),
+            _ => None,
+        })
+        .collect::<Vec<_>>();
+    assert_eq!(
+        images,
+        vec![("https://example.com/charts/revenue.png", Some((800, 450)))]
+    );
+}
+
+#[test]
+fn repeated_rules_collapse_and_rules_before_sections_drop() {
+    let heading = |level: u8, text: &str| ReaderBlock::Heading {
+        level,
+        text: text.to_string(),
+    };
+    let blocks = reader::normalize_blocks(vec![
+        paragraph("Intro"),
+        ReaderBlock::Rule,
+        ReaderBlock::Rule,
+        paragraph("Middle"),
+        ReaderBlock::Rule,
+        heading(2, "Section"),
+        paragraph("Body"),
+        ReaderBlock::Rule,
+        heading(4, "Minor heading"),
+    ]);
+
+    assert_eq!(
+        blocks,
+        vec![
+            paragraph("Intro"),
+            ReaderBlock::Rule,
+            paragraph("Middle"),
+            heading(2, "Section"),
+            paragraph("Body"),
+            ReaderBlock::Rule,
+            heading(4, "Minor heading"),
+        ]
+    );
+}
+
+#[test]
+fn emphasis_is_kept_as_bold_and_italic_runs() {
+    let html = r#"<p>This is <strong>really</strong> <em>quite <b>important</b></em>, <i>honestly</i>.</p>"#;
+    let doc = scraper::Html::parse_fragment(html);
+    let p = doc
+        .select(&scraper::Selector::parse("p").unwrap())
+        .next()
+        .unwrap();
+    let url = url::Url::parse("https://example.com/").unwrap();
+
+    let runs = reader::extract_inline_runs(&p, &url);
+
+    let summary = runs
+        .iter()
+        .map(|run| (run.text.as_str(), run.bold, run.italic))
+        .collect::<Vec<_>>();
+    assert_eq!(
+        summary,
+        vec![
+            ("This is ", false, false),
+            ("really ", true, false),
+            ("quite ", false, true),
+            ("important", true, true),
+            (", ", false, false),
+            ("honestly", false, true),
+            (".", false, false),
+        ]
+    );
+    assert_eq!(
+        reader::inline_text(&runs),
+        "This is really quite important, honestly."
+    );
+}
+
+#[test]
+fn pull_quote_repeating_a_paragraph_is_dropped() {
+    let html = r#"<article>
+        <p>The team spent two years rebuilding the storage engine from scratch. Along the way
+        they found that most of the latency came from a single lock that nobody had looked at
+        since the first release.</p>
+        <blockquote class="pullquote"><p>“Most of the latency came from a single lock that
+        nobody had looked at…”</p></blockquote>
+        <p>Removing it took a week, but proving it was safe to remove took the rest of the
+        year, including a rewrite of the recovery path and a new test harness.</p>
+        <blockquote><p>Premature optimization is the root of all evil.</p></blockquote>
+    </article>"#;
+    let doc = scraper::Html::parse_fragment(html);
+    let url = url::Url::parse("https://example.com/post").unwrap();
+
+    let blocks = reader::extract_blocks(&doc.root_element(), &url);
+
+    let quotes = blocks
+        .iter()
+        .filter_map(|b| match b {
+            ReaderBlock::Quote(text) => Some(text.as_str()),
+            _ => None,
+        })
+        .collect::<Vec<_>>();
+    assert_eq!(
+        quotes,
+        vec!["Premature optimization is the root of all evil."]
+    );
+    assert_eq!(
+        blocks
+            .iter()
+            .filter(|b| matches!(b, ReaderBlock::Paragraph(_)))
+            .count(),
+        2
+    );
+}
+
+#[test]
+fn data_tables_become_table_blocks_and_single_column_tables_flatten() {
+    let many_rows = (1..=80)
+        .map(|i| format!("<tr><td>row {i}</td><td>{i}</td></tr>"))
+        .collect::<String>();
+    let html = format!(
+        r#"<article>
+        <p>The benchmark compares three allocators on the same workload, run ten times each.</p>
+        <table>
+          <thead><tr><th>Allocator</th><th>Median</th><th>p99</th></tr></thead>
+          <tbody>
+            <tr><td>system</td><td>12 ms</td><td>31 ms</td></tr>
+            <tr><td>jemalloc</td><td>9 ms</td></tr>
+          </tbody>
+        </table>
+        <p>A single-column table is really just a list of lines and reads better as text.</p>
+        <table><tr><td>First line</td></tr><tr><td>Second line</td></tr></table>
+        <table>{many_rows}</table>
+    </article>"#
+    );
+    let doc = scraper::Html::parse_fragment(&html);
+    let url = url::Url::parse("https://example.com/bench").unwrap();
+
+    let blocks = reader::extract_blocks(&doc.root_element(), &url);
+
+    let tables = blocks
+        .iter()
+        .filter_map(|b| match b {
+            ReaderBlock::Table { headers, rows } => Some((headers, rows)),
+            _ => None,
+        })
+        .collect::<Vec<_>>();
+    assert_eq!(tables.len(), 2);
+
+    let (headers, rows) = tables[0];
+    assert_eq!(headers, &vec!["Allocator", "Median", "p99"]);
+    // Short rows are padded so every row lines up with the header.
+    assert_eq!(
+        rows,
+        &vec![
+            vec!["system", "12 ms", "31 ms"],
+            vec!["jemalloc", "9 ms", ""]
+        ]
+    );
+
+    let (headers, rows) = tables[1];
+    assert!(headers.is_empty());
+    assert_eq!(rows.len(), reader::MAX_TABLE_ROWS);
+    assert_eq!(rows[0], vec!["row 1", "1"]);
+
+    assert!(blocks.contains(&paragraph("First line")));
+    assert!(blocks.contains(&paragraph("Second line")));
+}
+
+#[test]
+fn cache_repair_removes_corrupt_and_future_dated_entries() {
+    let dir = std::env::temp_dir().join(format!("oneapp-cache-repair-{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    let now = 1_700_000_000;
+    let item_url = url::Url::parse("https://news.ycombinator.com/item?id=1").unwrap();
+    let article = reader::self_text_article("<p>Hello</p>", "Ask HN", "op", &item_url);
+    let entry = |fetched_at: i64| {
+        serde_json::to_vec(&serde_json::json!({ "fetched_at": fetched_at, "article": article }))
+            .unwrap()
+    };
+
+    let healthy = entry(now - 60);
+    std::fs::write(dir.join("healthy.json"), &healthy).unwrap();
+    // A write cut off halfway through
+    std::fs::write(dir.join("truncated.json"), &healthy[..healthy.len() / 2]).unwrap();
+    std::fs::write(dir.join("future.json"), entry(now + 30 * 24 * 60 * 60)).unwrap();
+    std::fs::write(dir.join("notes.txt"), "not a cache entry").unwrap();
+
+    let repair =
+        reader::repair_disk_cache(&dir, now, 100, std::time::Duration::from_secs(5)).unwrap();
+    let mut left = std::fs::read_dir(&dir)
+        .unwrap()
+        .flatten()
+        .map(|e| e.file_name().to_string_lossy().into_owned())
+        .collect::<Vec<_>>();
+    left.sort();
+    let _ = std::fs::remove_dir_all(&dir);
+
+    assert_eq!((repair.scanned, repair.removed), (3, 2));
+    assert_eq!(left, vec!["healthy.json", "notes.txt"]);
+}
+
+#[test]
+fn stalled_fetches_time_out_with_a_timeout_error() {
+    use std::time::Duration;
+
+    let timeout = Duration::from_secs(15);
+    let stalled = futures::future::pending::<Result<Vec<u8>, String>>();
+    let result = futures::executor::block_on(reader::with_timeout(
+        stalled,
+        timeout,
+        futures::future::ready(()),
+    ));
+    let message = result.unwrap_err();
+    assert!(message.contains("timeout"), "{message}");
+
+    // A fetch that finishes first wins, even when the timer is already due
+    let result = futures::executor::block_on(reader::with_timeout(
+        futures::future::ready(Ok(7)),
+        timeout,
+        futures::future::ready(()),
+    ));
+    assert_eq!(result, Ok(7));
+
+    assert_eq!(
+        reader::parse_fetch_timeout(Some(" 3 ")),
+        Duration::from_secs(3)
+    );
+    assert_eq!(reader::parse_fetch_timeout(Some("0")), timeout);
+    assert_eq!(reader::parse_fetch_timeout(Some("soon")), timeout);
+    assert_eq!(reader::parse_fetch_timeout(None), timeout);
+}
+
+#[test]
+fn compressed_bodies_are_decoded_within_the_size_cap() {
+    use std::io::Write as _;
+
+    let html = "<html><body><p>Compressed article body.</p></body></html>".repeat(20);
+    let mut encoder = flate2::write::GzEncoder::new(Vec::new(), flate2::Compression::default());
+    encoder.write_all(html.as_bytes()).unwrap();
+    let gzipped = encoder.finish().unwrap();
+
+    let decoded = reader::decode_body(gzipped.clone(), "gzip", 4096).unwrap();
+    assert_eq!(decoded, html.as_bytes());
+
+    // The cap applies to the decoded size, not just what came over the wire
+    assert!(gzipped.len() < 200);
+    let message = reader::decode_body(gzipped, "gzip", 200).unwrap_err();
+    assert!(message.contains("too large"), "{message}");
+
+    // Mislabelled or unknown encodings fall back to the raw bytes
+    let plain = b"<p>Already plain</p>".to_vec();
+    assert_eq!(
+        reader::decode_body(plain.clone(), "gzip", 4096),
+        Ok(plain.clone())
+    );
+    assert_eq!(
+        reader::decode_body(plain.clone(), "identity", 4096),
+        Ok(plain)
+    );
+}
+
+#[test]
+fn pages_are_decoded_with_their_declared_charset() {
+    // "Café" in ISO-8859-1 (served as windows-1252 per the WHATWG encoding spec)
+    let latin1 = b"<html><body><p>Caf\xe9</p></body></html>";
+    assert!(reader::decode_html(latin1, "text/html; charset=ISO-8859-1").contains("Café"));
+
+    // "你好" in GBK, declared only in the markup
+    let gbk = b"<html><head><meta charset=\"gbk\"></head><body>\xc4\xe3\xba\xc3</body></html>";
+    assert!(reader::decode_html(gbk, "text/html").contains("你好"));
+
+    let shift_jis = b"<meta http-equiv=\"Content-Type\" content=\"text/html; charset=Shift_JIS\">\x93\xfa\x96\x7b";
+    assert!(reader::decode_html(shift_jis, "").contains("日本"));
+
+    // The header wins over the markup, and UTF-8 is the default
+    let utf8 = "<meta charset=\"gbk\"><p>Café</p>";
+    assert!(reader::decode_html(utf8.as_bytes(), "text/html; charset=\"utf-8\"").contains("Café"));
+    assert!(reader::decode_html("<p>Café</p>".as_bytes(), "text/html").contains("Café"));
+}
+
+#[test]
+fn cache_eviction_drops_least_recently_read_files_first() {
+    use std::time::{Duration, SystemTime};
+
+    let dir = std::env::temp_dir().join(format!("oneapp-cache-evict-{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    let now = SystemTime::now();
+    for (name, age_secs) in [
+        ("old", 300),
+        ("read-recently", 10),
+        ("middle", 200),
+        ("new", 0),
+    ] {
+        let path = dir.join(format!("{name}.json"));
+        std::fs::write(&path, vec![b' '; 100]).unwrap();
+        std::fs::File::options()
+            .write(true)
+            .open(&path)
+            .unwrap()
+            .set_modified(now - Duration::from_secs(age_secs))
+            .unwrap();
+    }
+    std::fs::write(dir.join("notes.txt"), vec![b' '; 1000]).unwrap();
+    let list = || {
+        let mut names = std::fs::read_dir(&dir)
+            .unwrap()
+            .flatten()
+            .map(|e| e.file_name().to_string_lossy().into_owned())
+            .collect::<Vec<_>>();
+        names.sort();
+        names
+    };
+
+    let keep = dir.join("new.json");
+    let under_budget = reader::evict_disk_cache(&dir, 400, &keep).unwrap();
+    let removed = reader::evict_disk_cache(&dir, 250, &keep).unwrap();
+    let after_first_pass = list();
+    // The file just written survives even a budget it doesn't fit in
+    reader::evict_disk_cache(&dir, 0, &keep).unwrap();
+    let after_second_pass = list();
+    let _ = std::fs::remove_dir_all(&dir);
+
+    assert_eq!(under_budget, 0);
+    assert_eq!(removed, 2);
+    assert_eq!(
+        after_first_pass,
+        vec!["new.json", "notes.txt", "read-recently.json"]
+    );
+    assert_eq!(after_second_pass, vec!["new.json", "notes.txt"]);
+}
+
+#[test]
+fn clearing_the_cache_removes_every_file() {
+    let dir = std::env::temp_dir().join(format!("oneapp-cache-clear-{}", std::process::id()));
+    std::fs::create_dir_all(dir.join("nested")).unwrap();
+    std::fs::write(dir.join("a.json"), "{}").unwrap();
+    std::fs::write(dir.join("b.json.tmp"), "{").unwrap();
+
+    let removed = reader::clear_cache_files(&dir).unwrap();
+    let left = std::fs::read_dir(&dir).unwrap().count();
+    let _ = std::fs::remove_dir_all(&dir);
+
+    assert_eq!(removed, 2);
+    // Only the directory is left
+    assert_eq!(left, 1);
+    assert_eq!(reader::clear_cache_files(&dir).unwrap(), 0);
+}
+
+#[test]
+fn pdfs_are_recognised_by_extension_or_content_type() {
+    let pdf = |url: &str| reader::is_pdf_url(&url::Url::parse(url).unwrap());
+    assert!(pdf("https://arxiv.org/pdf/2401.00001v2.pdf"));
+    assert!(pdf("https://example.com/Papers/Report.PDF?download=1"));
+    // Only the path counts, not a query parameter that happens to end in .pdf
+    assert!(!pdf("https://example.com/view?file=report.pdf"));
+    assert!(!pdf("https://example.com/pdf-tools"));
+
+    assert!(reader::is_pdf_content_type("application/pdf"));
+    assert!(reader::is_pdf_content_type(
+        "Application/PDF; charset=binary"
+    ));
+    assert!(!reader::is_pdf_content_type("text/html; charset=utf-8"));
+    assert!(!reader::is_pdf_content_type(""));
+}
+
+/// Serves a moved article: `old.example.com/post` redirects (relatively, then absolutely) to
+/// `new.example.com/2024/post`, and `/loop` redirects to itself forever.
+fn redirecting_client() -> std::sync::Arc<dyn gpui::http_client::HttpClient> {
+    use gpui::http_client::{AsyncBody, FakeHttpClient, Response};
+
+    FakeHttpClient::create(|req| async move {
+        let redirect = |location: &str| {
+            Response::builder()
+                .status(301)
+                .header("Location", location)
+                .body(AsyncBody::empty())
+        };
+        let response = match (req.uri().host(), req.uri().path()) {
+            (Some("old.example.com"), "/post") => redirect("/moved"),
+            (Some("old.example.com"), "/moved") => redirect("https://new.example.com/2024/post"),
+            (Some("new.example.com"), "/2024/post") => Response::builder()
+                .status(200)
+                .header("Content-Type", "text/html; charset=utf-8")
+                .body(AsyncBody::from(
+                    "<html><head><title>Moved post</title></head><body><article>\
+                     <p>This paragraph is the body of the article that moved to a new home.</p>\
+                     <p><a href=\"notes\">Notes</a> sit next to it on the new host.</p>\
+                     </article></body></html>"
+                        .to_string(),
+                )),
+            (_, "/loop") => redirect("/loop"),
+            _ => Response::builder().status(404).body(AsyncBody::empty()),
+        };
+        Ok(response.unwrap())
+    })
+}
+
+#[gpui::test]
+async fn redirected_articles_remember_where_they_landed(cx: &mut gpui::TestAppContext) {
+    let dir = std::env::temp_dir().join(format!("oneapp-reader-redirect-{}", std::process::id()));
+    let _ = std::fs::remove_dir_all(&dir);
+
+    let page = reader::load_article(
+        redirecting_client(),
+        &dir,
+        "https://old.example.com/post",
+        None,
+        cx.executor(),
+    )
+    .await
+    .unwrap();
+    let reader::LoadedPage::Article(article) = page else {
+        panic!("expected an article");
+    };
+    assert_eq!(
+        article.final_url.as_deref(),
+        Some("https://new.example.com/2024/post")
+    );
+    let _ = std::fs::remove_dir_all(&dir);
+}
+
+#[test]
+fn redirect_loops_give_up() {
+    let client = redirecting_client();
+    let url = url::Url::parse("https://old.example.com/loop").unwrap();
+    let error =
+        futures::executor::block_on(reader::send_following_redirects(client.as_ref(), &url))
+            .unwrap_err();
+    assert!(error.contains("Too many redirects"), "{error}");
+}
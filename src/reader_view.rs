@@ -1,34 +1,633 @@
-use crate::{reader, theme::Theme};
+use crate::{
+    history::VisitedUrls,
+    images::{ImageAction, ImageFailures, ImageReload, ImageReloads},
+    models::Comment,
+    reader,
+    theme::Theme,
+};
 use gpui::prelude::*;
-use gpui::{div, img, px, rems, AnyElement, FontWeight, ObjectFit};
+use gpui::{
+    div, img, px, rems, AnyElement, AnyView, ElementId, FontStyle, FontWeight, HighlightStyle,
+    Hsla, ImageSource, InteractiveText, ObjectFit, Rems, SharedString, StyledText, TextStyle,
+    UnderlineStyle, ViewContext, WindowContext,
+};
+use serde::{Deserialize, Serialize};
+use std::borrow::Cow;
+use std::ops::Range;
+use std::rc::Rc;
 
-pub(crate) fn render_reader_block(theme: &Theme, block: &reader::ReaderBlock) -> AnyElement {
+/// Height of the reader header once it has collapsed to a single compact row.
+pub(crate) const READER_HEADER_COLLAPSED_HEIGHT: f32 = 32.0;
+/// The header never collapses while the article is scrolled less than this.
+const HEADER_COLLAPSE_THRESHOLD: f32 = 48.0;
+/// Ignore tiny scroll jitter when deciding the scroll direction.
+const HEADER_SCROLL_HYSTERESIS: f32 = 4.0;
+/// Articles with a shorter estimated reading time don't get an outline minimap.
+pub(crate) const MINIMAP_MIN_READING_MINUTES: usize = 8;
+/// Articles with fewer headings than this don't get a table of contents.
+pub(crate) const TOC_MIN_HEADINGS: usize = 3;
+/// A heading this close below the top of the viewport already counts as the current section.
+const TOC_CURRENT_SLACK: f32 = 24.0;
+/// Image height cap when the intrinsic size is unknown.
+pub(crate) const DEFAULT_IMAGE_MAX_HEIGHT: f32 = 520.0;
+/// Bounds for the reader's text size multiplier.
+pub(crate) const READER_FONT_SCALE_MIN: f32 = 0.8;
+pub(crate) const READER_FONT_SCALE_MAX: f32 = 1.6;
+/// How much one press of the header's A−/A+ changes the multiplier.
+const READER_FONT_SCALE_STEP: f32 = 0.1;
+/// Unbroken tokens longer than this many chars get soft break points.
+const LONG_TOKEN_CHARS: usize = 40;
+const SOFT_BREAK: char = '\u{200B}';
+
+/// Collapses the header while scrolling down past the threshold and reveals it on scroll up.
+/// `scroll_y` is the distance scrolled from the top, in pixels.
+pub(crate) fn header_collapsed_after_scroll(collapsed: bool, last_y: f32, scroll_y: f32) -> bool {
+    if scroll_y <= HEADER_COLLAPSE_THRESHOLD {
+        false
+    } else if scroll_y > last_y + HEADER_SCROLL_HYSTERESIS {
+        true
+    } else if scroll_y < last_y - HEADER_SCROLL_HYSTERESIS {
+        false
+    } else {
+        collapsed
+    }
+}
+
+/// Inserts zero-width break opportunities into long unbroken tokens (URLs, hashes,
+/// base64 blobs) so the line wrapper splits them instead of running past the column.
+pub(crate) fn break_long_tokens(text: &str) -> Cow<'_, str> {
+    if !text
+        .split_whitespace()
+        .any(|token| token.chars().count() > LONG_TOKEN_CHARS)
+    {
+        return Cow::Borrowed(text);
+    }
+
+    let mut wrapped = String::with_capacity(text.len() + text.len() / LONG_TOKEN_CHARS * 3);
+    let mut token_len = 0;
+    for c in text.chars() {
+        if c.is_whitespace() {
+            token_len = 0;
+        } else {
+            if token_len == LONG_TOKEN_CHARS {
+                wrapped.push(SOFT_BREAK);
+                token_len = 0;
+            }
+            token_len += 1;
+        }
+        wrapped.push(c);
+    }
+    Cow::Owned(wrapped)
+}
+
+/// A comment's body as reader blocks, so the clean comment layout shares the article typography.
+pub(crate) fn comment_blocks(comment: &Comment) -> Vec<reader::ReaderBlock> {
+    let base = if crate::lobsters::is_lobsters_id(comment.id) {
+        url::Url::parse(crate::lobsters::LOBSTERS_BASE_URL)
+    } else {
+        url::Url::parse(&format!(
+            "https://news.ycombinator.com/item?id={}",
+            comment.id
+        ))
+    };
+    match (comment.text.as_deref(), base) {
+        (Some(html), Ok(base)) => reader::fragment_blocks(html, &base),
+        _ => vec![reader::ReaderBlock::Paragraph(comment.clean_text())],
+    }
+}
+
+/// Heading markers on the minimap as `(block index, fraction of the article height)`.
+/// `headings` holds each heading's top, measured from the start of the article.
+pub(crate) fn minimap_markers(headings: &[(usize, f32)], content_height: f32) -> Vec<(usize, f32)> {
+    if content_height <= 0.0 {
+        return Vec::new();
+    }
+    headings
+        .iter()
+        .map(|&(index, top)| (index, (top / content_height).clamp(0.0, 1.0)))
+        .collect()
+}
+
+/// The visible part of the article as `(start, length)` fractions of the minimap.
+pub(crate) fn minimap_viewport(
+    scroll_y: f32,
+    viewport_height: f32,
+    content_height: f32,
+) -> (f32, f32) {
+    if content_height <= 0.0 {
+        return (0.0, 1.0);
+    }
+    let start = (scroll_y / content_height).clamp(0.0, 1.0);
+    let length = (viewport_height / content_height).clamp(0.0, 1.0 - start);
+    (start, length)
+}
+
+/// How much of the article has been scrolled past, from 0 to 1. An article that fits in
+/// the viewport counts as fully read.
+pub(crate) fn reading_progress(scroll_y: f32, viewport_height: f32, content_height: f32) -> f32 {
+    let max_scroll = content_height - viewport_height;
+    if max_scroll <= 0.0 {
+        return 1.0;
+    }
+    (scroll_y / max_scroll).clamp(0.0, 1.0)
+}
+
+/// The table of contents entry for the section being read: the last heading at or above
+/// the top of the viewport. `headings` holds `(block index, top)` in reading order.
+pub(crate) fn current_toc_entry(headings: &[(usize, f32)], scroll_y: f32) -> Option<usize> {
+    headings
+        .iter()
+        .take_while(|(_, top)| *top <= scroll_y + TOC_CURRENT_SLACK)
+        .last()
+        .map(|(index, _)| *index)
+}
+
+/// Indent steps for a table of contents entry, relative to the article's top heading level.
+pub(crate) fn toc_indent(level: u8, top_level: u8) -> u8 {
+    level.saturating_sub(top_level).min(3)
+}
+
+/// Max image heights by orientation, so tall screenshots aren't squeezed into a
+/// landscape-sized box.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) struct ImageHeightCaps {
+    pub landscape: f32,
+    pub portrait: f32,
+}
+
+impl Default for ImageHeightCaps {
+    fn default() -> Self {
+        Self {
+            landscape: DEFAULT_IMAGE_MAX_HEIGHT,
+            portrait: DEFAULT_IMAGE_MAX_HEIGHT,
+        }
+    }
+}
+
+/// Picks the height cap for an image from its intrinsic size; square images count as
+/// landscape and unknown sizes use the landscape cap.
+pub(crate) fn image_max_height(dimensions: Option<(u32, u32)>, caps: ImageHeightCaps) -> f32 {
+    match dimensions {
+        Some((width, height)) if width > 0 && height > width => caps.portrait,
+        _ => caps.landscape,
+    }
+}
+
+/// Clamps a stored font scale into range; anything unusable falls back to 1.0.
+pub(crate) fn clamp_font_scale(scale: f32) -> f32 {
+    if scale.is_finite() {
+        scale.clamp(READER_FONT_SCALE_MIN, READER_FONT_SCALE_MAX)
+    } else {
+        1.0
+    }
+}
+
+/// One A−/A+ step, snapped to the step size so repeated presses don't drift.
+pub(crate) fn step_font_scale(scale: f32, larger: bool) -> f32 {
+    let step = if larger {
+        READER_FONT_SCALE_STEP
+    } else {
+        -READER_FONT_SCALE_STEP
+    };
+    let stepped = ((clamp_font_scale(scale) + step) / READER_FONT_SCALE_STEP).round();
+    clamp_font_scale(stepped * READER_FONT_SCALE_STEP)
+}
+
+/// Per-user layout knobs for reader blocks.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) struct ReaderLayout {
+    pub image_caps: ImageHeightCaps,
+    /// Multiplier for text sizes and line heights.
+    pub font_scale: f32,
+    /// Body text color when it should not be `text_primary`, e.g. dimmed seen comments.
+    pub text_color: Option<Hsla>,
+}
+
+impl Default for ReaderLayout {
+    fn default() -> Self {
+        Self {
+            image_caps: ImageHeightCaps::default(),
+            font_scale: 1.0,
+            text_color: None,
+        }
+    }
+}
+
+/// Typeface for article text; code blocks always stay monospaced.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ReaderFont {
+    #[default]
+    Sans,
+    Serif,
+    Mono,
+}
+
+impl ReaderFont {
+    pub fn all() -> [ReaderFont; 3] {
+        [ReaderFont::Sans, ReaderFont::Serif, ReaderFont::Mono]
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            ReaderFont::Sans => "Sans",
+            ReaderFont::Serif => "Serif",
+            ReaderFont::Mono => "Mono",
+        }
+    }
+
+    pub fn family(self) -> &'static str {
+        match self {
+            ReaderFont::Sans => ".SystemUIFont",
+            ReaderFont::Serif => "Georgia",
+            ReaderFont::Mono => "Menlo",
+        }
+    }
+}
+
+/// Width of the article column.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ReaderWidth {
+    Narrow,
+    #[default]
+    Normal,
+    Wide,
+}
+
+impl ReaderWidth {
+    pub fn all() -> [ReaderWidth; 3] {
+        [ReaderWidth::Narrow, ReaderWidth::Normal, ReaderWidth::Wide]
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            ReaderWidth::Narrow => "Narrow",
+            ReaderWidth::Normal => "Normal",
+            ReaderWidth::Wide => "Wide",
+        }
+    }
+
+    /// Max column width in px, padding included.
+    pub fn max_width(self) -> f32 {
+        match self {
+            ReaderWidth::Narrow => 620.,
+            ReaderWidth::Normal => 760.,
+            ReaderWidth::Wide => 960.,
+        }
+    }
+}
+
+/// Colors for the article body: the app theme, or a warm paper look.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ReaderTheme {
+    #[default]
+    App,
+    Sepia,
+}
+
+impl ReaderTheme {
+    /// The theme the article is drawn with; `app` is the rest of the UI's.
+    pub fn resolve(self, app: &Theme) -> Cow<'_, Theme> {
+        match self {
+            ReaderTheme::App => Cow::Borrowed(app),
+            ReaderTheme::Sepia => Cow::Owned(Theme::sepia()),
+        }
+    }
+}
+
+/// What inline links need from the app: visited state and what a click does.
+pub(crate) struct InlineLinks<'a> {
+    pub visited: &'a VisitedUrls,
+    pub visited_color: Hsla,
+    pub on_click: Rc<dyn Fn(&str, &mut WindowContext)>,
+}
+
+/// Images the user asked to load again, and how a reload is started.
+pub(crate) struct ReaderImages<'a> {
+    pub reloads: &'a ImageReloads,
+    pub on_reload: Rc<dyn Fn(&str, &mut WindowContext)>,
+    /// Images that gpui failed to load are recorded here while drawing.
+    pub failures: ImageFailures,
+    /// Show each image's description instead of the image.
+    pub text_only: bool,
+}
+
+/// Link, abbreviation and emphasis styling for a paragraph's runs, as byte ranges
+/// into the concatenated text. Links win over abbreviations when a run is both.
+pub(crate) fn inline_highlights(
+    theme: &Theme,
+    runs: &[reader::InlineRun],
+    visited: &VisitedUrls,
+    visited_color: Hsla,
+) -> Vec<(Range<usize>, HighlightStyle)> {
+    let mut offset = 0;
+    let mut highlights = Vec::new();
+    for run in runs {
+        let range = offset..offset + run.text.len();
+        offset = range.end;
+        let mut style = HighlightStyle {
+            font_weight: run.bold.then_some(FontWeight::BOLD),
+            font_style: run.italic.then_some(FontStyle::Italic),
+            ..Default::default()
+        };
+        if let Some(link) = run.link.as_deref() {
+            let color = if visited.contains(link) {
+                visited_color
+            } else {
+                theme.accent
+            };
+            style.color = Some(color);
+            style.underline = Some(UnderlineStyle {
+                thickness: px(1.),
+                color: Some(color),
+                wavy: false,
+            });
+        } else if run.abbr_title.is_some() {
+            // gpui has no dotted underline; a thin muted one reads the same at text size.
+            style.underline = Some(UnderlineStyle {
+                thickness: px(1.),
+                color: Some(theme.text_muted),
+                wavy: false,
+            });
+        }
+        if style != HighlightStyle::default() {
+            highlights.push((range, style));
+        }
+    }
+    highlights
+}
+
+fn render_inline_runs(
+    theme: &Theme,
+    index: usize,
+    runs: &[reader::InlineRun],
+    links: &InlineLinks<'_>,
+    color: Hsla,
+) -> AnyElement {
+    let default_style = TextStyle {
+        color,
+        font_family: ".SystemUIFont".into(),
+        ..Default::default()
+    };
+    let runs = &runs
+        .iter()
+        .map(|run| reader::InlineRun {
+            text: break_long_tokens(&run.text).into_owned(),
+            ..run.clone()
+        })
+        .collect::<Vec<_>>();
+    let highlights = inline_highlights(theme, runs, links.visited, links.visited_color);
+
+    let mut offset = 0;
+    let mut ranges = Vec::new();
+    let mut urls = Vec::new();
+    let mut abbrs = Vec::new();
+    for run in runs {
+        let range = offset..offset + run.text.len();
+        offset = range.end;
+        if let Some(link) = run.link.clone() {
+            ranges.push(range.clone());
+            urls.push(link);
+        }
+        if let Some(title) = run.abbr_title.clone() {
+            abbrs.push((range, SharedString::from(title)));
+        }
+    }
+
+    let on_click = links.on_click.clone();
+    InteractiveText::new(
+        ElementId::Name(format!("reader-inline-{index}").into()),
+        StyledText::new(reader::inline_text(runs)).with_highlights(&default_style, highlights),
+    )
+    .on_click(ranges, move |ix, cx| {
+        if let Some(url) = urls.get(ix) {
+            on_click(url, cx);
+        }
+    })
+    .map(|text| {
+        if abbrs.is_empty() {
+            return text;
+        }
+        let (bg, border, color) = (theme.bg_secondary, theme.border, theme.text_primary);
+        text.tooltip(move |ix, cx| {
+            let (_, title) = abbrs.iter().find(|(range, _)| range.contains(&ix))?;
+            let title = title.clone();
+            Some(AnyView::from(cx.new_view(|_| TextTooltip {
+                title,
+                bg,
+                border,
+                color,
+            })))
+        })
+    })
+    .into_any_element()
+}
+
+/// Tooltip builder for a single line of text, e.g. an absolute timestamp.
+pub(crate) fn text_tooltip(
+    theme: &Theme,
+    title: impl Into<SharedString>,
+) -> impl Fn(&mut WindowContext) -> AnyView + 'static {
+    let title = title.into();
+    let (bg, border, color) = (theme.bg_secondary, theme.border, theme.text_primary);
+    move |cx| {
+        let title = title.clone();
+        AnyView::from(cx.new_view(|_| TextTooltip {
+            title,
+            bg,
+            border,
+            color,
+        }))
+    }
+}
+
+/// Hover tooltip with one line of text, such as an abbreviation's expansion.
+struct TextTooltip {
+    title: SharedString,
+    bg: Hsla,
+    border: Hsla,
+    color: Hsla,
+}
+
+impl Render for TextTooltip {
+    fn render(&mut self, _cx: &mut ViewContext<Self>) -> impl IntoElement {
+        div()
+            .px_2()
+            .py_1()
+            .bg(self.bg)
+            .border_1()
+            .border_color(self.border)
+            .rounded_md()
+            .text_xs()
+            .text_color(self.color)
+            .child(self.title.clone())
+    }
+}
+
+/// Context menu for an article image, positioned over its top-right corner.
+pub(crate) fn render_image_menu(
+    theme: &Theme,
+    on_action: Rc<dyn Fn(ImageAction, &mut WindowContext)>,
+) -> AnyElement {
+    let hover_bg = theme.bg_hover;
+    div()
+        .absolute()
+        .top_2()
+        .right_2()
+        .min_w(px(150.))
+        .py_1()
+        .flex()
+        .flex_col()
+        .bg(theme.bg_primary)
+        .border_1()
+        .border_color(theme.border)
+        .rounded_md()
+        .shadow_md()
+        .text_sm()
+        .text_color(theme.text_primary)
+        .children([ImageAction::Copy, ImageAction::Save].map(|action| {
+            let on_action = on_action.clone();
+            div()
+                .id(action.label())
+                .px_3()
+                .py_1()
+                .cursor_pointer()
+                .hover(move |s| s.bg(hover_bg))
+                .on_click(move |_event, cx| on_action(action, cx))
+                .child(action.label())
+        }))
+        .into_any_element()
+}
+
+/// The text that stands for an image: its caption, else its alt text. Blank captions
+/// don't hide the alt text.
+pub(crate) fn image_text<'a>(alt: Option<&'a str>, caption: Option<&'a str>) -> Option<&'a str> {
+    [caption, alt]
+        .into_iter()
+        .flatten()
+        .map(str::trim)
+        .find(|s| !s.is_empty())
+}
+
+/// What a text-only reader shows in place of an image.
+pub(crate) fn image_description(alt: Option<&str>, caption: Option<&str>) -> String {
+    match image_text(alt, caption) {
+        Some(text) => format!("Image: {text}"),
+        None => "Image".to_string(),
+    }
+}
+
+/// Stands in for an image that didn't load, with a button to fetch it again.
+pub(crate) fn render_image_placeholder(
+    theme: &Theme,
+    message: String,
+    on_reload: Option<Rc<dyn Fn(&mut WindowContext)>>,
+) -> AnyElement {
+    let hover_bg = theme.bg_hover;
+    div()
+        .w_full()
+        .min_h(px(96.))
+        .p_4()
+        .flex()
+        .flex_col()
+        .items_center()
+        .justify_center()
+        .gap_2()
+        .bg(theme.bg_secondary)
+        .border_1()
+        .border_color(theme.border_subtle)
+        .rounded_md()
+        .text_sm()
+        .text_color(theme.text_muted)
+        .child(div().whitespace_normal().child(message))
+        .when_some(on_reload, |this, on_reload| {
+            this.child(
+                div()
+                    .id("reload-image")
+                    .px_3()
+                    .py_1()
+                    .border_1()
+                    .border_color(theme.border)
+                    .rounded_md()
+                    .text_color(theme.text_primary)
+                    .cursor_pointer()
+                    .hover(move |s| s.bg(hover_bg))
+                    .on_click(move |_event, cx| on_reload(cx))
+                    .child("Reload image"),
+            )
+        })
+        .into_any_element()
+}
+
+/// One muted line standing in for a captioned image that failed to load.
+fn render_image_unavailable(
+    theme: &Theme,
+    on_reload: Rc<dyn Fn(&mut WindowContext)>,
+) -> AnyElement {
+    div()
+        .flex()
+        .items_center()
+        .gap_2()
+        .text_sm()
+        .text_color(theme.text_muted)
+        .child("Image unavailable")
+        .child("·")
+        .child(
+            div()
+                .id("reload-image")
+                .text_color(theme.accent)
+                .cursor_pointer()
+                .hover(|s| s.underline())
+                .on_click(move |_event, cx| on_reload(cx))
+                .child("Reload"),
+        )
+        .into_any_element()
+}
+
+pub(crate) fn render_reader_block(
+    theme: &Theme,
+    index: usize,
+    block: &reader::ReaderBlock,
+    links: &InlineLinks<'_>,
+    images: &ReaderImages<'_>,
+    layout: ReaderLayout,
+) -> AnyElement {
+    // Sizes in rems at 1.0 (text_xl is 1.25, text_sm 0.875 and so on).
+    let scaled = |size: f32| rems(size * layout.font_scale);
+    let body_color = layout.text_color.unwrap_or(theme.text_primary);
     match block {
         reader::ReaderBlock::Heading { level, text } => {
             let base = div()
                 .w_full()
                 .font_weight(FontWeight::SEMIBOLD)
-                .line_height(rems(1.25))
+                .line_height(scaled(1.25))
                 .whitespace_normal()
-                .child(text.clone());
-
-            match level {
-                1 => base.text_xl().into_any_element(),
-                2 => base.text_lg().into_any_element(),
-                3 => base.text_base().into_any_element(),
-                _ => base
-                    .text_base()
-                    .text_color(theme.text_secondary)
-                    .into_any_element(),
+                .text_size(scaled(heading_size(*level)))
+                .child(break_long_tokens(text).into_owned());
+
+            if *level > 3 {
+                base.text_color(theme.text_secondary).into_any_element()
+            } else {
+                base.into_any_element()
             }
         }
         reader::ReaderBlock::Paragraph(text) => div()
             .w_full()
-            .text_base()
-            .line_height(rems(1.75))
-            .text_color(theme.text_primary)
+            .text_size(scaled(1.0))
+            .line_height(scaled(1.75))
+            .text_color(body_color)
+            .whitespace_normal()
+            .child(break_long_tokens(text).into_owned())
+            .into_any_element(),
+        reader::ReaderBlock::RichParagraph(runs) => div()
+            .w_full()
+            .text_size(scaled(1.0))
+            .line_height(scaled(1.75))
+            .text_color(body_color)
             .whitespace_normal()
-            .child(text.clone())
+            .child(render_inline_runs(theme, index, runs, links, body_color))
             .into_any_element(),
         reader::ReaderBlock::Quote(text) => div()
             .w_full()
@@ -39,11 +638,11 @@ pub(crate) fn render_reader_block(theme: &Theme, block: &reader::ReaderBlock) ->
             .rounded_md()
             .border_l_2()
             .border_color(theme.border)
-            .text_base()
-            .line_height(rems(1.7))
+            .text_size(scaled(1.0))
+            .line_height(scaled(1.7))
             .text_color(theme.text_secondary)
             .whitespace_normal()
-            .child(text.clone())
+            .child(break_long_tokens(text).into_owned())
             .into_any_element(),
         reader::ReaderBlock::List { ordered, items } => div()
             .w_full()
@@ -71,11 +670,11 @@ pub(crate) fn render_reader_block(theme: &Theme, block: &reader::ReaderBlock) ->
                                 div()
                                     .flex_1()
                                     .min_w(px(0.))
-                                    .text_base()
-                                    .line_height(rems(1.7))
-                                    .text_color(theme.text_primary)
+                                    .text_size(scaled(1.0))
+                                    .line_height(scaled(1.7))
+                                    .text_color(body_color)
                                     .whitespace_normal()
-                                    .child(item.clone()),
+                                    .child(break_long_tokens(item).into_owned()),
                             )
                             .into_any_element()
                     })
@@ -114,48 +713,166 @@ pub(crate) fn render_reader_block(theme: &Theme, block: &reader::ReaderBlock) ->
                         .px_4()
                         .py_3()
                         .font_family("Menlo")
-                        .text_sm()
-                        .line_height(rems(1.55))
+                        .text_size(scaled(0.875))
+                        .line_height(scaled(1.55))
                         .text_color(theme.text_primary)
                         .whitespace_normal()
                         .overflow_x_hidden()
-                        .child(text.clone()),
+                        .child(break_long_tokens(text).into_owned()),
                 )
                 .into_any_element()
         }
-        reader::ReaderBlock::Image { url, alt, caption } => {
-            let caption = caption
-                .clone()
-                .or_else(|| alt.clone())
-                .filter(|s| !s.is_empty());
+        reader::ReaderBlock::Table { headers, rows } => {
+            render_table(theme, headers, rows, scaled(0.875), scaled(1.45))
+        }
+        reader::ReaderBlock::Image {
+            url,
+            alt,
+            caption,
+            dimensions,
+        } => {
+            if images.text_only {
+                return render_image_placeholder(
+                    theme,
+                    image_description(alt.as_deref(), caption.as_deref()),
+                    None,
+                );
+            }
+            let caption = image_text(alt.as_deref(), caption.as_deref()).map(str::to_string);
 
-            let mut container = div().w_full().flex().flex_col().gap_2().child(
-                img(url.clone())
-                    .w_full()
-                    .max_h(px(520.))
-                    .rounded_md()
-                    .border_1()
-                    .border_color(theme.border_subtle)
-                    .object_fit(ObjectFit::Contain),
-            );
+            let on_reload: Rc<dyn Fn(&mut WindowContext)> = {
+                let reload = images.on_reload.clone();
+                let url = url.clone();
+                Rc::new(move |cx: &mut WindowContext| reload(&url, cx))
+            };
+            // A reloaded image is shown from the bytes we fetched ourselves.
+            let image = match images.reloads.get(url) {
+                Some(ImageReload::Loading) => {
+                    render_image_placeholder(theme, "Loading image…".to_string(), None)
+                }
+                Some(ImageReload::Failed(error)) => render_image_placeholder(
+                    theme,
+                    format!("Image failed to load: {error}"),
+                    Some(on_reload),
+                ),
+                reloaded => {
+                    let source: ImageSource = match reloaded {
+                        Some(ImageReload::Loaded(image)) => image.clone().into(),
+                        _ => url.clone().into(),
+                    };
+                    let loading_theme = theme.clone();
+                    let fallback_theme = theme.clone();
+                    let failures = images.failures.clone();
+                    let failed_url = url.clone();
+                    let has_caption = caption.is_some();
+                    img(source)
+                        .w_full()
+                        .max_h(px(image_max_height(*dimensions, layout.image_caps)))
+                        .rounded_md()
+                        .border_1()
+                        .border_color(theme.border_subtle)
+                        .object_fit(ObjectFit::Contain)
+                        .with_loading(move || {
+                            render_image_placeholder(
+                                &loading_theme,
+                                "Loading image…".to_string(),
+                                None,
+                            )
+                        })
+                        // A broken image collapses to its caption or alt text; without either
+                        // there's nothing worth keeping a box around.
+                        .with_fallback(move || {
+                            failures.record(&failed_url);
+                            if has_caption {
+                                render_image_unavailable(&fallback_theme, on_reload.clone())
+                            } else {
+                                div().into_any_element()
+                            }
+                        })
+                        .into_any_element()
+                }
+            };
+
+            let mut container = div().w_full().flex().flex_col().gap_2().child(image);
 
             if let Some(caption) = caption {
                 container = container.child(
                     div()
-                        .text_sm()
+                        .text_size(scaled(0.875))
                         .text_color(theme.text_muted)
                         .whitespace_normal()
-                        .child(caption),
+                        .child(break_long_tokens(&caption).into_owned()),
                 );
             }
 
             container.into_any_element()
         }
+        // Extra margin so a section break reads as one, not as another line of text.
         reader::ReaderBlock::Rule => div()
             .w_full()
+            .my_4()
             .h(px(1.))
             .bg(theme.border_subtle)
             .into_any_element(),
     }
 }
 
+/// Tables are drawn as rows of equal-width cells that wrap their text, so a wide table
+/// stays inside the article column.
+fn render_table(
+    theme: &Theme,
+    headers: &[String],
+    rows: &[Vec<String>],
+    text_size: Rems,
+    line_height: Rems,
+) -> AnyElement {
+    let row = |cells: &[String], header: bool| {
+        div()
+            .w_full()
+            .flex()
+            .when(header, |this| {
+                this.bg(theme.bg_secondary)
+                    .font_weight(FontWeight::SEMIBOLD)
+            })
+            .children(cells.iter().map(|cell| {
+                div()
+                    .flex_1()
+                    .min_w(px(0.))
+                    .px_3()
+                    .py_2()
+                    .whitespace_normal()
+                    .child(break_long_tokens(cell).into_owned())
+            }))
+    };
+
+    div()
+        .w_full()
+        .min_w(px(0.))
+        .flex()
+        .flex_col()
+        .rounded_md()
+        .border_1()
+        .border_color(theme.border_subtle)
+        .overflow_hidden()
+        .text_size(text_size)
+        .line_height(line_height)
+        .text_color(theme.text_primary)
+        .when(!headers.is_empty(), |this| {
+            this.child(row(headers, true).border_b_1().border_color(theme.border))
+        })
+        .children(rows.iter().enumerate().map(|(index, cells)| {
+            row(cells.as_slice(), false)
+                .when(index > 0, |this| this.border_t_1())
+                .border_color(theme.border_subtle)
+        }))
+        .into_any_element()
+}
+
+/// Heading sizes in rems before scaling: h1 is text_xl, h2 text_lg, the rest text_base.
+fn heading_size(level: u8) -> f32 {
+    match level {
+        1 => 1.25,
+        2 => 1.125,
+        _ => 1.0,
+    }
+}
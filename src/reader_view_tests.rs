@@ -0,0 +1,135 @@
+use crate::{history::VisitedUrls, reader, reader_view, theme, theme::Theme};
+
+#[test]
+fn visited_inline_links_use_the_visited_color() {
+    let theme = Theme::default();
+    let visited_color = theme::parse_hex_color("#7a5c99").unwrap();
+    let mut visited = VisitedUrls::default();
+    visited.record("https://example.com/seen".to_string());
+
+    let runs = vec![
+        reader::InlineRun {
+            text: "See ".to_string(),
+            link: None,
+            abbr_title: None,
+            ..Default::default()
+        },
+        reader::InlineRun {
+            text: "this".to_string(),
+            link: Some("https://example.com/seen".to_string()),
+            abbr_title: None,
+            ..Default::default()
+        },
+        reader::InlineRun {
+            text: " and ".to_string(),
+            link: None,
+            abbr_title: None,
+            ..Default::default()
+        },
+        reader::InlineRun {
+            text: "that".to_string(),
+            link: Some("https://example.com/new".to_string()),
+            abbr_title: None,
+            ..Default::default()
+        },
+    ];
+
+    let highlights = reader_view::inline_highlights(&theme, &runs, &visited, visited_color);
+
+    let colors = highlights
+        .iter()
+        .map(|(range, style)| (range.clone(), style.color))
+        .collect::<Vec<_>>();
+    assert_eq!(
+        colors,
+        vec![(4..8, Some(visited_color)), (13..17, Some(theme.accent))]
+    );
+}
+
+#[test]
+fn clean_comment_layout_uses_reader_blocks() {
+    let comment = crate::models::Comment {
+        id: 42,
+        by: Some("alice".to_string()),
+        text: Some(
+            "First point.<p>See <a href=\"https://example.com/x\">this</a>.<p><pre><code>  let x = 1;\n</code></pre>"
+                .to_string(),
+        ),
+        time: 1_700_000_000,
+        kids: None,
+        parent: 1,
+        depth: 1,
+        reply_count: 0,
+        avatar_url: None,
+    };
+
+    let blocks = reader_view::comment_blocks(&comment);
+    assert!(matches!(&blocks[0], reader::ReaderBlock::Paragraph(text) if text == "First point."));
+    assert!(matches!(
+        &blocks[1],
+        reader::ReaderBlock::RichParagraph(runs)
+            if runs.iter().any(|run| run.link.as_deref() == Some("https://example.com/x"))
+    ));
+    assert!(matches!(&blocks[2], reader::ReaderBlock::Code { text, .. } if text == "let x = 1;"));
+
+    let deleted = crate::models::Comment {
+        text: None,
+        ..comment
+    };
+    assert!(matches!(
+        reader_view::comment_blocks(&deleted).as_slice(),
+        [reader::ReaderBlock::Paragraph(text)] if text == "[deleted]"
+    ));
+}
+
+#[test]
+fn toc_highlights_the_last_heading_above_the_viewport_top() {
+    let headings = [(3, 400.0), (9, 1500.0), (15, 3000.0)];
+
+    // Before the first heading nothing is current yet.
+    assert_eq!(reader_view::current_toc_entry(&headings, 0.0), None);
+    assert_eq!(reader_view::current_toc_entry(&headings, 1000.0), Some(3));
+    // A heading just below the top, as after jumping to it, already counts.
+    assert_eq!(reader_view::current_toc_entry(&headings, 1490.0), Some(9));
+    assert_eq!(reader_view::current_toc_entry(&headings, 9000.0), Some(15));
+
+    assert_eq!(reader_view::toc_indent(2, 2), 0);
+    assert_eq!(reader_view::toc_indent(4, 2), 2);
+    assert_eq!(reader_view::toc_indent(1, 2), 0);
+    assert_eq!(reader_view::toc_indent(6, 1), 3);
+}
+
+#[test]
+fn image_height_cap_follows_orientation() {
+    let caps = reader_view::ImageHeightCaps {
+        landscape: 480.0,
+        portrait: 900.0,
+    };
+
+    assert_eq!(
+        reader_view::image_max_height(Some((1600, 900)), caps),
+        480.0
+    );
+    assert_eq!(reader_view::image_max_height(Some((800, 800)), caps), 480.0);
+    assert_eq!(
+        reader_view::image_max_height(Some((750, 2400)), caps),
+        900.0
+    );
+    // Unknown or bogus sizes use the landscape cap.
+    assert_eq!(reader_view::image_max_height(None, caps), 480.0);
+    assert_eq!(reader_view::image_max_height(Some((0, 300)), caps), 480.0);
+}
+
+#[test]
+fn failed_images_fall_back_to_caption_then_alt_text() {
+    assert_eq!(
+        reader_view::image_text(Some("Build graph"), Some("Figure 2")),
+        Some("Figure 2")
+    );
+    // A blank caption doesn't hide the alt text.
+    assert_eq!(
+        reader_view::image_text(Some("Build graph"), Some("  ")),
+        Some("Build graph")
+    );
+    assert_eq!(reader_view::image_text(Some(""), None), None);
+}
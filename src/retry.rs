@@ -0,0 +1,101 @@
+use crate::reader::BodyError;
+use gpui::http_client::http::StatusCode;
+use std::collections::hash_map::RandomState;
+use std::future::Future;
+use std::hash::{BuildHasher, Hasher};
+use std::time::Duration;
+
+/// 一次请求失败的原因；`transient` 的（网络错误、超时、408、429、5xx）值得再试
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FetchError {
+    pub message: String,
+    pub transient: bool,
+}
+
+impl FetchError {
+    pub fn transient(message: impl Into<String>) -> Self {
+        Self {
+            message: message.into(),
+            transient: true,
+        }
+    }
+
+    pub fn fatal(message: impl Into<String>) -> Self {
+        Self {
+            message: message.into(),
+            transient: false,
+        }
+    }
+
+    /// 非 2xx 的响应，按状态码决定值不值得重试
+    pub fn from_status(status: StatusCode, url: &str) -> Self {
+        Self {
+            message: format!("HTTP {} for {}", status, url),
+            transient: is_transient_status(status.as_u16()),
+        }
+    }
+}
+
+/// 读到一半断开可以重试，超过大小上限重试也没用
+impl From<BodyError> for FetchError {
+    fn from(error: BodyError) -> Self {
+        match error {
+            BodyError::Io(message) => Self::transient(message),
+            BodyError::TooLarge { .. } => Self::fatal(error.to_string()),
+        }
+    }
+}
+
+/// 最多试几次、每次隔多久
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RetryPolicy {
+    /// 最多尝试的次数（含第一次）
+    pub attempts: usize,
+    /// 第一次重试前的等待，之后每次翻倍
+    pub base_delay: Duration,
+}
+
+impl RetryPolicy {
+    /// 第 `retry` 次重试（从 0 开始）前的等待：指数退避，再加最多一半的随机抖动，
+    /// 同一批失败的请求不会在同一时刻一起重试。`jitter` 取 0 到 1
+    pub fn delay(&self, retry: u32, jitter: f32) -> Duration {
+        let base = self.base_delay * 2u32.saturating_pow(retry);
+        base + base.mul_f32(jitter.clamp(0., 1.) / 2.)
+    }
+}
+
+/// 超时、限流和服务器错误可能下次就好了，其它 4xx 重试也没用
+pub fn is_transient_status(status: u16) -> bool {
+    status == 408 || status == 429 || (500..600).contains(&status)
+}
+
+/// 暂时性失败时按 `policy` 退避重试；`sleep` 负责等待，测试里可以直接返回
+pub async fn with_retries<T, F, Fut, S, SFut>(
+    policy: RetryPolicy,
+    mut fetch: F,
+    mut sleep: S,
+) -> Result<T, String>
+where
+    F: FnMut() -> Fut,
+    Fut: Future<Output = Result<T, FetchError>>,
+    S: FnMut(Duration) -> SFut,
+    SFut: Future<Output = ()>,
+{
+    let mut retry = 0;
+    loop {
+        match fetch().await {
+            Ok(value) => return Ok(value),
+            Err(e) if e.transient && (retry as usize) + 1 < policy.attempts => {
+                sleep(policy.delay(retry, random_jitter())).await;
+                retry += 1;
+            }
+            Err(e) => return Err(e.message),
+        }
+    }
+}
+
+fn random_jitter() -> f32 {
+    let mut hasher = RandomState::new().build_hasher();
+    hasher.write_u32(std::process::id());
+    (hasher.finish() % 1000) as f32 / 1000.
+}
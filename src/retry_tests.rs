@@ -0,0 +1,84 @@
+use crate::reader::BodyError;
+use crate::retry::{self, FetchError, RetryPolicy};
+use gpui::http_client::http::StatusCode;
+use std::cell::Cell;
+use std::time::Duration;
+
+const POLICY: RetryPolicy = RetryPolicy {
+    attempts: 3,
+    base_delay: Duration::from_millis(250),
+};
+
+#[test]
+fn delay_doubles_with_bounded_jitter() {
+    assert_eq!(POLICY.delay(0, 0.), Duration::from_millis(250));
+    assert_eq!(POLICY.delay(1, 0.), Duration::from_millis(500));
+    assert_eq!(POLICY.delay(2, 0.), Duration::from_millis(1000));
+    // 抖动最多再加一半
+    assert_eq!(POLICY.delay(1, 1.), Duration::from_millis(750));
+    assert_eq!(POLICY.delay(1, 7.), Duration::from_millis(750));
+}
+
+#[test]
+fn retries_are_bounded_and_skip_permanent_failures() {
+    let attempts = Cell::new(0);
+    let slept = Cell::new(0);
+    let result: Result<(), String> = futures::executor::block_on(retry::with_retries(
+        POLICY,
+        || {
+            attempts.set(attempts.get() + 1);
+            async { Err(FetchError::transient("HTTP 503")) }
+        },
+        |_| {
+            slept.set(slept.get() + 1);
+            async {}
+        },
+    ));
+    assert_eq!(result, Err("HTTP 503".to_string()));
+    assert_eq!(attempts.get(), POLICY.attempts);
+    assert_eq!(slept.get(), POLICY.attempts - 1);
+
+    // 404 之类重试也没用
+    attempts.set(0);
+    let result: Result<(), String> = futures::executor::block_on(retry::with_retries(
+        POLICY,
+        || {
+            attempts.set(attempts.get() + 1);
+            async {
+                Err(FetchError::from_status(
+                    StatusCode::NOT_FOUND,
+                    "https://a.test",
+                ))
+            }
+        },
+        |_| async {},
+    ));
+    assert_eq!(
+        result,
+        Err("HTTP 404 Not Found for https://a.test".to_string())
+    );
+    assert_eq!(attempts.get(), 1);
+}
+
+#[test]
+fn timeouts_rate_limits_and_server_errors_are_transient() {
+    for status in [408, 429, 500, 503] {
+        assert!(retry::is_transient_status(status), "{status}");
+    }
+    for status in [400, 403, 404, 410] {
+        assert!(!retry::is_transient_status(status), "{status}");
+    }
+    assert!(FetchError::from_status(StatusCode::TOO_MANY_REQUESTS, "https://a.test").transient);
+}
+
+#[test]
+fn oversized_bodies_are_not_retried() {
+    let error = FetchError::from(BodyError::TooLarge {
+        limit: 2 * 1024 * 1024,
+    });
+    assert!(!error.transient);
+    assert_eq!(error.message, "Response too large (>2 MB)");
+
+    // 读到一半断开可以再试
+    assert!(FetchError::from(BodyError::Io("connection reset".to_string())).transient);
+}
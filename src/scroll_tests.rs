@@ -1,12 +1,37 @@
-use crate::{reader, reader_view, theme::Theme};
+use crate::{history::VisitedUrls, images::ImageReloads, reader, reader_view, theme::Theme};
 use gpui::prelude::*;
-use gpui::{div, point, px, size, ScrollDelta, ScrollHandle, ScrollWheelEvent, TestAppContext};
+use gpui::{
+    div, point, px, size, ScrollDelta, ScrollHandle, ScrollWheelEvent, TestAppContext,
+    WindowContext,
+};
+use std::rc::Rc;
+
+fn inline_links(visited: &VisitedUrls) -> reader_view::InlineLinks<'_> {
+    reader_view::InlineLinks {
+        visited,
+        visited_color: Theme::default().link_visited,
+        on_click: Rc::new(|_: &str, _: &mut WindowContext| {}),
+    }
+}
+
+fn reader_images(reloads: &ImageReloads) -> reader_view::ReaderImages<'_> {
+    reader_view::ReaderImages {
+        reloads,
+        on_reload: Rc::new(|_: &str, _: &mut WindowContext| {}),
+        failures: Default::default(),
+        text_only: false,
+    }
+}
 
 #[gpui::test]
 fn code_block_does_not_trap_vertical_scroll(cx: &mut TestAppContext) {
     let cx = cx.add_empty_window();
 
     let theme = Theme::default();
+    let visited = VisitedUrls::default();
+    let links = inline_links(&visited);
+    let reloads = ImageReloads::default();
+    let images = reader_images(&reloads);
     let outer_scroll = ScrollHandle::new();
 
     let code_text = (0..120)
@@ -36,17 +61,22 @@ fn code_block_does_not_trap_vertical_scroll(cx: &mut TestAppContext) {
             .overflow_y_scroll()
             .track_scroll(&outer_scroll)
             .child(
-                div()
-                    .w_full()
-                    .flex()
-                    .flex_col()
-                    .gap_4()
-                    .children(
-                        blocks
-                            .iter()
-                            .map(|block| reader_view::render_reader_block(&theme, block))
-                            .collect::<Vec<_>>(),
-                    ),
+                div().w_full().flex().flex_col().gap_4().children(
+                    blocks
+                        .iter()
+                        .enumerate()
+                        .map(|(i, block)| {
+                            reader_view::render_reader_block(
+                                &theme,
+                                i,
+                                block,
+                                &links,
+                                &images,
+                                Default::default(),
+                            )
+                        })
+                        .collect::<Vec<_>>(),
+                ),
             )
     });
 
@@ -69,6 +99,10 @@ fn reader_nested_flex_layout_allows_scrolling(cx: &mut TestAppContext) {
     let cx = cx.add_empty_window();
 
     let theme = Theme::default();
+    let visited = VisitedUrls::default();
+    let links = inline_links(&visited);
+    let reloads = ImageReloads::default();
+    let images = reader_images(&reloads);
     let scroll = ScrollHandle::new();
 
     let blocks = (0..80)
@@ -118,7 +152,17 @@ fn reader_nested_flex_layout_allows_scrolling(cx: &mut TestAppContext) {
                                         .children(
                                             blocks
                                                 .iter()
-                                                .map(|b| reader_view::render_reader_block(&theme, b))
+                                                .enumerate()
+                                                .map(|(i, b)| {
+                                                    reader_view::render_reader_block(
+                                                        &theme,
+                                                        i,
+                                                        b,
+                                                        &links,
+                                                        &images,
+                                                        Default::default(),
+                                                    )
+                                                })
                                                 .collect::<Vec<_>>(),
                                         ),
                                 ),
@@ -140,3 +184,96 @@ fn reader_nested_flex_layout_allows_scrolling(cx: &mut TestAppContext) {
         "expected nested flex scroll container to scroll"
     );
 }
+
+#[test]
+fn reader_header_collapses_after_scrolling_down_past_threshold() {
+    use reader_view::header_collapsed_after_scroll as collapsed_after;
+
+    // Near the top the header always stays expanded.
+    assert!(!collapsed_after(false, 0.0, 30.0));
+    // Scrolling down past the threshold collapses it.
+    assert!(collapsed_after(false, 30.0, 120.0));
+    // Jitter keeps the current state.
+    assert!(collapsed_after(true, 120.0, 118.0));
+    // Scrolling back up reveals it again.
+    assert!(!collapsed_after(true, 400.0, 300.0));
+    assert!(!collapsed_after(true, 300.0, 10.0));
+
+    assert!(reader_view::READER_HEADER_COLLAPSED_HEIGHT < 40.0);
+}
+
+#[gpui::test]
+fn long_unbroken_token_wraps_within_the_column(cx: &mut TestAppContext) {
+    let cx = cx.add_empty_window();
+
+    let theme = Theme::default();
+    let visited = VisitedUrls::default();
+    let links = inline_links(&visited);
+    let reloads = ImageReloads::default();
+    let images = reader_images(&reloads);
+    let column = ScrollHandle::new();
+
+    let token = "a1b2c3d4e5".repeat(50);
+    assert_eq!(token.len(), 500);
+    let block = reader::ReaderBlock::Paragraph(format!("Checksum: {token} (sha)"));
+    let wrapped = reader_view::break_long_tokens(&token);
+    assert!(wrapped
+        .split('\u{200B}')
+        .all(|piece| piece.chars().count() <= 40));
+
+    cx.draw(point(px(0.), px(0.)), size(px(320.), px(600.)), |_| {
+        div()
+            .id("reader-column")
+            .w(px(320.))
+            .h_full()
+            .overflow_y_scroll()
+            .track_scroll(&column)
+            .child(reader_view::render_reader_block(
+                &theme,
+                0,
+                &block,
+                &links,
+                &images,
+                Default::default(),
+            ))
+    });
+
+    let bounds = column.bounds_for_item(0).expect("paragraph is laid out");
+    assert!(bounds.size.width <= px(320.));
+    assert!(
+        bounds.size.height > px(100.),
+        "expected the token to wrap onto several lines, got {:?}",
+        bounds.size
+    );
+}
+
+#[test]
+fn minimap_places_headings_and_viewport_by_article_height() {
+    let markers = reader_view::minimap_markers(&[(2, 0.0), (7, 1000.0), (12, 5000.0)], 4000.0);
+    assert_eq!(markers, vec![(2, 0.0), (7, 0.25), (12, 1.0)]);
+    assert!(reader_view::minimap_markers(&[(2, 10.0)], 0.0).is_empty());
+
+    assert_eq!(
+        reader_view::minimap_viewport(1000.0, 800.0, 4000.0),
+        (0.25, 0.2)
+    );
+    // Near the bottom the band stops at the end of the track.
+    let (start, length) = reader_view::minimap_viewport(3600.0, 800.0, 4000.0);
+    assert_eq!(start, 0.9);
+    assert!(
+        (start + length - 1.0).abs() < 1e-6,
+        "band overflows: {length}"
+    );
+}
+
+#[test]
+fn reading_progress_runs_from_top_to_the_last_scrollable_pixel() {
+    assert_eq!(reader_view::reading_progress(0.0, 800.0, 4000.0), 0.0);
+    assert_eq!(reader_view::reading_progress(1600.0, 800.0, 4000.0), 0.5);
+    assert_eq!(reader_view::reading_progress(3200.0, 800.0, 4000.0), 1.0);
+    // Overscroll past either end stays in range.
+    assert_eq!(reader_view::reading_progress(-40.0, 800.0, 4000.0), 0.0);
+    assert_eq!(reader_view::reading_progress(3300.0, 800.0, 4000.0), 1.0);
+    // A short article is read as soon as it is shown.
+    assert_eq!(reader_view::reading_progress(0.0, 800.0, 600.0), 1.0);
+}
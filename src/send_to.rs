@@ -0,0 +1,93 @@
+use gpui::http_client::{http, AsyncBody, HttpClient, Method};
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+
+/// 把文章转发到稍后读服务（Instapaper、Pocket 或自建服务）的 webhook
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(default)]
+pub struct SendToIntegration {
+    /// 阅读器顶栏里显示为 “Send to <name>”
+    pub name: String,
+    /// 接收 POST 的地址
+    pub endpoint: String,
+    /// 认证头的名字
+    pub auth_header: String,
+    /// 认证头的值，原样发送（例如 `Bearer xxx`）；为空时不带认证头
+    pub token: String,
+}
+
+impl Default for SendToIntegration {
+    fn default() -> Self {
+        Self {
+            name: "Read Later".to_string(),
+            endpoint: String::new(),
+            auth_header: "Authorization".to_string(),
+            token: String::new(),
+        }
+    }
+}
+
+/// POST 的 JSON 内容
+#[derive(Debug, Serialize, Deserialize, PartialEq)]
+pub struct SendToPayload {
+    pub url: String,
+    pub title: Option<String>,
+}
+
+impl SendToIntegration {
+    pub fn is_configured(&self) -> bool {
+        !self.endpoint.trim().is_empty()
+    }
+
+    pub fn label(&self) -> String {
+        let name = self.name.trim();
+        if name.is_empty() {
+            "Send to…".to_string()
+        } else {
+            format!("Send to {name}")
+        }
+    }
+
+    pub fn build_request(
+        &self,
+        url: &str,
+        title: Option<&str>,
+    ) -> Result<http::Request<AsyncBody>, String> {
+        let endpoint = url::Url::parse(self.endpoint.trim()).map_err(|e| e.to_string())?;
+        if !matches!(endpoint.scheme(), "http" | "https") {
+            return Err(format!("Unsupported endpoint: {endpoint}"));
+        }
+        let payload = SendToPayload {
+            url: url.to_string(),
+            title: title.map(str::to_string),
+        };
+        let body = serde_json::to_vec(&payload).map_err(|e| e.to_string())?;
+
+        let mut request = http::Request::builder()
+            .method(Method::POST)
+            .uri(endpoint.as_str())
+            .header("Content-Type", "application/json")
+            .header("User-Agent", "OneApp/0.1 (GPUI Send To)");
+        let token = self.token.trim();
+        if !token.is_empty() {
+            request = request.header(self.auth_header.trim(), token);
+        }
+        request
+            .body(AsyncBody::from(body))
+            .map_err(|e| e.to_string())
+    }
+}
+
+pub async fn send_article(
+    http_client: Arc<dyn HttpClient>,
+    integration: &SendToIntegration,
+    url: &str,
+    title: Option<&str>,
+) -> Result<(), String> {
+    let request = integration.build_request(url, title)?;
+    let response = http_client.send(request).await.map_err(|e| e.to_string())?;
+    if !response.status().is_success() {
+        return Err(format!("HTTP {}", response.status()));
+    }
+    Ok(())
+}
@@ -0,0 +1,46 @@
+use crate::send_to::{SendToIntegration, SendToPayload};
+use futures::AsyncReadExt as _;
+
+fn integration(token: &str) -> SendToIntegration {
+    SendToIntegration {
+        name: "Later".to_string(),
+        endpoint: " https://later.example.com/api/save ".to_string(),
+        auth_header: "X-Api-Key".to_string(),
+        token: token.to_string(),
+    }
+}
+
+#[test]
+fn send_to_posts_url_and_title_with_the_configured_auth_header() {
+    let request = integration("secret")
+        .build_request("https://example.com/post", Some("A post"))
+        .unwrap();
+
+    assert_eq!(request.method(), "POST");
+    assert_eq!(request.uri(), "https://later.example.com/api/save");
+    assert_eq!(request.headers()["X-Api-Key"], "secret");
+    assert_eq!(request.headers()["Content-Type"], "application/json");
+
+    let mut body = Vec::new();
+    futures::executor::block_on(request.into_body().read_to_end(&mut body)).unwrap();
+    let payload: SendToPayload = serde_json::from_slice(&body).unwrap();
+    assert_eq!(
+        payload,
+        SendToPayload {
+            url: "https://example.com/post".to_string(),
+            title: Some("A post".to_string()),
+        }
+    );
+
+    // 没有 token 时不带认证头
+    let request = integration("")
+        .build_request("https://example.com/post", None)
+        .unwrap();
+    assert!(request.headers().get("X-Api-Key").is_none());
+
+    let unconfigured = SendToIntegration::default();
+    assert!(!unconfigured.is_configured());
+    assert!(unconfigured
+        .build_request("https://example.com", None)
+        .is_err());
+}
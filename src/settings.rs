@@ -0,0 +1,408 @@
+use crate::images::ImageMode;
+use crate::list_width::SplitterReset;
+use crate::models::{CommentLimits, NewsChannel, ReaderSource, StorySort};
+use crate::reader::ReaderLinkTarget;
+use crate::reader_view::{ReaderFont, ReaderTheme, ReaderWidth};
+use crate::send_to::SendToIntegration;
+use chrono::NaiveTime;
+use gpui::{point, px, size, Bounds, Pixels, WindowBounds};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+pub(crate) const SETTINGS_FILE: &str = "settings.json";
+
+/// 持久化的用户设置，存放在数据目录的 `settings.json`
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(default)]
+pub struct Settings {
+    /// 没有保存过窗口状态时的启动尺寸
+    pub startup_window_size: WindowSize,
+    pub window: Option<WindowMode>,
+    /// 打开阅读模式时把键盘焦点移到阅读器
+    pub focus_reader_on_open: bool,
+    /// 来源提供头像时在评论作者旁显示
+    pub show_comment_avatars: bool,
+    pub quiet_hours: QuietHours,
+    /// 选中 story 时同时打开文章和评论（左右分栏）
+    pub split_view_on_select: bool,
+    /// 向下滚动文章时收起阅读器顶栏，向上滚动时展开
+    pub collapse_reader_header: bool,
+    /// 覆盖来源默认的评论深度，`None` 时使用来源默认值
+    pub comment_max_depth: Option<usize>,
+    /// 覆盖来源默认的每层评论数
+    pub comment_max_per_level: Option<usize>,
+    /// story 同时有链接和正文时，阅读模式默认打开哪一个
+    pub reader_default_source: ReaderSource,
+    /// 通过 Algolia 一次请求整棵评论树，失败时退回逐条请求
+    pub batch_comment_fetch: bool,
+    /// 重新打开 story 时淡化上次已经看过的评论
+    pub dim_seen_comments: bool,
+    /// 阅读器中已访问链接的颜色（`#RRGGBB`），`None` 时使用主题默认值
+    pub visited_link_color: Option<String>,
+    /// 紧凑顶栏：标题与 traffic lights 放在同一行，不再单独留空
+    pub compact_header: bool,
+    /// 置顶到侧边栏顶部的频道（`NewsChannel::key`），按显示顺序保存
+    pub pinned_channels: Vec<String>,
+    /// 用户为各频道选过的排序（`NewsChannel::key` -> 排序），没选过的用频道默认值
+    pub channel_sorts: BTreeMap<String, StorySort>,
+    /// 额外信任的 CA 证书（PEM）路径，用于会拦截 TLS 的企业网络；
+    /// 只有显式设置时才生效，环境变量 `ONEAPP_EXTRA_CA_CERT` 优先
+    pub extra_ca_certificate: Option<String>,
+    /// 折叠标签在直接回复数后附上整个子树的回复总数，如 `▸ 3 (12)`
+    pub show_total_reply_count: bool,
+    /// 滚动到距离已加载列表末尾还剩多少行时预取下一页
+    pub story_prefetch_rows: usize,
+    /// 减少动态效果（关闭加载骨架屏的呼吸等动画），`None` 时跟随系统设置
+    pub reduce_motion: Option<bool>,
+    /// 在阅读模式标题上方显示文章头图（`og:image` 或正文开头的图片）
+    pub show_hero_image: bool,
+    /// 阅读模式里横图的最大高度（px），尺寸未知的图片也用它
+    pub image_max_height_landscape: f32,
+    /// 竖图（截图、长图）的最大高度（px）
+    pub image_max_height_portrait: f32,
+    /// 长文右侧显示标题位置的大纲小地图
+    pub show_reader_minimap: bool,
+    /// 打开 story 时用最新的分数和评论数更新列表里的条目（缓存的列表可能已过时）
+    pub refresh_story_on_open: bool,
+    /// 悬停列表行时预览前几条评论（每个 story 会多发几个请求，默认关闭）
+    pub comment_preview_on_hover: bool,
+    /// 开发调试工具，例如查看 story/评论的原始 JSON
+    pub debug_tools: bool,
+    /// 评论用阅读模式的排版（居中、较宽行距）显示，而不是卡片样式
+    pub clean_comment_layout: bool,
+    /// 跟随系统的浅色/深色外观；手动切换后本次运行不再跟随
+    pub follow_system_appearance: bool,
+    /// 列表里滚出顶部的 story 也标记为已读（像 RSS 阅读器那样），默认只在选中时标记
+    pub mark_read_on_scroll_past: bool,
+    /// 使用导入的自定义主题（数据目录下的 `custom_theme.json`），文件无效时退回内置主题
+    pub custom_theme: bool,
+    /// 阅读模式的字号倍数（0.8–1.6），阅读器顶部的 A−/A+ 调整
+    pub reader_font_scale: f32,
+    /// 双击列表和详情之间的分隔条时恢复到默认宽度、记住的宽度，或者隐藏列表
+    pub story_list_reset: SplitterReset,
+    /// 上次拖动分隔条得到的列表宽度，启动时沿用
+    pub story_list_preferred_width: Option<f32>,
+    /// 评论里直接贴出的链接下方显示标题预览卡片（每个链接要多请求一次页面，默认关闭）
+    pub comment_link_unfurls: bool,
+    /// 阅读模式显示图片，还是只显示图片的说明文字
+    pub reader_image_mode: ImageMode,
+    /// 阅读模式正文的字体
+    pub reader_font: ReaderFont,
+    /// 阅读模式正文栏的宽度
+    pub reader_width: ReaderWidth,
+    /// 阅读模式正文的配色：跟随界面主题，或暖色的 Sepia
+    pub reader_theme: ReaderTheme,
+    /// 阅读器顶栏 “Send to…” 转发文章用的稍后读服务，未设置时不显示
+    pub send_to: Option<SendToIntegration>,
+    /// 文章里的链接在当前标签替换（← Back 返回原文）、新阅读器标签，还是浏览器中打开
+    pub reader_link_target: ReaderLinkTarget,
+    /// 窗口在前台时每隔多少分钟在后台刷新当前频道，`None` 时不自动刷新
+    pub auto_refresh_minutes: Option<u64>,
+    /// Feeds 频道订阅的 RSS/Atom 地址，按添加顺序
+    pub feeds: Vec<String>,
+}
+
+impl Default for Settings {
+    fn default() -> Self {
+        Self {
+            startup_window_size: WindowSize {
+                width: 1200.0,
+                height: 800.0,
+            },
+            window: None,
+            focus_reader_on_open: true,
+            show_comment_avatars: true,
+            quiet_hours: QuietHours::default(),
+            split_view_on_select: false,
+            collapse_reader_header: false,
+            comment_max_depth: None,
+            comment_max_per_level: None,
+            reader_default_source: ReaderSource::Link,
+            batch_comment_fetch: true,
+            dim_seen_comments: false,
+            visited_link_color: None,
+            compact_header: false,
+            pinned_channels: Vec::new(),
+            channel_sorts: BTreeMap::new(),
+            extra_ca_certificate: None,
+            show_total_reply_count: false,
+            story_prefetch_rows: 10,
+            reduce_motion: None,
+            show_hero_image: true,
+            image_max_height_landscape: 520.0,
+            image_max_height_portrait: 820.0,
+            show_reader_minimap: true,
+            refresh_story_on_open: false,
+            comment_preview_on_hover: false,
+            debug_tools: false,
+            clean_comment_layout: false,
+            follow_system_appearance: true,
+            mark_read_on_scroll_past: false,
+            custom_theme: false,
+            reader_font_scale: 1.0,
+            story_list_reset: SplitterReset::Default,
+            story_list_preferred_width: None,
+            comment_link_unfurls: false,
+            reader_image_mode: ImageMode::Images,
+            reader_font: ReaderFont::Sans,
+            reader_width: ReaderWidth::Normal,
+            reader_theme: ReaderTheme::App,
+            send_to: None,
+            reader_link_target: ReaderLinkTarget::Replace,
+            auto_refresh_minutes: None,
+            feeds: Vec::new(),
+        }
+    }
+}
+
+impl Settings {
+    pub fn load(dir: &Path) -> Self {
+        std::fs::read(settings_path(dir))
+            .ok()
+            .and_then(|bytes| serde_json::from_slice(&bytes).ok())
+            .unwrap_or_default()
+    }
+
+    pub fn save(&self, dir: &Path) -> Result<(), String> {
+        let path = settings_path(dir);
+        let json = serde_json::to_vec_pretty(self).map_err(|e| e.to_string())?;
+        write_atomic(&path, &json)
+    }
+
+    /// 来源默认的评论上限叠加用户覆盖
+    pub fn comment_limits(&self, defaults: CommentLimits) -> CommentLimits {
+        defaults.with_overrides(self.comment_max_depth, self.comment_max_per_level)
+    }
+
+    /// 置顶的频道，忽略已经不存在或重复的条目
+    pub fn pinned_channels(&self) -> Vec<NewsChannel> {
+        let mut pinned = Vec::new();
+        for channel in self
+            .pinned_channels
+            .iter()
+            .filter_map(|key| NewsChannel::from_key(key))
+        {
+            if !pinned.contains(&channel) {
+                pinned.push(channel);
+            }
+        }
+        pinned
+    }
+
+    /// 侧边栏顺序：置顶频道在前，其余按 `NewsChannel::all()` 的顺序
+    pub fn channel_order(&self) -> Vec<NewsChannel> {
+        let pinned = self.pinned_channels();
+        let rest = NewsChannel::all()
+            .iter()
+            .copied()
+            .filter(|channel| !pinned.contains(channel));
+        pinned.iter().copied().chain(rest).collect()
+    }
+
+    pub fn is_pinned(&self, channel: NewsChannel) -> bool {
+        self.pinned_channels().contains(&channel)
+    }
+
+    /// 置顶或取消置顶，新置顶的频道放在最后
+    pub fn toggle_pinned(&mut self, channel: NewsChannel) {
+        let mut pinned = self.pinned_channels();
+        match pinned.iter().position(|&c| c == channel) {
+            Some(index) => {
+                pinned.remove(index);
+            }
+            None => pinned.push(channel),
+        }
+        self.set_pinned(pinned);
+    }
+
+    /// 在置顶频道中上移（负数）或下移，返回是否有变化
+    pub fn move_pinned(&mut self, channel: NewsChannel, offset: isize) -> bool {
+        let mut pinned = self.pinned_channels();
+        let Some(index) = pinned.iter().position(|&c| c == channel) else {
+            return false;
+        };
+        let target = index.saturating_add_signed(offset).min(pinned.len() - 1);
+        if target == index {
+            return false;
+        }
+        let channel = pinned.remove(index);
+        pinned.insert(target, channel);
+        self.set_pinned(pinned);
+        true
+    }
+
+    pub fn story_sort(&self, channel: NewsChannel) -> StorySort {
+        self.channel_sorts
+            .get(channel.key())
+            .copied()
+            .unwrap_or_else(|| channel.default_sort())
+    }
+
+    pub fn set_story_sort(&mut self, channel: NewsChannel, sort: StorySort) {
+        self.channel_sorts.insert(channel.key().to_string(), sort);
+    }
+
+    fn set_pinned(&mut self, pinned: Vec<NewsChannel>) {
+        self.pinned_channels = pinned
+            .into_iter()
+            .map(|channel| channel.key().to_string())
+            .collect();
+    }
+}
+
+pub(crate) fn settings_path(dir: &Path) -> PathBuf {
+    dir.join(SETTINGS_FILE)
+}
+
+pub(crate) fn write_atomic(path: &std::path::Path, bytes: &[u8]) -> Result<(), String> {
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent).map_err(|e| e.to_string())?;
+    }
+
+    let tmp_path = path.with_extension("json.tmp");
+    std::fs::write(&tmp_path, bytes).map_err(|e| e.to_string())?;
+    if let Err(error) = std::fs::rename(&tmp_path, path) {
+        let _ = std::fs::remove_file(path);
+        std::fs::rename(&tmp_path, path).map_err(|_| error.to_string())?;
+    }
+    Ok(())
+}
+
+/// 定时切换到深色主题的时间段（本地时间，`HH:MM`）
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(default)]
+pub struct QuietHours {
+    pub enabled: bool,
+    pub start: String,
+    pub end: String,
+}
+
+impl Default for QuietHours {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            start: "22:00".to_string(),
+            end: "07:00".to_string(),
+        }
+    }
+}
+
+impl QuietHours {
+    pub fn is_active_at(&self, now: NaiveTime) -> bool {
+        if !self.enabled {
+            return false;
+        }
+        let (Some(start), Some(end)) = (parse_clock_time(&self.start), parse_clock_time(&self.end))
+        else {
+            return false;
+        };
+        is_within_quiet_hours(now, start, end)
+    }
+
+    pub fn is_active_now(&self) -> bool {
+        self.is_active_at(chrono::Local::now().time())
+    }
+}
+
+fn parse_clock_time(value: &str) -> Option<NaiveTime> {
+    NaiveTime::parse_from_str(value.trim(), "%H:%M").ok()
+}
+
+/// `[start, end)` 时间窗，`start > end` 时跨越午夜
+pub fn is_within_quiet_hours(now: NaiveTime, start: NaiveTime, end: NaiveTime) -> bool {
+    if start == end {
+        false
+    } else if start < end {
+        start <= now && now < end
+    } else {
+        now >= start || now < end
+    }
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+pub struct WindowSize {
+    pub width: f32,
+    pub height: f32,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+pub struct SavedBounds {
+    pub x: f32,
+    pub y: f32,
+    pub width: f32,
+    pub height: f32,
+}
+
+impl SavedBounds {
+    fn from_bounds(bounds: Bounds<Pixels>) -> Self {
+        Self {
+            x: bounds.origin.x.0,
+            y: bounds.origin.y.0,
+            width: bounds.size.width.0,
+            height: bounds.size.height.0,
+        }
+    }
+
+    fn to_bounds(self) -> Bounds<Pixels> {
+        Bounds::new(
+            point(px(self.x), px(self.y)),
+            size(px(self.width), px(self.height)),
+        )
+    }
+}
+
+/// 窗口模式：普通窗口记录位置尺寸，最大化时记录还原尺寸
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+#[serde(tag = "mode", rename_all = "snake_case")]
+pub enum WindowMode {
+    Windowed { bounds: SavedBounds },
+    Maximized { restore: SavedBounds },
+}
+
+impl WindowMode {
+    /// 全屏按最大化处理，重新启动时不直接进入全屏
+    pub fn from_window_bounds(bounds: WindowBounds) -> Self {
+        match bounds {
+            WindowBounds::Windowed(b) => WindowMode::Windowed {
+                bounds: SavedBounds::from_bounds(b),
+            },
+            WindowBounds::Maximized(b) | WindowBounds::Fullscreen(b) => WindowMode::Maximized {
+                restore: SavedBounds::from_bounds(b),
+            },
+        }
+    }
+
+    fn saved_bounds(&self) -> SavedBounds {
+        match self {
+            WindowMode::Windowed { bounds } => *bounds,
+            WindowMode::Maximized { restore } => *restore,
+        }
+    }
+}
+
+/// 根据保存的窗口模式决定启动时的 `WindowBounds`；
+/// 保存的位置不在任何显示器上（例如外接屏已拔掉）时回退到默认尺寸
+pub fn initial_window_bounds(
+    saved: Option<WindowMode>,
+    displays: &[Bounds<Pixels>],
+    default_bounds: Bounds<Pixels>,
+) -> WindowBounds {
+    let Some(mode) = saved else {
+        return WindowBounds::Windowed(default_bounds);
+    };
+
+    let saved_bounds = mode.saved_bounds();
+    if saved_bounds.width < 200.0 || saved_bounds.height < 200.0 {
+        return WindowBounds::Windowed(default_bounds);
+    }
+
+    let bounds = saved_bounds.to_bounds();
+    let on_screen = displays.is_empty() || displays.iter().any(|d| d.intersects(&bounds));
+    let restore = if on_screen { bounds } else { default_bounds };
+
+    match mode {
+        WindowMode::Windowed { .. } => WindowBounds::Windowed(restore),
+        WindowMode::Maximized { .. } => WindowBounds::Maximized(restore),
+    }
+}
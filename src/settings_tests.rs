@@ -0,0 +1,201 @@
+use crate::models::{NewsChannel, StorySort};
+use crate::reader_view;
+use crate::settings::{self, SavedBounds, Settings, WindowMode};
+use gpui::{point, px, size, Bounds, WindowBounds};
+
+fn display() -> Bounds<gpui::Pixels> {
+    Bounds::new(point(px(0.), px(0.)), size(px(1920.), px(1080.)))
+}
+
+fn default_bounds() -> Bounds<gpui::Pixels> {
+    Bounds::new(point(px(360.), px(140.)), size(px(1200.), px(800.)))
+}
+
+#[test]
+fn window_mode_round_trips_through_json() {
+    let mode = WindowMode::Maximized {
+        restore: SavedBounds {
+            x: 10.0,
+            y: 20.0,
+            width: 900.0,
+            height: 700.0,
+        },
+    };
+    let json = serde_json::to_string(&mode).unwrap();
+    assert!(json.contains("\"mode\":\"maximized\""));
+    assert_eq!(serde_json::from_str::<WindowMode>(&json).unwrap(), mode);
+
+    let settings = Settings {
+        window: Some(mode),
+        ..Settings::default()
+    };
+    let json = serde_json::to_string(&settings).unwrap();
+    assert_eq!(serde_json::from_str::<Settings>(&json).unwrap(), settings);
+    assert_eq!(
+        serde_json::from_str::<Settings>("{}").unwrap(),
+        Settings::default()
+    );
+}
+
+#[test]
+fn initial_window_bounds_restores_mode_and_validates_displays() {
+    let saved = SavedBounds {
+        x: 100.0,
+        y: 80.0,
+        width: 1000.0,
+        height: 700.0,
+    };
+    let expected = Bounds::new(point(px(100.), px(80.)), size(px(1000.), px(700.)));
+
+    assert_eq!(
+        settings::initial_window_bounds(None, &[display()], default_bounds()),
+        WindowBounds::Windowed(default_bounds())
+    );
+    assert_eq!(
+        settings::initial_window_bounds(
+            Some(WindowMode::Maximized { restore: saved }),
+            &[display()],
+            default_bounds()
+        ),
+        WindowBounds::Maximized(expected)
+    );
+    assert_eq!(
+        settings::initial_window_bounds(
+            Some(WindowMode::Windowed { bounds: saved }),
+            &[display()],
+            default_bounds()
+        ),
+        WindowBounds::Windowed(expected)
+    );
+
+    let off_screen = SavedBounds { x: 5000.0, ..saved };
+    assert_eq!(
+        settings::initial_window_bounds(
+            Some(WindowMode::Maximized {
+                restore: off_screen
+            }),
+            &[display()],
+            default_bounds()
+        ),
+        WindowBounds::Maximized(default_bounds())
+    );
+}
+
+#[test]
+fn quiet_hours_window_handles_wrap_around_midnight() {
+    let t = |h, m| chrono::NaiveTime::from_hms_opt(h, m, 0).unwrap();
+
+    // 跨午夜：22:00 - 07:00
+    assert!(settings::is_within_quiet_hours(
+        t(23, 30),
+        t(22, 0),
+        t(7, 0)
+    ));
+    assert!(settings::is_within_quiet_hours(t(2, 0), t(22, 0), t(7, 0)));
+    assert!(settings::is_within_quiet_hours(t(22, 0), t(22, 0), t(7, 0)));
+    assert!(!settings::is_within_quiet_hours(t(7, 0), t(22, 0), t(7, 0)));
+    assert!(!settings::is_within_quiet_hours(
+        t(12, 0),
+        t(22, 0),
+        t(7, 0)
+    ));
+
+    // 同一天内：13:00 - 15:00
+    assert!(settings::is_within_quiet_hours(
+        t(14, 0),
+        t(13, 0),
+        t(15, 0)
+    ));
+    assert!(!settings::is_within_quiet_hours(
+        t(16, 0),
+        t(13, 0),
+        t(15, 0)
+    ));
+
+    // 起止相同视为关闭
+    assert!(!settings::is_within_quiet_hours(t(8, 0), t(8, 0), t(8, 0)));
+
+    let quiet = settings::QuietHours {
+        enabled: true,
+        ..Default::default()
+    };
+    assert!(quiet.is_active_at(t(23, 0)));
+    assert!(!settings::QuietHours::default().is_active_at(t(23, 0)));
+}
+
+#[test]
+fn pinned_channels_drop_removed_entries_and_persist_order() {
+    let mut settings = Settings {
+        pinned_channels: vec![
+            "removed_feed".to_string(),
+            "hacker_news".to_string(),
+            "hacker_news".to_string(),
+        ],
+        ..Settings::default()
+    };
+    assert_eq!(settings.pinned_channels(), vec![NewsChannel::HackerNews]);
+    assert_eq!(settings.channel_order(), NewsChannel::all().to_vec());
+    // 只有一个置顶频道时无处可移
+    assert!(!settings.move_pinned(NewsChannel::HackerNews, -1));
+
+    settings.toggle_pinned(NewsChannel::HackerNews);
+    assert!(!settings.is_pinned(NewsChannel::HackerNews));
+    assert!(settings.pinned_channels.is_empty());
+
+    settings.toggle_pinned(NewsChannel::HackerNews);
+    let json = serde_json::to_string(&settings).unwrap();
+    let restored = serde_json::from_str::<Settings>(&json).unwrap();
+    assert_eq!(restored.pinned_channels, vec!["hacker_news".to_string()]);
+    assert_eq!(restored.channel_order()[0], NewsChannel::HackerNews);
+}
+
+#[test]
+fn story_sort_is_remembered_per_channel() {
+    let mut settings = Settings::default();
+    // 没选过时用频道自己的默认值
+    assert_eq!(
+        settings.story_sort(NewsChannel::HackerNews),
+        StorySort::Score
+    );
+    assert_eq!(settings.story_sort(NewsChannel::New), StorySort::Newest);
+
+    settings.set_story_sort(NewsChannel::New, StorySort::Comments);
+    assert_eq!(settings.story_sort(NewsChannel::New), StorySort::Comments);
+    assert_eq!(
+        settings.story_sort(NewsChannel::HackerNews),
+        StorySort::Score
+    );
+
+    let json = serde_json::to_string(&settings).unwrap();
+    let restored = serde_json::from_str::<Settings>(&json).unwrap();
+    assert_eq!(restored.story_sort(NewsChannel::New), StorySort::Comments);
+    assert_eq!(
+        restored.channel_sorts.get("hn_new"),
+        Some(&StorySort::Comments)
+    );
+}
+
+#[test]
+fn reader_font_scale_steps_and_stays_in_range() {
+    let mut scale = 1.0;
+    for _ in 0..3 {
+        scale = reader_view::step_font_scale(scale, true);
+    }
+    assert!((scale - 1.3).abs() < 1e-4, "got {scale}");
+
+    for _ in 0..20 {
+        scale = reader_view::step_font_scale(scale, true);
+    }
+    assert_eq!(scale, reader_view::READER_FONT_SCALE_MAX);
+    for _ in 0..20 {
+        scale = reader_view::step_font_scale(scale, false);
+    }
+    assert_eq!(scale, reader_view::READER_FONT_SCALE_MIN);
+
+    // 手改的设置也会被拉回范围内
+    assert_eq!(
+        reader_view::clamp_font_scale(5.0),
+        reader_view::READER_FONT_SCALE_MAX
+    );
+    assert_eq!(reader_view::clamp_font_scale(f32::NAN), 1.0);
+}
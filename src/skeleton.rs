@@ -0,0 +1,39 @@
+/// 窗口很矮时也至少画几条
+const MIN_SKELETON_ITEMS: usize = 3;
+/// 超大显示器上也不生成太多元素
+const MAX_SKELETON_ITEMS: usize = 40;
+
+/// 一种骨架屏的尺寸估计（px）
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SkeletonLayout {
+    /// 单个占位项的高度，包括间距和分隔线
+    pub item_height: f32,
+    /// 占位项上方的标题栏、提示文字和留白
+    pub reserved_height: f32,
+}
+
+/// 列表的 story 行：标题和元信息两条，上下 `py_3`
+pub const STORY_ROWS: SkeletonLayout = SkeletonLayout {
+    item_height: 57.0,
+    reserved_height: 150.0,
+};
+
+/// 阅读模式的正文行：12px 的条加 `gap_3`
+pub const READER_LINES: SkeletonLayout = SkeletonLayout {
+    item_height: 24.0,
+    reserved_height: 260.0,
+};
+
+/// 评论卡片：三条加内边距和 `gap_2`
+pub const COMMENT_CARDS: SkeletonLayout = SkeletonLayout {
+    item_height: 70.0,
+    reserved_height: 320.0,
+};
+
+/// 加载骨架屏填满视口剩余高度需要的占位项数，最后一项可以只露出一部分
+#[must_use]
+pub fn skeleton_count(viewport_height: f32, layout: SkeletonLayout) -> usize {
+    let available = (viewport_height - layout.reserved_height).max(0.0);
+    let count = (available / layout.item_height).ceil() as usize;
+    count.clamp(MIN_SKELETON_ITEMS, MAX_SKELETON_ITEMS)
+}
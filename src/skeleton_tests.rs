@@ -0,0 +1,21 @@
+use crate::skeleton::{self, SkeletonLayout};
+
+#[test]
+fn skeleton_count_fills_the_viewport_within_bounds() {
+    let layout = SkeletonLayout {
+        item_height: 50.0,
+        reserved_height: 100.0,
+    };
+
+    assert_eq!(skeleton::skeleton_count(600.0, layout), 10);
+    // 最后一行只露出一部分也画上
+    assert_eq!(skeleton::skeleton_count(620.0, layout), 11);
+    // 窗口很矮时至少有几条
+    assert_eq!(skeleton::skeleton_count(120.0, layout), 3);
+    assert_eq!(skeleton::skeleton_count(0.0, layout), 3);
+    // 超高的窗口也有上限
+    assert_eq!(skeleton::skeleton_count(100_000.0, layout), 40);
+
+    // 默认窗口（800px 高）和以前固定的条数差不多
+    assert_eq!(skeleton::skeleton_count(800.0, skeleton::STORY_ROWS), 12);
+}
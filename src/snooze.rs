@@ -0,0 +1,103 @@
+use chrono::{DateTime, Days, TimeZone};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+pub(crate) const SNOOZED_FILE: &str = "snoozed.json";
+pub const SNOOZE_HOURS: i64 = 3;
+
+/// 暂时从列表中移除的 story，到期后重新出现
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+pub struct SnoozedStories {
+    entries: HashMap<i64, SnoozedStory>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct SnoozedStory {
+    pub title: String,
+    /// 到期时间（unix 秒）
+    pub until: i64,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SnoozeDuration {
+    Hours(i64),
+    UntilTomorrow,
+}
+
+impl SnoozeDuration {
+    pub fn label(&self) -> String {
+        match self {
+            SnoozeDuration::Hours(hours) => format!("Snooze {hours}h"),
+            SnoozeDuration::UntilTomorrow => "Snooze until tomorrow".to_string(),
+        }
+    }
+
+    pub fn expires_at<Tz: TimeZone>(&self, now: &DateTime<Tz>) -> i64 {
+        match self {
+            SnoozeDuration::Hours(hours) => now.timestamp() + hours * 3600,
+            SnoozeDuration::UntilTomorrow => next_day_start(now),
+        }
+    }
+}
+
+/// 本地时区下一天 0 点
+pub fn next_day_start<Tz: TimeZone>(now: &DateTime<Tz>) -> i64 {
+    now.date_naive()
+        .checked_add_days(Days::new(1))
+        .and_then(|day| day.and_hms_opt(0, 0, 0))
+        .and_then(|midnight| now.timezone().from_local_datetime(&midnight).earliest())
+        .map_or_else(|| now.timestamp() + 24 * 3600, |t| t.timestamp())
+}
+
+impl SnoozedStories {
+    /// 读取时顺便清理已经到期的条目
+    pub fn load(dir: &Path, now: i64) -> Self {
+        let mut snoozed: Self = std::fs::read(snoozed_path(dir))
+            .ok()
+            .and_then(|bytes| serde_json::from_slice(&bytes).ok())
+            .unwrap_or_default();
+        snoozed.prune_expired(now);
+        snoozed
+    }
+
+    pub fn save(&self, dir: &Path) -> Result<(), String> {
+        let path = snoozed_path(dir);
+        let json = serde_json::to_vec_pretty(self).map_err(|e| e.to_string())?;
+        crate::settings::write_atomic(&path, &json)
+    }
+
+    pub fn snooze(&mut self, story_id: i64, title: String, until: i64) {
+        self.entries.insert(story_id, SnoozedStory { title, until });
+    }
+
+    pub fn unsnooze(&mut self, story_id: i64) -> bool {
+        self.entries.remove(&story_id).is_some()
+    }
+
+    pub fn is_snoozed(&self, story_id: i64, now: i64) -> bool {
+        self.entries
+            .get(&story_id)
+            .is_some_and(|entry| entry.until > now)
+    }
+
+    /// 仍在生效的条目，最早到期的在前
+    pub fn active(&self, now: i64) -> Vec<(i64, &SnoozedStory)> {
+        let mut active = self
+            .entries
+            .iter()
+            .filter(|(_, entry)| entry.until > now)
+            .map(|(&id, entry)| (id, entry))
+            .collect::<Vec<_>>();
+        active.sort_by_key(|(id, entry)| (entry.until, *id));
+        active
+    }
+
+    pub fn prune_expired(&mut self, now: i64) {
+        self.entries.retain(|_, entry| entry.until > now);
+    }
+}
+
+fn snoozed_path(dir: &Path) -> PathBuf {
+    dir.join(SNOOZED_FILE)
+}
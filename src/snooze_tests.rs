@@ -0,0 +1,45 @@
+use crate::snooze::{next_day_start, SnoozeDuration, SnoozedStories};
+use chrono::{FixedOffset, TimeZone};
+
+#[test]
+fn snoozed_story_is_hidden_until_expiry() {
+    let mut snoozed = SnoozedStories::default();
+    snoozed.snooze(1, "Later".to_string(), 1_000);
+
+    assert!(snoozed.is_snoozed(1, 999));
+    assert!(!snoozed.is_snoozed(2, 999));
+    assert_eq!(snoozed.active(999).len(), 1);
+
+    // 到期后重新出现
+    assert!(!snoozed.is_snoozed(1, 1_000));
+    assert!(snoozed.active(1_000).is_empty());
+
+    snoozed.prune_expired(1_000);
+    assert_eq!(snoozed, SnoozedStories::default());
+}
+
+#[test]
+fn unsnooze_restores_story_immediately() {
+    let mut snoozed = SnoozedStories::default();
+    snoozed.snooze(1, "Later".to_string(), 5_000);
+    assert!(snoozed.unsnooze(1));
+    assert!(!snoozed.is_snoozed(1, 0));
+    assert!(!snoozed.unsnooze(1));
+}
+
+#[test]
+fn snooze_durations_expire_after_hours_or_at_next_local_midnight() {
+    let tz = FixedOffset::east_opt(8 * 3600).unwrap();
+    let now = tz.with_ymd_and_hms(2024, 3, 10, 22, 30, 0).unwrap();
+    let midnight = tz.with_ymd_and_hms(2024, 3, 11, 0, 0, 0).unwrap();
+
+    assert_eq!(next_day_start(&now), midnight.timestamp());
+    assert_eq!(
+        SnoozeDuration::UntilTomorrow.expires_at(&now),
+        midnight.timestamp()
+    );
+    assert_eq!(
+        SnoozeDuration::Hours(3).expires_at(&now),
+        now.timestamp() + 3 * 3600
+    );
+}
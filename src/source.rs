@@ -0,0 +1,65 @@
+use crate::models::{Comment, NewsChannel, PollOption, Story, StorySummary};
+use futures::future::LocalBoxFuture;
+use std::collections::HashSet;
+
+/// 界面用到的全部数据来源操作。`AppState` 按频道选用 `Arc<dyn NewsSource>`，
+/// 新的来源实现这个 trait 即可，不用改动列表和评论的加载逻辑。
+///
+/// 返回的 future 在前台任务里等待，不要求 `Send`
+pub trait NewsSource {
+    /// 频道榜单上全部 story 的 id，按排名顺序
+    fn fetch_story_ids(&self, channel: NewsChannel)
+        -> LocalBoxFuture<'_, Result<Vec<i64>, String>>;
+
+    /// 一页 story 的摘要，取不到的条目跳过
+    fn fetch_summaries<'a>(
+        &'a self,
+        channel: NewsChannel,
+        ids: &'a [i64],
+    ) -> LocalBoxFuture<'a, Vec<StorySummary>>;
+
+    /// 全文搜索 story，按相关度返回前 `limit` 条
+    fn search_stories<'a>(
+        &'a self,
+        query: &'a str,
+        limit: usize,
+    ) -> LocalBoxFuture<'a, Result<Vec<Story>, String>>;
+
+    /// 选中时获取完整的 story（含 `kids` 和正文）
+    fn fetch_story(&self, id: i64) -> LocalBoxFuture<'_, Result<Story, String>>;
+
+    /// 悬停预览用的前 `count` 条顶级评论
+    fn fetch_comment_preview(
+        &self,
+        story_id: i64,
+        count: usize,
+    ) -> LocalBoxFuture<'_, Result<Vec<Comment>, String>>;
+
+    /// 投票帖的选项，不是投票帖时为空
+    fn fetch_poll_options<'a>(&'a self, story: &'a Story) -> LocalBoxFuture<'a, Vec<PollOption>>;
+
+    /// 打开 story 时的第一批评论，按深度优先排好
+    fn fetch_comments<'a>(
+        &'a self,
+        story: &'a Story,
+    ) -> LocalBoxFuture<'a, Result<Vec<Comment>, String>>;
+
+    /// `fetch_comments` 会加载的顶级评论
+    fn initial_root_ids(&self, kids: &[i64]) -> Vec<i64>;
+
+    /// 还没加载的下一批顶级评论
+    fn next_root_ids(&self, kids: &[i64], loaded: &HashSet<i64>) -> Vec<i64>;
+
+    /// 一批顶级评论及其回复，可以直接接在已有评论后面
+    fn fetch_more_comments<'a>(&'a self, root_ids: &'a [i64]) -> LocalBoxFuture<'a, Vec<Comment>>;
+
+    /// 评论的全部直接回复，深度接在 `existing_depth` 后面
+    fn fetch_replies(
+        &self,
+        parent_id: i64,
+        existing_depth: usize,
+    ) -> LocalBoxFuture<'_, Result<Vec<Comment>, String>>;
+
+    /// 调试用：条目的原始 JSON
+    fn fetch_item_raw(&self, id: i64) -> LocalBoxFuture<'_, Option<serde_json::Value>>;
+}
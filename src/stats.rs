@@ -0,0 +1,121 @@
+use chrono::{Days, NaiveDate};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+use std::path::{Path, PathBuf};
+
+pub(crate) const STATS_FILE: &str = "reading_stats.json";
+/// 最多保留多少天的统计，超出时丢弃最早的
+pub const MAX_STAT_DAYS: usize = 90;
+/// 统计面板里显示的常读站点数
+const TOP_DOMAINS: usize = 5;
+
+/// 按天记录的阅读统计，键为本地日期 `YYYY-MM-DD`
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+pub struct ReadingStats {
+    days: BTreeMap<String, DayStats>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+#[serde(default)]
+struct DayStats {
+    articles: u32,
+    reading_minutes: u32,
+    stories: u32,
+    comments: u32,
+    domains: HashMap<String, u32>,
+}
+
+/// 统计面板显示的汇总
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct StatsSummary {
+    pub articles_today: u32,
+    pub articles_this_week: u32,
+    pub reading_minutes: u64,
+    pub stories_viewed: u64,
+    pub comments_viewed: u64,
+    pub top_domains: Vec<(String, u32)>,
+}
+
+impl ReadingStats {
+    pub fn load(dir: &Path) -> Self {
+        std::fs::read(stats_path(dir))
+            .ok()
+            .and_then(|bytes| serde_json::from_slice(&bytes).ok())
+            .unwrap_or_default()
+    }
+
+    pub fn save(&self, dir: &Path) -> Result<(), String> {
+        let path = stats_path(dir);
+        let json = serde_json::to_vec(self).map_err(|e| e.to_string())?;
+        crate::settings::write_atomic(&path, &json)
+    }
+
+    pub fn record_article(&mut self, day: NaiveDate, domain: Option<&str>, minutes: usize) {
+        let stats = self.day_mut(day);
+        stats.articles += 1;
+        stats.reading_minutes = stats
+            .reading_minutes
+            .saturating_add(u32::try_from(minutes).unwrap_or(u32::MAX));
+        if let Some(domain) = domain.filter(|d| !d.is_empty()) {
+            *stats.domains.entry(domain.to_string()).or_default() += 1;
+        }
+    }
+
+    pub fn record_story(&mut self, day: NaiveDate, comments: usize) {
+        let stats = self.day_mut(day);
+        stats.stories += 1;
+        stats.comments = stats
+            .comments
+            .saturating_add(u32::try_from(comments).unwrap_or(u32::MAX));
+    }
+
+    /// 今天、最近 7 天（含今天）和全部保留天数的汇总
+    pub fn summary(&self, today: NaiveDate) -> StatsSummary {
+        let week_start = today.checked_sub_days(Days::new(6)).unwrap_or(today);
+        let mut summary = StatsSummary::default();
+        let mut domains: HashMap<&str, u32> = HashMap::new();
+
+        for (key, stats) in &self.days {
+            let Ok(day) = NaiveDate::parse_from_str(key, "%Y-%m-%d") else {
+                continue;
+            };
+            if day == today {
+                summary.articles_today += stats.articles;
+            }
+            if day >= week_start && day <= today {
+                summary.articles_this_week += stats.articles;
+            }
+            summary.reading_minutes += u64::from(stats.reading_minutes);
+            summary.stories_viewed += u64::from(stats.stories);
+            summary.comments_viewed += u64::from(stats.comments);
+            for (domain, count) in &stats.domains {
+                *domains.entry(domain).or_default() += count;
+            }
+        }
+
+        let mut top_domains = domains
+            .into_iter()
+            .map(|(domain, count)| (domain.to_string(), count))
+            .collect::<Vec<_>>();
+        top_domains.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+        top_domains.truncate(TOP_DOMAINS);
+        summary.top_domains = top_domains;
+        summary
+    }
+
+    fn day_mut(&mut self, day: NaiveDate) -> &mut DayStats {
+        let key = day.format("%Y-%m-%d").to_string();
+        if !self.days.contains_key(&key) {
+            self.days.insert(key.clone(), DayStats::default());
+            // 日期字符串按字典序即时间顺序，从最早的开始淘汰
+            while self.days.len() > MAX_STAT_DAYS {
+                self.days.pop_first();
+            }
+        }
+        self.days.entry(key).or_default()
+    }
+}
+
+fn stats_path(dir: &Path) -> PathBuf {
+    dir.join(STATS_FILE)
+}
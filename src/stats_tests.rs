@@ -0,0 +1,54 @@
+use crate::stats::{ReadingStats, MAX_STAT_DAYS};
+use chrono::{Days, NaiveDate};
+
+fn day(y: i32, m: u32, d: u32) -> NaiveDate {
+    NaiveDate::from_ymd_opt(y, m, d).unwrap()
+}
+
+#[test]
+fn summary_splits_today_week_and_older_days() {
+    let today = day(2024, 3, 10);
+    let mut stats = ReadingStats::default();
+
+    stats.record_article(today, Some("example.com"), 4);
+    stats.record_article(today, Some("rust-lang.org"), 6);
+    // 跨过零点：昨天仍属于本周
+    stats.record_article(day(2024, 3, 9), Some("example.com"), 3);
+    // 7 天前不算本周，但计入累计数据
+    stats.record_article(day(2024, 3, 3), Some("example.com"), 10);
+    stats.record_story(today, 12);
+    stats.record_story(day(2024, 3, 1), 30);
+
+    let summary = stats.summary(today);
+    assert_eq!(summary.articles_today, 2);
+    assert_eq!(summary.articles_this_week, 3);
+    assert_eq!(summary.reading_minutes, 23);
+    assert_eq!(summary.stories_viewed, 2);
+    assert_eq!(summary.comments_viewed, 42);
+    assert_eq!(
+        summary.top_domains,
+        vec![
+            ("example.com".to_string(), 3),
+            ("rust-lang.org".to_string(), 1)
+        ]
+    );
+
+    // 第二天打开时，“今天”清零，本周仍包含前一天
+    let summary = stats.summary(day(2024, 3, 11));
+    assert_eq!(summary.articles_today, 0);
+    assert_eq!(summary.articles_this_week, 3);
+}
+
+#[test]
+fn oldest_days_are_dropped_past_the_limit() {
+    let start = day(2024, 1, 1);
+    let mut stats = ReadingStats::default();
+    for offset in 0..=MAX_STAT_DAYS as u64 {
+        stats.record_article(start + Days::new(offset), None, 1);
+    }
+
+    let last = start + Days::new(MAX_STAT_DAYS as u64);
+    let summary = stats.summary(last);
+    assert_eq!(summary.reading_minutes, MAX_STAT_DAYS as u64);
+    assert!(summary.top_domains.is_empty());
+}
@@ -0,0 +1,72 @@
+use crate::models::StorySummary;
+use std::time::Duration;
+
+/// 停止输入这么久之后才发请求
+pub const STORY_SEARCH_DEBOUNCE: Duration = Duration::from_millis(350);
+/// 一次搜索返回的结果数
+pub const STORY_SEARCH_LIMIT: usize = 30;
+
+/// 列表顶部的全文搜索，由当前频道的来源执行（HN 用 Algolia）
+#[derive(Debug, Clone, Default)]
+pub struct StorySearch {
+    pub query: String,
+    /// 每次输入加一，防抖和过期结果都靠它判断
+    generation: usize,
+    /// 最近一次完成的搜索结果，按相关度排序
+    pub results: Option<Vec<StorySummary>>,
+    pub is_searching: bool,
+    pub error: Option<String>,
+}
+
+impl StorySearch {
+    /// 输入框非空时列表显示搜索结果
+    pub fn is_active(&self) -> bool {
+        !self.query.trim().is_empty()
+    }
+
+    /// 更新输入并返回这次输入的代号；清空时恢复频道列表
+    pub fn set_query(&mut self, query: String) -> usize {
+        self.query = query;
+        self.generation = self.generation.wrapping_add(1);
+        self.error = None;
+        if self.is_active() {
+            self.is_searching = true;
+        } else {
+            self.results = None;
+            self.is_searching = false;
+        }
+        self.generation
+    }
+
+    /// 防抖结束或请求返回时，输入是否还没变过
+    pub fn is_current(&self, generation: usize) -> bool {
+        generation == self.generation
+    }
+
+    /// 保存搜索结果，过期的直接丢弃；返回是否采用
+    pub fn finish(&mut self, generation: usize, result: Result<Vec<StorySummary>, String>) -> bool {
+        if !self.is_current(generation) {
+            return false;
+        }
+        self.is_searching = false;
+        match result {
+            Ok(stories) => self.results = Some(stories),
+            Err(e) => {
+                self.results = Some(Vec::new());
+                self.error = Some(e);
+            }
+        }
+        true
+    }
+
+    /// 列表为空时显示的提示
+    pub fn status(&self) -> Option<String> {
+        if let Some(error) = &self.error {
+            return Some(format!("Search failed: {}", error));
+        }
+        match &self.results {
+            Some(results) if results.is_empty() => Some("No stories match".to_string()),
+            _ => None,
+        }
+    }
+}
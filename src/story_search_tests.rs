@@ -0,0 +1,37 @@
+use crate::story_search::StorySearch;
+use crate::test_support::story_summary;
+
+#[test]
+fn stale_search_results_are_dropped() {
+    let mut search = StorySearch::default();
+    let first = search.set_query("rus".to_string());
+    let second = search.set_query("rust".to_string());
+    assert!(search.is_searching);
+
+    // 先发出的请求晚到，不覆盖新输入
+    assert!(!search.finish(first, Ok(vec![story_summary(1)])));
+    assert!(search.results.is_none());
+    assert!(search.finish(second, Ok(vec![story_summary(2)])));
+    assert!(!search.is_searching);
+    assert_eq!(
+        search
+            .results
+            .as_ref()
+            .map(|r| r.iter().map(|s| s.id).collect::<Vec<_>>()),
+        Some(vec![2])
+    );
+}
+
+#[test]
+fn clearing_the_query_restores_the_channel_list() {
+    let mut search = StorySearch::default();
+    let generation = search.set_query("gpui".to_string());
+    search.finish(generation, Err("HTTP 503".to_string()));
+    assert_eq!(search.status().as_deref(), Some("Search failed: HTTP 503"));
+
+    search.set_query("  ".to_string());
+    assert!(!search.is_active());
+    assert!(!search.is_searching);
+    assert!(search.results.is_none());
+    assert_eq!(search.status(), None);
+}
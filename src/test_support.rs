@@ -0,0 +1,41 @@
+use crate::models::StorySummary;
+use gpui::http_client::{AsyncBody, FakeHttpClient, HttpClient, Response};
+use std::collections::HashMap;
+use std::sync::Arc;
+
+/// 测试共用的 story 摘要，其他字段按需用结构体更新语法覆盖
+pub(crate) fn story_summary(id: i64) -> StorySummary {
+    StorySummary {
+        id,
+        title: format!("Story {id}"),
+        url: None,
+        score: 1,
+        by: "op".to_string(),
+        time: 1_700_000_000,
+        descendants: Some(0),
+        story_type: "story".to_string(),
+        has_text: false,
+    }
+}
+
+/// 按请求路径返回固定内容的假 HTTP 客户端，未知路径返回 404
+pub(crate) fn fake_http(routes: &[(&str, &str)]) -> Arc<dyn HttpClient> {
+    let routes: Arc<HashMap<String, String>> = Arc::new(
+        routes
+            .iter()
+            .map(|(path, body)| (path.to_string(), body.to_string()))
+            .collect(),
+    );
+    FakeHttpClient::create(move |req| {
+        let routes = routes.clone();
+        async move {
+            let response = match routes.get(req.uri().path()) {
+                Some(body) => Response::builder()
+                    .status(200)
+                    .body(AsyncBody::from(body.clone())),
+                None => Response::builder().status(404).body(AsyncBody::empty()),
+            };
+            Ok(response.unwrap())
+        }
+    })
+}
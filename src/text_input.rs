@@ -0,0 +1,33 @@
+use gpui::Keystroke;
+
+/// 搜索框、订阅地址这类单行输入框共用的编辑：退格删掉最后一个字符，
+/// 其它可打印的按键追加输入的字符。返回 false 表示这个按键不是文字输入
+/// （快捷键、方向键等），交给调用方处理
+pub fn apply_keystroke(text: &mut String, keystroke: &Keystroke) -> bool {
+    if keystroke.key == "backspace" {
+        text.pop();
+        return true;
+    }
+    let modifiers = &keystroke.modifiers;
+    if modifiers.control || modifiers.alt || modifiers.platform {
+        return false;
+    }
+    match typed_text(keystroke) {
+        Some(typed) => {
+            text.push_str(typed);
+            true
+        }
+        None => false,
+    }
+}
+
+/// 按键输入的字符。`key` 是没按 shift 时的键（`/` 而不是 `?`），所以优先用 `ime_key`
+pub fn typed_text(keystroke: &Keystroke) -> Option<&str> {
+    let typed = match (keystroke.ime_key.as_deref(), keystroke.key.as_str()) {
+        (Some(ime_key), _) => ime_key,
+        (None, "space") => " ",
+        (None, key) if key.chars().count() == 1 => key,
+        _ => return None,
+    };
+    (!typed.is_empty() && !typed.chars().any(char::is_control)).then_some(typed)
+}
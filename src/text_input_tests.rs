@@ -0,0 +1,63 @@
+use crate::text_input;
+use gpui::{Keystroke, Modifiers};
+
+fn keystroke(key: &str, ime_key: Option<&str>, modifiers: Modifiers) -> Keystroke {
+    Keystroke {
+        modifiers,
+        key: key.to_string(),
+        ime_key: ime_key.map(str::to_string),
+    }
+}
+
+#[test]
+fn shifted_characters_come_from_the_ime_key() {
+    let mut url = String::new();
+    let typed = [
+        keystroke("h", Some("h"), Modifiers::default()),
+        keystroke(";", Some(":"), Modifiers::shift()),
+        keystroke("/", Some("/"), Modifiers::default()),
+        keystroke("/", Some("?"), Modifiers::shift()),
+        keystroke("a", Some("A"), Modifiers::shift()),
+        keystroke("=", Some("="), Modifiers::default()),
+    ];
+    for keystroke in &typed {
+        assert!(text_input::apply_keystroke(&mut url, keystroke));
+    }
+    assert_eq!(url, "h:/?A=");
+
+    // 没有 ime_key 时退回按键本身
+    assert!(text_input::apply_keystroke(
+        &mut url,
+        &keystroke("space", None, Modifiers::default())
+    ));
+    assert!(text_input::apply_keystroke(
+        &mut url,
+        &keystroke("1", None, Modifiers::default())
+    ));
+    assert_eq!(url, "h:/?A= 1");
+}
+
+#[test]
+fn shortcuts_and_named_keys_are_not_text() {
+    let mut text = "ab".to_string();
+
+    assert!(!text_input::apply_keystroke(
+        &mut text,
+        &keystroke("v", None, Modifiers::command())
+    ));
+    assert!(!text_input::apply_keystroke(
+        &mut text,
+        &keystroke("left", None, Modifiers::default())
+    ));
+    assert!(!text_input::apply_keystroke(
+        &mut text,
+        &keystroke("enter", Some("\n"), Modifiers::default())
+    ));
+    assert_eq!(text, "ab");
+
+    assert!(text_input::apply_keystroke(
+        &mut text,
+        &keystroke("backspace", None, Modifiers::default())
+    ));
+    assert_eq!(text, "a");
+}
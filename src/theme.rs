@@ -1,21 +1,49 @@
-use gpui::{hsla, Hsla};
+use gpui::{hsla, rgb, Hsla, WindowAppearance};
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
 
+pub(crate) const CUSTOM_THEME_FILE: &str = "custom_theme.json";
+
+/// 导入/导出的主题文件里每个字段都必须有，颜色见 `hsla_json`
 #[allow(dead_code)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct Theme {
+    #[serde(with = "hsla_json")]
     pub bg_primary: Hsla,
+    #[serde(with = "hsla_json")]
     pub bg_secondary: Hsla,
+    #[serde(with = "hsla_json")]
     pub bg_tertiary: Hsla,
+    #[serde(with = "hsla_json")]
     pub bg_hover: Hsla,
+    #[serde(with = "hsla_json")]
     pub bg_selected: Hsla,
+    #[serde(with = "hsla_json")]
     pub text_primary: Hsla,
+    #[serde(with = "hsla_json")]
     pub text_secondary: Hsla,
+    #[serde(with = "hsla_json")]
     pub text_muted: Hsla,
+    #[serde(with = "hsla_json")]
     pub accent: Hsla,
+    #[serde(with = "hsla_json")]
     pub accent_hover: Hsla,
+    #[serde(with = "hsla_json")]
+    pub link_visited: Hsla,
+    /// 评论查找命中的背景色，当前命中用更深的一档
+    #[serde(with = "hsla_json")]
+    pub search_match: Hsla,
+    #[serde(with = "hsla_json")]
+    pub search_match_current: Hsla,
+    #[serde(with = "hsla_json")]
     pub border: Hsla,
+    #[serde(with = "hsla_json")]
     pub border_subtle: Hsla,
+    #[serde(with = "hsla_json")]
     pub success: Hsla,
+    #[serde(with = "hsla_json")]
     pub warning: Hsla,
+    #[serde(with = "hsla_json")]
     pub error: Hsla,
 }
 
@@ -32,6 +60,9 @@ impl Theme {
             text_muted: hsla(0., 0., 0.55, 1.0),
             accent: hsla(24., 1.0, 0.50, 1.0), // HN Orange
             accent_hover: hsla(24., 1.0, 0.45, 1.0),
+            link_visited: hsla(24., 0.35, 0.45, 1.0),
+            search_match: hsla(50., 1.0, 0.50, 0.35),
+            search_match_current: hsla(32., 1.0, 0.50, 0.55),
             border: hsla(0., 0., 0.85, 1.0),
             border_subtle: hsla(0., 0., 0.90, 1.0),
             success: hsla(142., 0.71, 0.45, 1.0),
@@ -41,6 +72,147 @@ impl Theme {
     }
 }
 
+impl Theme {
+    pub fn dark() -> Self {
+        Self {
+            bg_primary: hsla(0., 0., 0.11, 1.0),
+            bg_secondary: hsla(0., 0., 0.14, 1.0),
+            bg_tertiary: hsla(0., 0., 0.20, 1.0),
+            bg_hover: hsla(0., 0., 0.18, 1.0),
+            bg_selected: hsla(24., 0.45, 0.20, 1.0),
+            text_primary: hsla(0., 0., 0.92, 1.0),
+            text_secondary: hsla(0., 0., 0.72, 1.0),
+            text_muted: hsla(0., 0., 0.52, 1.0),
+            accent: hsla(24., 1.0, 0.55, 1.0), // HN Orange, lifted for dark backgrounds
+            accent_hover: hsla(24., 1.0, 0.62, 1.0),
+            link_visited: hsla(24., 0.35, 0.60, 1.0),
+            search_match: hsla(50., 0.90, 0.45, 0.35),
+            search_match_current: hsla(32., 0.90, 0.50, 0.55),
+            border: hsla(0., 0., 0.26, 1.0),
+            border_subtle: hsla(0., 0., 0.20, 1.0),
+            success: hsla(142., 0.60, 0.50, 1.0),
+            warning: hsla(38., 0.90, 0.58, 1.0),
+            error: hsla(0., 0.70, 0.58, 1.0),
+        }
+    }
+
+    /// 阅读区的暖色纸张配色，只用于文章正文
+    pub fn sepia() -> Self {
+        Self {
+            bg_primary: hsla(39., 0.55, 0.92, 1.0),
+            bg_secondary: hsla(38., 0.45, 0.88, 1.0),
+            bg_tertiary: hsla(37., 0.40, 0.84, 1.0),
+            bg_hover: hsla(37., 0.40, 0.86, 1.0),
+            bg_selected: hsla(32., 0.70, 0.85, 1.0),
+            text_primary: hsla(30., 0.35, 0.18, 1.0),
+            text_secondary: hsla(30., 0.25, 0.33, 1.0),
+            text_muted: hsla(30., 0.15, 0.48, 1.0),
+            accent: hsla(20., 0.75, 0.42, 1.0),
+            accent_hover: hsla(20., 0.75, 0.36, 1.0),
+            link_visited: hsla(20., 0.30, 0.40, 1.0),
+            search_match: hsla(50., 1.0, 0.50, 0.35),
+            search_match_current: hsla(32., 1.0, 0.50, 0.55),
+            border: hsla(35., 0.30, 0.76, 1.0),
+            border_subtle: hsla(35., 0.30, 0.82, 1.0),
+            success: hsla(142., 0.55, 0.38, 1.0),
+            warning: hsla(32., 0.85, 0.42, 1.0),
+            error: hsla(0., 0.65, 0.45, 1.0),
+        }
+    }
+
+    pub fn for_mode(dark: bool) -> Self {
+        if dark {
+            Self::dark()
+        } else {
+            Self::light()
+        }
+    }
+
+    pub fn to_json(&self) -> Result<String, String> {
+        serde_json::to_string_pretty(self).map_err(|e| e.to_string())
+    }
+
+    /// 解析主题文件，缺字段或颜色分量越界时报错
+    pub fn from_json(json: &str) -> Result<Self, String> {
+        serde_json::from_str(json).map_err(|e| e.to_string())
+    }
+
+    /// 之前导入的自定义主题，文件不存在或已损坏时返回 `None`（退回内置主题）
+    pub fn load_custom(dir: &Path) -> Option<Self> {
+        let json = std::fs::read_to_string(custom_theme_path(dir)).ok()?;
+        Self::from_json(&json).ok()
+    }
+
+    pub fn save_custom(&self, dir: &Path) -> Result<(), String> {
+        let path = custom_theme_path(dir);
+        crate::settings::write_atomic(&path, self.to_json()?.as_bytes())
+    }
+}
+
+fn custom_theme_path(dir: &Path) -> PathBuf {
+    dir.join(CUSTOM_THEME_FILE)
+}
+
+/// 颜色写成 `{"h": 0.07, "s": 1.0, "l": 0.5, "a": 1.0}`，各分量都在 0–1 之间
+mod hsla_json {
+    use gpui::Hsla;
+    use serde::de::Error;
+    use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+    #[derive(Serialize, Deserialize)]
+    struct Components {
+        h: f32,
+        s: f32,
+        l: f32,
+        a: f32,
+    }
+
+    pub fn serialize<S: Serializer>(color: &Hsla, serializer: S) -> Result<S::Ok, S::Error> {
+        Components {
+            h: color.h,
+            s: color.s,
+            l: color.l,
+            a: color.a,
+        }
+        .serialize(serializer)
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Hsla, D::Error> {
+        let c = Components::deserialize(deserializer)?;
+        for (name, value) in [("h", c.h), ("s", c.s), ("l", c.l), ("a", c.a)] {
+            if !(0.0..=1.0).contains(&value) {
+                return Err(D::Error::custom(format!(
+                    "`{}` = {} is outside 0–1",
+                    name, value
+                )));
+            }
+        }
+        Ok(Hsla {
+            h: c.h,
+            s: c.s,
+            l: c.l,
+            a: c.a,
+        })
+    }
+}
+
+/// 系统外观是否为深色
+pub fn appearance_is_dark(appearance: WindowAppearance) -> bool {
+    matches!(
+        appearance,
+        WindowAppearance::Dark | WindowAppearance::VibrantDark
+    )
+}
+
+/// 解析 `#RRGGBB`（`#` 可省略）
+pub fn parse_hex_color(value: &str) -> Option<Hsla> {
+    let hex = value.trim().trim_start_matches('#');
+    if hex.len() != 6 {
+        return None;
+    }
+    u32::from_str_radix(hex, 16).ok().map(|v| rgb(v).into())
+}
+
 impl Default for Theme {
     fn default() -> Self {
         Self::light()
@@ -0,0 +1,27 @@
+use crate::theme::Theme;
+
+#[test]
+fn theme_round_trips_through_json() {
+    let theme = Theme::dark();
+    let json = theme.to_json().unwrap();
+    let parsed = Theme::from_json(&json).unwrap();
+
+    assert_eq!(parsed, theme);
+    assert_ne!(parsed, Theme::light());
+}
+
+#[test]
+fn malformed_theme_files_are_rejected() {
+    let mut value: serde_json::Value =
+        serde_json::from_str(&Theme::light().to_json().unwrap()).unwrap();
+
+    // 颜色分量越界
+    value["accent"]["l"] = serde_json::json!(1.5);
+    let error = Theme::from_json(&value.to_string()).unwrap_err();
+    assert!(error.contains("outside 0–1"), "{error}");
+
+    // 缺字段
+    value.as_object_mut().unwrap().remove("accent");
+    assert!(Theme::from_json(&value.to_string()).is_err());
+    assert!(Theme::from_json("not json").is_err());
+}
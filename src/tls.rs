@@ -0,0 +1,91 @@
+use reqwest_client::ReqwestClient;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+/// 额外信任的 CA 证书路径，优先于设置项
+pub const EXTRA_CA_ENV: &str = "ONEAPP_EXTRA_CA_CERT";
+
+const CONNECT_TIMEOUT: Duration = Duration::from_secs(10);
+const PEM_CERTIFICATE_HEADER: &str = "-----BEGIN CERTIFICATE-----";
+
+/// 启动时自定义 CA 的状态，用于在界面上显示警告横幅
+#[derive(Debug, Clone, PartialEq)]
+pub enum CustomCa {
+    /// 已加入信任列表，系统根证书仍然有效
+    Active(PathBuf),
+    /// 证书无法使用，退回默认客户端
+    Failed { path: PathBuf, error: String },
+}
+
+impl CustomCa {
+    #[must_use]
+    pub fn message(&self) -> String {
+        match self {
+            CustomCa::Active(path) => {
+                format!("Trusting an extra CA certificate: {}", path.display())
+            }
+            CustomCa::Failed { path, error } => format!(
+                "Custom CA certificate {} was not loaded: {}",
+                path.display(),
+                error
+            ),
+        }
+    }
+
+    #[must_use]
+    pub fn is_active(&self) -> bool {
+        matches!(self, CustomCa::Active(_))
+    }
+}
+
+/// 环境变量优先于设置；两者都为空时不启用
+#[must_use]
+pub fn configured_ca_path(setting: Option<&str>, env: Option<&str>) -> Option<PathBuf> {
+    [env, setting]
+        .into_iter()
+        .flatten()
+        .map(str::trim)
+        .find(|path| !path.is_empty())
+        .map(PathBuf::from)
+}
+
+/// 读取 PEM 文件中的所有证书，文件不存在、不是 PEM 或没有证书时返回错误
+pub fn load_ca_certificates(path: &Path) -> Result<Vec<reqwest::Certificate>, String> {
+    let bytes =
+        std::fs::read(path).map_err(|e| format!("Cannot read {}: {}", path.display(), e))?;
+    let pem = String::from_utf8_lossy(&bytes);
+    if !pem.contains(PEM_CERTIFICATE_HEADER) {
+        return Err(format!("{} is not a PEM certificate", path.display()));
+    }
+    let certificates = reqwest::Certificate::from_pem_bundle(&bytes)
+        .map_err(|e| format!("Invalid certificate in {}: {}", path.display(), e))?;
+    if certificates.is_empty() {
+        return Err(format!("No certificates found in {}", path.display()));
+    }
+    Ok(certificates)
+}
+
+/// 构造 HTTP 客户端；只在显式配置了 CA 路径时额外信任它，从不关闭证书校验
+pub fn build_http_client(ca_path: Option<PathBuf>) -> (ReqwestClient, Option<CustomCa>) {
+    let Some(path) = ca_path else {
+        return (ReqwestClient::new(), None);
+    };
+
+    let client = load_ca_certificates(&path).and_then(|certificates| {
+        certificates
+            .into_iter()
+            .fold(
+                reqwest::Client::builder()
+                    .use_rustls_tls()
+                    .connect_timeout(CONNECT_TIMEOUT),
+                |builder, certificate| builder.add_root_certificate(certificate),
+            )
+            .build()
+            .map_err(|e| e.to_string())
+    });
+
+    match client {
+        Ok(client) => (client.into(), Some(CustomCa::Active(path))),
+        Err(error) => (ReqwestClient::new(), Some(CustomCa::Failed { path, error })),
+    }
+}
@@ -0,0 +1,58 @@
+use crate::tls::{self, CustomCa};
+
+const TEST_CA: &str = "-----BEGIN CERTIFICATE-----
+MIIBiDCCAS+gAwIBAgIUCndjWOpa8kYB+9BO//KECukTDqgwCgYIKoZIzj0EAwIw
+GTEXMBUGA1UEAwwOT25lQXBwIFRlc3QgQ0EwIBcNMjYxMDE2MTEyOTE4WhgPMjEy
+NjA5MjIxMTI5MThaMBkxFzAVBgNVBAMMDk9uZUFwcCBUZXN0IENBMFkwEwYHKoZI
+zj0CAQYIKoZIzj0DAQcDQgAE45/BLI0XiYPzPzxxneGNzZ2fbYNIsbxhbLgLg1s4
+UWYU4bIU014FUPHJPQmbToH/OW9esTw8okbih+nZC5MzkaNTMFEwHQYDVR0OBBYE
+FITxCMMGiJ3/e4VAQZsHmAITuhrpMB8GA1UdIwQYMBaAFITxCMMGiJ3/e4VAQZsH
+mAITuhrpMA8GA1UdEwEB/wQFMAMBAf8wCgYIKoZIzj0EAwIDRwAwRAIgN90DgnD2
+lSnH0iDVoMv3VhhbumfI5XOqrm5/XwBwju8CID5xcXMDHteIgs9KZTY3Iy4jc1Wu
+EL0b79xqhbvzYIBy
+-----END CERTIFICATE-----
+";
+
+#[test]
+fn env_path_wins_and_blank_values_disable_custom_ca() {
+    assert_eq!(tls::configured_ca_path(None, None), None);
+    assert_eq!(tls::configured_ca_path(Some("  "), Some("")), None);
+    assert_eq!(
+        tls::configured_ca_path(Some("/etc/corp.pem"), None),
+        Some("/etc/corp.pem".into())
+    );
+    assert_eq!(
+        tls::configured_ca_path(Some("/etc/corp.pem"), Some("/tmp/env.pem")),
+        Some("/tmp/env.pem".into())
+    );
+}
+
+#[test]
+fn ca_path_is_validated_before_the_client_trusts_it() {
+    let dir = std::env::temp_dir().join(format!("oneapp-tls-{}", std::process::id()));
+    let _ = std::fs::remove_dir_all(&dir);
+    std::fs::create_dir_all(&dir).unwrap();
+
+    let valid = dir.join("corp-ca.pem");
+    std::fs::write(&valid, TEST_CA).unwrap();
+    assert_eq!(tls::load_ca_certificates(&valid).unwrap().len(), 1);
+
+    let missing = dir.join("missing.pem");
+    assert!(tls::load_ca_certificates(&missing)
+        .unwrap_err()
+        .contains("Cannot read"));
+
+    let not_pem = dir.join("key.der");
+    std::fs::write(&not_pem, [0x30, 0x82, 0x01, 0x0a]).unwrap();
+    assert!(tls::load_ca_certificates(&not_pem)
+        .unwrap_err()
+        .contains("not a PEM certificate"));
+
+    // 加载失败时退回默认客户端，并把原因交给横幅显示
+    let (_, status) = tls::build_http_client(Some(missing.clone()));
+    assert!(matches!(status, Some(CustomCa::Failed { path, .. }) if path == missing));
+    let (_, status) = tls::build_http_client(None);
+    assert_eq!(status, None);
+
+    std::fs::remove_dir_all(&dir).unwrap();
+}
@@ -0,0 +1,160 @@
+use crate::reader;
+use futures::AsyncReadExt as _;
+use gpui::http_client::{http, AsyncBody, HttpClient, HttpRequestExt, Method, RedirectPolicy};
+use scraper::Html;
+use std::collections::HashMap;
+use std::sync::Arc;
+
+/// 只读取页面开头这么多字节，`og:title` 等标签都在 `<head>` 里
+const UNFURL_MAX_BYTES: usize = 256 * 1024;
+/// 每条评论最多显示的预览卡片数
+pub const UNFURLS_PER_COMMENT: usize = 2;
+/// 打开一个 story 时最多请求多少个链接预览，评论多的讨论不会一下发出几百个请求
+pub const UNFURLS_PER_STORY: usize = 24;
+/// 页面很重或者需要登录、拿不到有用标题的站点，不去请求
+const HEAVY_DOMAINS: &[&str] = &[
+    "facebook.com",
+    "instagram.com",
+    "linkedin.com",
+    "tiktok.com",
+    "twitter.com",
+    "x.com",
+    "youtu.be",
+    "youtube.com",
+];
+/// 指向文件而不是网页的链接
+const FILE_EXTENSIONS: &[&str] = &[
+    "7z", "dmg", "exe", "gif", "gz", "jpeg", "jpg", "mov", "mp3", "mp4", "pdf", "png", "tar",
+    "webm", "zip",
+];
+
+/// 评论里链接的预览卡片
+#[derive(Debug, Clone, PartialEq)]
+pub struct Unfurl {
+    pub url: String,
+    /// 去掉 `www.` 的域名
+    pub domain: String,
+    pub title: Option<String>,
+    pub site_name: Option<String>,
+}
+
+/// 值得请求预览的链接：http(s) 网页，不是 HN 自己、重型站点或文件
+pub fn should_unfurl(url: &str) -> bool {
+    let Ok(url) = url::Url::parse(url) else {
+        return false;
+    };
+    if !matches!(url.scheme(), "http" | "https") {
+        return false;
+    }
+    let Some(host) = reader::host_without_www(&url) else {
+        return false;
+    };
+    let on_domain = |domain: &str| host == domain || host.ends_with(&format!(".{domain}"));
+    if HEAVY_DOMAINS.iter().any(|domain| on_domain(domain)) || on_domain("ycombinator.com") {
+        return false;
+    }
+    let extension = url
+        .path()
+        .rsplit('/')
+        .next()
+        .and_then(|name| name.rsplit_once('.'))
+        .map(|(_, ext)| ext.to_ascii_lowercase());
+    !extension.is_some_and(|ext| FILE_EXTENSIONS.contains(&ext.as_str()))
+}
+
+/// 从页面的 `og:title`/`<title>` 和 `og:site_name` 生成卡片；页面没有标题时只有域名
+pub fn unfurl_from_html(html: &str, url: &str) -> Option<Unfurl> {
+    let parsed = url::Url::parse(url).ok()?;
+    let domain = reader::host_without_www(&parsed)?;
+    let doc = Html::parse_document(html);
+    Some(Unfurl {
+        url: url.to_string(),
+        domain,
+        title: reader::extract_title(&doc),
+        site_name: reader::extract_meta(&doc, "meta[property=\"og:site_name\"]"),
+    })
+}
+
+pub async fn fetch_unfurl(http_client: Arc<dyn HttpClient>, url: &str) -> Result<Unfurl, String> {
+    let request = http::Request::builder()
+        .method(Method::GET)
+        .uri(url)
+        .follow_redirects(RedirectPolicy::FollowAll)
+        .header("User-Agent", "OneApp/0.1 (GPUI Link Preview)")
+        .header("Accept", "text/html,application/xhtml+xml")
+        .body(AsyncBody::empty())
+        .map_err(|e| e.to_string())?;
+
+    let response = http_client.send(request).await.map_err(|e| e.to_string())?;
+    if !response.status().is_success() {
+        return Err(format!("HTTP {} for {}", response.status(), url));
+    }
+    let content_type = response
+        .headers()
+        .get(http::header::CONTENT_TYPE)
+        .and_then(|v| v.to_str().ok())
+        .unwrap_or("")
+        .to_string();
+    if !content_type.is_empty() && !content_type.contains("html") {
+        return Err(format!("Unsupported content type: {content_type}"));
+    }
+
+    let mut body = response.into_body();
+    let bytes = read_head(&mut body, UNFURL_MAX_BYTES).await?;
+    unfurl_from_html(&String::from_utf8_lossy(&bytes), url)
+        .ok_or_else(|| format!("Invalid URL: {url}"))
+}
+
+/// 读到 `limit` 字节就停下，剩下的不要了（不像 `read_to_end_limited` 那样报错）
+async fn read_head(body: &mut AsyncBody, limit: usize) -> Result<Vec<u8>, String> {
+    let mut bytes = Vec::new();
+    let mut buf = [0u8; 8192];
+    while bytes.len() < limit {
+        let n = body.read(&mut buf).await.map_err(|e| e.to_string())?;
+        if n == 0 {
+            break;
+        }
+        bytes.extend_from_slice(&buf[..n]);
+    }
+    Ok(bytes)
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum UnfurlState {
+    Loading,
+    Ready(Unfurl),
+    Failed,
+}
+
+/// 按链接缓存的预览，同一链接只请求一次（失败也不重试）
+#[derive(Debug, Default)]
+pub struct Unfurls {
+    entries: HashMap<String, UnfurlState>,
+}
+
+impl Unfurls {
+    /// 标记开始获取；已经缓存或正在获取时返回 false
+    pub fn begin(&mut self, url: &str) -> bool {
+        if self.entries.contains_key(url) {
+            return false;
+        }
+        self.entries.insert(url.to_string(), UnfurlState::Loading);
+        true
+    }
+
+    pub fn finish(&mut self, url: &str, result: Result<Unfurl, String>) {
+        let state = match result {
+            Ok(unfurl) => UnfurlState::Ready(unfurl),
+            Err(_) => UnfurlState::Failed,
+        };
+        self.entries.insert(url.to_string(), state);
+    }
+
+    /// 已经拿到的预览，还在加载或失败时为 None
+    pub fn ready(&self, url: &str) -> Option<&Unfurl> {
+        match self.entries.get(url) {
+            Some(UnfurlState::Ready(unfurl)) => Some(unfurl),
+            _ => None,
+        }
+    }
+}
@@ -0,0 +1,94 @@
+use crate::models::{Comment, RawComment};
+use crate::unfurls::{self, Unfurl, Unfurls};
+use gpui::http_client::{AsyncBody, FakeHttpClient, HttpClient, Response};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+
+const ARTICLE_PAGE: &str = r#"<html><head>
+    <title>Fallback title | Example Blog</title>
+    <meta property="og:title" content="How we cut build times in half">
+    <meta property="og:site_name" content="Example Blog">
+    </head><body><p>Body text.</p></body></html>"#;
+
+#[test]
+fn unfurl_is_built_from_the_pages_og_tags_and_cached_per_url() {
+    let fetches = Arc::new(AtomicUsize::new(0));
+    let counter = fetches.clone();
+    let http: Arc<dyn HttpClient> = FakeHttpClient::create(move |req| {
+        let counter = counter.clone();
+        async move {
+            counter.fetch_add(1, Ordering::SeqCst);
+            let (status, body) = match req.uri().path() {
+                "/posts/builds" => (200, ARTICLE_PAGE),
+                _ => (404, ""),
+            };
+            Ok(Response::builder()
+                .status(status)
+                .header("Content-Type", "text/html; charset=utf-8")
+                .body(AsyncBody::from(body.to_string()))
+                .unwrap())
+        }
+    });
+    let url = "https://www.example.com/posts/builds";
+    let mut cache = Unfurls::default();
+
+    for _ in 0..3 {
+        if cache.begin(url) {
+            let result = futures::executor::block_on(unfurls::fetch_unfurl(http.clone(), url));
+            cache.finish(url, result);
+        }
+    }
+
+    assert_eq!(fetches.load(Ordering::SeqCst), 1);
+    assert_eq!(
+        cache.ready(url),
+        Some(&Unfurl {
+            url: url.to_string(),
+            domain: "example.com".to_string(),
+            title: Some("How we cut build times in half".to_string()),
+            site_name: Some("Example Blog".to_string()),
+        })
+    );
+
+    let missing = "https://example.com/gone";
+    assert!(cache.begin(missing));
+    let result = futures::executor::block_on(unfurls::fetch_unfurl(http, missing));
+    cache.finish(missing, result);
+    assert_eq!(cache.ready(missing), None);
+    assert!(!cache.begin(missing));
+}
+
+#[test]
+fn only_bare_web_links_are_unfurled() {
+    let comment = Comment::from(RawComment {
+        id: 1,
+        by: Some("alice".to_string()),
+        text: Some(concat!(
+            r#"See <a href="https:&#x2F;&#x2F;example.com&#x2F;a" rel="nofollow">https:&#x2F;&#x2F;example.com&#x2F;a</a>"#,
+            r#" and <a href="https:&#x2F;&#x2F;example.com&#x2F;a-very-long-path&#x2F;with-more" rel="nofollow">https:&#x2F;&#x2F;example.com&#x2F;a-very-long-path&#x2F;...</a>"#,
+            r#"<p>but not <a href="https:&#x2F;&#x2F;example.com&#x2F;b">this article</a>."#,
+        )
+        .to_string()),
+        time: 1_700_000_000,
+        kids: None,
+        parent: 0,
+        comment_type: "comment".to_string(),
+    });
+
+    assert_eq!(
+        comment.bare_links(),
+        vec![
+            "https://example.com/a",
+            "https://example.com/a-very-long-path/with-more"
+        ]
+    );
+
+    assert!(unfurls::should_unfurl("https://example.com/a"));
+    assert!(!unfurls::should_unfurl("ftp://example.com/a"));
+    assert!(!unfurls::should_unfurl("https://www.youtube.com/watch?v=1"));
+    assert!(!unfurls::should_unfurl("https://m.youtube.com/watch?v=1"));
+    assert!(!unfurls::should_unfurl(
+        "https://news.ycombinator.com/item?id=1"
+    ));
+    assert!(!unfurls::should_unfurl("https://example.com/paper.PDF"));
+}